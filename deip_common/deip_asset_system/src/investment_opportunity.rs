@@ -30,7 +30,7 @@ impl Default for SimpleCrowdfundingStatus {
 #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
-pub enum FundingModel<Moment, Asset> {
+pub enum FundingModel<Moment, Asset, ProjectId, NftClassId> {
     SimpleCrowdfunding {
         /// a moment when the crowdfunding starts. Must be later than current moment.
         start_time: Moment,
@@ -40,9 +40,33 @@ pub enum FundingModel<Moment, Asset> {
         soft_cap: Asset,
         /// amount upper limit of units to raise. Must be greater or equal to `soft_cap`.
         hard_cap: Asset,
+        /// optional perks minted as NFTs to investors whose contribution reaches a tier.
+        #[cfg_attr(feature = "std", serde(default))]
+        perks: Option<ContributionPerks<Asset, ProjectId, NftClassId>>,
     },
 }
 
+/// Contribution tier: every investor who contributed at least `threshold`
+/// receives an instance of the NFT class `class`.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ContributionTier<Asset, NftClassId> {
+    /// minimal contribution to qualify for the tier. Must be in the asset of `soft_cap`.
+    pub threshold: Asset,
+    /// NFT class to mint the perk from.
+    pub class: NftClassId,
+}
+
+/// Perks of the crowdfunding. Classes of all tiers must belong to the team of `project_id`.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ContributionPerks<Asset, ProjectId, NftClassId> {
+    pub project_id: ProjectId,
+    pub tiers: Vec<ContributionTier<Asset, NftClassId>>,
+}
+
 /// The object represents a sale of tokens with various parameters.
 #[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -4,8 +4,11 @@ pub mod asset;
 pub mod investment_opportunity;
 
 pub use deip_assets_error::{ReserveError, UnreserveError};
-use frame_support::dispatch::Parameter;
-use sp_runtime::traits::{AtLeast32BitUnsigned, Member};
+use frame_support::{dispatch::Parameter, weights::Weight};
+use sp_runtime::{
+    traits::{AtLeast32BitUnsigned, Member},
+    DispatchError, DispatchResult,
+};
 use sp_std::prelude::*;
 
 pub trait AssetIdInitT<AssetId> {
@@ -23,11 +26,16 @@ pub trait DeipAssetSystem<AccountId, SourceId, InvestmentId>: AssetIdInitT<Self:
 
     fn total_supply(asset: &Self::AssetId) -> Self::Balance;
 
+    /// Minimum balance an account must hold to be created with the asset.
+    fn minimum_balance(asset: &Self::AssetId) -> Self::Balance;
+
+    /// Makes all `transfers` from `from` or none of them.
+    /// Returns the error of the first failed transfer.
     fn transactionally_transfer(
         from: &AccountId,
         asset: Self::AssetId,
         transfers: &[(Self::Balance, AccountId)],
-    ) -> Result<(), ()>;
+    ) -> DispatchResult;
 
     /// Tries to transfer assets specified by `shares` from
     /// `account` to a specific balance identified by `id`.
@@ -39,6 +47,16 @@ pub trait DeipAssetSystem<AccountId, SourceId, InvestmentId>: AssetIdInitT<Self:
         asset: Self::AssetId,
     ) -> Result<(), ReserveError<Self::AssetId>>;
 
+    /// Same as `transactionally_reserve`, but `asset` isn't marked as reserved,
+    /// so its team may still freeze or destroy it. Used by escrows which pass
+    /// `asset` on in the same transaction it's received.
+    fn transactionally_reserve_escrow(
+        account: &AccountId,
+        id: InvestmentId,
+        shares: &[(Self::AssetId, Self::Balance)],
+        asset: Self::AssetId,
+    ) -> Result<(), ReserveError<Self::AssetId>>;
+
     /// Transfers all assets currently owned by `id` to the account, used in
     /// transactionally_reserve, in a transactional way.
     fn transactionally_unreserve(id: InvestmentId) -> Result<(), UnreserveError<Self::AssetId>>;
@@ -59,3 +77,144 @@ pub trait DeipAssetSystem<AccountId, SourceId, InvestmentId>: AssetIdInitT<Self:
         amount: Self::Balance,
     ) -> Result<(), UnreserveError<Self::AssetId>>;
 }
+
+/// Provides assets to benchmarks of pallets using [`DeipAssetSystem`].
+#[cfg(feature = "runtime-benchmarks")]
+pub trait DeipAssetSystemBenchmarking<AccountId, SourceId, InvestmentId>:
+    DeipAssetSystem<AccountId, SourceId, InvestmentId>
+{
+    /// Mints `amount` of the benchmark asset with `index` to `account`,
+    /// creating the asset on the first call.
+    fn benchmark_mint(index: u8, account: &AccountId, amount: Self::Balance) -> Self::AssetId;
+}
+
+pub trait DeipNftSystem<AccountId> {
+    /// The identifier of NFT class.
+    type ClassId: Member + Parameter + Copy;
+
+    /// The identifier of NFT instance within its class.
+    type InstanceId: Member + Parameter + Copy;
+
+    /// Returns the owner of `class` if the class exists.
+    fn class_owner(class: &Self::ClassId) -> Option<AccountId>;
+
+    /// Returns the account allowed to mint instances of `class` if the class exists.
+    fn class_issuer(class: &Self::ClassId) -> Option<AccountId>;
+
+    /// Returns the account which holds `instance` of `class` for its own benefit:
+    /// the owner, or the seller while the instance is listed for sale.
+    /// Returns `None` if the instance doesn't exist or is fractionalized.
+    fn holder(class: &Self::ClassId, instance: &Self::InstanceId) -> Option<AccountId>;
+
+    /// Mints a new instance of `class` to `owner` on behalf of `issuer`.
+    /// Instance id is chosen by the implementation and returned.
+    fn mint(
+        issuer: &AccountId,
+        class: Self::ClassId,
+        owner: &AccountId,
+    ) -> Result<Self::InstanceId, ()>;
+
+    /// Weight of a single call to `mint`.
+    fn mint_weight() -> Weight;
+
+    /// Checks if `account` holds any instance of `class`.
+    fn is_holder(class: &Self::ClassId, account: &AccountId) -> bool;
+}
+
+/// Provides NFT classes to benchmarks of pallets using [`DeipNftSystem`].
+#[cfg(feature = "runtime-benchmarks")]
+pub trait DeipNftSystemBenchmarking<AccountId>: DeipNftSystem<AccountId> {
+    /// Creates the benchmark class with `index` owned and issued by `owner`.
+    fn benchmark_class(index: u8, owner: &AccountId) -> Self::ClassId;
+}
+
+/// Handler of destroyed NFT classes, e.g. to unlink them from other entities.
+pub trait OnNftClassDestroyed<ClassId> {
+    /// Called after `class` has been destroyed. Returns the consumed weight.
+    fn on_nft_class_destroyed(class: &ClassId) -> Weight;
+
+    /// Upper bound of the weight consumed by `on_nft_class_destroyed`.
+    fn max_weight() -> Weight;
+}
+
+impl<ClassId> OnNftClassDestroyed<ClassId> for () {
+    fn on_nft_class_destroyed(_class: &ClassId) -> Weight {
+        0
+    }
+
+    fn max_weight() -> Weight {
+        0
+    }
+}
+
+pub trait DeipFractions<AccountId> {
+    /// The units in which shares are recorded.
+    type Balance: Member + Parameter + AtLeast32BitUnsigned + Default + Copy;
+
+    /// The identifier of the asset of shares.
+    type AssetId: Member + Parameter + Copy;
+
+    /// Checks that `asset` may be created, e.g. its id isn't taken or reserved.
+    fn ensure_can_create(asset: &Self::AssetId) -> DispatchResult;
+
+    /// Creates `asset` with `name` in its metadata, mints `amount` of shares to `owner`
+    /// and passes all rights on the asset to `vault`. Deposits are paid by `owner`.
+    fn create(
+        owner: &AccountId,
+        vault: &AccountId,
+        asset: Self::AssetId,
+        name: Vec<u8>,
+        amount: Self::Balance,
+    ) -> DispatchResult;
+
+    fn account_balance(account: &AccountId, asset: &Self::AssetId) -> Self::Balance;
+
+    fn total_supply(asset: &Self::AssetId) -> Self::Balance;
+
+    /// Burns `amount` of shares owned by `who` on behalf of `vault`.
+    fn burn(
+        vault: &AccountId,
+        asset: Self::AssetId,
+        who: &AccountId,
+        amount: Self::Balance,
+    ) -> DispatchResult;
+}
+
+pub trait DeipCheckpoints<AccountId> {
+    /// The units in which asset balances are recorded.
+    type Balance: Member + Parameter + AtLeast32BitUnsigned + Default + Copy;
+
+    /// The identifier of the asset.
+    type AssetId: Member + Parameter + Copy;
+
+    /// The identifier of the checkpoint of the asset.
+    type CheckpointId: Member + Parameter + Copy;
+
+    /// Records balances of all holders of `asset` at the current moment.
+    fn create_checkpoint(asset: Self::AssetId) -> Result<Self::CheckpointId, DispatchError>;
+
+    /// Records balances of all holders of `asset` like `create_checkpoint` and keeps them
+    /// till `unpin_checkpoint` is called. Latest checkpoints aren't evicted by pinned ones.
+    fn pin_checkpoint(asset: Self::AssetId) -> Result<Self::CheckpointId, DispatchError>;
+
+    /// Releases `checkpoint` pinned by `pin_checkpoint`.
+    fn unpin_checkpoint(asset: &Self::AssetId, checkpoint: Self::CheckpointId);
+
+    /// Balance of `account` at `checkpoint`, `None` if the checkpoint doesn't exist.
+    fn balance_at(
+        asset: &Self::AssetId,
+        account: &AccountId,
+        checkpoint: Self::CheckpointId,
+    ) -> Option<Self::Balance>;
+
+    /// Total supply of `asset` at `checkpoint`, `None` if the checkpoint doesn't exist.
+    fn total_supply_at(
+        asset: &Self::AssetId,
+        checkpoint: Self::CheckpointId,
+    ) -> Option<Self::Balance>;
+
+    /// Mints `amount` of the asset used by benchmarks to `account`,
+    /// creating the asset on the first call.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn benchmark_mint(account: &AccountId, amount: Self::Balance) -> Self::AssetId;
+}
@@ -2,7 +2,7 @@
 
 use deip_serializable_u128::SerializableAtLeast32BitUnsigned;
 use serde::Serialize;
-use sp_runtime::{AccountId32, MultiAddress as SpMultiAddress};
+use sp_runtime::{traits::AtLeast32BitUnsigned, AccountId32, MultiAddress as SpMultiAddress};
 
 #[derive(Serialize)]
 pub(crate) struct AssetsCreateCallArgs {
@@ -248,6 +248,40 @@ impl AssetsTransferApprovedCallArgs {
     }
 }
 
+#[derive(Serialize)]
+pub(crate) struct DeipAssetsApproveTransferCallArgs<A, B, C: Clone + AtLeast32BitUnsigned> {
+    id: A,
+    delegate: B,
+    amount: SerializableAtLeast32BitUnsigned<C>,
+}
+
+impl<A, B, C: Clone + AtLeast32BitUnsigned> DeipAssetsApproveTransferCallArgs<A, B, C> {
+    pub(crate) fn new(id: A, delegate: B, amount: &C) -> Self {
+        Self { id, delegate, amount: SerializableAtLeast32BitUnsigned(amount.clone()) }
+    }
+}
+
+#[derive(Serialize)]
+pub(crate) struct DeipAssetsCancelApprovalCallArgs<A, B> {
+    pub(crate) id: A,
+    pub(crate) delegate: B,
+}
+
+#[derive(Serialize)]
+pub(crate) struct DeipAssetsTransferApprovedCallArgs<A, B, C, D: Clone + AtLeast32BitUnsigned> {
+    id: A,
+    owner: B,
+    destination: C,
+    amount: SerializableAtLeast32BitUnsigned<D>,
+}
+
+impl<A, B, C, D: Clone + AtLeast32BitUnsigned> DeipAssetsTransferApprovedCallArgs<A, B, C, D> {
+    pub(crate) fn new(id: A, owner: B, destination: C, amount: &D) -> Self {
+        let amount = SerializableAtLeast32BitUnsigned(amount.clone());
+        Self { id, owner, destination, amount }
+    }
+}
+
 #[derive(Serialize)]
 enum MultiAddress<AccountId, AccountIndex> {
     /// It's an account ID (pubkey).
@@ -10,6 +10,11 @@ use sp_std::borrow::Borrow;
 
 use pallet_deip_proposal::proposal::{BatchItem, InputProposalBatch};
 
+use assets_call_args::{
+    DeipAssetsApproveTransferCallArgs, DeipAssetsCancelApprovalCallArgs,
+    DeipAssetsTransferApprovedCallArgs,
+};
+
 use deip_serializable_u128::SerializableAtLeast32BitUnsigned;
 
 #[derive(Clone, Debug, Eq, PartialEq, Decode, Encode, Deserialize, TypeInfo)]
@@ -55,11 +60,11 @@ impl Serialize for WrappedCall<Call> {
             Call::DeipDao(deip_dao_call) =>
                 Self::serialize_deip_dao_call(deip_dao_call, serializer),
 
-            // Call::DeipAssets(deip_assets_call) =>
-            //     Self::serialize_deip_assets_call(deip_assets_call, serializer),
+            Call::DeipAssets(deip_assets_call) =>
+                Self::serialize_deip_assets_call(deip_assets_call, serializer),
             Call::Assets(..) |
             Call::Uniques(..) |
-            // Call::DeipUniques(..) |
+            Call::DeipUniques(..) |
             Call::System(_) |
             Call::DeipPortal(_) |
             Call::Timestamp(_) |
@@ -75,7 +80,9 @@ impl Serialize for WrappedCall<Call> {
             Call::ImOnline(_) |
             Call::Utility(_) |
             Call::Multisig(_) |
-            Call::DeipVesting(_) => CallObject {
+            Call::DeipVesting(_) |
+            Call::DeipSwap(_) |
+            Call::DeipAmm(_) => CallObject {
                 module: "unsupported_module",
                 call: "unsupported_call",
                 args: &UnsupportedCallArgs {},
@@ -274,6 +281,57 @@ impl WrappedCall<Call> {
             }
             .serialize(serializer),
 
+            set_license_nft_class { project_id, class } => CallObject {
+                module: "deip",
+                call: "set_license_nft_class",
+                args: &DeipSetLicenseNftClassCallArgs { project_id, class },
+            }
+            .serialize(serializer),
+
+            set_content_nft_class { project_content_external_id, class } => CallObject {
+                module: "deip",
+                call: "set_content_nft_class",
+                args: &DeipSetContentNftClassCallArgs { project_content_external_id, class },
+            }
+            .serialize(serializer),
+
+            create_content_access_request {
+                external_id,
+                project_content_external_id,
+                public_key,
+            } => CallObject {
+                module: "deip",
+                call: "create_content_access_request",
+                args: &DeipCreateContentAccessRequestCallArgs {
+                    external_id,
+                    project_content_external_id,
+                    public_key: &public_key[..],
+                },
+            }
+            .serialize(serializer),
+
+            fulfill_content_access_request {
+                external_id,
+                encrypted_key,
+                proof_of_encrypted_key,
+            } => CallObject {
+                module: "deip",
+                call: "fulfill_content_access_request",
+                args: &DeipFulfillContentAccessRequestCallArgs {
+                    external_id,
+                    encrypted_key: &encrypted_key[..],
+                    proof_of_encrypted_key: &proof_of_encrypted_key[..],
+                },
+            }
+            .serialize(serializer),
+
+            reject_content_access_request { external_id } => CallObject {
+                module: "deip",
+                call: "reject_content_access_request",
+                args: &DeipRejectContentAccessRequestCallArgs { external_id },
+            }
+            .serialize(serializer),
+
             __PhantomItem(..) => unreachable!(),
         }
     }
@@ -406,11 +464,182 @@ impl WrappedCall<Call> {
             }
             .serialize(serializer),
 
+            set_token_governance { governance } => CallObject {
+                module: "deip_dao",
+                call: "set_token_governance",
+                args: &DeipDaoSetTokenGovernanceCallArgs { governance },
+            }
+            .serialize(serializer),
+
+            propose { dao, id, call } => CallObject {
+                module: "deip_dao",
+                call: "propose",
+                args: &DeipDaoProposeCallArgs { dao, id, call: &WrappedCall::wrap(call.borrow()) },
+            }
+            .serialize(serializer),
+
+            vote { id, aye } => CallObject {
+                module: "deip_dao",
+                call: "vote",
+                args: &DeipDaoVoteCallArgs { id, aye },
+            }
+            .serialize(serializer),
+
+            close_proposal { id, proposal_weight_bound } => CallObject {
+                module: "deip_dao",
+                call: "close_proposal",
+                args: &DeipDaoCloseProposalCallArgs { id, proposal_weight_bound },
+            }
+            .serialize(serializer),
+
+            define_role { role, permissions } => CallObject {
+                module: "deip_dao",
+                call: "define_role",
+                args: &DeipDaoDefineRoleCallArgs { role, permissions },
+            }
+            .serialize(serializer),
+
+            remove_role { role } => CallObject {
+                module: "deip_dao",
+                call: "remove_role",
+                args: &DeipDaoRemoveRoleCallArgs { role },
+            }
+            .serialize(serializer),
+
+            grant_role { role, member } => CallObject {
+                module: "deip_dao",
+                call: "grant_role",
+                args: &DeipDaoRoleGrantCallArgs { role, member },
+            }
+            .serialize(serializer),
+
+            revoke_role { role, member } => CallObject {
+                module: "deip_dao",
+                call: "revoke_role",
+                args: &DeipDaoRoleGrantCallArgs { role, member },
+            }
+            .serialize(serializer),
+
+            on_behalf_as_role { name, role, call } => CallObject {
+                module: "deip_dao",
+                call: "on_behalf_as_role",
+                args: &DeipDaoOnBehalfAsRoleCallArgs {
+                    name,
+                    role,
+                    call: &WrappedCall::wrap(call.borrow()),
+                },
+            }
+            .serialize(serializer),
+
+            set_budget { asset, budget } => CallObject {
+                module: "deip_dao",
+                call: "set_budget",
+                args: &DeipDaoSetBudgetCallArgs { asset, budget },
+            }
+            .serialize(serializer),
+
+            on_behalf_over_budget { name, call } => CallObject {
+                module: "deip_dao",
+                call: "on_behalf_over_budget",
+                args: &DeipDaoOnBehalfCallArgs { name, call: &WrappedCall::wrap(call.borrow()) },
+            }
+            .serialize(serializer),
+
+            dissolve { beneficiary, witness } => CallObject {
+                module: "deip_dao",
+                call: "dissolve",
+                args: &DeipDaoDissolveCallArgs { beneficiary, witness },
+            }
+            .serialize(serializer),
+
+            invite_member { member, preserve_threshold } => CallObject {
+                module: "deip_dao",
+                call: "invite_member",
+                args: &DeipDaoMemberCallArgs { member, preserve_threshold },
+            }
+            .serialize(serializer),
+
+            accept_invitation { name } => CallObject {
+                module: "deip_dao",
+                call: "accept_invitation",
+                args: &DeipDaoNameCallArgs { name },
+            }
+            .serialize(serializer),
+
+            reject_invitation { name } => CallObject {
+                module: "deip_dao",
+                call: "reject_invitation",
+                args: &DeipDaoNameCallArgs { name },
+            }
+            .serialize(serializer),
+
+            request_to_join { name } => CallObject {
+                module: "deip_dao",
+                call: "request_to_join",
+                args: &DeipDaoNameCallArgs { name },
+            }
+            .serialize(serializer),
+
+            accept_join_request { member, preserve_threshold } => CallObject {
+                module: "deip_dao",
+                call: "accept_join_request",
+                args: &DeipDaoMemberCallArgs { member, preserve_threshold },
+            }
+            .serialize(serializer),
+
+            reject_join_request { member } => CallObject {
+                module: "deip_dao",
+                call: "reject_join_request",
+                args: &DeipDaoRejectJoinRequestCallArgs { member },
+            }
+            .serialize(serializer),
+
+            set_role_budget { role, asset, budget } => CallObject {
+                module: "deip_dao",
+                call: "set_role_budget",
+                args: &DeipDaoSetRoleBudgetCallArgs { role, asset, budget },
+            }
+            .serialize(serializer),
+
+            set_budget_guardian { name, guardian } => CallObject {
+                module: "deip_dao",
+                call: "set_budget_guardian",
+                args: &DeipDaoSetBudgetGuardianCallArgs { name, guardian },
+            }
+            .serialize(serializer),
+
+            set_budget_by_guardian { name, asset, budget } => CallObject {
+                module: "deip_dao",
+                call: "set_budget_by_guardian",
+                args: &DeipDaoSetBudgetByGuardianCallArgs { name, asset, budget },
+            }
+            .serialize(serializer),
+
+            approve_over_budget { name, call_hash } => CallObject {
+                module: "deip_dao",
+                call: "approve_over_budget",
+                args: &DeipDaoApproveOverBudgetCallArgs { name, call_hash },
+            }
+            .serialize(serializer),
+
+            prune_membership_requests { name, member } => CallObject {
+                module: "deip_dao",
+                call: "prune_membership_requests",
+                args: &DeipDaoPruneMembershipRequestsCallArgs { name, member },
+            }
+            .serialize(serializer),
+
+            consent_to_nesting { parent } => CallObject {
+                module: "deip_dao",
+                call: "consent_to_nesting",
+                args: &DeipDaoConsentToNestingCallArgs { parent },
+            }
+            .serialize(serializer),
+
             __Ignore(..) => unreachable!(),
         }
     }
 
-    #[allow(dead_code)]
     fn serialize_deip_assets_call<S>(
         call: &pallet_deip_assets::Call<Runtime>,
         serializer: S,
@@ -422,10 +651,10 @@ impl WrappedCall<Call> {
 
         let module = "deip_assets";
         match call {
-            deip_create { id, admin, min_balance } => CallObject {
+            deip_create { id, admin, min_balance, max_supply, schedule } => CallObject {
                 module,
                 call: "deip_create",
-                args: &DeipAssetsCreateCallArgs::new(id, admin, min_balance),
+                args: &DeipAssetsCreateCallArgs::new(id, admin, min_balance, max_supply, schedule),
             }
             .serialize(serializer),
 
@@ -436,6 +665,13 @@ impl WrappedCall<Call> {
             }
             .serialize(serializer),
 
+            start_destroy { id } => CallObject {
+                module,
+                call: "start_destroy",
+                args: &DeipAssetsStartDestroyCallArgs { id },
+            }
+            .serialize(serializer),
+
             deip_mint { id, beneficiary, amount } => CallObject {
                 module,
                 call: "deip_mint",
@@ -457,6 +693,27 @@ impl WrappedCall<Call> {
             }
             .serialize(serializer),
 
+            deip_approve_transfer { id, delegate, amount } => CallObject {
+                module,
+                call: "deip_approve_transfer",
+                args: &DeipAssetsApproveTransferCallArgs::new(id, delegate, amount),
+            }
+            .serialize(serializer),
+
+            deip_cancel_approval { id, delegate } => CallObject {
+                module,
+                call: "deip_cancel_approval",
+                args: &DeipAssetsCancelApprovalCallArgs { id, delegate },
+            }
+            .serialize(serializer),
+
+            deip_transfer_approved { id, owner, destination, amount } => CallObject {
+                module,
+                call: "deip_transfer_approved",
+                args: &DeipAssetsTransferApprovedCallArgs::new(id, owner, destination, amount),
+            }
+            .serialize(serializer),
+
             deip_freeze { id, who } => CallObject {
                 module,
                 call: "deip_freeze",
@@ -503,6 +760,69 @@ impl WrappedCall<Call> {
             }
             .serialize(serializer),
 
+            checkpoint { asset } => CallObject {
+                module,
+                call: "checkpoint",
+                args: &DeipAssetsCheckpointCallArgs { asset },
+            }
+            .serialize(serializer),
+
+            distribute { asset, payment_asset, amount } => CallObject {
+                module,
+                call: "distribute",
+                args: &DeipAssetsDistributeCallArgs::new(asset, payment_asset, amount),
+            }
+            .serialize(serializer),
+
+            claim { asset, checkpoint } => CallObject {
+                module,
+                call: "claim",
+                args: &DeipAssetsClaimCallArgs { asset, checkpoint },
+            }
+            .serialize(serializer),
+
+            reclaim_dividends { asset, checkpoint, claims } => CallObject {
+                module,
+                call: "reclaim_dividends",
+                args: &DeipAssetsReclaimDividendsCallArgs { asset, checkpoint, claims },
+            }
+            .serialize(serializer),
+
+            set_transfer_rules { asset, rules } => CallObject {
+                module,
+                call: "set_transfer_rules",
+                args: &DeipAssetsSetTransferRulesCallArgs { asset, rules },
+            }
+            .serialize(serializer),
+
+            allowlist_add { asset, who, jurisdiction } => CallObject {
+                module,
+                call: "allowlist_add",
+                args: &DeipAssetsAllowlistAddCallArgs { asset, who, jurisdiction },
+            }
+            .serialize(serializer),
+
+            allowlist_remove { asset, who } => CallObject {
+                module,
+                call: "allowlist_remove",
+                args: &DeipAssetsAllowlistRemoveCallArgs { asset, who },
+            }
+            .serialize(serializer),
+
+            set_security_metadata { asset, metadata } => CallObject {
+                module,
+                call: "set_security_metadata",
+                args: &DeipAssetsSetSecurityMetadataCallArgs { asset, metadata },
+            }
+            .serialize(serializer),
+
+            attest_security_metadata { asset, version } => CallObject {
+                module,
+                call: "attest_security_metadata",
+                args: &DeipAssetsAttestSecurityMetadataCallArgs { asset, version },
+            }
+            .serialize(serializer),
+
             __Ignore(..) => unreachable!(),
         }
     }
@@ -511,6 +831,73 @@ impl WrappedCall<Call> {
 #[derive(Serialize)]
 struct UnsupportedCallArgs {}
 
+#[derive(Serialize)]
+struct DeipAssetsStartDestroyCallArgs<A> {
+    id: A,
+}
+
+#[derive(Serialize)]
+struct DeipAssetsCheckpointCallArgs<A> {
+    asset: A,
+}
+
+#[derive(Serialize)]
+struct DeipAssetsClaimCallArgs<A, B> {
+    asset: A,
+    checkpoint: B,
+}
+
+#[derive(Serialize)]
+struct DeipAssetsReclaimDividendsCallArgs<A, B, C> {
+    asset: A,
+    checkpoint: B,
+    claims: C,
+}
+
+#[derive(Serialize)]
+struct DeipAssetsSetTransferRulesCallArgs<A, B> {
+    asset: A,
+    rules: B,
+}
+
+#[derive(Serialize)]
+struct DeipAssetsAllowlistAddCallArgs<A, B, C> {
+    asset: A,
+    who: B,
+    jurisdiction: C,
+}
+
+#[derive(Serialize)]
+struct DeipAssetsAllowlistRemoveCallArgs<A, B> {
+    asset: A,
+    who: B,
+}
+
+#[derive(Serialize)]
+struct DeipAssetsSetSecurityMetadataCallArgs<A, B> {
+    asset: A,
+    metadata: B,
+}
+
+#[derive(Serialize)]
+struct DeipAssetsAttestSecurityMetadataCallArgs<A, B> {
+    asset: A,
+    version: B,
+}
+
+#[derive(Serialize)]
+struct DeipAssetsDistributeCallArgs<A, B, C: Clone + AtLeast32BitUnsigned> {
+    asset: A,
+    payment_asset: B,
+    amount: SerializableAtLeast32BitUnsigned<C>,
+}
+
+impl<A, B, C: Clone + AtLeast32BitUnsigned> DeipAssetsDistributeCallArgs<A, B, C> {
+    fn new(asset: A, payment_asset: B, amount: &C) -> Self {
+        Self { asset, payment_asset, amount: SerializableAtLeast32BitUnsigned(amount.clone()) }
+    }
+}
+
 #[derive(Serialize)]
 struct DeipAssetsSetMetadataCallArgs<A, B, C, D> {
     id: A,
@@ -607,15 +994,23 @@ struct DeipAssetsDestroyCallArgs<A, B> {
 }
 
 #[derive(Serialize)]
-struct DeipAssetsCreateCallArgs<A, B, D: Clone + AtLeast32BitUnsigned> {
+struct DeipAssetsCreateCallArgs<A, B, D: Clone + AtLeast32BitUnsigned, E> {
     id: A,
     admin: B,
     min_balance: SerializableAtLeast32BitUnsigned<D>,
+    max_supply: Option<SerializableAtLeast32BitUnsigned<D>>,
+    schedule: E,
 }
 
-impl<A, B, D: Clone + AtLeast32BitUnsigned> DeipAssetsCreateCallArgs<A, B, D> {
-    fn new(id: A, admin: B, min_balance: &D) -> Self {
-        Self { id, admin, min_balance: SerializableAtLeast32BitUnsigned(min_balance.clone()) }
+impl<A, B, D: Clone + AtLeast32BitUnsigned, E> DeipAssetsCreateCallArgs<A, B, D, E> {
+    fn new(id: A, admin: B, min_balance: &D, max_supply: &Option<D>, schedule: E) -> Self {
+        Self {
+            id,
+            admin,
+            min_balance: SerializableAtLeast32BitUnsigned(min_balance.clone()),
+            max_supply: max_supply.clone().map(SerializableAtLeast32BitUnsigned),
+            schedule,
+        }
     }
 }
 
@@ -642,6 +1037,119 @@ struct DeipDaoCreateCallArgs<A, B, C> {
     metadata: C,
 }
 
+#[derive(Serialize)]
+struct DeipDaoSetTokenGovernanceCallArgs<A> {
+    governance: A,
+}
+
+#[derive(Serialize)]
+struct DeipDaoProposeCallArgs<A, B, C> {
+    dao: A,
+    id: B,
+    call: C,
+}
+
+#[derive(Serialize)]
+struct DeipDaoVoteCallArgs<A, B> {
+    id: A,
+    aye: B,
+}
+
+#[derive(Serialize)]
+struct DeipDaoCloseProposalCallArgs<A, B> {
+    id: A,
+    proposal_weight_bound: B,
+}
+
+#[derive(Serialize)]
+struct DeipDaoDefineRoleCallArgs<A, B> {
+    role: A,
+    permissions: B,
+}
+
+#[derive(Serialize)]
+struct DeipDaoRemoveRoleCallArgs<A> {
+    role: A,
+}
+
+#[derive(Serialize)]
+struct DeipDaoRoleGrantCallArgs<A, B> {
+    role: A,
+    member: B,
+}
+
+#[derive(Serialize)]
+struct DeipDaoOnBehalfAsRoleCallArgs<A, B, C> {
+    name: A,
+    role: B,
+    call: C,
+}
+
+#[derive(Serialize)]
+struct DeipDaoSetBudgetCallArgs<A, B> {
+    asset: A,
+    budget: B,
+}
+
+#[derive(Serialize)]
+struct DeipDaoSetRoleBudgetCallArgs<A, B, C> {
+    role: A,
+    asset: B,
+    budget: C,
+}
+
+#[derive(Serialize)]
+struct DeipDaoSetBudgetGuardianCallArgs<A, B> {
+    name: A,
+    guardian: B,
+}
+
+#[derive(Serialize)]
+struct DeipDaoSetBudgetByGuardianCallArgs<A, B, C> {
+    name: A,
+    asset: B,
+    budget: C,
+}
+
+#[derive(Serialize)]
+struct DeipDaoApproveOverBudgetCallArgs<A, B> {
+    name: A,
+    call_hash: B,
+}
+
+#[derive(Serialize)]
+struct DeipDaoDissolveCallArgs<A, B> {
+    beneficiary: A,
+    witness: B,
+}
+
+#[derive(Serialize)]
+struct DeipDaoMemberCallArgs<A, B> {
+    member: A,
+    preserve_threshold: B,
+}
+
+#[derive(Serialize)]
+struct DeipDaoNameCallArgs<A> {
+    name: A,
+}
+
+#[derive(Serialize)]
+struct DeipDaoRejectJoinRequestCallArgs<A> {
+    member: A,
+}
+
+#[derive(Serialize)]
+struct DeipDaoPruneMembershipRequestsCallArgs<A, B> {
+    name: A,
+    member: B,
+}
+
+#[derive(Serialize)]
+struct DeipDaoConsentToNestingCallArgs<A> {
+    parent: A,
+}
+
 #[derive(Serialize)]
 struct DeipProposalDecideCallArgs<A, B> {
     proposal_id: A,
@@ -687,6 +1195,37 @@ struct DeipRejectContractAgreementCallArgs<A, B> {
     party: B,
 }
 
+#[derive(Serialize)]
+struct DeipSetLicenseNftClassCallArgs<A, B> {
+    project_id: A,
+    class: B,
+}
+
+#[derive(Serialize)]
+struct DeipSetContentNftClassCallArgs<A, B> {
+    project_content_external_id: A,
+    class: B,
+}
+
+#[derive(Serialize)]
+struct DeipCreateContentAccessRequestCallArgs<A, B, C> {
+    external_id: A,
+    project_content_external_id: B,
+    public_key: C,
+}
+
+#[derive(Serialize)]
+struct DeipFulfillContentAccessRequestCallArgs<A, B, C> {
+    external_id: A,
+    encrypted_key: B,
+    proof_of_encrypted_key: C,
+}
+
+#[derive(Serialize)]
+struct DeipRejectContentAccessRequestCallArgs<A> {
+    external_id: A,
+}
+
 #[derive(Serialize)]
 struct DeipCreateReviewCallArgs<A, B, C, D, E, F, G> {
     external_id: A,
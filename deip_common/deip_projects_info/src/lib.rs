@@ -8,6 +8,7 @@ pub trait DeipProjectsInfo<AccountId>: TypeInfo {
         + Clone
         + frame_support::codec::FullCodec
         + PartialEq
+        + Eq
         + AsRef<[u8]>
         + TypeInfo;
     type InvestmentId: sp_std::fmt::Debug
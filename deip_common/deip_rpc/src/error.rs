@@ -52,6 +52,13 @@ pub enum Error {
     DeipClassIdInverseIndexFailed = 43,
     ClassInstanceDecodeFailed = 44,
     ClassDetailsDecodeFailed = 45,
+    OfferIdDecodeFailed = 46,
+    OfferDecodeFailed = 47,
+    AssetsApiRemainingSupplyFailed = 48,
+    SecurityMetadataDecodeFailed = 49,
+    NftMetadataDecodeFailed = 50,
+    NftAttributeDecodeFailed = 51,
+    InstanceIdDecodeFailed = 52,
 }
 
 impl From<Error> for RpcErrorCode {
@@ -19,6 +19,7 @@ use crate::{
         deip::events as deip_events, deip_dao::events as dao_events,
         deip_investment_opportunity::events as deip_investment_opportunity_events,
         deip_proposal::events as proposal_events, assets::events as assets_events,
+        deip_uniques::events as uniques_events,
     },
     frame::deip_proposal::{self, DeipProposal},
 };
@@ -157,10 +158,19 @@ where
             SimpleCrowdfundingFinished(e) => e.serialize(serializer),
             SimpleCrowdfundingExpired(e) => e.serialize(serializer),
             Invested(e) => e.serialize(serializer),
+            // =============== DeipUniques:
+            NftBatchMinted(e) => e.serialize(serializer),
             // =============== DeipDao:
             DaoCreate(e) => e.serialize(serializer),
             DaoAlterAuthority(e) => e.serialize(serializer),
             DaoMetadataUpdated(e) => e.serialize(serializer),
+            DaoMemberInvited(e) => e.serialize(serializer),
+            DaoInvitationRejected(e) => e.serialize(serializer),
+            DaoJoinRequested(e) => e.serialize(serializer),
+            DaoJoinRequestRejected(e) => e.serialize(serializer),
+            DaoMemberJoined(e) => e.serialize(serializer),
+            DaoInvitationExpired(e) => e.serialize(serializer),
+            DaoJoinRequestExpired(e) => e.serialize(serializer),
             // =============== Assets:
             AssetClassCreated(e) => e.serialize(serializer),
             AssetIssued(e) => e.serialize(serializer),
@@ -251,10 +261,19 @@ pub enum DomainEventData<T: DeipProposal> {
         deip_investment_opportunity_events::Invested,
         (),
     >),
+    // DeipUniques:
+    NftBatchMinted(uniques_events::BatchMinted),
     // DeipDao:
     DaoCreate(dao_events::DaoCreate),
     DaoAlterAuthority(dao_events::DaoAlterAuthority),
     DaoMetadataUpdated(dao_events::DaoMetadataUpdated),
+    DaoMemberInvited(dao_events::MemberInvited),
+    DaoInvitationRejected(dao_events::InvitationRejected),
+    DaoJoinRequested(dao_events::JoinRequested),
+    DaoJoinRequestRejected(dao_events::JoinRequestRejected),
+    DaoMemberJoined(dao_events::MemberJoined),
+    DaoInvitationExpired(dao_events::InvitationExpired),
+    DaoJoinRequestExpired(dao_events::JoinRequestExpired),
     // Assets:
     AssetClassCreated(assets_events::Created),
     AssetIssued(assets_events::Issued),
@@ -470,6 +489,12 @@ where
             data: DomainEventData::Invested(decode_event_data(raw).map(LegacyEvent::Current)?),
             meta,
         },
+        // =========== DeipUniques:
+        (uniques_events::BatchMinted::PALLET, uniques_events::BatchMinted::EVENT) => DomainEvent {
+            name: "nft_batchMinted".to_string(),
+            data: decode_event_data(raw).map(DomainEventData::NftBatchMinted)?,
+            meta,
+        },
         // =========== DeipDao:
         (dao_events::DaoCreate::PALLET, dao_events::DaoCreate::EVENT) => DomainEvent {
             name: "dao_create".to_string(),
@@ -488,6 +513,45 @@ where
                 data: decode_event_data(raw).map(DomainEventData::DaoMetadataUpdated)?,
                 meta,
             },
+        (dao_events::MemberInvited::PALLET, dao_events::MemberInvited::EVENT) => DomainEvent {
+            name: "dao_memberInvited".to_string(),
+            data: decode_event_data(raw).map(DomainEventData::DaoMemberInvited)?,
+            meta,
+        },
+        (dao_events::InvitationRejected::PALLET, dao_events::InvitationRejected::EVENT) =>
+            DomainEvent {
+                name: "dao_invitationRejected".to_string(),
+                data: decode_event_data(raw).map(DomainEventData::DaoInvitationRejected)?,
+                meta,
+            },
+        (dao_events::JoinRequested::PALLET, dao_events::JoinRequested::EVENT) => DomainEvent {
+            name: "dao_joinRequested".to_string(),
+            data: decode_event_data(raw).map(DomainEventData::DaoJoinRequested)?,
+            meta,
+        },
+        (dao_events::JoinRequestRejected::PALLET, dao_events::JoinRequestRejected::EVENT) =>
+            DomainEvent {
+                name: "dao_joinRequestRejected".to_string(),
+                data: decode_event_data(raw).map(DomainEventData::DaoJoinRequestRejected)?,
+                meta,
+            },
+        (dao_events::MemberJoined::PALLET, dao_events::MemberJoined::EVENT) => DomainEvent {
+            name: "dao_memberJoined".to_string(),
+            data: decode_event_data(raw).map(DomainEventData::DaoMemberJoined)?,
+            meta,
+        },
+        (dao_events::InvitationExpired::PALLET, dao_events::InvitationExpired::EVENT) =>
+            DomainEvent {
+                name: "dao_invitationExpired".to_string(),
+                data: decode_event_data(raw).map(DomainEventData::DaoInvitationExpired)?,
+                meta,
+            },
+        (dao_events::JoinRequestExpired::PALLET, dao_events::JoinRequestExpired::EVENT) =>
+            DomainEvent {
+                name: "dao_joinRequestExpired".to_string(),
+                data: decode_event_data(raw).map(DomainEventData::DaoJoinRequestExpired)?,
+                meta,
+            },
         // =========== Assets:
         (assets_events::Created::PALLET, assets_events::Created::EVENT) => DomainEvent {
             name: "asset_class_created".to_string(),
@@ -40,8 +40,12 @@ where
 
         Event::Assets(assets_event) => match_event_deip_assets(assets_event),
 
+        Event::DeipUniques(e) => match_event_deip_uniques(e),
+
         Event::Uniques(..)
 
+        | Event::DeipAssets(..)
+
         | Event::Session(..) | Event::ImOnline(..)
 
         | Event::OctopusAppchain(..) | Event::OctopusLpos(..) | Event::OctopusUpwardMessages(..)
@@ -52,6 +56,8 @@ where
         | Event::Balances(_)
         | Event::Sudo(_)
         | Event::DeipVesting(_)
+        | Event::DeipSwap(_)
+        | Event::DeipAmm(_)
         | Event::Multisig(_)
         => unreachable!(),
     }
@@ -76,6 +82,50 @@ where
             // deip_dao::DaoMetadataUpdatedEvent
             unimplemented!()
         },
+        MemberInvited(..) => {
+            // deip_dao::MemberInvitedEvent
+            unimplemented!()
+        },
+        InvitationRejected(..) => {
+            // deip_dao::InvitationRejectedEvent
+            unimplemented!()
+        },
+        JoinRequested(..) => {
+            // deip_dao::JoinRequestedEvent
+            unimplemented!()
+        },
+        JoinRequestRejected(..) => {
+            // deip_dao::JoinRequestRejectedEvent
+            unimplemented!()
+        },
+        MemberJoined(..) => {
+            // deip_dao::MemberJoinedEvent
+            unimplemented!()
+        },
+        InvitationExpired(..) => {
+            // deip_dao::InvitationExpiredEvent
+            unimplemented!()
+        },
+        JoinRequestExpired(..) => {
+            // deip_dao::JoinRequestExpiredEvent
+            unimplemented!()
+        },
+        TokenGovernanceSet(..)
+        | Proposed(..)
+        | Voted(..)
+        | ProposalRejected(..)
+        | ProposalExecuted(..)
+        | RoleDefined(..)
+        | RoleRemoved(..)
+        | RoleGranted(..)
+        | RoleRevoked(..)
+        | BudgetSet(..)
+        | BudgetOverridden(..)
+        | BudgetGuardianSet(..)
+        | OverBudgetApproved(..)
+        | RoleBudgetSet(..)
+        | DaoDissolved(..)
+        | NestingConsented(..) => unimplemented!(),
         __Ignore(..) => unreachable!(),
     }
 }
@@ -259,6 +309,12 @@ where
             // deip::ContractAgreementRejectedEvent
             unimplemented!()
         },
+        LicenseNftClassSet(..)
+        | LicenseNftMinted(..)
+        | ContentNftClassSet(..)
+        | ContentAccessRequestCreated(..)
+        | ContentAccessRequestFulfilled(..)
+        | ContentAccessRequestRejected(..) => unimplemented!(),
     }
 }
 
@@ -289,6 +345,31 @@ where
             // deip::InvestedEvent
             unimplemented!()
         },
+        ContributionPerkMinted(..) | ContributionPerkMintFailed(..) => unimplemented!(),
         __Ignore { .. } => unreachable!(),
     }
 }
+
+fn match_event_deip_uniques<T>(e: &pallet_deip_uniques::Event<Runtime>) -> DomainEventData<T>
+where
+    T: DeipProposal,
+{
+    use pallet_deip_uniques::Event::*;
+
+    match e {
+        Fractionalized(..)
+        | Redeemed(..)
+        | BoughtOut(..)
+        | BuyoutClaimed(..)
+        | ClassProjectSet(..)
+        | RoyaltySet(..)
+        | SaleListed(..)
+        | SaleCancelled(..)
+        | Sold(..) => unimplemented!(),
+        BatchMinted(..) => {
+            // deip_uniques::BatchMintedEvent
+            unimplemented!()
+        },
+        __Ignore(..) => unreachable!(),
+    }
+}
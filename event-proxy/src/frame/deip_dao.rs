@@ -3,7 +3,10 @@ use serde::{
     Serialize,
 };
 
-use crate::appchain_deip::deip_dao::events::{DaoAlterAuthority, DaoCreate, DaoMetadataUpdated};
+use crate::appchain_deip::deip_dao::events::{
+    DaoAlterAuthority, DaoCreate, DaoMetadataUpdated, InvitationExpired, InvitationRejected,
+    JoinRequestExpired, JoinRequestRejected, JoinRequested, MemberInvited, MemberJoined,
+};
 
 impl Serialize for DaoCreate {
     fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
@@ -37,3 +40,89 @@ impl Serialize for DaoMetadataUpdated {
         s.end()
     }
 }
+
+impl Serialize for MemberInvited {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("MemberInvitedEvent", 3)?;
+        s.serialize_field("dao", &self.0)?;
+        s.serialize_field("member", &self.1)?;
+        s.serialize_field("expires", &self.2)?;
+        s.end()
+    }
+}
+
+impl Serialize for InvitationRejected {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("InvitationRejectedEvent", 2)?;
+        s.serialize_field("dao", &self.0)?;
+        s.serialize_field("member", &self.1)?;
+        s.end()
+    }
+}
+
+impl Serialize for JoinRequested {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("JoinRequestedEvent", 3)?;
+        s.serialize_field("dao", &self.0)?;
+        s.serialize_field("member", &self.1)?;
+        s.serialize_field("expires", &self.2)?;
+        s.end()
+    }
+}
+
+impl Serialize for JoinRequestRejected {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("JoinRequestRejectedEvent", 2)?;
+        s.serialize_field("dao", &self.0)?;
+        s.serialize_field("member", &self.1)?;
+        s.end()
+    }
+}
+
+impl Serialize for MemberJoined {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("MemberJoinedEvent", 2)?;
+        s.serialize_field("dao", &self.0)?;
+        s.serialize_field("member", &self.1)?;
+        s.end()
+    }
+}
+
+impl Serialize for InvitationExpired {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("InvitationExpiredEvent", 2)?;
+        s.serialize_field("dao", &self.0)?;
+        s.serialize_field("member", &self.1)?;
+        s.end()
+    }
+}
+
+impl Serialize for JoinRequestExpired {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("JoinRequestExpiredEvent", 2)?;
+        s.serialize_field("dao", &self.0)?;
+        s.serialize_field("member", &self.1)?;
+        s.end()
+    }
+}
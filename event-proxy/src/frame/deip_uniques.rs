@@ -0,0 +1,18 @@
+use serde::{
+    ser::{SerializeStruct, Serializer},
+    Serialize,
+};
+
+use crate::appchain_deip::deip_uniques::events::BatchMinted;
+
+impl Serialize for BatchMinted {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("BatchMintedEvent", 2)?;
+        s.serialize_field("class", &self.0)?;
+        s.serialize_field("count", &self.1)?;
+        s.end()
+    }
+}
@@ -6,3 +6,4 @@ pub mod octopus_appchain;
 #[cfg(feature = "octopus")]
 pub mod octopus_lpos;
 pub mod deip_investment_opportunity;
+pub mod deip_uniques;
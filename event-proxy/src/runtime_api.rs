@@ -6344,6 +6344,144 @@ pub mod api {
                 const PALLET: &'static str = "DeipDao";
                 const EVENT: &'static str = "DaoMetadataUpdated";
             }
+            #[derive(
+                :: subxt :: codec :: Encode,
+                :: subxt :: codec :: Decode,
+                Debug,
+                Clone,
+                Eq,
+                PartialEq,
+                scale_info :: TypeInfo,
+            )]
+            pub struct MemberInvited(
+                pub runtime_types::primitive_types::H160,
+                pub ::subxt::sp_core::crypto::AccountId32,
+                pub ::core::primitive::u32,
+            );
+            impl ::subxt::Event for MemberInvited {
+                const PALLET: &'static str = "DeipDao";
+                const EVENT: &'static str = "MemberInvited";
+            }
+            #[derive(
+                :: subxt :: codec :: Encode,
+                :: subxt :: codec :: Decode,
+                Debug,
+                Clone,
+                Eq,
+                PartialEq,
+                scale_info :: TypeInfo,
+            )]
+            pub struct InvitationRejected(
+                pub runtime_types::primitive_types::H160,
+                pub ::subxt::sp_core::crypto::AccountId32,
+            );
+            impl ::subxt::Event for InvitationRejected {
+                const PALLET: &'static str = "DeipDao";
+                const EVENT: &'static str = "InvitationRejected";
+            }
+            #[derive(
+                :: subxt :: codec :: Encode,
+                :: subxt :: codec :: Decode,
+                Debug,
+                Clone,
+                Eq,
+                PartialEq,
+                scale_info :: TypeInfo,
+            )]
+            pub struct JoinRequested(
+                pub runtime_types::primitive_types::H160,
+                pub ::subxt::sp_core::crypto::AccountId32,
+                pub ::core::primitive::u32,
+            );
+            impl ::subxt::Event for JoinRequested {
+                const PALLET: &'static str = "DeipDao";
+                const EVENT: &'static str = "JoinRequested";
+            }
+            #[derive(
+                :: subxt :: codec :: Encode,
+                :: subxt :: codec :: Decode,
+                Debug,
+                Clone,
+                Eq,
+                PartialEq,
+                scale_info :: TypeInfo,
+            )]
+            pub struct JoinRequestRejected(
+                pub runtime_types::primitive_types::H160,
+                pub ::subxt::sp_core::crypto::AccountId32,
+            );
+            impl ::subxt::Event for JoinRequestRejected {
+                const PALLET: &'static str = "DeipDao";
+                const EVENT: &'static str = "JoinRequestRejected";
+            }
+            #[derive(
+                :: subxt :: codec :: Encode,
+                :: subxt :: codec :: Decode,
+                Debug,
+                Clone,
+                Eq,
+                PartialEq,
+                scale_info :: TypeInfo,
+            )]
+            pub struct MemberJoined(
+                pub runtime_types::primitive_types::H160,
+                pub ::subxt::sp_core::crypto::AccountId32,
+            );
+            impl ::subxt::Event for MemberJoined {
+                const PALLET: &'static str = "DeipDao";
+                const EVENT: &'static str = "MemberJoined";
+            }
+            #[derive(
+                :: subxt :: codec :: Encode,
+                :: subxt :: codec :: Decode,
+                Debug,
+                Clone,
+                Eq,
+                PartialEq,
+                scale_info :: TypeInfo,
+            )]
+            pub struct InvitationExpired(
+                pub runtime_types::primitive_types::H160,
+                pub ::subxt::sp_core::crypto::AccountId32,
+            );
+            impl ::subxt::Event for InvitationExpired {
+                const PALLET: &'static str = "DeipDao";
+                const EVENT: &'static str = "InvitationExpired";
+            }
+            #[derive(
+                :: subxt :: codec :: Encode,
+                :: subxt :: codec :: Decode,
+                Debug,
+                Clone,
+                Eq,
+                PartialEq,
+                scale_info :: TypeInfo,
+            )]
+            pub struct JoinRequestExpired(
+                pub runtime_types::primitive_types::H160,
+                pub ::subxt::sp_core::crypto::AccountId32,
+            );
+            impl ::subxt::Event for JoinRequestExpired {
+                const PALLET: &'static str = "DeipDao";
+                const EVENT: &'static str = "JoinRequestExpired";
+            }
+            #[derive(
+                :: subxt :: codec :: Encode,
+                :: subxt :: codec :: Decode,
+                Debug,
+                Clone,
+                Eq,
+                PartialEq,
+                scale_info :: TypeInfo,
+            )]
+            pub struct NestingConsented(
+                pub runtime_types::primitive_types::H160,
+                pub ::core::option::Option<runtime_types::primitive_types::H160>,
+            );
+            impl ::subxt::Event for NestingConsented {
+                const PALLET: &'static str = "DeipDao";
+                const EVENT: &'static str = "NestingConsented";
+            }
         }
         pub mod storage {
             use super::runtime_types;
@@ -7002,6 +7140,29 @@ pub mod api {
             }
         }
     }
+    pub mod deip_uniques {
+        use super::runtime_types;
+        pub mod events {
+            use super::runtime_types;
+            #[derive(
+                :: subxt :: codec :: Encode,
+                :: subxt :: codec :: Decode,
+                Debug,
+                Clone,
+                Eq,
+                PartialEq,
+                scale_info :: TypeInfo,
+            )]
+            pub struct BatchMinted(
+                pub runtime_types::primitive_types::H160,
+                pub ::core::primitive::u32,
+            );
+            impl ::subxt::Event for BatchMinted {
+                const PALLET: &'static str = "DeipUniques";
+                const EVENT: &'static str = "BatchMinted";
+            }
+        }
+    }
     pub mod runtime_types {
         use super::runtime_types;
         pub mod appchain_deip_runtime {
@@ -9602,6 +9763,48 @@ pub mod api {
                             runtime_types::primitive_types::H160,
                         >,
                     ),
+                    #[codec(index = 15)]
+                    MemberInvited(
+                        runtime_types::primitive_types::H160,
+                        ::subxt::sp_core::crypto::AccountId32,
+                        ::core::primitive::u32,
+                    ),
+                    #[codec(index = 16)]
+                    InvitationRejected(
+                        runtime_types::primitive_types::H160,
+                        ::subxt::sp_core::crypto::AccountId32,
+                    ),
+                    #[codec(index = 17)]
+                    JoinRequested(
+                        runtime_types::primitive_types::H160,
+                        ::subxt::sp_core::crypto::AccountId32,
+                        ::core::primitive::u32,
+                    ),
+                    #[codec(index = 18)]
+                    JoinRequestRejected(
+                        runtime_types::primitive_types::H160,
+                        ::subxt::sp_core::crypto::AccountId32,
+                    ),
+                    #[codec(index = 19)]
+                    MemberJoined(
+                        runtime_types::primitive_types::H160,
+                        ::subxt::sp_core::crypto::AccountId32,
+                    ),
+                    #[codec(index = 20)]
+                    InvitationExpired(
+                        runtime_types::primitive_types::H160,
+                        ::subxt::sp_core::crypto::AccountId32,
+                    ),
+                    #[codec(index = 21)]
+                    JoinRequestExpired(
+                        runtime_types::primitive_types::H160,
+                        ::subxt::sp_core::crypto::AccountId32,
+                    ),
+                    #[codec(index = 22)]
+                    NestingConsented(
+                        runtime_types::primitive_types::H160,
+                        ::core::option::Option<runtime_types::primitive_types::H160>,
+                    ),
                 }
             }
         }
@@ -116,6 +116,7 @@ where
     C::Api: BlockBuilder<Block>,
     C::Api: Metadata<Block>,
     C::Api: deip_dao_rpc::DeipDaoRuntimeApi<Block, AccountId>,
+    C::Api: deip_assets_rpc::DeipAssetsRuntimeApi<Block, DeipAssetId, AccountId, AssetBalance>,
     C::Api: deip_rpc::DeipStorageRuntimeApi<
         Block,
         AccountId,
@@ -198,10 +199,12 @@ where
         Balance,
         AssetExtra,
         DeipAssetId,
+        pallet_deip::ProjectId,
     >::to_delegate(deip_assets_rpc::DeipAssetsRpcObj::<
+        C,
         sc_rpc::state::State<Block, C>,
         Block,
-    >::new(state)));
+    >::new(client.clone(), state)));
 
     let subscriptions = SubscriptionManager::new(Arc::new(subscription_executor.clone()));
     let (state, _) = sc_rpc::state::new_full(client.clone(), subscriptions, deny_unsafe, None);
@@ -214,10 +217,12 @@ where
         Balance,
         AssetExtra,
         DeipAssetId,
+        pallet_deip::ProjectId,
     >::to_delegate(deip_uniques_rpc::DeipUniquesRpcObj::<
+        C,
         sc_rpc::state::State<Block, C>,
         Block,
-    >::new(state)));
+    >::new(client.clone(), state)));
 
     let subscriptions = SubscriptionManager::new(Arc::new(subscription_executor.clone()));
     let (state, _) = sc_rpc::state::new_full(client.clone(), subscriptions, deny_unsafe, None);
@@ -227,6 +232,20 @@ where
         state,
     )));
 
+    let subscriptions = SubscriptionManager::new(Arc::new(subscription_executor.clone()));
+    let (state, _) = sc_rpc::state::new_full(client.clone(), subscriptions, deny_unsafe, None);
+
+    io.extend_with(deip_swap_rpc::DeipSwapRpc::<
+        <Block as BlockT>::Hash,
+        AccountId,
+        DeipAssetId,
+        AssetBalance,
+        Moment,
+    >::to_delegate(deip_swap_rpc::DeipSwapRpcObj::<
+        sc_rpc::state::State<Block, C>,
+        Block,
+    >::new(state)));
+
     let subscriptions = SubscriptionManager::new(Arc::new(subscription_executor));
     let (state, _) = sc_rpc::state::new_full(client.clone(), subscriptions, deny_unsafe, None);
 
@@ -22,6 +22,8 @@ sp_api::decl_runtime_apis! {
         fn get_project_content(id: &ProjectContentId) -> Option<ProjectContent<Hash, AccountId>>;
         fn get_nda(nda_id: &NdaId) -> Option<Nda<Hash, AccountId, Moment>>;
         fn get_review(review_id: &ReviewId) -> Option<Review<Hash, AccountId>>;
-        fn get_contract_agreement(id: &ContractAgreementId) -> Option<contract::Agreement<AccountId, Hash, Moment>>;
+        fn get_contract_agreement(id: &ContractAgreementId) -> Option<contract::Agreement<AccountId, Hash, Moment, Asset<AssetId, AssetBalance>>>;
+        fn may_access_content(account: &AccountId, id: &ProjectContentId) -> bool;
+        fn get_content_access_request(id: &ContentAccessRequestId) -> Option<ContentAccessRequest<AccountId>>;
     }
 }
@@ -17,8 +17,9 @@ use crate::{
     },
     Pallet,
 };
-use sp_runtime::traits::{Hash, Saturating, Scale, StaticLookup};
+use sp_runtime::traits::{Bounded, Hash, Saturating, Scale, StaticLookup};
 
+use deip_asset_system::{DeipNftSystem, DeipNftSystemBenchmarking};
 use deip_projects_info::DeipProjectsInfo;
 use deip_serializable_u128::SerializableAtLeast32BitUnsigned;
 use pallet_assets::Config as AssetsConfig;
@@ -98,6 +99,8 @@ fn create_project_asset<T: Config + AssetsConfig + DeipAssetsConfig + BalancesCo
         T::AssetIdInit::asset_id(project.external_id.as_bytes()),
         project.team_id.clone().into(),
         <T as AssetsConfig>::Balance::from(200u16),
+        None,
+        None,
     )
     .unwrap();
     Ok(None.into())
@@ -300,8 +303,57 @@ fn _create_review<T: Config>(review: ReviewOf<T>) -> ReviewOf<T> {
     ReviewMapV1::<T>::get(external_id)
 }
 
+fn create_gated_content<T: Config>() -> (ProjectContentOf<T>, NftClassIdOf<T>)
+where
+    T::NftSystem: DeipNftSystemBenchmarking<T::AccountId>,
+{
+    let project = _create_project::<T>(init_project::<T>(1, 0));
+    let content = _create_project_content::<T>(init_project_content::<T>(&project, 0, None));
+    let class = T::NftSystem::benchmark_class(1, &content.team_id);
+    Pallet::<T>::set_content_nft_class(
+        RawOrigin::Signed(content.team_id.clone()).into(),
+        content.external_id,
+        Some(class),
+    )
+    .unwrap();
+    (content, class)
+}
+
+fn init_content_requester<T: Config>(
+    content: &ProjectContentOf<T>,
+    class: NftClassIdOf<T>,
+) -> T::AccountId {
+    let requester = init_member::<T>(1);
+    T::Currency::make_free_balance_be(&requester, BalanceOf::<T>::max_value() / 2u32.into());
+    T::NftSystem::mint(&content.team_id, class, &requester).unwrap();
+    requester
+}
+
+fn init_content_access_key<T: Config>(byte: u8) -> ContentAccessKeyOf<T> {
+    vec![byte; T::MaxContentAccessKeyLength::get() as usize].try_into().unwrap()
+}
+
+fn _create_content_access_request<T: Config>(
+    requester: &T::AccountId,
+    content: &ProjectContentOf<T>,
+) -> ContentAccessRequestId {
+    let external_id = ContentAccessRequestId::from([1; 20]);
+    Pallet::<T>::create_content_access_request(
+        RawOrigin::Signed(requester.clone()).into(),
+        external_id,
+        content.external_id,
+        init_content_access_key::<T>(1),
+    )
+    .unwrap();
+    external_id
+}
+
 benchmarks! {
-    where_clause { where T: pallet_deip_assets::Config + pallet_balances::Config }
+    where_clause {
+        where
+            T: pallet_deip_assets::Config + pallet_balances::Config,
+            T::NftSystem: DeipNftSystemBenchmarking<T::AccountId>
+    }
 
     create_project {
         let d in 1 .. 50;
@@ -479,6 +531,60 @@ benchmarks! {
     //     ).into());
     // }
 
+    set_content_nft_class {
+        let project = _create_project::<T>(init_project::<T>(1, 0));
+        let content = _create_project_content::<T>(init_project_content::<T>(&project, 0, None));
+        let class = T::NftSystem::benchmark_class(1, &content.team_id);
+    }: _(RawOrigin::Signed(content.team_id.clone()), content.external_id, Some(class))
+    verify {
+        assert_last_event::<T>(Event::<T>::ContentNftClassSet(
+            content.team_id,
+            content.external_id
+        ).into());
+    }
+
+    create_content_access_request {
+        let (content, class) = create_gated_content::<T>();
+        let requester = init_content_requester::<T>(&content, class);
+        let external_id = ContentAccessRequestId::from([1; 20]);
+        let public_key = init_content_access_key::<T>(1);
+    }: _(RawOrigin::Signed(requester.clone()), external_id, content.external_id, public_key)
+    verify {
+        assert_last_event::<T>(Event::<T>::ContentAccessRequestCreated(
+            requester,
+            external_id
+        ).into());
+    }
+
+    fulfill_content_access_request {
+        let (content, class) = create_gated_content::<T>();
+        let requester = init_content_requester::<T>(&content, class);
+        let external_id = _create_content_access_request::<T>(&requester, &content);
+        let encrypted_key = init_content_access_key::<T>(2);
+        let proof_of_encrypted_key = init_content_access_key::<T>(3);
+    }: _(RawOrigin::Signed(content.team_id.clone()),
+            external_id,
+            encrypted_key,
+            proof_of_encrypted_key)
+    verify {
+        assert_last_event::<T>(Event::<T>::ContentAccessRequestFulfilled(
+            content.team_id,
+            external_id
+        ).into());
+    }
+
+    reject_content_access_request {
+        let (content, class) = create_gated_content::<T>();
+        let requester = init_content_requester::<T>(&content, class);
+        let external_id = _create_content_access_request::<T>(&requester, &content);
+    }: _(RawOrigin::Signed(content.team_id.clone()), external_id)
+    verify {
+        assert_last_event::<T>(Event::<T>::ContentAccessRequestRejected(
+            content.team_id,
+            external_id
+        ).into());
+    }
+
     create_review {
         let d in 1 .. 50;
 
@@ -911,6 +1017,8 @@ fn _add_balance<T: Config + AssetsConfig + DeipAssetsConfig + BalancesConfig>(
         asset_id.clone(),
         asset_admin.clone(),
         min_balance.clone(),
+        None,
+        None,
     )
     .unwrap();
 
@@ -943,6 +1051,8 @@ fn _create_asset<T: Config + AssetsConfig + DeipAssetsConfig + BalancesConfig>(
         asset_id,
         asset_admin,
         min_balance,
+        None,
+        None,
     )
 }
 
@@ -0,0 +1,244 @@
+use super::*;
+use deip_asset_system::{DeipNftSystem, OnNftClassDestroyed};
+
+/// Unique Content Access Request reference
+pub type RequestId = H160;
+
+/// Statuses of content access requests
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Status {
+    Pending,
+    Fulfilled,
+    Rejected,
+}
+
+/// Request of a holder of the NFT class linked to the content
+/// to receive the key which decrypts the content.
+/// The team of the content should fulfill or reject it.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct Request<AccountId> {
+    /// Reference for external world and uniques control
+    pub(crate) external_id: RequestId,
+    /// Reference to Project Content
+    pub(crate) project_content_external_id: ProjectContentId,
+    /// Reference to Requester (creator of this request)
+    pub(crate) requester: AccountId,
+    /// Public key of the requester to encrypt the content key with
+    pub(crate) public_key: Vec<u8>,
+    /// Execution status
+    pub(crate) status: Status,
+    /// Reference to access granter if approved
+    pub(crate) grantor: Option<AccountId>,
+    /// Encrypted key which decrypts the content
+    pub(crate) encrypted_key: Option<Vec<u8>>,
+    /// Proof that the key is encrypted with the public key of the requester
+    pub(crate) proof_of_encrypted_key: Option<Vec<u8>>,
+}
+
+pub type NftClassIdOf<T> =
+    <<T as Config>::NftSystem as DeipNftSystem<<T as system::Config>::AccountId>>::ClassId;
+pub type RequestOf<T> = Request<<T as system::Config>::AccountId>;
+/// Public or encrypted key bounded by `MaxContentAccessKeyLength`
+pub type KeyOf<T> = BoundedVec<u8, <T as Config>::MaxContentAccessKeyLength>;
+
+impl<T: Config> Module<T> {
+    /// Checks if `account` may access the content.
+    /// The team always may, holders of the linked NFT class may while they hold
+    /// an instance of it, anyone may access not gated content of public projects.
+    pub fn may_access_content(account: &T::AccountId, id: &ProjectContentId) -> bool {
+        let content = match ProjectContentMapV1::<T>::try_get(id) {
+            Ok(content) => content,
+            Err(_) => return false,
+        };
+        if *account == content.team_id {
+            return true
+        }
+
+        match NftClassIdByContentId::<T>::get(id) {
+            Some(class) => T::NftSystem::is_holder(&class, account),
+            None => !ProjectMapV1::<T>::get(content.project_external_id).is_private,
+        }
+    }
+
+    pub fn get_content_access_request(id: &RequestId) -> Option<RequestOf<T>> {
+        ContentAccessRequestMap::<T>::get(id)
+    }
+
+    pub(super) fn set_content_nft_class_impl(
+        account: T::AccountId,
+        id: ProjectContentId,
+        class: Option<NftClassIdOf<T>>,
+    ) -> DispatchResult {
+        let content =
+            ProjectContentMapV1::<T>::try_get(id).map_err(|_| Error::<T>::NoSuchProjectContent)?;
+        ensure!(account == content.team_id, Error::<T>::NoPermission);
+
+        let previous = NftClassIdByContentId::<T>::get(id);
+        if let Some(ref class) = class {
+            ensure!(
+                T::NftSystem::class_owner(class).as_ref() == Some(&content.team_id),
+                Error::<T>::NftClassNotOwnedByTeam
+            );
+            ensure!(
+                previous.as_ref() == Some(class) ||
+                    ContentCountByNftClassId::<T>::get(class) < T::MaxContentsPerNftClass::get(),
+                Error::<T>::TooManyContentsPerNftClass
+            );
+        }
+
+        if let Some(previous) = previous {
+            NftClassIdByContentId::<T>::remove(id);
+            ContentIdByNftClassId::<T>::remove(previous, id);
+            ContentCountByNftClassId::<T>::mutate(previous, |count| {
+                *count = count.saturating_sub(1)
+            });
+        }
+        if let Some(class) = class {
+            NftClassIdByContentId::<T>::insert(id, class);
+            ContentIdByNftClassId::<T>::insert(class, id, ());
+            ContentCountByNftClassId::<T>::mutate(class, |count| *count += 1);
+        }
+
+        Self::deposit_event(RawEvent::ContentNftClassSet(account, id));
+
+        Ok(())
+    }
+
+    pub(super) fn create_content_access_request_impl(
+        account: T::AccountId,
+        external_id: RequestId,
+        project_content_external_id: ProjectContentId,
+        public_key: KeyOf<T>,
+    ) -> DispatchResult {
+        ensure!(
+            !ContentAccessRequestMap::<T>::contains_key(external_id),
+            Error::<T>::ContentAccessRequestAlreadyExists
+        );
+        let class = NftClassIdByContentId::<T>::get(project_content_external_id)
+            .ok_or(Error::<T>::ContentIsNotNftGated)?;
+        ensure!(T::NftSystem::is_holder(&class, &account), Error::<T>::NotContentNftHolder);
+
+        let deposit = T::ContentAccessRequestDeposit::get();
+        T::Currency::reserve(&account, deposit)?;
+        ContentAccessRequestDeposits::<T>::insert(external_id, deposit);
+
+        let request = Request {
+            external_id,
+            project_content_external_id,
+            requester: account.clone(),
+            public_key: public_key.into_inner(),
+            status: Status::Pending,
+            grantor: None,
+            encrypted_key: None,
+            proof_of_encrypted_key: None,
+        };
+        ContentAccessRequestMap::<T>::insert(external_id, request);
+        ContentAccessRequestIdByContentId::insert(project_content_external_id, external_id, ());
+
+        Self::deposit_event(RawEvent::ContentAccessRequestCreated(account, external_id));
+
+        Ok(())
+    }
+
+    pub(super) fn fulfill_content_access_request_impl(
+        account: T::AccountId,
+        external_id: RequestId,
+        encrypted_key: KeyOf<T>,
+        proof_of_encrypted_key: KeyOf<T>,
+    ) -> DispatchResult {
+        let requester = ContentAccessRequestMap::<T>::try_mutate(
+            external_id,
+            |maybe_request| -> Result<T::AccountId, DispatchError> {
+                let request =
+                    maybe_request.as_mut().ok_or(Error::<T>::NoSuchContentAccessRequest)?;
+                ensure!(
+                    request.status == Status::Pending,
+                    Error::<T>::ContentAccessRequestAlreadyFinalized
+                );
+                Self::ensure_content_team(&account, &request.project_content_external_id)?;
+                // the NFT may have moved since the request was created
+                let content = &request.project_content_external_id;
+                ensure!(
+                    Self::may_access_content(&request.requester, content),
+                    Error::<T>::NotContentNftHolder
+                );
+
+                request.status = Status::Fulfilled;
+                request.grantor = Some(account.clone());
+                request.encrypted_key = Some(encrypted_key.into_inner());
+                request.proof_of_encrypted_key = Some(proof_of_encrypted_key.into_inner());
+
+                Ok(request.requester.clone())
+            },
+        )?;
+        Self::unreserve_request_deposit(&requester, external_id);
+
+        Self::deposit_event(RawEvent::ContentAccessRequestFulfilled(account, external_id));
+
+        Ok(())
+    }
+
+    pub(super) fn reject_content_access_request_impl(
+        account: T::AccountId,
+        external_id: RequestId,
+    ) -> DispatchResult {
+        let requester = ContentAccessRequestMap::<T>::try_mutate(
+            external_id,
+            |maybe_request| -> Result<T::AccountId, DispatchError> {
+                let request =
+                    maybe_request.as_mut().ok_or(Error::<T>::NoSuchContentAccessRequest)?;
+                ensure!(
+                    request.status == Status::Pending,
+                    Error::<T>::ContentAccessRequestAlreadyFinalized
+                );
+                Self::ensure_content_team(&account, &request.project_content_external_id)?;
+
+                request.status = Status::Rejected;
+                request.grantor = Some(account.clone());
+
+                Ok(request.requester.clone())
+            },
+        )?;
+        Self::unreserve_request_deposit(&requester, external_id);
+
+        Self::deposit_event(RawEvent::ContentAccessRequestRejected(account, external_id));
+
+        Ok(())
+    }
+
+    fn unreserve_request_deposit(requester: &T::AccountId, id: RequestId) {
+        let deposit = ContentAccessRequestDeposits::<T>::take(id);
+        T::Currency::unreserve(requester, deposit);
+    }
+
+    fn ensure_content_team(account: &T::AccountId, id: &ProjectContentId) -> DispatchResult {
+        let content =
+            ProjectContentMapV1::<T>::try_get(id).map_err(|_| Error::<T>::NoSuchProjectContent)?;
+        ensure!(*account == content.team_id, Error::<T>::NoPermission);
+        Ok(())
+    }
+}
+
+/// Contents gated by a destroyed class become not gated and licenses of the project
+/// aren't represented by the class anymore. A new class with the same id doesn't inherit them.
+impl<T: Config> OnNftClassDestroyed<NftClassIdOf<T>> for Module<T> {
+    fn on_nft_class_destroyed(class: &NftClassIdOf<T>) -> Weight {
+        let mut contents: u64 = 0;
+        for (id, ()) in ContentIdByNftClassId::<T>::drain_prefix(class) {
+            NftClassIdByContentId::<T>::remove(id);
+            contents += 1;
+        }
+        ContentCountByNftClassId::<T>::remove(class);
+        T::DbWeight::get()
+            .reads_writes(contents + 1, contents * 2 + 1)
+            .saturating_add(Self::unlink_license_nft_class(class))
+    }
+
+    fn max_weight() -> Weight {
+        let contents = T::MaxContentsPerNftClass::get() as Weight;
+        T::DbWeight::get().reads_writes(contents + 2, contents * 2 + 3)
+    }
+}
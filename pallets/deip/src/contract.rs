@@ -1,5 +1,7 @@
 use crate::*;
 
+use deip_asset_system::DeipNftSystem;
+use sp_runtime::traits::Zero;
 use sp_std::vec;
 
 pub type Id = H160;
@@ -7,28 +9,42 @@ pub type Id = H160;
 #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
-pub enum Terms {
+pub enum Terms<Asset> {
     GenericContractAgreement,
+    /// License of the project `source` paid by the licensee with `price`.
+    LicenseAgreement { source: ProjectId, price: Asset },
 }
 
+pub type TermsOf<T> = Terms<DeipAsset<T>>;
+
 #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 pub enum IndexTerms {
     GenericContractAgreement,
+    LicenseAgreement,
 }
 
 #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
-pub enum Agreement<AccountId, Hash, Moment> {
+pub enum Agreement<AccountId, Hash, Moment, Asset> {
     None,
     GenericContract(GenericContractStatus<AccountId, Hash, Moment>),
+    License(LicenseStatus<AccountId, Hash, Moment, Asset>),
 }
 
-pub type AgreementOf<T> = Agreement<AccountIdOf<T>, HashOf<T>, MomentOf<T>>;
+pub type AgreementOf<T> = Agreement<AccountIdOf<T>, HashOf<T>, MomentOf<T>, DeipAsset<T>>;
+
+pub type NftInstanceIdOf<T> = <<T as Config>::NftSystem as DeipNftSystem<
+    <T as system::Config>::AccountId,
+>>::InstanceId;
 
-impl<AccountId, Hash, Moment> Default for Agreement<AccountId, Hash, Moment> {
+/// NFT instance which represents a signed license.
+/// Holder of the instance is the licensee.
+pub type LicenseNftOf<T> = (NftClassIdOf<T>, NftInstanceIdOf<T>);
+
+impl<AccountId, Hash, Moment, Asset> Default for Agreement<AccountId, Hash, Moment, Asset> {
     fn default() -> Self {
         Agreement::None
     }
@@ -59,6 +75,9 @@ pub enum LicenseStatus<AccountId, Hash, Moment, Asset> {
     SignedByLicenser(License<AccountId, Hash, Moment, Asset>),
     Signed(License<AccountId, Hash, Moment, Asset>),
     Rejected(License<AccountId, Hash, Moment, Asset>),
+    /// Signed license which NFT has no holder: it's burned, fractionalized or its class
+    /// is destroyed. Never stored, see [`Module::get_contract_agreement`].
+    Void(License<AccountId, Hash, Moment, Asset>),
 }
 
 #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
@@ -96,7 +115,7 @@ impl<T: Config> Module<T> {
         hash: HashOf<T>,
         activation_time: Option<MomentOf<T>>,
         expiration_time: Option<MomentOf<T>>,
-        terms: Terms,
+        terms: TermsOf<T>,
     ) -> DispatchResultWithPostInfo {
         ensure!(account == creator, Error::<T>::NoPermission);
         ensure!(!parties.is_empty(), Error::<T>::ContractAgreementNoParties);
@@ -135,6 +154,16 @@ impl<T: Config> Module<T> {
                 activation_time,
                 expiration_time,
             ),
+            Terms::LicenseAgreement { source, price } => Self::create_license(
+                id,
+                creator,
+                parties,
+                hash,
+                activation_time,
+                expiration_time,
+                source,
+                price,
+            ),
         }
     }
 
@@ -150,6 +179,7 @@ impl<T: Config> Module<T> {
 
         match agreement {
             Agreement::GenericContract(status) => Self::accept_generic_contract(party, status),
+            Agreement::License(status) => Self::accept_license(party, status),
             Agreement::None => Err(Error::<T>::ContractAgreementWrongAgreement.into()),
         }
     }
@@ -167,6 +197,7 @@ impl<T: Config> Module<T> {
         match agreement {
             Agreement::None => Err(Error::<T>::ContractAgreementWrongAgreement.into()),
             Agreement::GenericContract(status) => Self::reject_generic_contract(party, status),
+            Agreement::License(status) => Self::reject_license(party, status),
         }
     }
 
@@ -278,4 +309,245 @@ impl<T: Config> Module<T> {
             },
         }
     }
+
+    /// Returns the license NFT of the contract agreement if it has been minted.
+    pub fn license_nft(id: &Id) -> Option<LicenseNftOf<T>> {
+        LicenseNftByContractAgreementId::<T>::get(id)
+    }
+
+    /// Returns the fee `licensee` pays on accepting the license signed by its licenser.
+    pub fn license_fee(id: &Id, licensee: &AccountIdOf<T>) -> Option<DeipAsset<T>> {
+        match ContractAgreementMap::<T>::get(id) {
+            Agreement::License(LicenseStatus::SignedByLicenser(license))
+                if license.licensee == *licensee =>
+                Some(license.price),
+            _ => None,
+        }
+    }
+
+    /// Replaces the licensee of the signed license with the current holder of its NFT.
+    /// The license is void if its NFT has no holder.
+    pub(super) fn resolve_licensee(id: &Id, agreement: AgreementOf<T>) -> AgreementOf<T> {
+        match agreement {
+            Agreement::License(LicenseStatus::Signed(mut license)) => {
+                let (class, instance) = match Self::license_nft(id) {
+                    Some(nft) => nft,
+                    None => return Agreement::License(LicenseStatus::Signed(license)),
+                };
+                match T::NftSystem::holder(&class, &instance) {
+                    Some(holder) => {
+                        license.licensee = holder;
+                        Agreement::License(LicenseStatus::Signed(license))
+                    },
+                    None => Agreement::License(LicenseStatus::Void(license)),
+                }
+            },
+            agreement => agreement,
+        }
+    }
+
+    pub(super) fn set_license_nft_class_impl(
+        account: AccountIdOf<T>,
+        project_id: ProjectId,
+        class: Option<NftClassIdOf<T>>,
+    ) -> DispatchResult {
+        let project =
+            ProjectMapV1::<T>::try_get(project_id).map_err(|_| Error::<T>::NoSuchProject)?;
+        ensure!(account == project.team_id, Error::<T>::NoPermission);
+
+        if let Some(class) = class {
+            ensure!(
+                T::NftSystem::class_issuer(&class).as_ref() == Some(&project.team_id),
+                Error::<T>::LicenseNftClassNotIssuedByTeam
+            );
+            ensure!(
+                ProjectIdByLicenseNftClassId::<T>::get(class)
+                    .map_or(true, |linked| linked == project_id),
+                Error::<T>::LicenseNftClassUsedByOtherProject
+            );
+        }
+
+        if let Some(previous) = LicenseNftClassIdByProjectId::<T>::take(project_id) {
+            ProjectIdByLicenseNftClassId::<T>::remove(previous);
+        }
+        if let Some(class) = class {
+            LicenseNftClassIdByProjectId::<T>::insert(project_id, class);
+            ProjectIdByLicenseNftClassId::<T>::insert(class, project_id);
+        }
+
+        Self::deposit_event(RawEvent::LicenseNftClassSet(account, project_id));
+
+        Ok(())
+    }
+
+    /// Unsets the destroyed NFT class from the project which licenses it represents.
+    pub(super) fn unlink_license_nft_class(class: &NftClassIdOf<T>) -> Weight {
+        match ProjectIdByLicenseNftClassId::<T>::take(class) {
+            Some(project_id) => {
+                LicenseNftClassIdByProjectId::<T>::remove(project_id);
+                T::DbWeight::get().reads_writes(1, 2)
+            },
+            None => T::DbWeight::get().reads(1),
+        }
+    }
+
+    fn create_license(
+        id: Id,
+        creator: AccountIdOf<T>,
+        parties: Vec<T::AccountId>,
+        hash: HashOf<T>,
+        activation_time: Option<MomentOf<T>>,
+        expiration_time: Option<MomentOf<T>>,
+        project_id: ProjectId,
+        price: DeipAsset<T>,
+    ) -> DispatchResultWithPostInfo {
+        let project =
+            ProjectMapV1::<T>::try_get(project_id).map_err(|_| Error::<T>::NoSuchProject)?;
+        ensure!(!price.amount().is_zero(), Error::<T>::ContractAgreementFeeMustBePositive);
+        ensure!(parties.len() == 2, Error::<T>::ContractAgreementLicenseTwoPartiesRequired);
+
+        let licenser = project.team_id;
+        let licensee = match parties.iter().position(|p| *p == licenser) {
+            Some(0) => parties[1].clone(),
+            Some(_) => parties[0].clone(),
+            None =>
+                return Err(
+                    Error::<T>::ContractAgreementLicenseProjectTeamIsNotListedInParties.into()
+                ),
+        };
+
+        let license = License {
+            id,
+            creator,
+            licenser,
+            licensee,
+            hash,
+            activation_time,
+            expiration_time,
+            project_id,
+            price,
+        };
+
+        ContractAgreementMap::<T>::insert(id, Agreement::License(LicenseStatus::Unsigned(license)));
+        ContractAgreementIdByType::insert(IndexTerms::LicenseAgreement, id, ());
+
+        Self::deposit_event(RawEvent::ContractAgreementCreated(id));
+
+        Ok(Some(T::DeipWeightInfo::create_contract_agreement_project_license()).into())
+    }
+
+    fn accept_license(
+        party: AccountIdOf<T>,
+        status: LicenseStatus<AccountIdOf<T>, HashOf<T>, MomentOf<T>, DeipAsset<T>>,
+    ) -> DispatchResultWithPostInfo {
+        match status {
+            LicenseStatus::Rejected(_) => Err(Error::<T>::ContractAgreementRejected.into()),
+            LicenseStatus::Signed(_) | LicenseStatus::Void(_) =>
+                Err(Error::<T>::ContractAgreementAlreadyAccepted.into()),
+            LicenseStatus::Unsigned(license) => {
+                ensure!(
+                    party == license.licenser,
+                    Error::<T>::ContractAgreementLicensePartyIsNotLicenser
+                );
+
+                let id = license.id;
+                ContractAgreementMap::<T>::insert(
+                    id,
+                    Agreement::License(LicenseStatus::SignedByLicenser(license)),
+                );
+
+                Self::deposit_event(RawEvent::ContractAgreementAccepted(id, party));
+                Ok(Some(T::DeipWeightInfo::accept_contract_agreement_project_license_unsigned())
+                    .into())
+            },
+            LicenseStatus::SignedByLicenser(license) => {
+                ensure!(
+                    party == license.licensee,
+                    Error::<T>::ContractAgreementLicensePartyIsNotLicensee
+                );
+
+                let now = pallet_timestamp::Pallet::<T>::get();
+                if let Some(s) = license.activation_time {
+                    ensure!(s <= now, Error::<T>::ContractAgreementLicenseIsNotActive);
+                }
+                if let Some(e) = license.expiration_time {
+                    ensure!(now < e, Error::<T>::ContractAgreementLicenseExpired);
+                }
+
+                let asset = *license.price.id();
+                let amount = *license.price.amount();
+                ensure!(
+                    T::account_balance(&party, &asset) >= amount,
+                    Error::<T>::ContractAgreementLicenseNotEnoughBalance
+                );
+                T::transactionally_transfer(&party, asset, &[(amount, license.licenser.clone())])
+                    .map_err(|_| Error::<T>::ContractAgreementLicenseFailedToChargeFee)?;
+
+                Self::mint_license_nft(&license)?;
+
+                let id = license.id;
+                ContractAgreementMap::<T>::insert(
+                    id,
+                    Agreement::License(LicenseStatus::Signed(license)),
+                );
+
+                Self::deposit_event(RawEvent::ContractAgreementAccepted(id, party));
+                Self::deposit_event(RawEvent::ContractAgreementFinalized(id));
+                Ok(Some(
+                    T::DeipWeightInfo::accept_contract_agreement_project_license_signed_by_licenser(
+                    ),
+                )
+                .into())
+            },
+        }
+    }
+
+    /// Mints an instance of the license NFT class of the project to the licensee
+    /// if the project has such class.
+    fn mint_license_nft(license: &LicenseOf<T>) -> DispatchResult {
+        let class = match LicenseNftClassIdByProjectId::<T>::get(license.project_id) {
+            Some(class) => class,
+            None => return Ok(()),
+        };
+
+        let instance = T::NftSystem::mint(&license.licenser, class, &license.licensee)
+            .map_err(|_| Error::<T>::ContractAgreementLicenseNftMintFailed)?;
+        LicenseNftByContractAgreementId::<T>::insert(license.id, (class, instance));
+
+        Self::deposit_event(RawEvent::LicenseNftMinted(license.id));
+
+        Ok(())
+    }
+
+    fn reject_license(
+        party: AccountIdOf<T>,
+        status: LicenseStatus<AccountIdOf<T>, HashOf<T>, MomentOf<T>, DeipAsset<T>>,
+    ) -> DispatchResult {
+        let license = match status {
+            LicenseStatus::Rejected(_) => return Err(Error::<T>::ContractAgreementRejected.into()),
+            LicenseStatus::Signed(_) | LicenseStatus::Void(_) =>
+                return Err(Error::<T>::ContractAgreementAlreadyAccepted.into()),
+            LicenseStatus::Unsigned(license) => {
+                ensure!(
+                    party == license.licenser || party == license.licensee,
+                    Error::<T>::ContractAgreementPartyIsNotListed
+                );
+                license
+            },
+            LicenseStatus::SignedByLicenser(license) => {
+                ensure!(
+                    party == license.licensee,
+                    Error::<T>::ContractAgreementLicensePartyIsNotLicensee
+                );
+                license
+            },
+        };
+
+        let id = license.id;
+        ContractAgreementMap::<T>::insert(id, Agreement::License(LicenseStatus::Rejected(license)));
+
+        Self::deposit_event(RawEvent::ContractAgreementRejected(id, party));
+
+        Ok(())
+    }
 }
@@ -13,7 +13,12 @@ pub type DeipAssetBalance<T: crate::Config> =
 <T as DeipAssetSystem<T::AccountId, crate::ProjectId, InvestmentId>>::Balance;
 
 pub type DeipAsset<T: crate::Config> = Asset<DeipAssetId<T>, DeipAssetBalance<T>>;
-pub type FundingModelOf<T: crate::Config> = FundingModel<T::Moment, DeipAsset<T>>;
+pub type FundingModelOf<T: crate::Config> = FundingModel<
+    T::Moment,
+    DeipAsset<T>,
+    crate::ProjectId,
+    crate::content_access::NftClassIdOf<T>,
+>;
 
 pub type SimpleCrowdfundingOf<T: crate::Config> = SimpleCrowdfunding<
     T::Moment,
@@ -34,6 +34,11 @@
 //! * [`create_contract_agreement`](./enum.Call.html#variant.create_contract_agreement)
 //! * [`accept_contract_agreement`](./enum.Call.html#variant.accept_contract_agreement)
 //! * [`reject_contract_agreement`](./enum.Call.html#variant.reject_contract_agreement)
+//! * [`set_license_nft_class`](./enum.Call.html#variant.set_license_nft_class)
+//! * [`set_content_nft_class`](./enum.Call.html#variant.set_content_nft_class)
+//! * [`create_content_access_request`](./enum.Call.html#variant.create_content_access_request)
+//! * [`fulfill_content_access_request`](./enum.Call.html#variant.fulfill_content_access_request)
+//! * [`reject_content_access_request`](./enum.Call.html#variant.reject_content_access_request)
 //!
 //! [`Call`]: ./enum.Call.html
 //! [`Config`]: ./trait.Config.html
@@ -48,6 +53,7 @@ use frame_support::{
     pallet_prelude::*,
     storage::IterableStorageDoubleMap,
     traits::{Currency, ReservableCurrency},
+    transactional,
     weights::Weight,
     StorageMap,
 };
@@ -75,7 +81,14 @@ pub use review::{Id as ReviewId, Review, Vote as DeipReviewVote};
 pub mod contract;
 pub use contract::{
     AgreementOf as ContractAgreementOf, Id as ContractAgreementId,
-    IndexTerms as ContractAgreementIndexTerms, Terms,
+    IndexTerms as ContractAgreementIndexTerms, LicenseNftOf, NftInstanceIdOf, Terms,
+    TermsOf as ContractAgreementTermsOf,
+};
+
+pub mod content_access;
+pub use content_access::{
+    KeyOf as ContentAccessKeyOf, NftClassIdOf, Request as ContentAccessRequest,
+    RequestId as ContentAccessRequestId, RequestOf as ContentAccessRequestOf,
 };
 
 use deip_transaction_ctx::PortalCtxT;
@@ -141,6 +154,18 @@ pub trait Config:
 
     type MaxNdaParties: Get<u16>;
     type MaxInvestmentShares: Get<u16>;
+
+    /// NFT classes which gate access to project contents.
+    type NftSystem: deip_asset_system::DeipNftSystem<Self::AccountId>;
+
+    /// Max number of contents gated by an NFT class.
+    type MaxContentsPerNftClass: Get<u32>;
+
+    /// Max length of public and encrypted keys in content access requests.
+    type MaxContentAccessKeyLength: Get<u32>;
+
+    /// Deposit reserved from the requester till the content access request is resolved.
+    type ContentAccessRequestDeposit: Get<BalanceOf<Self>>;
 }
 
 /// Unique Project ID reference
@@ -320,6 +345,21 @@ decl_event! {
         ContractAgreementAccepted(ContractAgreementId, AccountId),
         ContractAgreementFinalized(ContractAgreementId),
         ContractAgreementRejected(ContractAgreementId, AccountId),
+        /// Event emitted when a NFT class for licenses has been set to the project or unset. [BelongsTo, ProjectId]
+        LicenseNftClassSet(AccountId, ProjectId),
+        /// Event emitted when a NFT representing the signed license has been minted to the licensee.
+        LicenseNftMinted(ContractAgreementId),
+
+        // ==== Content Access ====
+
+        /// Event emitted when a NFT class has been linked to the content or unlinked from it. [BelongsTo, ProjectContentId]
+        ContentNftClassSet(AccountId, ProjectContentId),
+        /// Event emitted when a content access request has been created. [BelongsTo, ContentAccessRequestId]
+        ContentAccessRequestCreated(AccountId, ContentAccessRequestId),
+        /// Event emitted when a content access request has been fulfilled. [BelongsTo, ContentAccessRequestId]
+        ContentAccessRequestFulfilled(AccountId, ContentAccessRequestId),
+        /// Event emitted when a content access request has been rejected. [BelongsTo, ContentAccessRequestId]
+        ContentAccessRequestRejected(AccountId, ContentAccessRequestId),
     }
 }
 
@@ -415,6 +455,28 @@ decl_error! {
         ContractAgreementPartyIsNotListed,
         ContractAgreementAlreadyAcceptedByParty,
         ContractAgreementRejected,
+        /// Failed to mint the NFT representing the license.
+        ContractAgreementLicenseNftMintFailed,
+        /// Instances of the NFT class for licenses must be minted by the team of the project.
+        LicenseNftClassNotIssuedByTeam,
+        /// The NFT class represents licenses of another project.
+        LicenseNftClassUsedByOtherProject,
+
+        // ==== Content Access ====
+
+        /// NFT class must be owned by the team of the content.
+        NftClassNotOwnedByTeam,
+        /// No NFT class is linked to the content.
+        ContentIsNotNftGated,
+        /// Account doesn't hold an instance of the NFT class linked to the content.
+        NotContentNftHolder,
+        /// Content Access Request with this ID already exists.
+        ContentAccessRequestAlreadyExists,
+        /// The Content Access Request with this ID does not exist.
+        NoSuchContentAccessRequest,
+        ContentAccessRequestAlreadyFinalized,
+        /// The NFT class gates the max number of contents.
+        TooManyContentsPerNftClass,
     }
 }
 
@@ -425,6 +487,8 @@ pub enum StorageVersion {
     V0,
     /// Separate investment_opportunity.
     V1,
+    /// Count active projects of teams.
+    V2,
 }
 
 decl_storage! {
@@ -432,7 +496,7 @@ decl_storage! {
         /// Should be used in conjunction with `on_runtime_upgrade` to ensure an upgrade is executed
         /// once, even if the code is not removed in time.
         pub PalletStorageVersion get(fn pallet_storage_version)
-            build(|_| StorageVersion::V1): StorageVersion = StorageVersion::V0;
+            build(|_| StorageVersion::V2): StorageVersion = StorageVersion::V0;
 
         ProjectMap: map hasher(identity) ProjectId => ProjectOf<T>;
         // Migrate key hasher
@@ -441,6 +505,8 @@ decl_storage! {
         ProjectIdByTeamId: double_map hasher(blake2_128_concat) AccountIdOf<T>, hasher(identity) ProjectId => ();
         // Migrate key hasher
         ProjectIdByTeamIdV1: double_map hasher(blake2_128_concat) AccountIdOf<T>, hasher(blake2_128_concat) ProjectId => ();
+        /// Number of projects of the team without final results
+        ActiveProjectCountByTeam: map hasher(blake2_128_concat) AccountIdOf<T> => u32;
 
         /// (DEPRECATED, moved to DeipInvestmentOpportunity)
         SimpleCrowdfundingMap: map hasher(identity) InvestmentId => SimpleCrowdfundingOf<T>;
@@ -506,6 +572,21 @@ decl_storage! {
 
         ContractAgreementMap: map hasher(blake2_128_concat) ContractAgreementId => ContractAgreementOf<T>;
         ContractAgreementIdByType: double_map hasher(twox_64_concat) ContractAgreementIndexTerms, hasher(blake2_128_concat) ContractAgreementId => ();
+        /// NFT class which instances represent signed licenses of the project
+        LicenseNftClassIdByProjectId: map hasher(blake2_128_concat) ProjectId => Option<NftClassIdOf<T>>;
+        /// Project which licenses are represented by the NFT class, unlinked when the class is destroyed
+        ProjectIdByLicenseNftClassId: map hasher(blake2_128_concat) NftClassIdOf<T> => Option<ProjectId>;
+        LicenseNftByContractAgreementId: map hasher(blake2_128_concat) ContractAgreementId => Option<LicenseNftOf<T>>;
+
+        /// NFT class which holders may access the content
+        NftClassIdByContentId: map hasher(blake2_128_concat) ProjectContentId => Option<NftClassIdOf<T>>;
+        /// Contents gated by the NFT class, unlinked when the class is destroyed
+        ContentIdByNftClassId: double_map hasher(blake2_128_concat) NftClassIdOf<T>, hasher(blake2_128_concat) ProjectContentId => ();
+        ContentCountByNftClassId: map hasher(blake2_128_concat) NftClassIdOf<T> => u32;
+        ContentAccessRequestMap: map hasher(blake2_128_concat) ContentAccessRequestId => Option<ContentAccessRequestOf<T>>;
+        ContentAccessRequestIdByContentId: double_map hasher(blake2_128_concat) ProjectContentId, hasher(blake2_128_concat) ContentAccessRequestId => ();
+        /// Deposit reserved from the requester, unreserved when the request is resolved
+        ContentAccessRequestDeposits: map hasher(blake2_128_concat) ContentAccessRequestId => BalanceOf<T>;
     }
 }
 
@@ -653,6 +734,38 @@ mod v1 {
     }
 }
 
+mod v2 {
+    use super::{
+        ActiveProjectCountByTeam, Config, Module, PalletStorageVersion, ProjectMapV1,
+        StorageVersion,
+    };
+    use core::convert::TryInto;
+    use frame_support::{
+        storage::{IterableStorageMap, StorageMap, StorageValue},
+        traits::Get,
+        weights::Weight,
+    };
+
+    pub(crate) fn set_storage_version<T: Config>() -> Weight {
+        PalletStorageVersion::put(StorageVersion::V2);
+        T::DbWeight::get().writes(1)
+    }
+
+    pub(crate) fn count_active_projects<T: Config>() -> Weight {
+        let mut reads: usize = 0;
+        for (id, project) in ProjectMapV1::<T>::iter() {
+            reads += 1;
+            if !Module::<T>::is_project_finished(&id) {
+                ActiveProjectCountByTeam::<T>::mutate(&project.team_id, |count| {
+                    *count = count.saturating_add(1)
+                });
+            }
+        }
+        let reads = reads.try_into().unwrap_or(Weight::MAX);
+        T::DbWeight::get().reads_writes(reads, reads)
+    }
+}
+
 // Dispatchable functions allows users to interact with the pallet and invoke state changes.
 // These functions materialize as "extrinsics", which are often compared to transactions.
 // Dispatchable functions must be annotated with a weight and must return a DispatchResult.
@@ -665,16 +778,20 @@ decl_module! {
         fn deposit_event() = default;
 
         fn on_runtime_upgrade() -> Weight {
+            let mut weight = 0;
             if Module::<T>::pallet_storage_version() == StorageVersion::V0 {
-                let mut weight = v1::migrate_investment_opportunity::<T>();
+                weight += v1::migrate_investment_opportunity::<T>();
                 weight += v1::migrate_projects_hasher::<T>();
                 weight += v1::migrate_project_contents_hasher::<T>();
                 weight += v1::migrate_nda_hasher::<T>();
                 weight += v1::migrate_reviews_hasher::<T>();
                 weight += v1::set_storage_version::<T>();
-                return weight;
             }
-            0
+            if Module::<T>::pallet_storage_version() == StorageVersion::V1 {
+                weight += v2::count_active_projects::<T>();
+                weight += v2::set_storage_version::<T>();
+            }
+            weight
         }
 
         /// Allow a user to create project.
@@ -716,6 +833,9 @@ decl_module! {
 
             ProjectMapV1::<T>::insert(project.external_id, project.clone());
             ProjectIdByTeamIdV1::<T>::insert(project.team_id.clone(), project.external_id, ());
+            ActiveProjectCountByTeam::<T>::mutate(&project.team_id, |count| {
+                *count = count.saturating_add(1)
+            });
 
             Self::deposit_event(RawEvent::ProjectCreated(account, project));
         }
@@ -810,6 +930,11 @@ decl_module! {
 
             ProjectContentMapV1::<T>::insert(content.external_id, content.clone());
             ContentIdByProjectIdV1::insert(content.project_external_id, content.external_id, ());
+            if content.content_type == ProjectContentType::FinalResult {
+                ActiveProjectCountByTeam::<T>::mutate(&project.team_id, |count| {
+                    *count = count.saturating_sub(1)
+                });
+            }
 
             Self::deposit_event(RawEvent::ProjectContnetCreated(account, content.external_id));
         }
@@ -1068,7 +1193,7 @@ decl_module! {
             hash: HashOf<T>,
             activation_time: Option<MomentOf<T>>,
             expiration_time: Option<MomentOf<T>>,
-            terms: Terms,
+            terms: ContractAgreementTermsOf<T>,
         ) -> DispatchResultWithPostInfo {
             let account = ensure_signed(origin)?;
             let parties = parties.into_iter().map(Into::into).collect();
@@ -1076,6 +1201,8 @@ decl_module! {
         }
 
         /// Allows a party to sign the contract agreement created earlier.
+        /// Signing of a license by the licensee mints the NFT representing it
+        /// if the project has NFT class for licenses.
         ///
         /// The origin for this call must be _Signed_.
         /// - `id` - identifies the contract to accept. Check [`ContractAgreementTerms`] for
@@ -1086,6 +1213,7 @@ decl_module! {
                 .max(T::DeipWeightInfo::accept_contract_agreement_generic_contract_partially_accepted())
                 .max(T::DeipWeightInfo::accept_contract_agreement_generic_contract_finalized())
         }]
+        #[transactional]
         fn accept_contract_agreement(origin,
             id: ContractAgreementId,
             party: T::DeipAccountId,
@@ -1110,6 +1238,83 @@ decl_module! {
             Self::reject_contract_agreement_impl(account, id, party.into())
         }
 
+        /// Allows the team to represent signed licenses of the project as NFTs of the class.
+        /// Holder of the NFT is the licensee. Unsets the class if `class` is `None`.
+        /// A class represents licenses of one project and is unset when destroyed.
+        ///
+        /// The origin for this call must be _Signed_ by the team of the project.
+        /// - `class` - NFT class which instances are minted by the team
+        #[weight = 10_000]
+        fn set_license_nft_class(origin,
+            project_id: ProjectId,
+            class: Option<NftClassIdOf<T>>,
+        ) -> DispatchResult {
+            let account = ensure_signed(origin)?;
+            Self::set_license_nft_class_impl(account, project_id, class)
+        }
+
+        /// Allows the team to gate access to the content by holding an instance
+        /// of the NFT class. Unlinks the class if `class` is `None`.
+        /// A class gates up to `MaxContentsPerNftClass` contents and is unlinked
+        /// from them when destroyed.
+        ///
+        /// The origin for this call must be _Signed_ by the team of the content.
+        /// - `class` - NFT class owned by the team
+        #[weight = T::DeipWeightInfo::set_content_nft_class()]
+        fn set_content_nft_class(origin,
+            project_content_external_id: ProjectContentId,
+            class: Option<NftClassIdOf<T>>,
+        ) -> DispatchResult {
+            let account = ensure_signed(origin)?;
+            Self::set_content_nft_class_impl(account, project_content_external_id, class)
+        }
+
+        /// Create [request](./struct.ContentAccessRequest.html) to receive the key
+        /// of NFT-gated content.
+        ///
+        /// The origin for this call must be _Signed_ by a holder of the NFT class
+        /// linked to the content.
+        /// - `public_key` - key of the requester to encrypt the content key with
+        ///
+        /// `ContentAccessRequestDeposit` is reserved from the requester till the request
+        /// is fulfilled or rejected.
+        #[weight = T::DeipWeightInfo::create_content_access_request()]
+        fn create_content_access_request(origin,
+            external_id: ContentAccessRequestId,
+            project_content_external_id: ProjectContentId,
+            public_key: ContentAccessKeyOf<T>,
+        ) -> DispatchResult {
+            let account = ensure_signed(origin)?;
+            Self::create_content_access_request_impl(account, external_id, project_content_external_id, public_key)
+        }
+
+        /// Fulfill content access request. Fails if the requester doesn't hold
+        /// the NFT anymore.
+        ///
+        /// The origin for this call must be _Signed_ by the team of the content.
+        /// - `encrypted_key` - key which decrypts the content, encrypted with the public key of the requester
+        /// - `proof_of_encrypted_key` - proof that the key is encrypted with the requester's key
+        #[weight = T::DeipWeightInfo::fulfill_content_access_request()]
+        fn fulfill_content_access_request(origin,
+            external_id: ContentAccessRequestId,
+            encrypted_key: ContentAccessKeyOf<T>,
+            proof_of_encrypted_key: ContentAccessKeyOf<T>,
+        ) -> DispatchResult {
+            let account = ensure_signed(origin)?;
+            Self::fulfill_content_access_request_impl(account, external_id, encrypted_key, proof_of_encrypted_key)
+        }
+
+        /// Reject content access request.
+        ///
+        /// The origin for this call must be _Signed_ by the team of the content.
+        #[weight = T::DeipWeightInfo::reject_content_access_request()]
+        fn reject_content_access_request(origin,
+            external_id: ContentAccessRequestId,
+        ) -> DispatchResult {
+            let account = ensure_signed(origin)?;
+            Self::reject_content_access_request_impl(account, external_id)
+        }
+
         fn offchain_worker(_n: T::BlockNumber) {
             if !sp_io::offchain::is_validator() {
                 return;
@@ -1125,6 +1330,11 @@ impl<T: Config> Module<T> {
             .any(|c| c.content_type == ProjectContentType::FinalResult)
     }
 
+    /// Whether `team` owns projects without final results.
+    pub fn has_active_projects(team: &AccountIdOf<T>) -> bool {
+        ActiveProjectCountByTeam::<T>::get(team) > 0
+    }
+
     pub fn get_project(project_id: &ProjectId) -> Option<ProjectOf<T>> {
         ProjectMapV1::<T>::try_get(project_id).ok()
     }
@@ -1157,7 +1367,9 @@ impl<T: Config> Module<T> {
     }
 
     pub fn get_contract_agreement(id: &ContractAgreementId) -> Option<ContractAgreementOf<T>> {
-        ContractAgreementMap::<T>::try_get(id).ok()
+        ContractAgreementMap::<T>::try_get(id)
+            .ok()
+            .map(|agreement| Self::resolve_licensee(id, agreement))
     }
 
     // /// Allow a user to create domains.
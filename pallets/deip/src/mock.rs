@@ -1,14 +1,20 @@
 use crate as pallet_deip;
-use frame_support::{parameter_types, traits::Get};
+use deip_asset_system::{AssetIdInitT, DeipAssetSystem, DeipNftSystem};
+use deip_projects_info::DeipProjectsInfo;
+use deip_transaction_ctx::{PortalCtxT, TransactionCtxId, TransactionCtxT};
+use frame_support::{
+    parameter_types,
+    traits::{Everything, Get},
+    weights::Weight,
+};
 use frame_system as system;
-use sp_core::H256;
+use sp_core::{H160, H256};
 use sp_runtime::{
     testing::{Header, TestXt},
-    traits::{BlakeTwo256, IdentityLookup},
+    traits::{BlakeTwo256, Dispatchable, IdentityLookup},
+    DispatchResult, DispatchResultWithInfo,
 };
-
-use codec::{Decode, Encode};
-use serde::{Deserialize, Serialize};
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap};
 
 use deip_assets_error::{ReserveError, UnreserveError};
 
@@ -27,8 +33,7 @@ type Balance = u128;
 type AccountId = u64;
 type AssetId = u32;
 
-#[derive(Encode, Decode, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
-pub struct DeipAssetId(pub AssetId);
+pub type DeipAssetId = H160;
 
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
@@ -42,7 +47,7 @@ frame_support::construct_runtime!(
         Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
         Deip: pallet_deip::{Pallet, Call, Storage, Event<T>, Config},
         Assets: pallet_assets::{Pallet, Storage, Event<T>},
-        DeipAssets: pallet_deip_assets::{Pallet, Storage, Call},
+        DeipAssets: pallet_deip_assets::{Pallet, Storage, Call, Event<T>},
     }
 );
 
@@ -52,7 +57,7 @@ parameter_types! {
 }
 
 impl system::Config for Test {
-    type BaseCallFilter = ();
+    type BaseCallFilter = Everything;
     type BlockWeights = ();
     type BlockLength = ();
     type DbWeight = ();
@@ -95,10 +100,73 @@ impl pallet_balances::Config for Test {
     type WeightInfo = ();
 }
 
+#[derive(Clone, Default, Eq, PartialEq, scale_info::TypeInfo)]
+pub struct TestCtx;
+
+impl TransactionCtxT for TestCtx {
+    type BlockNumber = u64;
+    type ExtrinsicId = u32;
+
+    fn current() -> Self {
+        Self
+    }
+
+    fn block_number(&self) -> Self::BlockNumber {
+        System::block_number()
+    }
+
+    fn extrinsic_id(&self) -> Self::ExtrinsicId {
+        0
+    }
+
+    fn id(&self) -> TransactionCtxId<Self> {
+        TransactionCtxId { block_number: self.block_number(), extrinsic_id: self.extrinsic_id() }
+    }
+
+    fn extrinsic_data(&self) -> Vec<u8> {
+        Vec::new()
+    }
+}
+
+impl<LocalCall> PortalCtxT<LocalCall> for TestCtx {
+    type PortalId = ();
+
+    fn portal_id(_ctx: &TransactionCtxId<Self>) -> Self::PortalId {}
+
+    fn dispatch<D: Dispatchable>(
+        &self,
+        _portal_id: Self::PortalId,
+        call: D,
+        origin: D::Origin,
+    ) -> DispatchResultWithInfo<D::PostInfo> {
+        call.dispatch(origin)
+    }
+
+    fn submit_postponed(_call: LocalCall, _ctx: TransactionCtxId<Self>) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+parameter_types! {
+    pub const MaxNdaParties: u16 = 50;
+    pub const MaxInvestmentShares: u16 = 10;
+    pub const MaxContentsPerNftClass: u32 = 2;
+    pub const MaxContentAccessKeyLength: u32 = 32;
+    pub const ContentAccessRequestDeposit: Balance = 10;
+}
+
 impl pallet_deip::Config for Test {
+    type TransactionCtx = TestCtx;
     type Event = Event;
     type DeipAccountId = Self::AccountId;
     type Currency = Balances;
+    type DeipWeightInfo = pallet_deip::Weights<Self>;
+    type MaxNdaParties = MaxNdaParties;
+    type MaxInvestmentShares = MaxInvestmentShares;
+    type NftSystem = TestNfts;
+    type MaxContentsPerNftClass = MaxContentsPerNftClass;
+    type MaxContentAccessKeyLength = MaxContentAccessKeyLength;
+    type ContentAccessRequestDeposit = ContentAccessRequestDeposit;
 }
 
 parameter_types! {
@@ -138,6 +206,11 @@ impl pallet_assets::Config for Test {
 
 parameter_types! {
     pub const WipePeriod: u64 = 10;
+    pub const MaxDividendClaimsPerBlock: u32 = 10;
+    pub const CheckpointRetention: u32 = 2;
+    pub const MaxPinnedCheckpoints: u32 = 2;
+    pub const MaxWipeAccountsPerBlock: u32 = 10;
+    pub NativeAssetId: DeipAssetId = H160::repeat_byte(0xff);
 }
 
 impl DeipProjectsInfo<AccountId> for Test {
@@ -147,6 +220,16 @@ impl DeipProjectsInfo<AccountId> for Test {
     fn try_get_project_team(id: &Self::ProjectId) -> Option<AccountId> {
         Deip::try_get_project_team(id)
     }
+
+    fn project_id(source: &[u8]) -> Self::ProjectId {
+        H160::from_slice(&sp_io::hashing::blake2_256(source)[..20])
+    }
+}
+
+impl AssetIdInitT<DeipAssetId> for Test {
+    fn asset_id(raw: &[u8]) -> DeipAssetId {
+        H160::from_slice(raw)
+    }
 }
 
 impl pallet_deip_assets::Config for Test {
@@ -154,7 +237,157 @@ impl pallet_deip_assets::Config for Test {
     type DeipAccountId = Self::AccountId;
     type AssetsAssetId = AssetId;
     type AssetId = DeipAssetId;
+    type AssetIdInit = Self;
+    type NativeAssetId = NativeAssetId;
     type WipePeriod = WipePeriod;
+    type MaxWipeAccountsPerBlock = MaxWipeAccountsPerBlock;
+    type Event = Event;
+    type MaxDividendClaimsPerBlock = MaxDividendClaimsPerBlock;
+    type CheckpointRetention = CheckpointRetention;
+    type MaxPinnedCheckpoints = MaxPinnedCheckpoints;
+    type TransferPolicy = ();
+}
+
+/// Assets of `DeipAssets` only, the native currency isn't routed.
+impl DeipAssetSystem<AccountId, pallet_deip::ProjectId, pallet_deip::InvestmentId> for Test {
+    type Balance = u64;
+    type AssetId = DeipAssetId;
+
+    fn account_balance(account: &AccountId, asset: &Self::AssetId) -> Self::Balance {
+        DeipAssets::account_balance(account, asset)
+    }
+
+    fn total_supply(asset: &Self::AssetId) -> Self::Balance {
+        DeipAssets::total_supply(asset)
+    }
+
+    fn minimum_balance(asset: &Self::AssetId) -> Self::Balance {
+        DeipAssets::minimum_balance(asset)
+    }
+
+    fn transactionally_transfer(
+        from: &AccountId,
+        asset: Self::AssetId,
+        transfers: &[(Self::Balance, AccountId)],
+    ) -> DispatchResult {
+        DeipAssets::transactionally_transfer(from, asset, transfers)
+    }
+
+    fn transactionally_reserve(
+        account: &AccountId,
+        id: pallet_deip::InvestmentId,
+        shares: &[(Self::AssetId, Self::Balance)],
+        asset: Self::AssetId,
+    ) -> Result<(), ReserveError<Self::AssetId>> {
+        DeipAssets::deip_transactionally_reserve(account, id, shares, asset, true)
+    }
+
+    fn transactionally_reserve_escrow(
+        account: &AccountId,
+        id: pallet_deip::InvestmentId,
+        shares: &[(Self::AssetId, Self::Balance)],
+        asset: Self::AssetId,
+    ) -> Result<(), ReserveError<Self::AssetId>> {
+        DeipAssets::deip_transactionally_reserve(account, id, shares, asset, false)
+    }
+
+    fn transactionally_unreserve(
+        id: pallet_deip::InvestmentId,
+    ) -> Result<(), UnreserveError<Self::AssetId>> {
+        DeipAssets::transactionally_unreserve(id)
+    }
+
+    fn transfer_from_reserved(
+        id: pallet_deip::InvestmentId,
+        who: &AccountId,
+        asset: Self::AssetId,
+        amount: Self::Balance,
+    ) -> Result<(), UnreserveError<Self::AssetId>> {
+        DeipAssets::transfer_from_reserved(id, who, asset, amount)
+    }
+
+    fn transfer_to_reserved(
+        who: &AccountId,
+        id: pallet_deip::InvestmentId,
+        amount: Self::Balance,
+    ) -> Result<(), UnreserveError<Self::AssetId>> {
+        DeipAssets::deip_transfer_to_reserved(who, id, amount)
+    }
+}
+
+thread_local! {
+    /// class -> (owner, issuer)
+    static CLASSES: RefCell<BTreeMap<u32, (AccountId, AccountId)>> =
+        RefCell::new(BTreeMap::new());
+    /// (class, instance) -> owner
+    static INSTANCES: RefCell<BTreeMap<(u32, u32), AccountId>> = RefCell::new(BTreeMap::new());
+}
+
+/// NFT classes kept apart from the runtime, like `pallet_uniques` they're
+/// owned by one account and minted by another.
+pub struct TestNfts;
+
+impl TestNfts {
+    pub fn create_class(class: u32, owner: AccountId, issuer: AccountId) {
+        CLASSES.with(|c| c.borrow_mut().insert(class, (owner, issuer)));
+    }
+
+    /// Destroys the class with its instances like `DeipUniques::deip_destroy`.
+    pub fn destroy_class(class: u32) {
+        CLASSES.with(|c| c.borrow_mut().remove(&class));
+        INSTANCES.with(|i| i.borrow_mut().retain(|(c, _), _| *c != class));
+        <Deip as deip_asset_system::OnNftClassDestroyed<u32>>::on_nft_class_destroyed(&class);
+    }
+
+    pub fn transfer(class: u32, instance: u32, dest: AccountId) {
+        INSTANCES.with(|i| i.borrow_mut().insert((class, instance), dest));
+    }
+
+    pub fn burn(class: u32, instance: u32) {
+        INSTANCES.with(|i| i.borrow_mut().remove(&(class, instance)));
+    }
+}
+
+impl DeipNftSystem<AccountId> for TestNfts {
+    type ClassId = u32;
+    type InstanceId = u32;
+
+    fn class_owner(class: &Self::ClassId) -> Option<AccountId> {
+        CLASSES.with(|c| c.borrow().get(class).map(|(owner, _)| *owner))
+    }
+
+    fn class_issuer(class: &Self::ClassId) -> Option<AccountId> {
+        CLASSES.with(|c| c.borrow().get(class).map(|(_, issuer)| *issuer))
+    }
+
+    fn holder(class: &Self::ClassId, instance: &Self::InstanceId) -> Option<AccountId> {
+        INSTANCES.with(|i| i.borrow().get(&(*class, *instance)).copied())
+    }
+
+    /// Like `pallet_uniques::mint`, only the issuer of the class may mint.
+    fn mint(
+        issuer: &AccountId,
+        class: Self::ClassId,
+        owner: &AccountId,
+    ) -> Result<Self::InstanceId, ()> {
+        if Self::class_issuer(&class).as_ref() != Some(issuer) {
+            return Err(())
+        }
+        INSTANCES.with(|i| {
+            let mut i = i.borrow_mut();
+            let instance = i.keys().filter(|(c, _)| *c == class).count() as u32;
+            i.insert((class, instance), *owner);
+            Ok(instance)
+        })
+    }
+
+    fn mint_weight() -> Weight {
+        1
+    }
+
+    fn is_holder(class: &Self::ClassId, account: &AccountId) -> bool {
+        INSTANCES.with(|i| i.borrow().iter().any(|((c, _), o)| c == class && o == account))
+    }
 }
 
 impl<LocalCall> system::offchain::SendTransactionTypes<LocalCall> for Test
@@ -165,12 +398,19 @@ where
     type Extrinsic = Extrinsic;
 }
 
+fn clear_nfts() {
+    CLASSES.with(|c| c.borrow_mut().clear());
+    INSTANCES.with(|i| i.borrow_mut().clear());
+}
+
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
+    clear_nfts();
     system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
 }
 
 pub fn new_test_ext2() -> sp_io::TestExternalities {
+    clear_nfts();
     let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
     pallet_balances::GenesisConfig::<Test> {
         balances: vec![
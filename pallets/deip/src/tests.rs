@@ -1,26 +1,9 @@
 use crate::{mock::*, *};
-use frame_support::{
-    assert_noop, assert_ok,
-    traits::{OffchainWorker, OnFinalize, OnInitialize, UnfilteredDispatchable},
-};
-use sp_core::{
-    offchain::{testing::*, TransactionPoolExt},
-    H256,
-};
-use sp_io::TestExternalities;
-use sp_runtime::{
-    offchain::OffchainWorkerExt,
-    traits::{One, Zero},
-};
-use sp_std::sync::Arc;
-use std::{
-    sync::RwLock,
-    time::{SystemTime, UNIX_EPOCH},
-};
-
-const DAY_IN_MILLIS: u64 = 86400000;
-
-type BlockNumber = <Test as system::Config>::BlockNumber;
+use deip_asset_system::DeipNftSystem;
+use frame_support::{assert_noop, assert_ok};
+use sp_core::H256;
+use sp_runtime::traits::{One, Zero};
+use sp_std::convert::TryInto;
 
 fn create_ok_project(
     maybe_account_id: Option<<Test as system::Config>::AccountId>,
@@ -55,125 +38,23 @@ fn create_ok_project(
     (project_id, project, domain_id, account_id)
 }
 
-fn create_ok_nda() -> (NdaId, NdaOf<Test>) {
-    let (project_id, ..) = create_ok_project(None);
-    let project_nda_id = NdaId::random();
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .expect("Time went backwards :)")
-        .as_millis() as u64;
-
-    let end_date = now + DAY_IN_MILLIS;
-    let contract_hash = H256::random();
-    let maybe_start_date = None;
-    let parties = vec![DEFAULT_ACCOUNT_ID];
-    let projects = vec![project_id];
-
-    assert_ok!(Deip::create_project_nda(
-        Origin::signed(DEFAULT_ACCOUNT_ID),
-        project_nda_id,
-        end_date,
-        contract_hash,
-        maybe_start_date,
-        parties.clone(),
-        projects.clone()
+fn create_ok_content(team: <Test as system::Config>::AccountId) -> ProjectContentId {
+    let (project_id, ..) = create_ok_project(Some(team));
+    let content_id = ProjectContentId::random();
+
+    assert_ok!(Deip::create_project_content(
+        Origin::signed(team),
+        content_id,
+        project_id,
+        team,
+        ProjectContentType::Announcement,
+        H256::random(),
+        H256::random(),
+        vec![team],
+        None
     ));
 
-    let expected_nda = Nda {
-        contract_creator: DEFAULT_ACCOUNT_ID,
-        external_id: project_nda_id,
-        end_date,
-        start_date: maybe_start_date,
-        contract_hash,
-        parties,
-        projects,
-    };
-
-    (project_nda_id, expected_nda)
-}
-
-fn create_ok_nda_content_access_request(
-    project_nda_id: NdaId,
-) -> (NdaAccessRequestId, NdaAccessRequestOf<Test>) {
-    let access_request_id = NdaAccessRequestId::random();
-    let encrypted_payload_hash = H256::random();
-    let encrypted_payload_iv = vec![1, 2, 3];
-
-    assert_ok!(Deip::create_nda_content_access_request(
-        Origin::signed(DEFAULT_ACCOUNT_ID),
-        access_request_id,
-        project_nda_id,
-        encrypted_payload_hash,
-        encrypted_payload_iv.clone()
-    ));
-
-    let expected_nda_request = NdaAccessRequest {
-        external_id: access_request_id,
-        nda_external_id: project_nda_id,
-        requester: DEFAULT_ACCOUNT_ID,
-        encrypted_payload_hash,
-        encrypted_payload_iv,
-        status: NdaAccessRequestStatus::Pending,
-        grantor: None,
-        encrypted_payload_encryption_key: None,
-        proof_of_encrypted_payload_encryption_key: None,
-    };
-
-    (access_request_id, expected_nda_request)
-}
-
-fn create_mint_asset(
-    account_id: AccountIdOf<Test>,
-    id: DeipAssetIdOf<Test>,
-    amount: DeipAssetBalanceOf<Test>,
-    project_id: Option<ProjectId>,
-) {
-    let call =
-        pallet_deip_assets::Call::<Test>::create_asset(id, account_id, 1u32.into(), project_id);
-    let result = call.dispatch_bypass_filter(Origin::signed(account_id));
-    assert_ok!(result);
-
-    let call = pallet_deip_assets::Call::<Test>::issue_asset(id, account_id, amount);
-    let result = call.dispatch_bypass_filter(Origin::signed(account_id));
-    assert_ok!(result);
-}
-
-/// convert an externalities to one that can handle offchain worker tests.
-/// Check substrate-v3.0.0/frame/staking/src/tests.rs +3452
-fn offchainify(ext: &mut TestExternalities, iterations: u32) -> Arc<RwLock<PoolState>> {
-    let (offchain, offchain_state) = TestOffchainExt::new();
-    let (pool, pool_state) = TestTransactionPoolExt::new();
-
-    let mut seed = [0_u8; 32];
-    seed[0..4].copy_from_slice(&iterations.to_le_bytes());
-    offchain_state.write().seed = seed;
-
-    ext.register_extension(OffchainWorkerExt::new(offchain));
-    ext.register_extension(TransactionPoolExt::new(pool));
-
-    pool_state
-}
-
-fn decode_validate_deip_call(encoded: &[u8]) -> crate::Call<Test> {
-    let mut encoded = encoded.clone();
-    let extrinsic: Extrinsic = Decode::decode(&mut encoded).unwrap();
-
-    let call = extrinsic.call;
-    let inner = match call {
-        mock::Call::Deip(inner) => inner,
-        _ => unreachable!(),
-    };
-
-    assert_eq!(
-        <Deip as sp_runtime::traits::ValidateUnsigned>::validate_unsigned(
-            TransactionSource::Local,
-            &inner,
-        )
-        .is_ok(),
-        true
-    );
-
-    inner
+    content_id
 }
 
 #[test]
@@ -556,823 +437,6 @@ fn cant_add_project_content_with_wrong_references() {
     })
 }
 
-#[test]
-fn create_project_nda() {
-    new_test_ext().execute_with(|| {
-        let (project_nda_id, expected_nda) = create_ok_nda();
-
-        let nda_list = Ndas::<Test>::get();
-        let nda_stored = NdaMapV1::<Test>::get(project_nda_id);
-
-        assert!(
-            <NdaMapV1<Test>>::contains_key(project_nda_id),
-            "NDA Map did not contain key, value was `{}`",
-            project_nda_id
-        );
-
-        assert_eq!(expected_nda, nda_stored);
-
-        assert!(
-            nda_list
-                .binary_search_by_key(&project_nda_id, |&(external_id, ..)| external_id)
-                .is_ok(),
-            "NDA List did not contain the NDA, value was `{}`",
-            project_nda_id
-        );
-    })
-}
-
-#[test]
-fn cant_create_project_nda_ends_in_past() {
-    new_test_ext().execute_with(|| {
-        let (project_id, ..) = create_ok_project(None);
-        let project_nda_id = NdaId::random();
-        let end_date = 0;
-
-        let contract_hash = H256::random();
-        let maybe_start_date = None;
-        let parties = vec![DEFAULT_ACCOUNT_ID];
-        let projects = vec![project_id];
-
-        assert_noop!(
-            Deip::create_project_nda(
-                Origin::signed(DEFAULT_ACCOUNT_ID),
-                project_nda_id,
-                end_date,
-                contract_hash,
-                maybe_start_date,
-                parties.clone(),
-                projects.clone()
-            ),
-            Error::<Test>::NdaEndDateMustBeLaterCurrentMoment
-        );
-    })
-}
-
-#[test]
-fn cant_create_project_nda_with_start_date_greater_end_date() {
-    new_test_ext().execute_with(|| {
-        let (project_id, ..) = create_ok_project(None);
-        let project_nda_id = NdaId::random();
-
-        let end_date = 1;
-        let maybe_start_date = Some(3);
-
-        let contract_hash = H256::random();
-
-        let parties = vec![DEFAULT_ACCOUNT_ID];
-        let projects = vec![project_id];
-
-        assert_noop!(
-            Deip::create_project_nda(
-                Origin::signed(DEFAULT_ACCOUNT_ID),
-                project_nda_id,
-                end_date,
-                contract_hash,
-                maybe_start_date,
-                parties.clone(),
-                projects.clone()
-            ),
-            Error::<Test>::NdaStartDateMustBeLessThanEndDate
-        );
-    })
-}
-
-#[test]
-fn cant_create_project_nda_with_non_existed_project() {
-    new_test_ext().execute_with(|| {
-        let project_id = ProjectId::random();
-        let project_nda_id = NdaId::random();
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards :)")
-            .as_millis() as u64;
-
-        let end_date = now + DAY_IN_MILLIS;
-
-        let contract_hash = H256::random();
-        let maybe_start_date = None;
-        let parties = vec![DEFAULT_ACCOUNT_ID];
-        let projects = vec![project_id];
-
-        assert_noop!(
-            Deip::create_project_nda(
-                Origin::signed(DEFAULT_ACCOUNT_ID),
-                project_nda_id,
-                end_date,
-                contract_hash,
-                maybe_start_date,
-                parties.clone(),
-                projects.clone()
-            ),
-            Error::<Test>::NoSuchProject
-        );
-    })
-}
-
-#[test]
-fn cant_create_project_nda_with_not_correct_parties() {
-    new_test_ext().execute_with(|| {
-        let (project_id, ..) = create_ok_project(None);
-        let project_nda_id = NdaId::random();
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards :)")
-            .as_millis() as u64;
-
-        let end_date = now + DAY_IN_MILLIS;
-
-        let wrong_account_id = 4;
-
-        let contract_hash = H256::random();
-        let maybe_start_date = None;
-        let parties = vec![wrong_account_id];
-        let projects = vec![project_id];
-
-        assert_noop!(
-            Deip::create_project_nda(
-                Origin::signed(wrong_account_id),
-                project_nda_id,
-                end_date,
-                contract_hash,
-                maybe_start_date,
-                parties.clone(),
-                projects.clone()
-            ),
-            Error::<Test>::TeamOfAllProjectsMustSpecifiedAsParty
-        );
-    })
-}
-
-#[test]
-fn cant_create_duplicated_project_nda() {
-    new_test_ext().execute_with(|| {
-        let (project_nda_id, ..) = create_ok_nda();
-
-        let (project_id, ..) = create_ok_project(None);
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards :)")
-            .as_millis() as u64;
-
-        let end_date = now + DAY_IN_MILLIS;
-
-        let contract_hash = H256::random();
-        let maybe_start_date = None;
-        let parties = vec![DEFAULT_ACCOUNT_ID];
-        let projects = vec![project_id];
-
-        assert_noop!(
-            Deip::create_project_nda(
-                Origin::signed(DEFAULT_ACCOUNT_ID),
-                project_nda_id,
-                end_date,
-                contract_hash,
-                maybe_start_date,
-                parties.clone(),
-                projects.clone()
-            ),
-            Error::<Test>::NdaAlreadyExists
-        );
-    })
-}
-
-#[test]
-fn create_nda_content_access_request() {
-    new_test_ext().execute_with(|| {
-        let (project_nda_id, ..) = create_ok_nda();
-
-        let (access_request_id, expected_nda_request) =
-            create_ok_nda_content_access_request(project_nda_id);
-
-        let nda_list = NdaAccessRequests::<Test>::get();
-        let nda_stored = NdaAccessRequestMapV1::<Test>::get(access_request_id);
-
-        assert!(
-            <NdaAccessRequestMapV1<Test>>::contains_key(access_request_id),
-            "NDA request Map did not contain key, value was `{}`",
-            access_request_id
-        );
-
-        assert_eq!(expected_nda_request, nda_stored);
-
-        assert!(
-            nda_list
-                .binary_search_by_key(&access_request_id, |&(external_id, ..)| external_id)
-                .is_ok(),
-            "NDA request List did not contain the NDA request, value was `{}`",
-            access_request_id
-        );
-    })
-}
-
-#[test]
-fn cant_create_nda_content_access_with_non_existed_nda() {
-    new_test_ext().execute_with(|| {
-        let project_nda_id = NdaId::random();
-
-        let access_request_id = NdaAccessRequestId::random();
-        let encrypted_payload_hash = H256::random();
-        let encrypted_payload_iv = vec![1, 2, 3];
-
-        assert_noop!(
-            Deip::create_nda_content_access_request(
-                Origin::signed(DEFAULT_ACCOUNT_ID),
-                access_request_id,
-                project_nda_id,
-                encrypted_payload_hash,
-                encrypted_payload_iv.clone()
-            ),
-            Error::<Test>::NoSuchNda
-        );
-    })
-}
-
-#[test]
-fn cant_create_duplicated_nda_content_access() {
-    new_test_ext().execute_with(|| {
-        let (project_nda_id, ..) = create_ok_nda();
-        let (access_request_id, expected_nda_request) =
-            create_ok_nda_content_access_request(project_nda_id);
-
-        assert_noop!(
-            Deip::create_nda_content_access_request(
-                Origin::signed(DEFAULT_ACCOUNT_ID),
-                access_request_id,
-                project_nda_id,
-                expected_nda_request.encrypted_payload_hash,
-                expected_nda_request.encrypted_payload_iv
-            ),
-            Error::<Test>::NdaAccessRequestAlreadyExists
-        );
-    })
-}
-
-#[test]
-fn fulfill_nda_content_access_request() {
-    new_test_ext().execute_with(|| {
-        let (project_nda_id, ..) = create_ok_nda();
-
-        let (access_request_id, nda_request) = create_ok_nda_content_access_request(project_nda_id);
-
-        let encrypted_payload_encryption_key = vec![1, 3, 4, 2];
-        let proof_of_encrypted_payload_encryption_key = vec![3, 4, 5, 6];
-
-        assert_ok!(Deip::fulfill_nda_content_access_request(
-            Origin::signed(DEFAULT_ACCOUNT_ID),
-            access_request_id.clone(),
-            encrypted_payload_encryption_key.clone(),
-            proof_of_encrypted_payload_encryption_key.clone()
-        ));
-
-        let nda_stored = NdaAccessRequestMapV1::<Test>::get(access_request_id);
-
-        let expected_nda_request = NdaAccessRequest {
-            status: NdaAccessRequestStatus::Fulfilled,
-            grantor: Some(DEFAULT_ACCOUNT_ID),
-            encrypted_payload_encryption_key: Some(encrypted_payload_encryption_key),
-            proof_of_encrypted_payload_encryption_key: Some(
-                proof_of_encrypted_payload_encryption_key,
-            ),
-            ..nda_request
-        };
-
-        assert_eq!(expected_nda_request, nda_stored);
-    })
-}
-
-#[test]
-fn cant_fulfill_not_existed_nda_content_access_request() {
-    new_test_ext().execute_with(|| {
-        let access_request_id = NdaAccessRequestId::random();
-
-        let encrypted_payload_encryption_key = vec![1, 3, 4, 2];
-        let proof_of_encrypted_payload_encryption_key = vec![3, 4, 5, 6];
-
-        assert_noop!(
-            Deip::fulfill_nda_content_access_request(
-                Origin::signed(DEFAULT_ACCOUNT_ID),
-                access_request_id.clone(),
-                encrypted_payload_encryption_key.clone(),
-                proof_of_encrypted_payload_encryption_key.clone()
-            ),
-            Error::<Test>::NoSuchNdaAccessRequest
-        );
-    })
-}
-
-#[test]
-fn cant_fulfill_finalized_nda_content_access_request() {
-    new_test_ext().execute_with(|| {
-        let (project_nda_id, ..) = create_ok_nda();
-
-        let (access_request_id, ..) = create_ok_nda_content_access_request(project_nda_id);
-
-        let encrypted_payload_encryption_key = vec![1, 3, 4, 2];
-        let proof_of_encrypted_payload_encryption_key = vec![3, 4, 5, 6];
-
-        assert_ok!(Deip::fulfill_nda_content_access_request(
-            Origin::signed(DEFAULT_ACCOUNT_ID),
-            access_request_id.clone(),
-            encrypted_payload_encryption_key.clone(),
-            proof_of_encrypted_payload_encryption_key.clone()
-        ));
-
-        assert_noop!(
-            Deip::fulfill_nda_content_access_request(
-                Origin::signed(DEFAULT_ACCOUNT_ID),
-                access_request_id.clone(),
-                encrypted_payload_encryption_key.clone(),
-                proof_of_encrypted_payload_encryption_key.clone()
-            ),
-            Error::<Test>::NdaAccessRequestAlreadyFinalized
-        );
-    })
-}
-
-#[test]
-fn reject_nda_content_access_request() {
-    new_test_ext().execute_with(|| {
-        let (project_nda_id, ..) = create_ok_nda();
-
-        let (access_request_id, nda_request) = create_ok_nda_content_access_request(project_nda_id);
-
-        assert_ok!(Deip::reject_nda_content_access_request(
-            Origin::signed(DEFAULT_ACCOUNT_ID),
-            access_request_id.clone(),
-        ));
-
-        let nda_stored = NdaAccessRequestMapV1::<Test>::get(access_request_id);
-
-        let expected_nda_request =
-            NdaAccessRequest { status: NdaAccessRequestStatus::Rejected, ..nda_request };
-
-        assert_eq!(expected_nda_request, nda_stored);
-    })
-}
-
-#[test]
-fn cant_reject_not_existed_nda_content_access_request() {
-    new_test_ext().execute_with(|| {
-        let access_request_id = NdaAccessRequestId::random();
-
-        assert_noop!(
-            Deip::reject_nda_content_access_request(
-                Origin::signed(DEFAULT_ACCOUNT_ID),
-                access_request_id.clone(),
-            ),
-            Error::<Test>::NoSuchNdaAccessRequest
-        );
-    })
-}
-
-#[test]
-fn cant_reject_finalized_nda_content_access_request() {
-    new_test_ext().execute_with(|| {
-        let (project_nda_id, ..) = create_ok_nda();
-
-        let (access_request_id, ..) = create_ok_nda_content_access_request(project_nda_id);
-
-        assert_ok!(Deip::reject_nda_content_access_request(
-            Origin::signed(DEFAULT_ACCOUNT_ID),
-            access_request_id.clone(),
-        ));
-
-        assert_noop!(
-            Deip::reject_nda_content_access_request(
-                Origin::signed(DEFAULT_ACCOUNT_ID),
-                access_request_id.clone(),
-            ),
-            Error::<Test>::NdaAccessRequestAlreadyFinalized
-        );
-    })
-}
-
-#[test]
-fn simple_crowdfunding_create_should_fail() {
-    new_test_ext2().execute_with(|| {
-        let start_time = pallet_timestamp::Pallet::<Test>::get();
-        assert_noop!(
-            Deip::create_simple_crowdfunding(
-                DEFAULT_ACCOUNT_ID,
-                H160::random(),
-                start_time,
-                start_time + 1,
-                DeipAsset::new(DeipAssetId(0u32), 100u32.into()),
-                DeipAsset::new(DeipAssetId(0u32), 120u32.into()),
-                vec![
-                    DeipAsset::new(DeipAssetId(0u32), 100u32.into()),
-                    DeipAsset::new(DeipAssetId(14u32), 200u32.into())
-                ]
-            ),
-            Error::<Test>::InvestmentOpportunityWrongAssetId
-        );
-
-        assert_noop!(
-            Deip::create_simple_crowdfunding(
-                DEFAULT_ACCOUNT_ID,
-                H160::random(),
-                start_time,
-                start_time + 1,
-                DeipAsset::new(DeipAssetId(0u32), 100u32.into()),
-                DeipAsset::new(DeipAssetId(0u32), 120u32.into()),
-                vec![]
-            ),
-            Error::<Test>::InvestmentOpportunitySecurityTokenNotSpecified
-        );
-    })
-}
-
-#[test]
-fn simple_crowdfunding_hard_cap_reached() {
-    let mut ext = new_test_ext2();
-    let state = offchainify(&mut ext, 2);
-    ext.execute_with(|| {
-        let (ref project_id, .., ref account_id) = create_ok_project(None);
-
-        let base_asset_id = DeipAssetId(3u32);
-        let base_asset_total = 120_000u64;
-        create_mint_asset(ALICE_ACCOUNT_ID, base_asset_id, base_asset_total, None);
-
-        let call = pallet_deip_assets::Call::<Test>::transfer {
-            id: base_asset_id,
-            target: BOB_ACCOUNT_ID,
-            amount: base_asset_total / 2,
-        };
-        let result = call.dispatch_bypass_filter(Origin::signed(ALICE_ACCOUNT_ID));
-        assert_ok!(result);
-
-        let usd_id = DeipAssetId(0u32);
-        let usd_total = 100_000u64;
-        create_mint_asset(*account_id, usd_id, usd_total, Some(*project_id));
-
-        let eur_id = DeipAssetId(1u32);
-        let eur_total = 80_000u64;
-        create_mint_asset(*account_id, eur_id, eur_total, Some(*project_id));
-
-        let balance_before = DeipAssets::account_balance(account_id, &base_asset_id);
-
-        let start_time = pallet_timestamp::Pallet::<Test>::get();
-        let sale_id = H160::random();
-        let soft_cap = 100_000u64;
-        let hard_cap = base_asset_total;
-        let usd_to_sale = 80_000u64;
-        let eur_to_sale = 75_000u64;
-        assert_ok!(Deip::create_simple_crowdfunding(
-            DEFAULT_ACCOUNT_ID,
-            sale_id,
-            start_time,
-            start_time + 100,
-            DeipAsset::new(base_asset_id, soft_cap),
-            DeipAsset::new(base_asset_id, hard_cap),
-            vec![DeipAsset::new(usd_id, usd_to_sale), DeipAsset::new(eur_id, eur_to_sale)]
-        ));
-
-        Deip::offchain_worker(System::block_number());
-        assert_eq!(state.read().transactions.len(), 1);
-
-        let inner = decode_validate_deip_call(&state.read().transactions[0]);
-        match inner {
-            crate::Call::activate_crowdfunding(id) => Deip::activate_crowdfunding_impl(id).unwrap(),
-            _ => unreachable!(),
-        };
-
-        assert_ok!(Deip::invest_to_crowdfunding_impl(
-            BOB_ACCOUNT_ID,
-            sale_id,
-            DeipAsset::new(base_asset_id, hard_cap / 2),
-        ));
-
-        // investors should get their tokens in any case
-        let call = pallet_deip_assets::Call::<Test>::freeze(usd_id, BOB_ACCOUNT_ID);
-        let _result = call.dispatch_bypass_filter(Origin::signed(*account_id));
-
-        let call = pallet_deip_assets::Call::<Test>::freeze_asset(eur_id);
-        let _result = call.dispatch_bypass_filter(Origin::signed(*account_id));
-
-        assert_ok!(Deip::invest_to_crowdfunding_impl(
-            ALICE_ACCOUNT_ID,
-            sale_id,
-            DeipAsset::new(base_asset_id, hard_cap / 2),
-        ));
-
-        assert_eq!(DeipAssets::account_balance(&BOB_ACCOUNT_ID, &usd_id), usd_to_sale / 2);
-        assert_eq!(DeipAssets::account_balance(&ALICE_ACCOUNT_ID, &usd_id), usd_to_sale / 2);
-        assert_eq!(
-            DeipAssets::account_balance(account_id, &usd_id),
-            DeipAssets::total_supply(&usd_id) - usd_to_sale
-        );
-
-        assert_eq!(DeipAssets::account_balance(&BOB_ACCOUNT_ID, &eur_id), eur_to_sale / 2);
-        assert_eq!(DeipAssets::account_balance(&ALICE_ACCOUNT_ID, &eur_id), eur_to_sale / 2);
-        assert_eq!(
-            DeipAssets::account_balance(account_id, &eur_id),
-            DeipAssets::total_supply(&eur_id) - eur_to_sale
-        );
-
-        assert_eq!(
-            DeipAssets::account_balance(account_id, &base_asset_id),
-            hard_cap + balance_before
-        );
-    })
-}
-
-#[test]
-fn simple_crowdfunding_expired() {
-    let mut ext = new_test_ext2();
-    let state = offchainify(&mut ext, 2);
-    ext.execute_with(|| {
-        let (ref project_id, .., ref account_id) = create_ok_project(None);
-
-        let base_asset_id = DeipAssetId(3u32);
-        let base_asset_total = 120_000u64;
-        create_mint_asset(*account_id, base_asset_id, base_asset_total, Some(*project_id));
-
-        let call = pallet_deip_assets::Call::<Test>::transfer(
-            base_asset_id,
-            ALICE_ACCOUNT_ID,
-            base_asset_total / 2,
-        );
-        let result = call.dispatch_bypass_filter(Origin::signed(*account_id));
-        assert_ok!(result);
-
-        let call = pallet_deip_assets::Call::<Test>::transfer(
-            base_asset_id,
-            BOB_ACCOUNT_ID,
-            base_asset_total / 2,
-        );
-        let result = call.dispatch_bypass_filter(Origin::signed(*account_id));
-        assert_ok!(result);
-
-        let usd_id = DeipAssetId(0u32);
-        let usd_total = 100_000u64;
-        create_mint_asset(*account_id, usd_id, usd_total, Some(*project_id));
-
-        let eur_id = DeipAssetId(1u32);
-        let eur_total = 80_000u64;
-        create_mint_asset(*account_id, eur_id, eur_total, Some(*project_id));
-
-        let balance_before = DeipAssets::account_balance(account_id, &base_asset_id);
-        let bob_balance_before = DeipAssets::account_balance(&BOB_ACCOUNT_ID, &base_asset_id);
-
-        let start_time_in_blocks = 5;
-        let start_time =
-            pallet_timestamp::Pallet::<Test>::get() + start_time_in_blocks * BLOCK_TIME;
-        let sale_id = H160::random();
-        let soft_cap = 100_000u64;
-        let alice_investing = soft_cap / 2;
-        let hard_cap = base_asset_total;
-        let usd_to_sale = 80_000u64;
-        let eur_to_sale = 75_000u64;
-        let duration_in_blocks = 5;
-        assert_ok!(Deip::create_simple_crowdfunding(
-            DEFAULT_ACCOUNT_ID,
-            sale_id,
-            start_time,
-            start_time + duration_in_blocks * BLOCK_TIME,
-            DeipAsset::new(base_asset_id, soft_cap),
-            DeipAsset::new(base_asset_id, hard_cap),
-            vec![DeipAsset::new(usd_id, usd_to_sale), DeipAsset::new(eur_id, eur_to_sale)]
-        ));
-
-        let start_block = System::block_number() + start_time_in_blocks + 1;
-        while System::block_number() < start_block {
-            let block_number = System::block_number();
-            <System as OnFinalize<BlockNumber>>::on_finalize(block_number);
-            Deip::offchain_worker(System::block_number());
-            System::set_block_number(block_number + 1);
-            <System as OnInitialize<BlockNumber>>::on_initialize(System::block_number());
-            Timestamp::set_timestamp(System::block_number() * BLOCK_TIME + INIT_TIMESTAMP);
-        }
-
-        assert_eq!(state.read().transactions.len(), 1);
-
-        let inner = decode_validate_deip_call(&state.read().transactions[0]);
-        match inner {
-            crate::Call::activate_crowdfunding(id) => Deip::activate_crowdfunding_impl(id).unwrap(),
-            _ => unreachable!(),
-        };
-
-        state.write().transactions.clear();
-
-        assert_ok!(Deip::invest_to_crowdfunding_impl(
-            BOB_ACCOUNT_ID,
-            sale_id,
-            DeipAsset::new(base_asset_id, soft_cap / 4),
-        ));
-
-        assert_ok!(Deip::invest_to_crowdfunding_impl(
-            ALICE_ACCOUNT_ID,
-            sale_id,
-            DeipAsset::new(base_asset_id, alice_investing),
-        ));
-
-        // make alice zombie
-        let alice_remainder = DeipAssets::account_balance(&ALICE_ACCOUNT_ID, &base_asset_id);
-        let call = pallet_deip_assets::Call::<Test>::transfer(
-            base_asset_id,
-            BOB_ACCOUNT_ID,
-            alice_remainder,
-        );
-        let result = call.dispatch_bypass_filter(Origin::signed(ALICE_ACCOUNT_ID));
-        assert_ok!(result);
-
-        let _a = Balances::slash(&ALICE_ACCOUNT_ID, Balances::total_balance(&ALICE_ACCOUNT_ID));
-
-        // since the sale expired the tokens should be transfered back to
-        // the seller doesn't matter if assets/accounts frozen or not
-        let call = pallet_deip_assets::Call::<Test>::freeze(eur_id, BOB_ACCOUNT_ID);
-        let _result = call.dispatch_bypass_filter(Origin::signed(*account_id));
-
-        let call = pallet_deip_assets::Call::<Test>::freeze_asset(usd_id);
-        let _result = call.dispatch_bypass_filter(Origin::signed(*account_id));
-
-        let end_block = start_block + duration_in_blocks + 1;
-        while System::block_number() < end_block {
-            let block_number = System::block_number();
-            <System as OnFinalize<BlockNumber>>::on_finalize(block_number);
-            Deip::offchain_worker(System::block_number());
-            System::set_block_number(block_number + 1);
-            <System as OnInitialize<BlockNumber>>::on_initialize(System::block_number());
-            Timestamp::set_timestamp(System::block_number() * BLOCK_TIME + INIT_TIMESTAMP);
-        }
-
-        let inner = decode_validate_deip_call(&state.read().transactions[0]);
-        match inner {
-            crate::Call::expire_crowdfunding(id) => Deip::expire_crowdfunding_impl(id).unwrap(),
-            _ => unreachable!(),
-        };
-
-        assert_eq!(
-            DeipAssets::account_balance(&BOB_ACCOUNT_ID, &base_asset_id),
-            bob_balance_before + alice_remainder
-        );
-        assert_eq!(DeipAssets::account_balance(&ALICE_ACCOUNT_ID, &base_asset_id), alice_investing);
-        assert_eq!(DeipAssets::account_balance(account_id, &base_asset_id), balance_before);
-
-        assert_eq!(DeipAssets::account_balance(&BOB_ACCOUNT_ID, &usd_id), 0);
-        assert_eq!(DeipAssets::account_balance(&ALICE_ACCOUNT_ID, &usd_id), 0);
-        assert_eq!(DeipAssets::account_balance(account_id, &usd_id), usd_total);
-
-        assert_eq!(DeipAssets::account_balance(&BOB_ACCOUNT_ID, &eur_id), 0);
-        assert_eq!(DeipAssets::account_balance(&ALICE_ACCOUNT_ID, &eur_id), 0);
-        assert_eq!(DeipAssets::account_balance(account_id, &eur_id), eur_total);
-    })
-}
-
-#[test]
-fn two_simultaneous_crowdfundings_expired() {
-    let mut ext = new_test_ext2();
-    let state = offchainify(&mut ext, 2);
-    ext.execute_with(|| {
-        let base_asset_id = DeipAssetId(3u32);
-        let base_asset_total = 120_000u64;
-        create_mint_asset(DEFAULT_ACCOUNT_ID, base_asset_id, base_asset_total, None);
-
-        let call = pallet_deip_assets::Call::<Test>::transfer(
-            base_asset_id,
-            ALICE_ACCOUNT_ID,
-            base_asset_total / 2,
-        );
-        let result = call.dispatch_bypass_filter(Origin::signed(DEFAULT_ACCOUNT_ID));
-        assert_ok!(result);
-
-        let call = pallet_deip_assets::Call::<Test>::transfer(
-            base_asset_id,
-            BOB_ACCOUNT_ID,
-            base_asset_total / 2,
-        );
-        let result = call.dispatch_bypass_filter(Origin::signed(DEFAULT_ACCOUNT_ID));
-        assert_ok!(result);
-
-        let usd_id = DeipAssetId(0u32);
-        let usd_total = 100_000u64;
-        create_mint_asset(ALICE_ACCOUNT_ID, usd_id, usd_total, None);
-
-        let eur_id = DeipAssetId(1u32);
-        let eur_total = 80_000u64;
-        create_mint_asset(BOB_ACCOUNT_ID, eur_id, eur_total, None);
-
-        let bob_usd_balance_before = DeipAssets::account_balance(&BOB_ACCOUNT_ID, &usd_id);
-        let bob_eur_balance_before = DeipAssets::account_balance(&BOB_ACCOUNT_ID, &eur_id);
-
-        let alice_usd_balance_before = DeipAssets::account_balance(&ALICE_ACCOUNT_ID, &usd_id);
-        let alice_base_balance_before =
-            DeipAssets::account_balance(&ALICE_ACCOUNT_ID, &base_asset_id);
-
-        let start_time_in_blocks = 5;
-        let start_time =
-            pallet_timestamp::Pallet::<Test>::get() + start_time_in_blocks * BLOCK_TIME;
-        let eur_sale_id = H160::random();
-        let usd_sale_id = H160::random();
-        let soft_cap = 50_000u64;
-        let hard_cap = 60_000_u64;
-        let usd_to_sale = 70_000u64;
-        let eur_to_sale = 75_000u64;
-        let duration_in_blocks = 5;
-        assert_ok!(Deip::create_simple_crowdfunding(
-            BOB_ACCOUNT_ID,
-            eur_sale_id,
-            start_time,
-            start_time + duration_in_blocks * BLOCK_TIME,
-            DeipAsset::new(usd_id, soft_cap),
-            DeipAsset::new(usd_id, hard_cap),
-            vec![DeipAsset::new(eur_id, eur_to_sale)]
-        ));
-
-        assert_ok!(Deip::create_simple_crowdfunding(
-            ALICE_ACCOUNT_ID,
-            usd_sale_id,
-            start_time,
-            start_time + duration_in_blocks * BLOCK_TIME,
-            DeipAsset::new(base_asset_id, soft_cap),
-            DeipAsset::new(base_asset_id, hard_cap),
-            vec![DeipAsset::new(usd_id, usd_to_sale)]
-        ));
-
-        let start_block = System::block_number() + start_time_in_blocks + 1;
-        while System::block_number() < start_block {
-            let block_number = System::block_number();
-            <System as OnFinalize<BlockNumber>>::on_finalize(block_number);
-            Deip::offchain_worker(System::block_number());
-            System::set_block_number(block_number + 1);
-            <System as OnInitialize<BlockNumber>>::on_initialize(System::block_number());
-            Timestamp::set_timestamp(System::block_number() * BLOCK_TIME + INIT_TIMESTAMP);
-        }
-
-        assert_eq!(state.read().transactions.len(), 2);
-
-        let inner = decode_validate_deip_call(&state.read().transactions[0]);
-        match inner {
-            crate::Call::activate_crowdfunding(id) => Deip::activate_crowdfunding_impl(id).unwrap(),
-            _ => unreachable!(),
-        };
-
-        let inner = decode_validate_deip_call(&state.read().transactions[1]);
-        match inner {
-            crate::Call::activate_crowdfunding(id) => Deip::activate_crowdfunding_impl(id).unwrap(),
-            _ => unreachable!(),
-        };
-
-        state.write().transactions.clear();
-
-        assert_ok!(Deip::invest_to_crowdfunding_impl(
-            BOB_ACCOUNT_ID,
-            usd_sale_id,
-            DeipAsset::new(base_asset_id, soft_cap / 4),
-        ));
-
-        assert_ok!(Deip::invest_to_crowdfunding_impl(
-            ALICE_ACCOUNT_ID,
-            eur_sale_id,
-            DeipAsset::new(usd_id, soft_cap / 2),
-        ));
-
-        // since the sale expired the tokens should be transfered back to
-        // the seller doesn't matter if assets/accounts frozen or not
-        let call = pallet_deip_assets::Call::<Test>::freeze(eur_id, BOB_ACCOUNT_ID);
-        let _result = call.dispatch_bypass_filter(Origin::signed(BOB_ACCOUNT_ID));
-
-        let call = pallet_deip_assets::Call::<Test>::freeze_asset(usd_id);
-        let _result = call.dispatch_bypass_filter(Origin::signed(ALICE_ACCOUNT_ID));
-
-        let end_block = start_block + duration_in_blocks + 1;
-        while System::block_number() < end_block {
-            let block_number = System::block_number();
-            <System as OnFinalize<BlockNumber>>::on_finalize(block_number);
-            Deip::offchain_worker(System::block_number());
-            System::set_block_number(block_number + 1);
-            <System as OnInitialize<BlockNumber>>::on_initialize(System::block_number());
-            Timestamp::set_timestamp(System::block_number() * BLOCK_TIME + INIT_TIMESTAMP);
-        }
-
-        let inner = decode_validate_deip_call(&state.read().transactions[0]);
-        match inner {
-            crate::Call::expire_crowdfunding(id) => Deip::expire_crowdfunding_impl(id).unwrap(),
-            _ => unreachable!(),
-        };
-
-        let inner = decode_validate_deip_call(&state.read().transactions[1]);
-        match inner {
-            crate::Call::expire_crowdfunding(id) => Deip::expire_crowdfunding_impl(id).unwrap(),
-            _ => unreachable!(),
-        };
-
-        assert_eq!(
-            DeipAssets::account_balance(&ALICE_ACCOUNT_ID, &base_asset_id),
-            alice_base_balance_before
-        );
-
-        assert_eq!(DeipAssets::account_balance(&BOB_ACCOUNT_ID, &usd_id), bob_usd_balance_before);
-        assert_eq!(
-            DeipAssets::account_balance(&ALICE_ACCOUNT_ID, &usd_id),
-            alice_usd_balance_before
-        );
-
-        assert_eq!(DeipAssets::account_balance(&BOB_ACCOUNT_ID, &eur_id), bob_eur_balance_before);
-    })
-}
-
 #[test]
 fn create_license_agreement_well_known_cases() {
     new_test_ext2().execute_with(|| {
@@ -1496,3 +560,1249 @@ fn create_license_agreement_well_known_cases() {
         );
     })
 }
+
+fn key(byte: u8) -> ContentAccessKeyOf<Test> {
+    vec![byte].try_into().unwrap()
+}
+
+#[test]
+fn content_access_request_is_fulfilled_for_holder() {
+    new_test_ext2().execute_with(|| {
+        let content_id = create_ok_content(DEFAULT_ACCOUNT_ID);
+        TestNfts::create_class(1, DEFAULT_ACCOUNT_ID, DEFAULT_ACCOUNT_ID);
+        assert_ok!(Deip::set_content_nft_class(
+            Origin::signed(DEFAULT_ACCOUNT_ID),
+            content_id,
+            Some(1)
+        ));
+        assert_ok!(TestNfts::mint(&DEFAULT_ACCOUNT_ID, 1, &ALICE_ACCOUNT_ID));
+
+        let request_id = ContentAccessRequestId::random();
+        assert_noop!(
+            Deip::create_content_access_request(
+                Origin::signed(BOB_ACCOUNT_ID),
+                request_id,
+                content_id,
+                key(1)
+            ),
+            Error::<Test>::NotContentNftHolder
+        );
+        assert_ok!(Deip::create_content_access_request(
+            Origin::signed(ALICE_ACCOUNT_ID),
+            request_id,
+            content_id,
+            key(1)
+        ));
+        assert_eq!(
+            Balances::reserved_balance(ALICE_ACCOUNT_ID),
+            ContentAccessRequestDeposit::get()
+        );
+
+        assert_noop!(
+            Deip::fulfill_content_access_request(
+                Origin::signed(ALICE_ACCOUNT_ID),
+                request_id,
+                key(2),
+                key(3)
+            ),
+            Error::<Test>::NoPermission
+        );
+        assert_ok!(Deip::fulfill_content_access_request(
+            Origin::signed(DEFAULT_ACCOUNT_ID),
+            request_id,
+            key(2),
+            key(3)
+        ));
+
+        let request = Deip::get_content_access_request(&request_id).unwrap();
+        assert_eq!(request.status, content_access::Status::Fulfilled);
+        assert_eq!(request.grantor, Some(DEFAULT_ACCOUNT_ID));
+        assert_eq!(request.encrypted_key, Some(vec![2]));
+        assert_eq!(Balances::reserved_balance(ALICE_ACCOUNT_ID), 0);
+    })
+}
+
+#[test]
+fn content_access_request_isnt_fulfilled_after_nft_moved() {
+    new_test_ext2().execute_with(|| {
+        let content_id = create_ok_content(DEFAULT_ACCOUNT_ID);
+        TestNfts::create_class(1, DEFAULT_ACCOUNT_ID, DEFAULT_ACCOUNT_ID);
+        assert_ok!(Deip::set_content_nft_class(
+            Origin::signed(DEFAULT_ACCOUNT_ID),
+            content_id,
+            Some(1)
+        ));
+        let instance = TestNfts::mint(&DEFAULT_ACCOUNT_ID, 1, &ALICE_ACCOUNT_ID).unwrap();
+
+        let request_id = ContentAccessRequestId::random();
+        assert_ok!(Deip::create_content_access_request(
+            Origin::signed(ALICE_ACCOUNT_ID),
+            request_id,
+            content_id,
+            key(1)
+        ));
+        TestNfts::transfer(1, instance, BOB_ACCOUNT_ID);
+
+        assert_noop!(
+            Deip::fulfill_content_access_request(
+                Origin::signed(DEFAULT_ACCOUNT_ID),
+                request_id,
+                key(2),
+                key(3)
+            ),
+            Error::<Test>::NotContentNftHolder
+        );
+        assert_ok!(Deip::reject_content_access_request(
+            Origin::signed(DEFAULT_ACCOUNT_ID),
+            request_id
+        ));
+        assert_eq!(
+            Deip::get_content_access_request(&request_id).unwrap().status,
+            content_access::Status::Rejected
+        );
+        assert_eq!(Balances::reserved_balance(ALICE_ACCOUNT_ID), 0);
+    })
+}
+
+#[test]
+fn content_is_unlinked_from_destroyed_nft_class() {
+    new_test_ext2().execute_with(|| {
+        let content_id = create_ok_content(DEFAULT_ACCOUNT_ID);
+        TestNfts::create_class(1, DEFAULT_ACCOUNT_ID, DEFAULT_ACCOUNT_ID);
+        assert_ok!(Deip::set_content_nft_class(
+            Origin::signed(DEFAULT_ACCOUNT_ID),
+            content_id,
+            Some(1)
+        ));
+
+        TestNfts::destroy_class(1);
+        assert_eq!(NftClassIdByContentId::<Test>::get(content_id), None);
+        assert_eq!(ContentCountByNftClassId::<Test>::get(1), 0);
+
+        // holders of a new class with the same id don't get access
+        TestNfts::create_class(1, BOB_ACCOUNT_ID, BOB_ACCOUNT_ID);
+        assert_ok!(TestNfts::mint(&BOB_ACCOUNT_ID, 1, &ALICE_ACCOUNT_ID));
+        assert_noop!(
+            Deip::create_content_access_request(
+                Origin::signed(ALICE_ACCOUNT_ID),
+                ContentAccessRequestId::random(),
+                content_id,
+                key(1)
+            ),
+            Error::<Test>::ContentIsNotNftGated
+        );
+    })
+}
+
+#[test]
+fn contents_gated_by_nft_class_are_bounded() {
+    new_test_ext().execute_with(|| {
+        TestNfts::create_class(1, DEFAULT_ACCOUNT_ID, DEFAULT_ACCOUNT_ID);
+        let contents: Vec<_> = (0..3).map(|_| create_ok_content(DEFAULT_ACCOUNT_ID)).collect();
+
+        for id in &contents[..2] {
+            assert_ok!(Deip::set_content_nft_class(
+                Origin::signed(DEFAULT_ACCOUNT_ID),
+                *id,
+                Some(1)
+            ));
+        }
+        assert_noop!(
+            Deip::set_content_nft_class(Origin::signed(DEFAULT_ACCOUNT_ID), contents[2], Some(1)),
+            Error::<Test>::TooManyContentsPerNftClass
+        );
+
+        assert_ok!(Deip::set_content_nft_class(
+            Origin::signed(DEFAULT_ACCOUNT_ID),
+            contents[0],
+            None
+        ));
+        assert_ok!(Deip::set_content_nft_class(
+            Origin::signed(DEFAULT_ACCOUNT_ID),
+            contents[2],
+            Some(1)
+        ));
+        assert_eq!(ContentCountByNftClassId::<Test>::get(1), 2);
+    })
+}
+
+/// Creates a project of the default account which licenses are represented by
+/// instances of NFT `class` and the license of the project signed by `licensee`.
+fn create_signed_license(
+    licensee: <Test as system::Config>::AccountId,
+    class: u32,
+) -> ContractAgreementId {
+    let team = DEFAULT_ACCOUNT_ID;
+    let (project_id, ..) = create_ok_project(Some(team));
+    TestNfts::create_class(class, team, team);
+    assert_ok!(Deip::set_license_nft_class(Origin::signed(team), project_id, Some(class)));
+
+    let asset = DeipAssetId::repeat_byte(1);
+    assert_ok!(DeipAssets::deip_create(Origin::signed(team), asset, team, 1, None, None));
+    assert_ok!(DeipAssets::deip_mint(Origin::signed(team), asset, licensee, 10));
+
+    let id = ContractAgreementId::random();
+    assert_ok!(Deip::create_contract_agreement_impl(
+        team,
+        id,
+        team,
+        vec![team, licensee],
+        HashOf::<Test>::random(),
+        None,
+        None,
+        ContractAgreementTermsOf::<Test>::LicenseAgreement {
+            source: project_id,
+            price: DeipAsset::new(asset, 5),
+        }
+    ));
+    assert_ok!(Deip::accept_contract_agreement(Origin::signed(team), id, team));
+    assert_ok!(Deip::accept_contract_agreement(Origin::signed(licensee), id, licensee));
+
+    id
+}
+
+fn licensee_of(id: ContractAgreementId) -> Option<<Test as system::Config>::AccountId> {
+    match Deip::get_contract_agreement(&id) {
+        Some(contract::Agreement::License(contract::LicenseStatus::Signed(license))) =>
+            Some(license.licensee),
+        _ => None,
+    }
+}
+
+#[test]
+fn license_nft_class_must_be_issued_by_team() {
+    new_test_ext2().execute_with(|| {
+        let (project_id, ..) = create_ok_project(Some(DEFAULT_ACCOUNT_ID));
+        // the team owns the class, but another account mints it
+        TestNfts::create_class(1, DEFAULT_ACCOUNT_ID, BOB_ACCOUNT_ID);
+        assert_noop!(
+            Deip::set_license_nft_class(Origin::signed(DEFAULT_ACCOUNT_ID), project_id, Some(1)),
+            Error::<Test>::LicenseNftClassNotIssuedByTeam
+        );
+
+        TestNfts::create_class(2, BOB_ACCOUNT_ID, DEFAULT_ACCOUNT_ID);
+        assert_ok!(Deip::set_license_nft_class(
+            Origin::signed(DEFAULT_ACCOUNT_ID),
+            project_id,
+            Some(2)
+        ));
+
+        let (other_project_id, ..) = create_ok_project(Some(DEFAULT_ACCOUNT_ID));
+        assert_noop!(
+            Deip::set_license_nft_class(
+                Origin::signed(DEFAULT_ACCOUNT_ID),
+                other_project_id,
+                Some(2)
+            ),
+            Error::<Test>::LicenseNftClassUsedByOtherProject
+        );
+    })
+}
+
+#[test]
+fn licensee_is_holder_of_license_nft() {
+    new_test_ext2().execute_with(|| {
+        let id = create_signed_license(ALICE_ACCOUNT_ID, 1);
+        let (class, instance) = Deip::license_nft(&id).unwrap();
+        assert_eq!(TestNfts::holder(&class, &instance), Some(ALICE_ACCOUNT_ID));
+        assert_eq!(licensee_of(id), Some(ALICE_ACCOUNT_ID));
+
+        TestNfts::transfer(class, instance, BOB_ACCOUNT_ID);
+        assert_eq!(licensee_of(id), Some(BOB_ACCOUNT_ID));
+    })
+}
+
+#[test]
+fn license_is_void_after_nft_burned() {
+    new_test_ext2().execute_with(|| {
+        let id = create_signed_license(ALICE_ACCOUNT_ID, 1);
+        let (class, instance) = Deip::license_nft(&id).unwrap();
+
+        TestNfts::burn(class, instance);
+        assert_eq!(licensee_of(id), None);
+        assert!(matches!(
+            Deip::get_contract_agreement(&id),
+            Some(contract::Agreement::License(contract::LicenseStatus::Void(_)))
+        ));
+    })
+}
+
+#[test]
+fn license_nft_class_is_unset_when_destroyed() {
+    new_test_ext2().execute_with(|| {
+        let id = create_signed_license(ALICE_ACCOUNT_ID, 1);
+        let project_id = ProjectIdByLicenseNftClassId::<Test>::get(1).unwrap();
+
+        TestNfts::destroy_class(1);
+        assert_eq!(LicenseNftClassIdByProjectId::<Test>::get(project_id), None);
+        assert_eq!(ProjectIdByLicenseNftClassId::<Test>::get(1), None);
+        assert!(matches!(
+            Deip::get_contract_agreement(&id),
+            Some(contract::Agreement::License(contract::LicenseStatus::Void(_)))
+        ));
+    })
+}
+
+// NDA and crowdfunding tests are kept commented out like the NDA calls, the crowdfunding
+// ones moved to pallet_deip_investment_opportunity. Restore them with the calls.
+//
+// use frame_support::{
+//     assert_noop, assert_ok,
+//     traits::{OffchainWorker, OnFinalize, OnInitialize, UnfilteredDispatchable},
+// };
+// use sp_core::{
+//     offchain::{testing::*, TransactionPoolExt},
+//     H256,
+// };
+// use sp_io::TestExternalities;
+// use sp_runtime::{
+//     offchain::OffchainWorkerExt,
+//     traits::{One, Zero},
+// };
+// use sp_std::sync::Arc;
+// use std::{
+//     sync::RwLock,
+//     time::{SystemTime, UNIX_EPOCH},
+// };
+//
+// const DAY_IN_MILLIS: u64 = 86400000;
+//
+// type BlockNumber = <Test as system::Config>::BlockNumber;
+//
+// fn create_ok_nda() -> (NdaId, NdaOf<Test>) {
+//     let (project_id, ..) = create_ok_project(None);
+//     let project_nda_id = NdaId::random();
+//     let now = SystemTime::now()
+//         .duration_since(UNIX_EPOCH)
+//         .expect("Time went backwards :)")
+//         .as_millis() as u64;
+//
+//     let end_date = now + DAY_IN_MILLIS;
+//     let contract_hash = H256::random();
+//     let maybe_start_date = None;
+//     let parties = vec![DEFAULT_ACCOUNT_ID];
+//     let projects = vec![project_id];
+//
+//     assert_ok!(Deip::create_project_nda(
+//         Origin::signed(DEFAULT_ACCOUNT_ID),
+//         project_nda_id,
+//         end_date,
+//         contract_hash,
+//         maybe_start_date,
+//         parties.clone(),
+//         projects.clone()
+//     ));
+//
+//     let expected_nda = Nda {
+//         contract_creator: DEFAULT_ACCOUNT_ID,
+//         external_id: project_nda_id,
+//         end_date,
+//         start_date: maybe_start_date,
+//         contract_hash,
+//         parties,
+//         projects,
+//     };
+//
+//     (project_nda_id, expected_nda)
+// }
+//
+// fn create_ok_nda_content_access_request(
+//     project_nda_id: NdaId,
+// ) -> (NdaAccessRequestId, NdaAccessRequestOf<Test>) {
+//     let access_request_id = NdaAccessRequestId::random();
+//     let encrypted_payload_hash = H256::random();
+//     let encrypted_payload_iv = vec![1, 2, 3];
+//
+//     assert_ok!(Deip::create_nda_content_access_request(
+//         Origin::signed(DEFAULT_ACCOUNT_ID),
+//         access_request_id,
+//         project_nda_id,
+//         encrypted_payload_hash,
+//         encrypted_payload_iv.clone()
+//     ));
+//
+//     let expected_nda_request = NdaAccessRequest {
+//         external_id: access_request_id,
+//         nda_external_id: project_nda_id,
+//         requester: DEFAULT_ACCOUNT_ID,
+//         encrypted_payload_hash,
+//         encrypted_payload_iv,
+//         status: NdaAccessRequestStatus::Pending,
+//         grantor: None,
+//         encrypted_payload_encryption_key: None,
+//         proof_of_encrypted_payload_encryption_key: None,
+//     };
+//
+//     (access_request_id, expected_nda_request)
+// }
+//
+// fn create_mint_asset(
+//     account_id: AccountIdOf<Test>,
+//     id: DeipAssetIdOf<Test>,
+//     amount: DeipAssetBalanceOf<Test>,
+//     project_id: Option<ProjectId>,
+// ) {
+//     let call =
+//         pallet_deip_assets::Call::<Test>::create_asset(id, account_id, 1u32.into(), project_id);
+//     let result = call.dispatch_bypass_filter(Origin::signed(account_id));
+//     assert_ok!(result);
+//
+//     let call = pallet_deip_assets::Call::<Test>::issue_asset(id, account_id, amount);
+//     let result = call.dispatch_bypass_filter(Origin::signed(account_id));
+//     assert_ok!(result);
+// }
+//
+// /// convert an externalities to one that can handle offchain worker tests.
+// /// Check substrate-v3.0.0/frame/staking/src/tests.rs +3452
+// fn offchainify(ext: &mut TestExternalities, iterations: u32) -> Arc<RwLock<PoolState>> {
+//     let (offchain, offchain_state) = TestOffchainExt::new();
+//     let (pool, pool_state) = TestTransactionPoolExt::new();
+//
+//     let mut seed = [0_u8; 32];
+//     seed[0..4].copy_from_slice(&iterations.to_le_bytes());
+//     offchain_state.write().seed = seed;
+//
+//     ext.register_extension(OffchainWorkerExt::new(offchain));
+//     ext.register_extension(TransactionPoolExt::new(pool));
+//
+//     pool_state
+// }
+//
+// fn decode_validate_deip_call(encoded: &[u8]) -> crate::Call<Test> {
+//     let mut encoded = encoded.clone();
+//     let extrinsic: Extrinsic = Decode::decode(&mut encoded).unwrap();
+//
+//     let call = extrinsic.call;
+//     let inner = match call {
+//         mock::Call::Deip(inner) => inner,
+//         _ => unreachable!(),
+//     };
+//
+//     assert_eq!(
+//         <Deip as sp_runtime::traits::ValidateUnsigned>::validate_unsigned(
+//             TransactionSource::Local,
+//             &inner,
+//         )
+//         .is_ok(),
+//         true
+//     );
+//
+//     inner
+// }
+//
+// #[test]
+// fn create_project_nda() {
+//     new_test_ext().execute_with(|| {
+//         let (project_nda_id, expected_nda) = create_ok_nda();
+//
+//         let nda_list = Ndas::<Test>::get();
+//         let nda_stored = NdaMapV1::<Test>::get(project_nda_id);
+//
+//         assert!(
+//             <NdaMapV1<Test>>::contains_key(project_nda_id),
+//             "NDA Map did not contain key, value was `{}`",
+//             project_nda_id
+//         );
+//
+//         assert_eq!(expected_nda, nda_stored);
+//
+//         assert!(
+//             nda_list
+//                 .binary_search_by_key(&project_nda_id, |&(external_id, ..)| external_id)
+//                 .is_ok(),
+//             "NDA List did not contain the NDA, value was `{}`",
+//             project_nda_id
+//         );
+//     })
+// }
+//
+// #[test]
+// fn cant_create_project_nda_ends_in_past() {
+//     new_test_ext().execute_with(|| {
+//         let (project_id, ..) = create_ok_project(None);
+//         let project_nda_id = NdaId::random();
+//         let end_date = 0;
+//
+//         let contract_hash = H256::random();
+//         let maybe_start_date = None;
+//         let parties = vec![DEFAULT_ACCOUNT_ID];
+//         let projects = vec![project_id];
+//
+//         assert_noop!(
+//             Deip::create_project_nda(
+//                 Origin::signed(DEFAULT_ACCOUNT_ID),
+//                 project_nda_id,
+//                 end_date,
+//                 contract_hash,
+//                 maybe_start_date,
+//                 parties.clone(),
+//                 projects.clone()
+//             ),
+//             Error::<Test>::NdaEndDateMustBeLaterCurrentMoment
+//         );
+//     })
+// }
+//
+// #[test]
+// fn cant_create_project_nda_with_start_date_greater_end_date() {
+//     new_test_ext().execute_with(|| {
+//         let (project_id, ..) = create_ok_project(None);
+//         let project_nda_id = NdaId::random();
+//
+//         let end_date = 1;
+//         let maybe_start_date = Some(3);
+//
+//         let contract_hash = H256::random();
+//
+//         let parties = vec![DEFAULT_ACCOUNT_ID];
+//         let projects = vec![project_id];
+//
+//         assert_noop!(
+//             Deip::create_project_nda(
+//                 Origin::signed(DEFAULT_ACCOUNT_ID),
+//                 project_nda_id,
+//                 end_date,
+//                 contract_hash,
+//                 maybe_start_date,
+//                 parties.clone(),
+//                 projects.clone()
+//             ),
+//             Error::<Test>::NdaStartDateMustBeLessThanEndDate
+//         );
+//     })
+// }
+//
+// #[test]
+// fn cant_create_project_nda_with_non_existed_project() {
+//     new_test_ext().execute_with(|| {
+//         let project_id = ProjectId::random();
+//         let project_nda_id = NdaId::random();
+//         let now = SystemTime::now()
+//             .duration_since(UNIX_EPOCH)
+//             .expect("Time went backwards :)")
+//             .as_millis() as u64;
+//
+//         let end_date = now + DAY_IN_MILLIS;
+//
+//         let contract_hash = H256::random();
+//         let maybe_start_date = None;
+//         let parties = vec![DEFAULT_ACCOUNT_ID];
+//         let projects = vec![project_id];
+//
+//         assert_noop!(
+//             Deip::create_project_nda(
+//                 Origin::signed(DEFAULT_ACCOUNT_ID),
+//                 project_nda_id,
+//                 end_date,
+//                 contract_hash,
+//                 maybe_start_date,
+//                 parties.clone(),
+//                 projects.clone()
+//             ),
+//             Error::<Test>::NoSuchProject
+//         );
+//     })
+// }
+//
+// #[test]
+// fn cant_create_project_nda_with_not_correct_parties() {
+//     new_test_ext().execute_with(|| {
+//         let (project_id, ..) = create_ok_project(None);
+//         let project_nda_id = NdaId::random();
+//         let now = SystemTime::now()
+//             .duration_since(UNIX_EPOCH)
+//             .expect("Time went backwards :)")
+//             .as_millis() as u64;
+//
+//         let end_date = now + DAY_IN_MILLIS;
+//
+//         let wrong_account_id = 4;
+//
+//         let contract_hash = H256::random();
+//         let maybe_start_date = None;
+//         let parties = vec![wrong_account_id];
+//         let projects = vec![project_id];
+//
+//         assert_noop!(
+//             Deip::create_project_nda(
+//                 Origin::signed(wrong_account_id),
+//                 project_nda_id,
+//                 end_date,
+//                 contract_hash,
+//                 maybe_start_date,
+//                 parties.clone(),
+//                 projects.clone()
+//             ),
+//             Error::<Test>::TeamOfAllProjectsMustSpecifiedAsParty
+//         );
+//     })
+// }
+//
+// #[test]
+// fn cant_create_duplicated_project_nda() {
+//     new_test_ext().execute_with(|| {
+//         let (project_nda_id, ..) = create_ok_nda();
+//
+//         let (project_id, ..) = create_ok_project(None);
+//         let now = SystemTime::now()
+//             .duration_since(UNIX_EPOCH)
+//             .expect("Time went backwards :)")
+//             .as_millis() as u64;
+//
+//         let end_date = now + DAY_IN_MILLIS;
+//
+//         let contract_hash = H256::random();
+//         let maybe_start_date = None;
+//         let parties = vec![DEFAULT_ACCOUNT_ID];
+//         let projects = vec![project_id];
+//
+//         assert_noop!(
+//             Deip::create_project_nda(
+//                 Origin::signed(DEFAULT_ACCOUNT_ID),
+//                 project_nda_id,
+//                 end_date,
+//                 contract_hash,
+//                 maybe_start_date,
+//                 parties.clone(),
+//                 projects.clone()
+//             ),
+//             Error::<Test>::NdaAlreadyExists
+//         );
+//     })
+// }
+//
+// #[test]
+// fn create_nda_content_access_request() {
+//     new_test_ext().execute_with(|| {
+//         let (project_nda_id, ..) = create_ok_nda();
+//
+//         let (access_request_id, expected_nda_request) =
+//             create_ok_nda_content_access_request(project_nda_id);
+//
+//         let nda_list = NdaAccessRequests::<Test>::get();
+//         let nda_stored = NdaAccessRequestMapV1::<Test>::get(access_request_id);
+//
+//         assert!(
+//             <NdaAccessRequestMapV1<Test>>::contains_key(access_request_id),
+//             "NDA request Map did not contain key, value was `{}`",
+//             access_request_id
+//         );
+//
+//         assert_eq!(expected_nda_request, nda_stored);
+//
+//         assert!(
+//             nda_list
+//                 .binary_search_by_key(&access_request_id, |&(external_id, ..)| external_id)
+//                 .is_ok(),
+//             "NDA request List did not contain the NDA request, value was `{}`",
+//             access_request_id
+//         );
+//     })
+// }
+//
+// #[test]
+// fn cant_create_nda_content_access_with_non_existed_nda() {
+//     new_test_ext().execute_with(|| {
+//         let project_nda_id = NdaId::random();
+//
+//         let access_request_id = NdaAccessRequestId::random();
+//         let encrypted_payload_hash = H256::random();
+//         let encrypted_payload_iv = vec![1, 2, 3];
+//
+//         assert_noop!(
+//             Deip::create_nda_content_access_request(
+//                 Origin::signed(DEFAULT_ACCOUNT_ID),
+//                 access_request_id,
+//                 project_nda_id,
+//                 encrypted_payload_hash,
+//                 encrypted_payload_iv.clone()
+//             ),
+//             Error::<Test>::NoSuchNda
+//         );
+//     })
+// }
+//
+// #[test]
+// fn cant_create_duplicated_nda_content_access() {
+//     new_test_ext().execute_with(|| {
+//         let (project_nda_id, ..) = create_ok_nda();
+//         let (access_request_id, expected_nda_request) =
+//             create_ok_nda_content_access_request(project_nda_id);
+//
+//         assert_noop!(
+//             Deip::create_nda_content_access_request(
+//                 Origin::signed(DEFAULT_ACCOUNT_ID),
+//                 access_request_id,
+//                 project_nda_id,
+//                 expected_nda_request.encrypted_payload_hash,
+//                 expected_nda_request.encrypted_payload_iv
+//             ),
+//             Error::<Test>::NdaAccessRequestAlreadyExists
+//         );
+//     })
+// }
+//
+// #[test]
+// fn fulfill_nda_content_access_request() {
+//     new_test_ext().execute_with(|| {
+//         let (project_nda_id, ..) = create_ok_nda();
+//
+//         let (access_request_id, nda_request) = create_ok_nda_content_access_request(project_nda_id);
+//
+//         let encrypted_payload_encryption_key = vec![1, 3, 4, 2];
+//         let proof_of_encrypted_payload_encryption_key = vec![3, 4, 5, 6];
+//
+//         assert_ok!(Deip::fulfill_nda_content_access_request(
+//             Origin::signed(DEFAULT_ACCOUNT_ID),
+//             access_request_id.clone(),
+//             encrypted_payload_encryption_key.clone(),
+//             proof_of_encrypted_payload_encryption_key.clone()
+//         ));
+//
+//         let nda_stored = NdaAccessRequestMapV1::<Test>::get(access_request_id);
+//
+//         let expected_nda_request = NdaAccessRequest {
+//             status: NdaAccessRequestStatus::Fulfilled,
+//             grantor: Some(DEFAULT_ACCOUNT_ID),
+//             encrypted_payload_encryption_key: Some(encrypted_payload_encryption_key),
+//             proof_of_encrypted_payload_encryption_key: Some(
+//                 proof_of_encrypted_payload_encryption_key,
+//             ),
+//             ..nda_request
+//         };
+//
+//         assert_eq!(expected_nda_request, nda_stored);
+//     })
+// }
+//
+// #[test]
+// fn cant_fulfill_not_existed_nda_content_access_request() {
+//     new_test_ext().execute_with(|| {
+//         let access_request_id = NdaAccessRequestId::random();
+//
+//         let encrypted_payload_encryption_key = vec![1, 3, 4, 2];
+//         let proof_of_encrypted_payload_encryption_key = vec![3, 4, 5, 6];
+//
+//         assert_noop!(
+//             Deip::fulfill_nda_content_access_request(
+//                 Origin::signed(DEFAULT_ACCOUNT_ID),
+//                 access_request_id.clone(),
+//                 encrypted_payload_encryption_key.clone(),
+//                 proof_of_encrypted_payload_encryption_key.clone()
+//             ),
+//             Error::<Test>::NoSuchNdaAccessRequest
+//         );
+//     })
+// }
+//
+// #[test]
+// fn cant_fulfill_finalized_nda_content_access_request() {
+//     new_test_ext().execute_with(|| {
+//         let (project_nda_id, ..) = create_ok_nda();
+//
+//         let (access_request_id, ..) = create_ok_nda_content_access_request(project_nda_id);
+//
+//         let encrypted_payload_encryption_key = vec![1, 3, 4, 2];
+//         let proof_of_encrypted_payload_encryption_key = vec![3, 4, 5, 6];
+//
+//         assert_ok!(Deip::fulfill_nda_content_access_request(
+//             Origin::signed(DEFAULT_ACCOUNT_ID),
+//             access_request_id.clone(),
+//             encrypted_payload_encryption_key.clone(),
+//             proof_of_encrypted_payload_encryption_key.clone()
+//         ));
+//
+//         assert_noop!(
+//             Deip::fulfill_nda_content_access_request(
+//                 Origin::signed(DEFAULT_ACCOUNT_ID),
+//                 access_request_id.clone(),
+//                 encrypted_payload_encryption_key.clone(),
+//                 proof_of_encrypted_payload_encryption_key.clone()
+//             ),
+//             Error::<Test>::NdaAccessRequestAlreadyFinalized
+//         );
+//     })
+// }
+//
+// #[test]
+// fn reject_nda_content_access_request() {
+//     new_test_ext().execute_with(|| {
+//         let (project_nda_id, ..) = create_ok_nda();
+//
+//         let (access_request_id, nda_request) = create_ok_nda_content_access_request(project_nda_id);
+//
+//         assert_ok!(Deip::reject_nda_content_access_request(
+//             Origin::signed(DEFAULT_ACCOUNT_ID),
+//             access_request_id.clone(),
+//         ));
+//
+//         let nda_stored = NdaAccessRequestMapV1::<Test>::get(access_request_id);
+//
+//         let expected_nda_request =
+//             NdaAccessRequest { status: NdaAccessRequestStatus::Rejected, ..nda_request };
+//
+//         assert_eq!(expected_nda_request, nda_stored);
+//     })
+// }
+//
+// #[test]
+// fn cant_reject_not_existed_nda_content_access_request() {
+//     new_test_ext().execute_with(|| {
+//         let access_request_id = NdaAccessRequestId::random();
+//
+//         assert_noop!(
+//             Deip::reject_nda_content_access_request(
+//                 Origin::signed(DEFAULT_ACCOUNT_ID),
+//                 access_request_id.clone(),
+//             ),
+//             Error::<Test>::NoSuchNdaAccessRequest
+//         );
+//     })
+// }
+//
+// #[test]
+// fn cant_reject_finalized_nda_content_access_request() {
+//     new_test_ext().execute_with(|| {
+//         let (project_nda_id, ..) = create_ok_nda();
+//
+//         let (access_request_id, ..) = create_ok_nda_content_access_request(project_nda_id);
+//
+//         assert_ok!(Deip::reject_nda_content_access_request(
+//             Origin::signed(DEFAULT_ACCOUNT_ID),
+//             access_request_id.clone(),
+//         ));
+//
+//         assert_noop!(
+//             Deip::reject_nda_content_access_request(
+//                 Origin::signed(DEFAULT_ACCOUNT_ID),
+//                 access_request_id.clone(),
+//             ),
+//             Error::<Test>::NdaAccessRequestAlreadyFinalized
+//         );
+//     })
+// }
+//
+// #[test]
+// fn simple_crowdfunding_create_should_fail() {
+//     new_test_ext2().execute_with(|| {
+//         let start_time = pallet_timestamp::Pallet::<Test>::get();
+//         assert_noop!(
+//             Deip::create_simple_crowdfunding(
+//                 DEFAULT_ACCOUNT_ID,
+//                 H160::random(),
+//                 start_time,
+//                 start_time + 1,
+//                 DeipAsset::new(DeipAssetId(0u32), 100u32.into()),
+//                 DeipAsset::new(DeipAssetId(0u32), 120u32.into()),
+//                 vec![
+//                     DeipAsset::new(DeipAssetId(0u32), 100u32.into()),
+//                     DeipAsset::new(DeipAssetId(14u32), 200u32.into())
+//                 ]
+//             ),
+//             Error::<Test>::InvestmentOpportunityWrongAssetId
+//         );
+//
+//         assert_noop!(
+//             Deip::create_simple_crowdfunding(
+//                 DEFAULT_ACCOUNT_ID,
+//                 H160::random(),
+//                 start_time,
+//                 start_time + 1,
+//                 DeipAsset::new(DeipAssetId(0u32), 100u32.into()),
+//                 DeipAsset::new(DeipAssetId(0u32), 120u32.into()),
+//                 vec![]
+//             ),
+//             Error::<Test>::InvestmentOpportunitySecurityTokenNotSpecified
+//         );
+//     })
+// }
+//
+// #[test]
+// fn simple_crowdfunding_hard_cap_reached() {
+//     let mut ext = new_test_ext2();
+//     let state = offchainify(&mut ext, 2);
+//     ext.execute_with(|| {
+//         let (ref project_id, .., ref account_id) = create_ok_project(None);
+//
+//         let base_asset_id = DeipAssetId(3u32);
+//         let base_asset_total = 120_000u64;
+//         create_mint_asset(ALICE_ACCOUNT_ID, base_asset_id, base_asset_total, None);
+//
+//         let call = pallet_deip_assets::Call::<Test>::transfer {
+//             id: base_asset_id,
+//             target: BOB_ACCOUNT_ID,
+//             amount: base_asset_total / 2,
+//         };
+//         let result = call.dispatch_bypass_filter(Origin::signed(ALICE_ACCOUNT_ID));
+//         assert_ok!(result);
+//
+//         let usd_id = DeipAssetId(0u32);
+//         let usd_total = 100_000u64;
+//         create_mint_asset(*account_id, usd_id, usd_total, Some(*project_id));
+//
+//         let eur_id = DeipAssetId(1u32);
+//         let eur_total = 80_000u64;
+//         create_mint_asset(*account_id, eur_id, eur_total, Some(*project_id));
+//
+//         let balance_before = DeipAssets::account_balance(account_id, &base_asset_id);
+//
+//         let start_time = pallet_timestamp::Pallet::<Test>::get();
+//         let sale_id = H160::random();
+//         let soft_cap = 100_000u64;
+//         let hard_cap = base_asset_total;
+//         let usd_to_sale = 80_000u64;
+//         let eur_to_sale = 75_000u64;
+//         assert_ok!(Deip::create_simple_crowdfunding(
+//             DEFAULT_ACCOUNT_ID,
+//             sale_id,
+//             start_time,
+//             start_time + 100,
+//             DeipAsset::new(base_asset_id, soft_cap),
+//             DeipAsset::new(base_asset_id, hard_cap),
+//             vec![DeipAsset::new(usd_id, usd_to_sale), DeipAsset::new(eur_id, eur_to_sale)]
+//         ));
+//
+//         Deip::offchain_worker(System::block_number());
+//         assert_eq!(state.read().transactions.len(), 1);
+//
+//         let inner = decode_validate_deip_call(&state.read().transactions[0]);
+//         match inner {
+//             crate::Call::activate_crowdfunding(id) => Deip::activate_crowdfunding_impl(id).unwrap(),
+//             _ => unreachable!(),
+//         };
+//
+//         assert_ok!(Deip::invest_to_crowdfunding_impl(
+//             BOB_ACCOUNT_ID,
+//             sale_id,
+//             DeipAsset::new(base_asset_id, hard_cap / 2),
+//         ));
+//
+//         // investors should get their tokens in any case
+//         let call = pallet_deip_assets::Call::<Test>::freeze(usd_id, BOB_ACCOUNT_ID);
+//         let _result = call.dispatch_bypass_filter(Origin::signed(*account_id));
+//
+//         let call = pallet_deip_assets::Call::<Test>::freeze_asset(eur_id);
+//         let _result = call.dispatch_bypass_filter(Origin::signed(*account_id));
+//
+//         assert_ok!(Deip::invest_to_crowdfunding_impl(
+//             ALICE_ACCOUNT_ID,
+//             sale_id,
+//             DeipAsset::new(base_asset_id, hard_cap / 2),
+//         ));
+//
+//         assert_eq!(DeipAssets::account_balance(&BOB_ACCOUNT_ID, &usd_id), usd_to_sale / 2);
+//         assert_eq!(DeipAssets::account_balance(&ALICE_ACCOUNT_ID, &usd_id), usd_to_sale / 2);
+//         assert_eq!(
+//             DeipAssets::account_balance(account_id, &usd_id),
+//             DeipAssets::total_supply(&usd_id) - usd_to_sale
+//         );
+//
+//         assert_eq!(DeipAssets::account_balance(&BOB_ACCOUNT_ID, &eur_id), eur_to_sale / 2);
+//         assert_eq!(DeipAssets::account_balance(&ALICE_ACCOUNT_ID, &eur_id), eur_to_sale / 2);
+//         assert_eq!(
+//             DeipAssets::account_balance(account_id, &eur_id),
+//             DeipAssets::total_supply(&eur_id) - eur_to_sale
+//         );
+//
+//         assert_eq!(
+//             DeipAssets::account_balance(account_id, &base_asset_id),
+//             hard_cap + balance_before
+//         );
+//     })
+// }
+//
+// #[test]
+// fn simple_crowdfunding_expired() {
+//     let mut ext = new_test_ext2();
+//     let state = offchainify(&mut ext, 2);
+//     ext.execute_with(|| {
+//         let (ref project_id, .., ref account_id) = create_ok_project(None);
+//
+//         let base_asset_id = DeipAssetId(3u32);
+//         let base_asset_total = 120_000u64;
+//         create_mint_asset(*account_id, base_asset_id, base_asset_total, Some(*project_id));
+//
+//         let call = pallet_deip_assets::Call::<Test>::transfer(
+//             base_asset_id,
+//             ALICE_ACCOUNT_ID,
+//             base_asset_total / 2,
+//         );
+//         let result = call.dispatch_bypass_filter(Origin::signed(*account_id));
+//         assert_ok!(result);
+//
+//         let call = pallet_deip_assets::Call::<Test>::transfer(
+//             base_asset_id,
+//             BOB_ACCOUNT_ID,
+//             base_asset_total / 2,
+//         );
+//         let result = call.dispatch_bypass_filter(Origin::signed(*account_id));
+//         assert_ok!(result);
+//
+//         let usd_id = DeipAssetId(0u32);
+//         let usd_total = 100_000u64;
+//         create_mint_asset(*account_id, usd_id, usd_total, Some(*project_id));
+//
+//         let eur_id = DeipAssetId(1u32);
+//         let eur_total = 80_000u64;
+//         create_mint_asset(*account_id, eur_id, eur_total, Some(*project_id));
+//
+//         let balance_before = DeipAssets::account_balance(account_id, &base_asset_id);
+//         let bob_balance_before = DeipAssets::account_balance(&BOB_ACCOUNT_ID, &base_asset_id);
+//
+//         let start_time_in_blocks = 5;
+//         let start_time =
+//             pallet_timestamp::Pallet::<Test>::get() + start_time_in_blocks * BLOCK_TIME;
+//         let sale_id = H160::random();
+//         let soft_cap = 100_000u64;
+//         let alice_investing = soft_cap / 2;
+//         let hard_cap = base_asset_total;
+//         let usd_to_sale = 80_000u64;
+//         let eur_to_sale = 75_000u64;
+//         let duration_in_blocks = 5;
+//         assert_ok!(Deip::create_simple_crowdfunding(
+//             DEFAULT_ACCOUNT_ID,
+//             sale_id,
+//             start_time,
+//             start_time + duration_in_blocks * BLOCK_TIME,
+//             DeipAsset::new(base_asset_id, soft_cap),
+//             DeipAsset::new(base_asset_id, hard_cap),
+//             vec![DeipAsset::new(usd_id, usd_to_sale), DeipAsset::new(eur_id, eur_to_sale)]
+//         ));
+//
+//         let start_block = System::block_number() + start_time_in_blocks + 1;
+//         while System::block_number() < start_block {
+//             let block_number = System::block_number();
+//             <System as OnFinalize<BlockNumber>>::on_finalize(block_number);
+//             Deip::offchain_worker(System::block_number());
+//             System::set_block_number(block_number + 1);
+//             <System as OnInitialize<BlockNumber>>::on_initialize(System::block_number());
+//             Timestamp::set_timestamp(System::block_number() * BLOCK_TIME + INIT_TIMESTAMP);
+//         }
+//
+//         assert_eq!(state.read().transactions.len(), 1);
+//
+//         let inner = decode_validate_deip_call(&state.read().transactions[0]);
+//         match inner {
+//             crate::Call::activate_crowdfunding(id) => Deip::activate_crowdfunding_impl(id).unwrap(),
+//             _ => unreachable!(),
+//         };
+//
+//         state.write().transactions.clear();
+//
+//         assert_ok!(Deip::invest_to_crowdfunding_impl(
+//             BOB_ACCOUNT_ID,
+//             sale_id,
+//             DeipAsset::new(base_asset_id, soft_cap / 4),
+//         ));
+//
+//         assert_ok!(Deip::invest_to_crowdfunding_impl(
+//             ALICE_ACCOUNT_ID,
+//             sale_id,
+//             DeipAsset::new(base_asset_id, alice_investing),
+//         ));
+//
+//         // make alice zombie
+//         let alice_remainder = DeipAssets::account_balance(&ALICE_ACCOUNT_ID, &base_asset_id);
+//         let call = pallet_deip_assets::Call::<Test>::transfer(
+//             base_asset_id,
+//             BOB_ACCOUNT_ID,
+//             alice_remainder,
+//         );
+//         let result = call.dispatch_bypass_filter(Origin::signed(ALICE_ACCOUNT_ID));
+//         assert_ok!(result);
+//
+//         let _a = Balances::slash(&ALICE_ACCOUNT_ID, Balances::total_balance(&ALICE_ACCOUNT_ID));
+//
+//         // since the sale expired the tokens should be transfered back to
+//         // the seller doesn't matter if assets/accounts frozen or not
+//         let call = pallet_deip_assets::Call::<Test>::freeze(eur_id, BOB_ACCOUNT_ID);
+//         let _result = call.dispatch_bypass_filter(Origin::signed(*account_id));
+//
+//         let call = pallet_deip_assets::Call::<Test>::freeze_asset(usd_id);
+//         let _result = call.dispatch_bypass_filter(Origin::signed(*account_id));
+//
+//         let end_block = start_block + duration_in_blocks + 1;
+//         while System::block_number() < end_block {
+//             let block_number = System::block_number();
+//             <System as OnFinalize<BlockNumber>>::on_finalize(block_number);
+//             Deip::offchain_worker(System::block_number());
+//             System::set_block_number(block_number + 1);
+//             <System as OnInitialize<BlockNumber>>::on_initialize(System::block_number());
+//             Timestamp::set_timestamp(System::block_number() * BLOCK_TIME + INIT_TIMESTAMP);
+//         }
+//
+//         let inner = decode_validate_deip_call(&state.read().transactions[0]);
+//         match inner {
+//             crate::Call::expire_crowdfunding(id) => Deip::expire_crowdfunding_impl(id).unwrap(),
+//             _ => unreachable!(),
+//         };
+//
+//         assert_eq!(
+//             DeipAssets::account_balance(&BOB_ACCOUNT_ID, &base_asset_id),
+//             bob_balance_before + alice_remainder
+//         );
+//         assert_eq!(DeipAssets::account_balance(&ALICE_ACCOUNT_ID, &base_asset_id), alice_investing);
+//         assert_eq!(DeipAssets::account_balance(account_id, &base_asset_id), balance_before);
+//
+//         assert_eq!(DeipAssets::account_balance(&BOB_ACCOUNT_ID, &usd_id), 0);
+//         assert_eq!(DeipAssets::account_balance(&ALICE_ACCOUNT_ID, &usd_id), 0);
+//         assert_eq!(DeipAssets::account_balance(account_id, &usd_id), usd_total);
+//
+//         assert_eq!(DeipAssets::account_balance(&BOB_ACCOUNT_ID, &eur_id), 0);
+//         assert_eq!(DeipAssets::account_balance(&ALICE_ACCOUNT_ID, &eur_id), 0);
+//         assert_eq!(DeipAssets::account_balance(account_id, &eur_id), eur_total);
+//     })
+// }
+//
+// #[test]
+// fn two_simultaneous_crowdfundings_expired() {
+//     let mut ext = new_test_ext2();
+//     let state = offchainify(&mut ext, 2);
+//     ext.execute_with(|| {
+//         let base_asset_id = DeipAssetId(3u32);
+//         let base_asset_total = 120_000u64;
+//         create_mint_asset(DEFAULT_ACCOUNT_ID, base_asset_id, base_asset_total, None);
+//
+//         let call = pallet_deip_assets::Call::<Test>::transfer(
+//             base_asset_id,
+//             ALICE_ACCOUNT_ID,
+//             base_asset_total / 2,
+//         );
+//         let result = call.dispatch_bypass_filter(Origin::signed(DEFAULT_ACCOUNT_ID));
+//         assert_ok!(result);
+//
+//         let call = pallet_deip_assets::Call::<Test>::transfer(
+//             base_asset_id,
+//             BOB_ACCOUNT_ID,
+//             base_asset_total / 2,
+//         );
+//         let result = call.dispatch_bypass_filter(Origin::signed(DEFAULT_ACCOUNT_ID));
+//         assert_ok!(result);
+//
+//         let usd_id = DeipAssetId(0u32);
+//         let usd_total = 100_000u64;
+//         create_mint_asset(ALICE_ACCOUNT_ID, usd_id, usd_total, None);
+//
+//         let eur_id = DeipAssetId(1u32);
+//         let eur_total = 80_000u64;
+//         create_mint_asset(BOB_ACCOUNT_ID, eur_id, eur_total, None);
+//
+//         let bob_usd_balance_before = DeipAssets::account_balance(&BOB_ACCOUNT_ID, &usd_id);
+//         let bob_eur_balance_before = DeipAssets::account_balance(&BOB_ACCOUNT_ID, &eur_id);
+//
+//         let alice_usd_balance_before = DeipAssets::account_balance(&ALICE_ACCOUNT_ID, &usd_id);
+//         let alice_base_balance_before =
+//             DeipAssets::account_balance(&ALICE_ACCOUNT_ID, &base_asset_id);
+//
+//         let start_time_in_blocks = 5;
+//         let start_time =
+//             pallet_timestamp::Pallet::<Test>::get() + start_time_in_blocks * BLOCK_TIME;
+//         let eur_sale_id = H160::random();
+//         let usd_sale_id = H160::random();
+//         let soft_cap = 50_000u64;
+//         let hard_cap = 60_000_u64;
+//         let usd_to_sale = 70_000u64;
+//         let eur_to_sale = 75_000u64;
+//         let duration_in_blocks = 5;
+//         assert_ok!(Deip::create_simple_crowdfunding(
+//             BOB_ACCOUNT_ID,
+//             eur_sale_id,
+//             start_time,
+//             start_time + duration_in_blocks * BLOCK_TIME,
+//             DeipAsset::new(usd_id, soft_cap),
+//             DeipAsset::new(usd_id, hard_cap),
+//             vec![DeipAsset::new(eur_id, eur_to_sale)]
+//         ));
+//
+//         assert_ok!(Deip::create_simple_crowdfunding(
+//             ALICE_ACCOUNT_ID,
+//             usd_sale_id,
+//             start_time,
+//             start_time + duration_in_blocks * BLOCK_TIME,
+//             DeipAsset::new(base_asset_id, soft_cap),
+//             DeipAsset::new(base_asset_id, hard_cap),
+//             vec![DeipAsset::new(usd_id, usd_to_sale)]
+//         ));
+//
+//         let start_block = System::block_number() + start_time_in_blocks + 1;
+//         while System::block_number() < start_block {
+//             let block_number = System::block_number();
+//             <System as OnFinalize<BlockNumber>>::on_finalize(block_number);
+//             Deip::offchain_worker(System::block_number());
+//             System::set_block_number(block_number + 1);
+//             <System as OnInitialize<BlockNumber>>::on_initialize(System::block_number());
+//             Timestamp::set_timestamp(System::block_number() * BLOCK_TIME + INIT_TIMESTAMP);
+//         }
+//
+//         assert_eq!(state.read().transactions.len(), 2);
+//
+//         let inner = decode_validate_deip_call(&state.read().transactions[0]);
+//         match inner {
+//             crate::Call::activate_crowdfunding(id) => Deip::activate_crowdfunding_impl(id).unwrap(),
+//             _ => unreachable!(),
+//         };
+//
+//         let inner = decode_validate_deip_call(&state.read().transactions[1]);
+//         match inner {
+//             crate::Call::activate_crowdfunding(id) => Deip::activate_crowdfunding_impl(id).unwrap(),
+//             _ => unreachable!(),
+//         };
+//
+//         state.write().transactions.clear();
+//
+//         assert_ok!(Deip::invest_to_crowdfunding_impl(
+//             BOB_ACCOUNT_ID,
+//             usd_sale_id,
+//             DeipAsset::new(base_asset_id, soft_cap / 4),
+//         ));
+//
+//         assert_ok!(Deip::invest_to_crowdfunding_impl(
+//             ALICE_ACCOUNT_ID,
+//             eur_sale_id,
+//             DeipAsset::new(usd_id, soft_cap / 2),
+//         ));
+//
+//         // since the sale expired the tokens should be transfered back to
+//         // the seller doesn't matter if assets/accounts frozen or not
+//         let call = pallet_deip_assets::Call::<Test>::freeze(eur_id, BOB_ACCOUNT_ID);
+//         let _result = call.dispatch_bypass_filter(Origin::signed(BOB_ACCOUNT_ID));
+//
+//         let call = pallet_deip_assets::Call::<Test>::freeze_asset(usd_id);
+//         let _result = call.dispatch_bypass_filter(Origin::signed(ALICE_ACCOUNT_ID));
+//
+//         let end_block = start_block + duration_in_blocks + 1;
+//         while System::block_number() < end_block {
+//             let block_number = System::block_number();
+//             <System as OnFinalize<BlockNumber>>::on_finalize(block_number);
+//             Deip::offchain_worker(System::block_number());
+//             System::set_block_number(block_number + 1);
+//             <System as OnInitialize<BlockNumber>>::on_initialize(System::block_number());
+//             Timestamp::set_timestamp(System::block_number() * BLOCK_TIME + INIT_TIMESTAMP);
+//         }
+//
+//         let inner = decode_validate_deip_call(&state.read().transactions[0]);
+//         match inner {
+//             crate::Call::expire_crowdfunding(id) => Deip::expire_crowdfunding_impl(id).unwrap(),
+//             _ => unreachable!(),
+//         };
+//
+//         let inner = decode_validate_deip_call(&state.read().transactions[1]);
+//         match inner {
+//             crate::Call::expire_crowdfunding(id) => Deip::expire_crowdfunding_impl(id).unwrap(),
+//             _ => unreachable!(),
+//         };
+//
+//         assert_eq!(
+//             DeipAssets::account_balance(&ALICE_ACCOUNT_ID, &base_asset_id),
+//             alice_base_balance_before
+//         );
+//
+//         assert_eq!(DeipAssets::account_balance(&BOB_ACCOUNT_ID, &usd_id), bob_usd_balance_before);
+//         assert_eq!(
+//             DeipAssets::account_balance(&ALICE_ACCOUNT_ID, &usd_id),
+//             alice_usd_balance_before
+//         );
+//
+//         assert_eq!(DeipAssets::account_balance(&BOB_ACCOUNT_ID, &eur_id), bob_eur_balance_before);
+//     })
+// }
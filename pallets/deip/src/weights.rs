@@ -56,6 +56,10 @@ pub trait WeightInfo {
     fn accept_contract_agreement_project_license_signed_by_licenser() -> Weight;
     fn accept_contract_agreement_generic_contract_partially_accepted() -> Weight;
     fn accept_contract_agreement_generic_contract_finalized() -> Weight;
+    fn set_content_nft_class() -> Weight;
+    fn create_content_access_request() -> Weight;
+    fn fulfill_content_access_request() -> Weight;
+    fn reject_content_access_request() -> Weight;
 }
 
 /// Weight functions for pallet_deip.
@@ -297,4 +301,48 @@ impl<T: frame_system::Config> WeightInfo for Weights<T> {
             .saturating_add(T::DbWeight::get().reads(1 as Weight))
             .saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    // Weights of content access calls are estimated from storage accesses
+    // till the benchmarks of the pallet are run again.
+    // Storage: Deip ProjectContentMapV1 (r:1 w:0)
+    // Storage: Deip NftClassIdByContentId (r:1 w:1)
+    // Storage: DeipUniques NftClassIdByDeipNftClassId (r:1 w:0)
+    // Storage: Uniques Class (r:1 w:0)
+    // Storage: Deip ContentCountByNftClassId (r:2 w:2)
+    // Storage: Deip ContentIdByNftClassId (r:0 w:2)
+    fn set_content_nft_class() -> Weight {
+        (45_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    // Storage: Deip ContentAccessRequestMap (r:1 w:1)
+    // Storage: Deip NftClassIdByContentId (r:1 w:0)
+    // Storage: DeipUniques HoldersByClass (r:1 w:0)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Deip ContentAccessRequestDeposits (r:0 w:1)
+    // Storage: Deip ContentAccessRequestIdByContentId (r:0 w:1)
+    fn create_content_access_request() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    // Storage: Deip ContentAccessRequestMap (r:1 w:1)
+    // Storage: Deip ProjectContentMapV1 (r:2 w:0)
+    // Storage: Deip NftClassIdByContentId (r:1 w:0)
+    // Storage: DeipUniques HoldersByClass (r:1 w:0)
+    // Storage: Deip ContentAccessRequestDeposits (r:1 w:1)
+    // Storage: System Account (r:1 w:1)
+    fn fulfill_content_access_request() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    // Storage: Deip ContentAccessRequestMap (r:1 w:1)
+    // Storage: Deip ProjectContentMapV1 (r:1 w:0)
+    // Storage: Deip ContentAccessRequestDeposits (r:1 w:1)
+    // Storage: System Account (r:1 w:1)
+    fn reject_content_access_request() -> Weight {
+        (50_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
 }
@@ -0,0 +1,16 @@
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+    pub trait DeipAmmRuntimeApi<AssetId, Balance>
+        where
+            AssetId: Codec,
+            Balance: Codec,
+    {
+        /// Amount of `asset_out` received for `amount_in` of `asset_in` with the fee deducted.
+        /// Returns `None` if there is no pool of the assets or it has no liquidity.
+        fn quote(asset_in: AssetId, asset_out: AssetId, amount_in: Balance) -> Option<Balance>;
+
+        /// Reserves of the pool in the order of passed assets.
+        fn reserves(asset_a: AssetId, asset_b: AssetId) -> Option<(Balance, Balance)>;
+    }
+}
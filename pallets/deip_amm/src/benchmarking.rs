@@ -0,0 +1,116 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use deip_asset_system::AssetIdInitT;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::Currency;
+use frame_system::{EventRecord, RawOrigin};
+use sp_runtime::traits::{Bounded, One};
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+type DeipAssets<T> = pallet_deip_assets::Pallet<T>;
+type CurrencyOf<T> = <T as pallet_deip_assets::pallet_assets::Config>::Currency;
+type CurrencyBalanceOf<T> = <CurrencyOf<T> as Currency<AccountIdOf<T>>>::Balance;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::Event) {
+    let events = frame_system::Pallet::<T>::events();
+    let system_event: <T as frame_system::Config>::Event = generic_event.into();
+    let EventRecord { event, .. } = &events[events.len() - 1];
+    assert_eq!(event, &system_event);
+}
+
+fn fund<T: Config>(who: &T::AccountId) {
+    CurrencyOf::<T>::make_free_balance_be(who, CurrencyBalanceOf::<T>::max_value() / 2u32.into());
+}
+
+/// Creates the asset with `index` and mints 1_000_000 of it to `holder`.
+fn create_asset<T: Config>(index: u8, holder: &T::AccountId) -> DeipAssetIdOf<T> {
+    let admin = account::<T::AccountId>("admin", index.into(), SEED);
+    fund::<T>(&admin);
+    let asset = T::AssetIdInit::asset_id(&[index; 20]);
+    let origin = RawOrigin::Signed(admin.clone());
+    DeipAssets::<T>::deip_create(
+        origin.clone().into(),
+        asset,
+        admin.into(),
+        One::one(),
+        None,
+        None,
+    )
+    .unwrap();
+    DeipAssets::<T>::deip_mint(origin.into(), asset, holder.clone().into(), 1_000_000u32.into())
+        .unwrap();
+    asset
+}
+
+/// Creates the pool of two assets held by `who`.
+fn create_pool<T: Config>(who: &T::AccountId) -> PairOf<T> {
+    fund::<T>(who);
+    let pair = (create_asset::<T>(1, who), create_asset::<T>(2, who));
+    Pallet::<T>::create_pool(RawOrigin::Signed(who.clone()).into(), pair.0, pair.1).unwrap();
+    pair
+}
+
+fn add_liquidity<T: Config>(who: &T::AccountId, pair: PairOf<T>) {
+    Pallet::<T>::add_liquidity(
+        RawOrigin::Signed(who.clone()).into(),
+        pair.0,
+        pair.1,
+        100_000u32.into(),
+        100_000u32.into(),
+        0u32.into(),
+        0u32.into(),
+    )
+    .unwrap();
+}
+
+benchmarks! {
+    create_pool {
+        let caller: T::AccountId = whitelisted_caller();
+        fund::<T>(&caller);
+        let asset_a = create_asset::<T>(1, &caller);
+        let asset_b = create_asset::<T>(2, &caller);
+        let lp_asset = Pallet::<T>::lp_asset_id(&(asset_a, asset_b));
+    }: _(RawOrigin::Signed(caller.clone()), asset_a, asset_b)
+    verify {
+        let event = Event::<T>::PoolCreated((asset_a, asset_b), lp_asset, caller);
+        assert_last_event::<T>(event.into());
+    }
+
+    // the first deposit also mints the locked LP tokens
+    add_liquidity {
+        let caller: T::AccountId = whitelisted_caller();
+        let pair = create_pool::<T>(&caller);
+        let amount: BalanceOf<T> = 100_000u32.into();
+        let zero: BalanceOf<T> = 0u32.into();
+    }: _(RawOrigin::Signed(caller), pair.0, pair.1, amount, amount, zero, zero)
+    verify {
+        assert_eq!(Pallet::<T>::reserves(&pair.0, &pair.1), Some((amount, amount)));
+    }
+
+    remove_liquidity {
+        let caller: T::AccountId = whitelisted_caller();
+        let pair = create_pool::<T>(&caller);
+        add_liquidity::<T>(&caller, pair);
+        let lp_amount: BalanceOf<T> = 1_000u32.into();
+        let zero: BalanceOf<T> = 0u32.into();
+    }: _(RawOrigin::Signed(caller.clone()), pair.0, pair.1, lp_amount, zero, zero)
+    verify {
+        let reserve: BalanceOf<T> = 99_000u32.into();
+        assert_eq!(Pallet::<T>::reserves(&pair.0, &pair.1), Some((reserve, reserve)));
+    }
+
+    swap {
+        let caller: T::AccountId = whitelisted_caller();
+        let pair = create_pool::<T>(&caller);
+        add_liquidity::<T>(&caller, pair);
+        let amount_in: BalanceOf<T> = 1_000u32.into();
+        let amount_out = Pallet::<T>::quote(&pair.0, &pair.1, amount_in).unwrap();
+    }: _(RawOrigin::Signed(caller.clone()), pair.0, pair.1, amount_in, amount_out)
+    verify {
+        let event = Event::<T>::Swapped(caller, pair.0, pair.1, amount_in, amount_out);
+        assert_last_event::<T>(event.into());
+    }
+}
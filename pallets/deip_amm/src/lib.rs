@@ -0,0 +1,232 @@
+//! # DEIP AMM Module
+//! A module provides constant-product liquidity pools for DEIP assets.
+//!
+//! - [`Config`](./trait.Config.html)
+//!
+//! ## Overview
+//! A pool holds reserves of two assets of [`pallet_deip_assets`](../pallet_deip_assets/index.html)
+//! on an account derived from the pair. Reserves are tracked in the pool and changed
+//! only by its calls, so assets sent to the pool account directly don't move the price.
+//! Liquidity providers receive LP tokens which are DEIP assets managed by the pool,
+//! their id is derived from the pair too.
+//! `MinimumLiquidity` of LP tokens minted on the first deposit is locked on the pool
+//! account forever, so the pool can't be drained to zero and the price of an LP token
+//! can't be inflated. Swaps keep the product of reserves and charge `SwapFee`
+//! from the input amount in favor of liquidity providers.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! * [`create_pool`](./enum.Call.html#variant.create_pool)
+//! * [`add_liquidity`](./enum.Call.html#variant.add_liquidity)
+//! * [`remove_liquidity`](./enum.Call.html#variant.remove_liquidity)
+//! * [`swap`](./enum.Call.html#variant.swap)
+//!
+//! [`Config`]: ./trait.Config.html
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod api;
+mod pool;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub mod benchmarking;
+pub mod weights;
+
+#[doc(inline)]
+pub use pallet::*;
+
+#[frame_support::pallet]
+#[doc(hidden)]
+pub mod pallet {
+    use frame_support::{
+        pallet_prelude::{
+            Blake2_128Concat, Decode, DispatchResultWithPostInfo, Encode, Get, IsType,
+            OptionQuery, StorageMap,
+        },
+        transactional, RuntimeDebug,
+    };
+    use frame_system::{ensure_signed, pallet_prelude::OriginFor};
+    use scale_info::TypeInfo;
+    use sp_runtime::Permill;
+
+    #[cfg(feature = "std")]
+    use serde::{Deserialize, Serialize};
+
+    use crate::weights::WeightInfo;
+
+    pub(crate) type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+    pub type DeipAssetIdOf<T> = pallet_deip_assets::DeipAssetIdOf<T>;
+    pub type BalanceOf<T> = <T as pallet_deip_assets::pallet_assets::Config>::Balance;
+    pub type PairOf<T> = (DeipAssetIdOf<T>, DeipAssetIdOf<T>);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + pallet_deip_assets::Config {
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+        /// Part of the input amount of a swap left in the pool for liquidity providers.
+        #[pallet::constant]
+        type SwapFee: Get<Permill>;
+
+        /// Amount of LP tokens locked on the pool account on the first deposit.
+        #[pallet::constant]
+        type MinimumLiquidity: Get<BalanceOf<Self>>;
+
+        type DeipAmmWeightInfo: WeightInfo;
+    }
+
+    #[doc(hidden)]
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(super) trait Store)]
+    pub struct Pallet<T>(_);
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Pool of the pair already exists in any order of assets.
+        PoolAlreadyExists,
+        PoolNotFound,
+        /// Pool can't be created for the same assets.
+        PoolSameAssets,
+        AmountMustBePositive,
+        /// Reserves of the pool are not enough for the operation.
+        InsufficientLiquidity,
+        /// Result of the operation is less than the minimum set by the caller.
+        SlippageExceeded,
+        LpBalanceIsNotEnough,
+        Overflow,
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Event emitted when a pool has been created: pair, LP token, creator.
+        PoolCreated(PairOf<T>, DeipAssetIdOf<T>, AccountIdOf<T>),
+        /// Event emitted when liquidity has been added to the pool:
+        /// pair, provider, amounts of assets, minted LP tokens.
+        LiquidityAdded(PairOf<T>, AccountIdOf<T>, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>),
+        /// Event emitted when liquidity has been removed from the pool:
+        /// pair, provider, amounts of assets, burned LP tokens.
+        LiquidityRemoved(PairOf<T>, AccountIdOf<T>, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>),
+        /// Event emitted when assets have been swapped: account, asset in, asset out,
+        /// amount in, amount out.
+        Swapped(AccountIdOf<T>, DeipAssetIdOf<T>, DeipAssetIdOf<T>, BalanceOf<T>, BalanceOf<T>),
+    }
+
+    #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct Pool<AssetId, Balance> {
+        /// DEIP asset of LP tokens.
+        pub lp_asset: AssetId,
+        /// Reserve of the first asset of the pair.
+        pub reserve_a: Balance,
+        /// Reserve of the second asset of the pair.
+        pub reserve_b: Balance,
+    }
+
+    pub type PoolOf<T> = Pool<DeipAssetIdOf<T>, BalanceOf<T>>;
+
+    /// Pools by pair of assets in the order they were created with.
+    #[pallet::storage]
+    pub(super) type Pools<T: Config> =
+        StorageMap<_, Blake2_128Concat, PairOf<T>, PoolOf<T>, OptionQuery>;
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Creates a pool of `asset_a` and `asset_b`. Id of its LP token is derived
+        /// from the pair, see [`lp_asset_id`](Pallet::lp_asset_id).
+        ///
+        /// The origin for this call must be _Signed_. The caller pays the deposit
+        /// of the LP token and the existential deposit of the pool account.
+        #[pallet::weight(T::DeipAmmWeightInfo::create_pool())]
+        #[transactional]
+        pub fn create_pool(
+            origin: OriginFor<T>,
+            asset_a: DeipAssetIdOf<T>,
+            asset_b: DeipAssetIdOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::create_pool_impl(who, (asset_a, asset_b))?;
+            Ok(().into())
+        }
+
+        /// Adds liquidity to the pool at its current price, up to desired amounts.
+        /// The first provider sets the price and `MinimumLiquidity` of its LP tokens
+        /// is locked.
+        ///
+        /// The origin for this call must be _Signed_.
+        ///
+        /// Fails if less than `amount_a_min` of `asset_a` or `amount_b_min` of `asset_b`
+        /// would be deposited.
+        #[pallet::weight(T::DeipAmmWeightInfo::add_liquidity())]
+        #[transactional]
+        pub fn add_liquidity(
+            origin: OriginFor<T>,
+            asset_a: DeipAssetIdOf<T>,
+            asset_b: DeipAssetIdOf<T>,
+            amount_a_desired: BalanceOf<T>,
+            amount_b_desired: BalanceOf<T>,
+            amount_a_min: BalanceOf<T>,
+            amount_b_min: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::add_liquidity_impl(
+                who,
+                (asset_a, asset_b),
+                (amount_a_desired, amount_b_desired),
+                (amount_a_min, amount_b_min),
+            )?;
+            Ok(().into())
+        }
+
+        /// Burns `lp_amount` of LP tokens and withdraws the share of reserves.
+        ///
+        /// The origin for this call must be _Signed_.
+        ///
+        /// Fails if less than `amount_a_min` of `asset_a` or `amount_b_min` of `asset_b`
+        /// would be withdrawn.
+        #[pallet::weight(T::DeipAmmWeightInfo::remove_liquidity())]
+        #[transactional]
+        pub fn remove_liquidity(
+            origin: OriginFor<T>,
+            asset_a: DeipAssetIdOf<T>,
+            asset_b: DeipAssetIdOf<T>,
+            lp_amount: BalanceOf<T>,
+            amount_a_min: BalanceOf<T>,
+            amount_b_min: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::remove_liquidity_impl(
+                who,
+                (asset_a, asset_b),
+                lp_amount,
+                (amount_a_min, amount_b_min),
+            )?;
+            Ok(().into())
+        }
+
+        /// Swaps `amount_in` of `asset_in` for `asset_out`.
+        ///
+        /// The origin for this call must be _Signed_.
+        ///
+        /// Fails if less than `amount_out_min` would be received.
+        #[pallet::weight(T::DeipAmmWeightInfo::swap())]
+        #[transactional]
+        pub fn swap(
+            origin: OriginFor<T>,
+            asset_in: DeipAssetIdOf<T>,
+            asset_out: DeipAssetIdOf<T>,
+            amount_in: BalanceOf<T>,
+            amount_out_min: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::swap_impl(who, asset_in, asset_out, amount_in, amount_out_min)?;
+            Ok(().into())
+        }
+    }
+}
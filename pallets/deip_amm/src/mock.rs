@@ -0,0 +1,218 @@
+use frame_support::{parameter_types, traits::Everything};
+use sp_core::{H160, H256};
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    Permill,
+};
+
+use crate as pallet_deip_amm;
+use deip_asset_system::AssetIdInitT;
+use deip_projects_info::DeipProjectsInfo;
+use pallet_deip_assets::{pallet_assets, OnChainTransferPolicy};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub type AccountId = u64;
+pub type Balance = u64;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+        Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+        Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
+        DeipAssets: pallet_deip_assets::{Pallet, Call, Storage, Event<T>},
+        DeipAmm: pallet_deip_amm::{Pallet, Call, Storage, Event<T>},
+    }
+);
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type Origin = Origin;
+    type Call = Call;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = Event;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: Balance = 1;
+    pub const MaxLocks: u32 = 50;
+    pub const MaxReserves: u32 = 50;
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = MaxLocks;
+    type MaxReserves = MaxReserves;
+    type ReserveIdentifier = [u8; 8];
+    type Balance = Balance;
+    type DustRemoval = ();
+    type Event = Event;
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const MinimumPeriod: u64 = 1;
+}
+
+impl pallet_timestamp::Config for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = MinimumPeriod;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const AssetDeposit: Balance = 0;
+    pub const ApprovalDeposit: Balance = 0;
+    pub const StringLimit: u32 = 50;
+    pub const MetadataDepositBase: Balance = 0;
+    pub const MetadataDepositPerByte: Balance = 0;
+}
+
+impl pallet_assets::Config for Test {
+    type Event = Event;
+    type Balance = Balance;
+    type AssetId = u32;
+    type Currency = Balances;
+    type ForceOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type AssetDeposit = AssetDeposit;
+    type StringLimit = StringLimit;
+    type MetadataDepositBase = MetadataDepositBase;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type ApprovalDeposit = ApprovalDeposit;
+    type Freezer = ();
+    type Extra = ();
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const WipePeriod: u64 = 10;
+    pub const MaxWipeAccountsPerBlock: u32 = 2;
+    pub const MaxDividendClaimsPerBlock: u32 = 2;
+    pub const CheckpointRetention: u32 = 2;
+    pub const MaxPinnedCheckpoints: u32 = 2;
+    pub const NativeAssetId: H160 = H160::zero();
+}
+
+pub const PROJECT_TEAM: AccountId = 1;
+
+pub fn project() -> H160 {
+    H160::from_low_u64_be(1)
+}
+
+impl DeipProjectsInfo<AccountId> for Test {
+    type ProjectId = H160;
+    type InvestmentId = H160;
+
+    fn try_get_project_team(id: &Self::ProjectId) -> Option<AccountId> {
+        if *id == project() {
+            Some(PROJECT_TEAM)
+        } else {
+            None
+        }
+    }
+
+    fn project_id(source: &[u8]) -> Self::ProjectId {
+        H160::from_slice(&sp_io::hashing::blake2_256(source)[..20])
+    }
+}
+
+pub struct AssetIdInit;
+impl AssetIdInitT<H160> for AssetIdInit {
+    fn asset_id(raw: &[u8]) -> H160 {
+        H160::from_slice(raw)
+    }
+}
+
+impl pallet_deip_assets::Config for Test {
+    type ProjectsInfo = Self;
+    type DeipAccountId = AccountId;
+    type AssetsAssetId = u32;
+    type AssetId = H160;
+    type AssetIdInit = AssetIdInit;
+    type NativeAssetId = NativeAssetId;
+    type WipePeriod = WipePeriod;
+    type MaxWipeAccountsPerBlock = MaxWipeAccountsPerBlock;
+    type Event = Event;
+    type MaxDividendClaimsPerBlock = MaxDividendClaimsPerBlock;
+    type CheckpointRetention = CheckpointRetention;
+    type MaxPinnedCheckpoints = MaxPinnedCheckpoints;
+    type TransferPolicy = OnChainTransferPolicy<Self, Timestamp>;
+}
+
+parameter_types! {
+    pub const SwapFee: Permill = Permill::from_percent(1);
+    pub const MinimumLiquidity: Balance = 10;
+}
+
+impl pallet_deip_amm::Config for Test {
+    type Event = Event;
+    type SwapFee = SwapFee;
+    type MinimumLiquidity = MinimumLiquidity;
+    type DeipAmmWeightInfo = crate::weights::Weights<Self>;
+}
+
+pub const ALICE: AccountId = 2;
+pub const BOB: AccountId = 3;
+pub const CHARLIE: AccountId = 4;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(PROJECT_TEAM, 1000), (ALICE, 1000), (BOB, 1000), (CHARLIE, 1000)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+pub fn asset(n: u64) -> H160 {
+    H160::from_low_u64_be(100 + n)
+}
+
+/// Creates the asset owned by `owner` and mints `balances` of it.
+pub fn create_asset(owner: AccountId, id: H160, balances: &[(AccountId, Balance)]) {
+    assert_ok!(DeipAssets::deip_create(Origin::signed(owner), id, owner, 1, None, None));
+    for (who, amount) in balances {
+        assert_ok!(DeipAssets::deip_mint(Origin::signed(owner), id, *who, *amount));
+    }
+}
+
+pub fn last_event() -> Event {
+    System::events().pop().expect("Event expected").event
+}
+
+pub use frame_support::{assert_noop, assert_ok};
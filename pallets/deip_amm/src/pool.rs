@@ -0,0 +1,370 @@
+use codec::{Decode, Encode};
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    traits::{Currency, ExistenceRequirement, Get},
+};
+use frame_system::{pallet_prelude::OriginFor, RawOrigin};
+use sp_runtime::{
+    helpers_128bit::multiply_by_rational,
+    traits::{CheckedAdd, CheckedSub, IntegerSquareRoot, One, Zero},
+    PerThing, SaturatedConversion,
+};
+
+use deip_asset_system::AssetIdInitT;
+
+use crate::{
+    AccountIdOf, BalanceOf, Config, DeipAssetIdOf, Error, Event, PairOf, Pallet, Pool, PoolOf,
+    Pools,
+};
+
+type DeipAssets<T> = pallet_deip_assets::Pallet<T>;
+type CurrencyOf<T> = <T as pallet_deip_assets::pallet_assets::Config>::Currency;
+
+fn signed<T: Config>(who: &AccountIdOf<T>) -> OriginFor<T> {
+    RawOrigin::Signed(who.clone()).into()
+}
+
+impl<T: Config> Pallet<T> {
+    pub fn pool_key(pair: &PairOf<T>) -> AccountIdOf<T> {
+        let entropy = (b"deip/pools/", pair).using_encoded(sp_io::hashing::blake2_256);
+        AccountIdOf::<T>::decode(&mut &entropy[..]).unwrap_or_default()
+    }
+
+    /// Id of the LP token of the pair. It's derived from the pair, so it can't be
+    /// taken by another asset in advance to block creation of the pool.
+    pub fn lp_asset_id(pair: &PairOf<T>) -> DeipAssetIdOf<T> {
+        let entropy = (b"deip/pools/lp/", pair).using_encoded(sp_io::hashing::blake2_256);
+        T::AssetIdInit::asset_id(&entropy[..20])
+    }
+
+    /// Returns the pool of assets in any order together with the pair it was created with.
+    pub fn pool_of(
+        asset_a: &DeipAssetIdOf<T>,
+        asset_b: &DeipAssetIdOf<T>,
+    ) -> Option<(PairOf<T>, PoolOf<T>)> {
+        let pair = (*asset_a, *asset_b);
+        if let Some(pool) = Pools::<T>::get(pair) {
+            return Some((pair, pool))
+        }
+
+        let pair = (*asset_b, *asset_a);
+        Pools::<T>::get(pair).map(|pool| (pair, pool))
+    }
+
+    /// Reserves of the pool in the order of passed assets.
+    pub fn reserves(
+        asset_a: &DeipAssetIdOf<T>,
+        asset_b: &DeipAssetIdOf<T>,
+    ) -> Option<(BalanceOf<T>, BalanceOf<T>)> {
+        let (pair, pool) = Self::pool_of(asset_a, asset_b)?;
+        Some(Self::ordered(&pair, asset_a, (pool.reserve_a, pool.reserve_b)))
+    }
+
+    /// Orders `values` of assets of the pair as `first` and the other one
+    /// from the order of the pair and back.
+    fn ordered<V>(pair: &PairOf<T>, first: &DeipAssetIdOf<T>, values: (V, V)) -> (V, V) {
+        if pair.0 == *first {
+            values
+        } else {
+            (values.1, values.0)
+        }
+    }
+
+    /// Adds `added` and subtracts `removed` amounts of `assets` to reserves of the pool.
+    fn update_reserves(
+        pair: PairOf<T>,
+        mut pool: PoolOf<T>,
+        assets: &PairOf<T>,
+        added: (BalanceOf<T>, BalanceOf<T>),
+        removed: (BalanceOf<T>, BalanceOf<T>),
+    ) -> Result<(), Error<T>> {
+        let added = Self::ordered(&pair, &assets.0, added);
+        let removed = Self::ordered(&pair, &assets.0, removed);
+        pool.reserve_a = pool
+            .reserve_a
+            .checked_add(&added.0)
+            .and_then(|r| r.checked_sub(&removed.0))
+            .ok_or(Error::<T>::Overflow)?;
+        pool.reserve_b = pool
+            .reserve_b
+            .checked_add(&added.1)
+            .and_then(|r| r.checked_sub(&removed.1))
+            .ok_or(Error::<T>::Overflow)?;
+        Pools::<T>::insert(pair, pool);
+        Ok(())
+    }
+
+    /// Amount of `asset_out` received for `amount_in` of `asset_in`.
+    pub fn quote(
+        asset_in: &DeipAssetIdOf<T>,
+        asset_out: &DeipAssetIdOf<T>,
+        amount_in: BalanceOf<T>,
+    ) -> Option<BalanceOf<T>> {
+        Self::amount_out(asset_in, asset_out, amount_in).ok()
+    }
+
+    fn amount_out(
+        asset_in: &DeipAssetIdOf<T>,
+        asset_out: &DeipAssetIdOf<T>,
+        amount_in: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, Error<T>> {
+        let (reserve_in, reserve_out) =
+            Self::reserves(asset_in, asset_out).ok_or(Error::<T>::PoolNotFound)?;
+        ensure!(!reserve_in.is_zero() && !reserve_out.is_zero(), Error::<T>::InsufficientLiquidity);
+
+        let amount_in: u128 = amount_in.saturated_into();
+        // the fee is rounded up in favor of liquidity providers
+        let amount_in_with_fee = amount_in.saturating_sub(T::SwapFee::get().mul_ceil(amount_in));
+        let reserve_in: u128 = reserve_in.saturated_into();
+        let amount_out = multiply_by_rational(
+            amount_in_with_fee,
+            reserve_out.saturated_into(),
+            reserve_in.checked_add(amount_in_with_fee).ok_or(Error::<T>::Overflow)?,
+        )
+        .map_err(|_| Error::<T>::Overflow)?;
+
+        Ok(amount_out.saturated_into())
+    }
+
+    pub(crate) fn create_pool_impl(
+        creator: AccountIdOf<T>,
+        pair: PairOf<T>,
+    ) -> DispatchResult {
+        ensure!(pair.0 != pair.1, Error::<T>::PoolSameAssets);
+        ensure!(Self::pool_of(&pair.0, &pair.1).is_none(), Error::<T>::PoolAlreadyExists);
+
+        let lp_asset = Self::lp_asset_id(&pair);
+        DeipAssets::<T>::ensure_asset_id_available(&lp_asset)?;
+
+        let pool = Self::pool_key(&pair);
+        CurrencyOf::<T>::transfer(
+            &creator,
+            &pool,
+            CurrencyOf::<T>::minimum_balance(),
+            ExistenceRequirement::KeepAlive,
+        )?;
+
+        // the creator pays the deposit and then passes all rights to the pool
+        // so that LP tokens are minted and burned only by it
+        DeipAssets::<T>::deip_create(
+            signed::<T>(&creator),
+            lp_asset,
+            creator.clone().into(),
+            One::one(),
+            None,
+            None,
+        )
+        .map_err(|e| e.error)?;
+        DeipAssets::<T>::deip_set_team(
+            signed::<T>(&creator),
+            lp_asset,
+            pool.clone().into(),
+            pool.clone().into(),
+            pool.clone().into(),
+        )
+        .map_err(|e| e.error)?;
+        DeipAssets::<T>::deip_transfer_ownership(signed::<T>(&creator), lp_asset, pool.into())
+            .map_err(|e| e.error)?;
+
+        let pool = Pool { lp_asset, reserve_a: Zero::zero(), reserve_b: Zero::zero() };
+        Pools::<T>::insert(pair, pool);
+
+        Self::deposit_event(Event::<T>::PoolCreated(pair, lp_asset, creator));
+
+        Ok(())
+    }
+
+    pub(crate) fn add_liquidity_impl(
+        who: AccountIdOf<T>,
+        assets: PairOf<T>,
+        desired: (BalanceOf<T>, BalanceOf<T>),
+        min: (BalanceOf<T>, BalanceOf<T>),
+    ) -> DispatchResult {
+        let (pair, pool) = Self::pool_of(&assets.0, &assets.1).ok_or(Error::<T>::PoolNotFound)?;
+        ensure!(!desired.0.is_zero() && !desired.1.is_zero(), Error::<T>::AmountMustBePositive);
+
+        let account = Self::pool_key(&pair);
+        let (reserve_a, reserve_b) =
+            Self::ordered(&pair, &assets.0, (pool.reserve_a, pool.reserve_b));
+        let (reserve_a, reserve_b): (u128, u128) =
+            (reserve_a.saturated_into(), reserve_b.saturated_into());
+        let supply: u128 = DeipAssets::<T>::total_supply(&pool.lp_asset).saturated_into();
+        let desired: (u128, u128) = (desired.0.saturated_into(), desired.1.saturated_into());
+        let min: (u128, u128) = (min.0.saturated_into(), min.1.saturated_into());
+
+        let minimum_liquidity: u128 = T::MinimumLiquidity::get().saturated_into();
+
+        let (amount_a, amount_b, lp_amount, locked) =
+            if supply.is_zero() || reserve_a.is_zero() || reserve_b.is_zero() {
+                // the first provider sets the price and the minimum liquidity is locked
+                let lp_amount = desired
+                    .0
+                    .checked_mul(desired.1)
+                    .ok_or(Error::<T>::Overflow)?
+                    .integer_sqrt()
+                    .checked_sub(minimum_liquidity)
+                    .ok_or(Error::<T>::InsufficientLiquidity)?;
+                (desired.0, desired.1, lp_amount, minimum_liquidity)
+            } else {
+                let optimal_b = multiply_by_rational(desired.0, reserve_b, reserve_a)
+                    .map_err(|_| Error::<T>::Overflow)?;
+                let (amount_a, amount_b) = if optimal_b <= desired.1 {
+                    (desired.0, optimal_b)
+                } else {
+                    let optimal_a = multiply_by_rational(desired.1, reserve_a, reserve_b)
+                        .map_err(|_| Error::<T>::Overflow)?;
+                    (optimal_a, desired.1)
+                };
+                let lp_amount = sp_std::cmp::min(
+                    multiply_by_rational(amount_a, supply, reserve_a)
+                        .map_err(|_| Error::<T>::Overflow)?,
+                    multiply_by_rational(amount_b, supply, reserve_b)
+                        .map_err(|_| Error::<T>::Overflow)?,
+                );
+                (amount_a, amount_b, lp_amount, Zero::zero())
+            };
+        ensure!(amount_a >= min.0 && amount_b >= min.1, Error::<T>::SlippageExceeded);
+        ensure!(!lp_amount.is_zero(), Error::<T>::InsufficientLiquidity);
+
+        let amount_a = amount_a.saturated_into();
+        let amount_b = amount_b.saturated_into();
+        let lp_amount = lp_amount.saturated_into();
+        let locked: BalanceOf<T> = locked.saturated_into();
+
+        DeipAssets::<T>::deip_transfer(
+            signed::<T>(&who),
+            assets.0,
+            account.clone().into(),
+            amount_a,
+        )
+        .map_err(|e| e.error)?;
+        DeipAssets::<T>::deip_transfer(
+            signed::<T>(&who),
+            assets.1,
+            account.clone().into(),
+            amount_b,
+        )
+        .map_err(|e| e.error)?;
+        DeipAssets::<T>::deip_mint(
+            signed::<T>(&account),
+            pool.lp_asset,
+            who.clone().into(),
+            lp_amount,
+        )
+        .map_err(|e| e.error)?;
+        if !locked.is_zero() {
+            // nobody can sign for the pool account, so the tokens are locked forever
+            DeipAssets::<T>::deip_mint(
+                signed::<T>(&account),
+                pool.lp_asset,
+                account.clone().into(),
+                locked,
+            )
+            .map_err(|e| e.error)?;
+        }
+        let zero = (Zero::zero(), Zero::zero());
+        Self::update_reserves(pair, pool, &assets, (amount_a, amount_b), zero)?;
+
+        Self::deposit_event(Event::<T>::LiquidityAdded(pair, who, amount_a, amount_b, lp_amount));
+
+        Ok(())
+    }
+
+    pub(crate) fn remove_liquidity_impl(
+        who: AccountIdOf<T>,
+        assets: PairOf<T>,
+        lp_amount: BalanceOf<T>,
+        min: (BalanceOf<T>, BalanceOf<T>),
+    ) -> DispatchResult {
+        let (pair, pool) = Self::pool_of(&assets.0, &assets.1).ok_or(Error::<T>::PoolNotFound)?;
+        ensure!(!lp_amount.is_zero(), Error::<T>::AmountMustBePositive);
+        ensure!(
+            DeipAssets::<T>::account_balance(&who, &pool.lp_asset) >= lp_amount,
+            Error::<T>::LpBalanceIsNotEnough
+        );
+
+        let account = Self::pool_key(&pair);
+        let (reserve_a, reserve_b) =
+            Self::ordered(&pair, &assets.0, (pool.reserve_a, pool.reserve_b));
+        let supply: u128 = DeipAssets::<T>::total_supply(&pool.lp_asset).saturated_into();
+
+        let share = |reserve: BalanceOf<T>| -> Result<BalanceOf<T>, Error<T>> {
+            multiply_by_rational(lp_amount.saturated_into(), reserve.saturated_into(), supply)
+                .map(SaturatedConversion::saturated_into)
+                .map_err(|_| Error::<T>::Overflow)
+        };
+        let amount_a = share(reserve_a)?;
+        let amount_b = share(reserve_b)?;
+        ensure!(amount_a >= min.0 && amount_b >= min.1, Error::<T>::SlippageExceeded);
+        ensure!(!amount_a.is_zero() && !amount_b.is_zero(), Error::<T>::InsufficientLiquidity);
+
+        DeipAssets::<T>::deip_burn(
+            signed::<T>(&account),
+            pool.lp_asset,
+            who.clone().into(),
+            lp_amount,
+        )
+        .map_err(|e| e.error)?;
+        DeipAssets::<T>::deip_transfer(
+            signed::<T>(&account),
+            assets.0,
+            who.clone().into(),
+            amount_a,
+        )
+        .map_err(|e| e.error)?;
+        DeipAssets::<T>::deip_transfer(
+            signed::<T>(&account),
+            assets.1,
+            who.clone().into(),
+            amount_b,
+        )
+        .map_err(|e| e.error)?;
+        let zero = (Zero::zero(), Zero::zero());
+        Self::update_reserves(pair, pool, &assets, zero, (amount_a, amount_b))?;
+
+        Self::deposit_event(Event::<T>::LiquidityRemoved(pair, who, amount_a, amount_b, lp_amount));
+
+        Ok(())
+    }
+
+    pub(crate) fn swap_impl(
+        who: AccountIdOf<T>,
+        asset_in: DeipAssetIdOf<T>,
+        asset_out: DeipAssetIdOf<T>,
+        amount_in: BalanceOf<T>,
+        amount_out_min: BalanceOf<T>,
+    ) -> DispatchResult {
+        ensure!(!amount_in.is_zero(), Error::<T>::AmountMustBePositive);
+
+        let amount_out = Self::amount_out(&asset_in, &asset_out, amount_in)?;
+        ensure!(amount_out >= amount_out_min, Error::<T>::SlippageExceeded);
+        ensure!(!amount_out.is_zero(), Error::<T>::InsufficientLiquidity);
+
+        let (pair, pool) =
+            Self::pool_of(&asset_in, &asset_out).ok_or(Error::<T>::PoolNotFound)?;
+        let account = Self::pool_key(&pair);
+
+        DeipAssets::<T>::deip_transfer(
+            signed::<T>(&who),
+            asset_in,
+            account.clone().into(),
+            amount_in,
+        )
+        .map_err(|e| e.error)?;
+        DeipAssets::<T>::deip_transfer(
+            signed::<T>(&account),
+            asset_out,
+            who.clone().into(),
+            amount_out,
+        )
+        .map_err(|e| e.error)?;
+        let zero = Zero::zero();
+        let assets = (asset_in, asset_out);
+        Self::update_reserves(pair, pool, &assets, (amount_in, zero), (zero, amount_out))?;
+
+        Self::deposit_event(Event::<T>::Swapped(who, asset_in, asset_out, amount_in, amount_out));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+use crate::{mock::*, Error, Event as AmmEvent, Pool, Pools};
+use sp_core::H160;
+
+fn create_pool() {
+    create_asset(PROJECT_TEAM, asset(1), &[(ALICE, 1000), (BOB, 1000)]);
+    create_asset(PROJECT_TEAM, asset(2), &[(ALICE, 1000), (BOB, 1000)]);
+    assert_ok!(DeipAmm::create_pool(Origin::signed(ALICE), asset(1), asset(2)));
+}
+
+fn add_liquidity(who: AccountId, amount_a: Balance, amount_b: Balance) {
+    assert_ok!(DeipAmm::add_liquidity(
+        Origin::signed(who),
+        asset(1),
+        asset(2),
+        amount_a,
+        amount_b,
+        0,
+        0
+    ));
+}
+
+fn balance(who: AccountId, id: H160) -> Balance {
+    DeipAssets::account_balance(&who, &id)
+}
+
+fn pool_account() -> AccountId {
+    DeipAmm::pool_key(&(asset(1), asset(2)))
+}
+
+fn lp_asset() -> H160 {
+    DeipAmm::lp_asset_id(&(asset(1), asset(2)))
+}
+
+#[test]
+fn lp_asset_is_derived_from_pair() {
+    new_test_ext().execute_with(|| {
+        create_pool();
+
+        let pool = Pool { lp_asset: lp_asset(), reserve_a: 0, reserve_b: 0 };
+        assert_eq!(Pools::<Test>::get((asset(1), asset(2))), Some(pool));
+        assert_eq!(
+            last_event(),
+            Event::DeipAmm(AmmEvent::PoolCreated((asset(1), asset(2)), lp_asset(), ALICE))
+        );
+        assert_noop!(
+            DeipAmm::create_pool(Origin::signed(BOB), asset(2), asset(1)),
+            Error::<Test>::PoolAlreadyExists
+        );
+        assert_noop!(
+            DeipAmm::create_pool(Origin::signed(BOB), asset(1), asset(1)),
+            Error::<Test>::PoolSameAssets
+        );
+    });
+}
+
+#[test]
+fn first_deposit_locks_minimum_liquidity() {
+    new_test_ext().execute_with(|| {
+        create_pool();
+
+        // sqrt(10 * 10) doesn't exceed the minimum liquidity
+        assert_noop!(
+            DeipAmm::add_liquidity(Origin::signed(ALICE), asset(1), asset(2), 10, 10, 0, 0),
+            Error::<Test>::InsufficientLiquidity
+        );
+
+        add_liquidity(ALICE, 100, 100);
+        assert_eq!(balance(ALICE, lp_asset()), 90);
+        assert_eq!(balance(pool_account(), lp_asset()), 10);
+        assert_eq!(DeipAssets::total_supply(&lp_asset()), 100);
+
+        add_liquidity(BOB, 50, 100);
+        assert_eq!(balance(BOB, lp_asset()), 50);
+        assert_eq!(DeipAmm::reserves(&asset(1), &asset(2)), Some((150, 150)));
+
+        assert_ok!(DeipAmm::remove_liquidity(Origin::signed(ALICE), asset(1), asset(2), 90, 0, 0));
+        assert_ok!(DeipAmm::remove_liquidity(Origin::signed(BOB), asset(1), asset(2), 50, 0, 0));
+
+        // the pool can't be drained
+        assert_eq!(DeipAmm::reserves(&asset(1), &asset(2)), Some((10, 10)));
+        assert_eq!(balance(ALICE, asset(1)), 1000);
+        assert_eq!(balance(BOB, asset(2)), 1000);
+    });
+}
+
+#[test]
+fn swap_keeps_fee_in_pool() {
+    new_test_ext().execute_with(|| {
+        create_pool();
+        add_liquidity(ALICE, 100, 100);
+
+        // 10 - 1% rounded up = 9 is swapped: 9 * 100 / (100 + 9)
+        assert_eq!(DeipAmm::quote(&asset(1), &asset(2), 10), Some(8));
+        assert_noop!(
+            DeipAmm::swap(Origin::signed(BOB), asset(1), asset(2), 10, 9),
+            Error::<Test>::SlippageExceeded
+        );
+        assert_ok!(DeipAmm::swap(Origin::signed(BOB), asset(1), asset(2), 10, 8));
+
+        assert_eq!(balance(BOB, asset(1)), 990);
+        assert_eq!(balance(BOB, asset(2)), 1008);
+        assert_eq!(DeipAmm::reserves(&asset(2), &asset(1)), Some((92, 110)));
+    });
+}
+
+#[test]
+fn assets_sent_to_pool_account_dont_move_price() {
+    new_test_ext().execute_with(|| {
+        create_pool();
+        assert_ok!(DeipAssets::deip_transfer(Origin::signed(BOB), asset(1), pool_account(), 100));
+
+        // the first deposit sets the price regardless of the balance of the pool account
+        add_liquidity(ALICE, 100, 100);
+        assert_eq!(DeipAmm::reserves(&asset(1), &asset(2)), Some((100, 100)));
+        assert_eq!(balance(pool_account(), asset(1)), 200);
+
+        assert_ok!(DeipAssets::deip_transfer(Origin::signed(BOB), asset(2), pool_account(), 100));
+        assert_eq!(DeipAmm::quote(&asset(1), &asset(2), 10), Some(8));
+        assert_ok!(DeipAmm::swap(Origin::signed(ALICE), asset(1), asset(2), 10, 8));
+        assert_eq!(DeipAmm::reserves(&asset(1), &asset(2)), Some((110, 92)));
+    });
+}
@@ -0,0 +1,45 @@
+//! Weights for pallet_deip_amm
+//!
+//! Estimated from storage accesses of the calls and of `pallet_deip_assets`
+//! calls they dispatch. Run the benchmarks of the pallet to replace them
+//! with measured values.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+pub trait WeightInfo {
+    fn create_pool() -> Weight;
+    fn add_liquidity() -> Weight;
+    fn remove_liquidity() -> Weight;
+    fn swap() -> Weight;
+}
+
+/// Weight functions for pallet_deip_amm.
+pub struct Weights<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for Weights<T> {
+    fn create_pool() -> Weight {
+        (160_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(10 as Weight))
+            .saturating_add(T::DbWeight::get().writes(11 as Weight))
+    }
+    // the first deposit which also mints locked LP tokens
+    fn add_liquidity() -> Weight {
+        (260_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(24 as Weight))
+            .saturating_add(T::DbWeight::get().writes(22 as Weight))
+    }
+    fn remove_liquidity() -> Weight {
+        (230_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(22 as Weight))
+            .saturating_add(T::DbWeight::get().writes(18 as Weight))
+    }
+    fn swap() -> Weight {
+        (160_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(16 as Weight))
+            .saturating_add(T::DbWeight::get().writes(13 as Weight))
+    }
+}
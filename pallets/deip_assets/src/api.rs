@@ -0,0 +1,24 @@
+use codec::Codec;
+
+use super::CheckpointId;
+
+sp_api::decl_runtime_apis! {
+    pub trait DeipAssetsRuntimeApi<AssetId, AccountId, Balance>
+        where
+            AssetId: Codec,
+            AccountId: Codec,
+            Balance: Codec,
+    {
+        /// Balance of the account at the checkpoint of the asset.
+        /// Returns `None` if the checkpoint wasn't made yet.
+        fn balance_at(
+            asset: AssetId,
+            account: AccountId,
+            checkpoint: CheckpointId,
+        ) -> Option<Balance>;
+
+        /// Amount of the asset which can be minted at the moment.
+        /// Returns `None` if minting of the asset isn't limited.
+        fn remaining_supply(asset: AssetId) -> Option<Balance>;
+    }
+}
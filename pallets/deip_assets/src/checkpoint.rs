@@ -0,0 +1,160 @@
+use frame_support::{ensure, traits::Get};
+use sp_runtime::traits::{One, Zero};
+use sp_std::{marker::PhantomData, prelude::*};
+
+use crate::{
+    AccountIdOf, AssetsBalanceOf, BalanceSnapshots, CheckpointId, CheckpointIdByAsset,
+    CheckpointPins, Config, DeipAssetIdOf, Error, Event, LastPinnedCheckpoint, Pallet,
+    PinnedCheckpoints, RingCheckpoints, TotalSupplyAt,
+};
+
+/// Max number of retained checkpoints of an asset: latest and pinned ones.
+pub struct RetainedCheckpointsLimit<T>(PhantomData<T>);
+
+impl<T: Config> Get<u32> for RetainedCheckpointsLimit<T> {
+    fn get() -> u32 {
+        T::CheckpointRetention::get().saturating_add(T::MaxPinnedCheckpoints::get())
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Records a new checkpoint of the asset and its total supply.
+    /// Balances of accounts are recorded lazily before their first change
+    /// after the checkpoint. Only `CheckpointRetention` latest checkpoints are kept,
+    /// pinned checkpoints don't count.
+    pub fn create_checkpoint(asset: DeipAssetIdOf<T>) -> Result<CheckpointId, Error<T>> {
+        let id = Self::next_checkpoint(asset)?;
+
+        let mut ring = RingCheckpoints::<T>::get(asset);
+        if !ring.is_empty() && ring.len() as u32 >= T::CheckpointRetention::get() {
+            let expired = ring.remove(0);
+            if !CheckpointPins::<T>::contains_key(asset, expired) {
+                TotalSupplyAt::<T>::remove(asset, expired);
+            }
+        }
+        let _ = ring.try_push(id);
+        RingCheckpoints::<T>::insert(asset, ring);
+
+        Ok(id)
+    }
+
+    /// Records a checkpoint of the asset which is kept till it's unpinned
+    /// with [`unpin_checkpoint`](Self::unpin_checkpoint). Checkpoints pinned
+    /// in the same block are shared. Up to `MaxPinnedCheckpoints` checkpoints
+    /// of the asset may be pinned at once.
+    pub fn pin_checkpoint(asset: DeipAssetIdOf<T>) -> Result<CheckpointId, Error<T>> {
+        let now = frame_system::Pallet::<T>::block_number();
+        let id = match LastPinnedCheckpoint::<T>::get(asset) {
+            Some((block, id)) if block == now && CheckpointPins::<T>::contains_key(asset, id) => id,
+            _ => {
+                let mut pinned = PinnedCheckpoints::<T>::get(asset);
+                ensure!(
+                    (pinned.len() as u32) < T::MaxPinnedCheckpoints::get(),
+                    Error::<T>::TooManyPinnedCheckpoints
+                );
+                let id = Self::next_checkpoint(asset)?;
+                pinned.try_push(id).map_err(|_| Error::<T>::TooManyPinnedCheckpoints)?;
+                PinnedCheckpoints::<T>::insert(asset, pinned);
+                LastPinnedCheckpoint::<T>::insert(asset, (now, id));
+                id
+            },
+        };
+        CheckpointPins::<T>::mutate(asset, id, |pins| *pins = pins.saturating_add(1));
+
+        Ok(id)
+    }
+
+    /// Removes a pin of the checkpoint. The checkpoint is removed with the last pin
+    /// unless it's one of `CheckpointRetention` latest ones.
+    pub fn unpin_checkpoint(asset: &DeipAssetIdOf<T>, checkpoint: CheckpointId) {
+        let pins = CheckpointPins::<T>::get(asset, checkpoint);
+        if pins > 1 {
+            CheckpointPins::<T>::insert(asset, checkpoint, pins - 1);
+            return
+        }
+        if pins.is_zero() {
+            return
+        }
+
+        CheckpointPins::<T>::remove(asset, checkpoint);
+        PinnedCheckpoints::<T>::mutate(asset, |pinned| pinned.retain(|id| *id != checkpoint));
+        if !RingCheckpoints::<T>::get(asset).contains(&checkpoint) {
+            TotalSupplyAt::<T>::remove(asset, checkpoint);
+        }
+    }
+
+    /// Checks if the checkpoint was made and is still retained.
+    pub fn is_checkpoint_retained(asset: &DeipAssetIdOf<T>, checkpoint: CheckpointId) -> bool {
+        CheckpointPins::<T>::contains_key(asset, checkpoint) ||
+            RingCheckpoints::<T>::get(asset).contains(&checkpoint)
+    }
+
+    /// Balance of the account at the checkpoint.
+    /// Returns `None` if the checkpoint wasn't made yet or isn't retained.
+    pub fn balance_at(
+        asset: &DeipAssetIdOf<T>,
+        account: &AccountIdOf<T>,
+        checkpoint: CheckpointId,
+    ) -> Option<AssetsBalanceOf<T>> {
+        if !Self::is_checkpoint_retained(asset, checkpoint) {
+            return None
+        }
+
+        let snapshots = BalanceSnapshots::<T>::get(asset, account);
+        let index = snapshots.partition_point(|(id, _)| *id < checkpoint);
+        match snapshots.get(index) {
+            Some((_, balance)) => Some(*balance),
+            // the balance hasn't changed since the checkpoint
+            None => Some(Self::account_balance(account, asset)),
+        }
+    }
+
+    /// Total supply of the asset at the checkpoint.
+    pub fn total_supply_at(
+        asset: &DeipAssetIdOf<T>,
+        checkpoint: CheckpointId,
+    ) -> Option<AssetsBalanceOf<T>> {
+        TotalSupplyAt::<T>::get(asset, checkpoint)
+    }
+
+    /// Should be called before any change of the account balance.
+    pub(crate) fn update_balance_snapshot(asset: &DeipAssetIdOf<T>, account: &AccountIdOf<T>) {
+        let checkpoint = CheckpointIdByAsset::<T>::get(asset);
+        if checkpoint.is_zero() {
+            return
+        }
+
+        BalanceSnapshots::<T>::mutate(asset, account, |snapshots| {
+            if snapshots.last().map_or(true, |(id, _)| *id < checkpoint) {
+                let mut retained = RingCheckpoints::<T>::get(asset).into_inner();
+                retained.extend(PinnedCheckpoints::<T>::get(asset).into_inner());
+                // a snapshot is the balance at retained checkpoints made since the previous one,
+                // so there are no more snapshots than retained checkpoints
+                let mut previous = 0;
+                snapshots.retain(|(id, _)| {
+                    let needed = retained.iter().any(|c| previous < *c && c <= id);
+                    previous = *id;
+                    needed
+                });
+                if retained.iter().any(|c| previous < *c) {
+                    let _ = snapshots.try_push((checkpoint, Self::account_balance(account, asset)));
+                }
+            }
+        });
+    }
+
+    fn next_checkpoint(asset: DeipAssetIdOf<T>) -> Result<CheckpointId, Error<T>> {
+        Self::ensure_not_destroying(&asset)?;
+
+        let id = CheckpointIdByAsset::<T>::get(asset)
+            .checked_add(One::one())
+            .ok_or(Error::<T>::CheckpointIdOverflow)?;
+
+        CheckpointIdByAsset::<T>::insert(asset, id);
+        TotalSupplyAt::<T>::insert(asset, id, Self::total_supply(&asset));
+
+        Self::deposit_event(Event::<T>::CheckpointCreated(asset, id));
+
+        Ok(id)
+    }
+}
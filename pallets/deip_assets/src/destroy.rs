@@ -0,0 +1,230 @@
+use codec::{Decode, Encode};
+use frame_support::{
+    dispatch::{DispatchResult, UnfilteredDispatchable, Weight},
+    ensure,
+    log::error,
+    traits::Get,
+};
+use frame_system::RawOrigin;
+use pallet_assets::WeightInfo;
+use sp_runtime::traits::{Saturating, StaticLookup, Zero};
+use sp_std::prelude::*;
+
+use crate::{
+    AccountIdOf, Allowlist, AssetIdByDeipAssetIdV1, AssetIdByProjectId, AssetMetadataMap,
+    AssetsAssetIdOf, Config, DeipAssetIdByAssetIdV1, DeipAssetIdOf, DestroyedAssets,
+    DestroyingAssets, Error, Event, HoldersByAsset, InvestmentByAssetIdV1, Pallet,
+    ProjectIdByAssetId, SecurityMetadataByAsset, SecurityMetadataHistory, SupplyLimits,
+    TransferRulesByAsset, WipeQueue, WipeQueueRange,
+};
+
+type AssetsWeightInfoOf<T> = <T as pallet_assets::Config>::WeightInfo;
+
+/// Encoded the same way as `pallet_assets::DestroyWitness` which fields are private.
+#[derive(Encode, Decode)]
+pub(crate) struct DestroyWitness {
+    #[codec(compact)]
+    pub(crate) accounts: u32,
+    #[codec(compact)]
+    pub(crate) sufficients: u32,
+    #[codec(compact)]
+    pub(crate) approvals: u32,
+}
+
+impl<T: Config> Pallet<T> {
+    /// Weight of [`deip_destroy`](Self::deip_destroy) by its witness.
+    pub(crate) fn destroy_weight(witness: &pallet_assets::DestroyWitness) -> Weight {
+        let (accounts, sufficients, approvals) =
+            match DestroyWitness::decode(&mut &witness.encode()[..]) {
+                Ok(w) => (w.accounts, w.sufficients, w.approvals),
+                // can't happen, see `destroy_witness_layout` test
+                Err(_) => (u32::MAX, u32::MAX, u32::MAX),
+            };
+        AssetsWeightInfoOf::<T>::destroy(accounts, sufficients, approvals)
+            .saturating_add(T::DbWeight::get().reads_writes(2, 10))
+    }
+
+    pub(crate) fn ensure_not_destroying(asset: &DeipAssetIdOf<T>) -> Result<(), Error<T>> {
+        ensure!(!DestroyingAssets::<T>::contains_key(asset), Error::<T>::AssetIsBeingDestroyed);
+        Ok(())
+    }
+
+    /// Freezes the asset and schedules wiping of its accounts after `WipePeriod`.
+    pub(crate) fn start_destroy_impl(
+        who: AccountIdOf<T>,
+        asset: DeipAssetIdOf<T>,
+    ) -> DispatchResult {
+        let details = Self::asset_details(&asset).ok_or(Error::<T>::DeipAssetIdDoesNotExist)?;
+        ensure!(who == details.owner, Error::<T>::NoPermission);
+        ensure!(
+            !InvestmentByAssetIdV1::<T>::contains_key(asset),
+            Error::<T>::ReservedAssetCannotBeDestroyed
+        );
+        Self::ensure_not_destroying(&asset)?;
+
+        if !details.is_frozen {
+            let asset_id = Self::assets_asset_id(&asset)?;
+            let call = pallet_assets::Call::<T>::freeze_asset { id: asset_id };
+            call.dispatch_bypass_filter(RawOrigin::Signed(details.freezer).into())
+                .map_err(|e| e.error)?;
+        }
+
+        let wipe_at =
+            frame_system::Pallet::<T>::block_number().saturating_add(T::WipePeriod::get());
+        DestroyingAssets::<T>::insert(asset, wipe_at);
+        WipeQueueRange::<T>::mutate(|(_, end)| {
+            WipeQueue::<T>::insert(*end, asset);
+            *end = end.wrapping_add(1);
+        });
+
+        Self::deposit_event(Event::<T>::DestroyStarted(asset, wipe_at));
+
+        Ok(())
+    }
+
+    /// Wipes accounts of assets which `WipePeriod` is over in the order of the queue.
+    /// At most `MaxWipeAccountsPerBlock` accounts are wiped in a block,
+    /// each asset takes at least one of them.
+    pub(crate) fn wipe_destroying_assets(now: T::BlockNumber) -> Weight {
+        let (mut head, end) = WipeQueueRange::<T>::get();
+        let mut weight = T::DbWeight::get().reads(1);
+        let mut budget = T::MaxWipeAccountsPerBlock::get();
+
+        while head != end && !budget.is_zero() {
+            weight = weight.saturating_add(T::DbWeight::get().reads(2));
+            let asset = WipeQueue::<T>::get(head);
+            let wipe_at = asset.and_then(DestroyingAssets::<T>::get);
+
+            if let (Some(asset), Some(wipe_at)) = (asset, wipe_at) {
+                if wipe_at > now {
+                    // assets behind aren't due either
+                    break
+                }
+
+                let left = budget;
+                let (wipe_weight, is_wiped) = Self::wipe_accounts(asset, &mut budget);
+                weight = weight.saturating_add(wipe_weight);
+                if budget == left {
+                    budget = budget.saturating_sub(1);
+                }
+                if !is_wiped {
+                    break
+                }
+            }
+
+            // wiped or destroyed with `deip_destroy` already
+            WipeQueue::<T>::remove(head);
+            head = head.wrapping_add(1);
+            WipeQueueRange::<T>::put((head, end));
+            weight = weight.saturating_add(T::DbWeight::get().writes(2));
+        }
+
+        weight
+    }
+
+    /// Wipes up to `budget` accounts of the asset.
+    /// Returns `true` if no holders of the asset remain.
+    fn wipe_accounts(asset: DeipAssetIdOf<T>, budget: &mut u32) -> (Weight, bool) {
+        let mut weight = T::DbWeight::get().reads(2);
+
+        let (asset_id, details) = match (Self::assets_asset_id(&asset), Self::asset_details(&asset))
+        {
+            (Ok(asset_id), Some(details)) => (asset_id, details),
+            _ => {
+                // destroyed outside of the pallet
+                DestroyingAssets::<T>::remove(asset);
+                return (weight.saturating_add(T::DbWeight::get().writes(1)), true)
+            },
+        };
+
+        // frozen assets can't be burned, the asset is frozen back below
+        let call = pallet_assets::Call::<T>::thaw_asset { id: asset_id };
+        if let Err(e) = call.dispatch_bypass_filter(RawOrigin::Signed(details.admin.clone()).into())
+        {
+            error!("failed to thaw asset: {:?}", e.error);
+            return (weight, true)
+        }
+        weight = weight.saturating_add(AssetsWeightInfoOf::<T>::thaw_asset());
+
+        let accounts: Vec<_> =
+            HoldersByAsset::<T>::iter_key_prefix(asset).take(*budget as usize).collect();
+        let wiped = accounts.len() as u32;
+        for account in accounts {
+            Self::burn_all(&asset, asset_id, &details.admin, &account);
+            weight = weight.saturating_add(
+                AssetsWeightInfoOf::<T>::burn()
+                    .saturating_add(AssetsWeightInfoOf::<T>::thaw())
+                    .saturating_add(T::DbWeight::get().reads_writes(3, 3)),
+            );
+        }
+        *budget = budget.saturating_sub(wiped);
+
+        // the asset stays frozen until the owner destroys it
+        let call = pallet_assets::Call::<T>::freeze_asset { id: asset_id };
+        let _ = call.dispatch_bypass_filter(RawOrigin::Signed(details.freezer).into());
+        weight = weight.saturating_add(AssetsWeightInfoOf::<T>::freeze_asset());
+
+        // accounts with dust below the minimal balance aren't indexed as holders
+        // and are removed by `pallet_assets` itself
+        let remaining = Self::holder_count(&asset);
+        Self::deposit_event(Event::<T>::AccountsWiped(asset, wiped, remaining));
+
+        (weight.saturating_add(T::DbWeight::get().reads(1)), remaining.is_zero())
+    }
+
+    fn burn_all(
+        asset: &DeipAssetIdOf<T>,
+        asset_id: AssetsAssetIdOf<T>,
+        admin: &AccountIdOf<T>,
+        who: &AccountIdOf<T>,
+    ) {
+        // balances at checkpoints are kept so holders may claim distributed dividends
+        Self::update_balance_snapshot(asset, who);
+
+        let amount = pallet_assets::Pallet::<T>::balance(asset_id, who.clone());
+        if !amount.is_zero() {
+            let who_source = <T::Lookup as StaticLookup>::unlookup(who.clone());
+            let origin = || RawOrigin::Signed(admin.clone()).into();
+
+            // frozen accounts can't be burned from
+            let call = pallet_assets::Call::<T>::thaw { id: asset_id, who: who_source.clone() };
+            let _ = call.dispatch_bypass_filter(origin());
+
+            let call = pallet_assets::Call::<T>::burn { id: asset_id, who: who_source, amount };
+            if let Err(e) = call.dispatch_bypass_filter(origin()) {
+                error!("failed to wipe account: {:?}", e.error);
+            }
+        }
+
+        // the account is removed from the index even if burning failed,
+        // otherwise it would be wiped in every block
+        Self::remove_holder(asset, who);
+    }
+
+    /// Removes the asset from DEIP index maps.
+    /// Checkpoints and dividends of the asset are kept until they are claimed
+    /// or reclaimed, so its id is kept in `DestroyedAssets`.
+    pub(crate) fn clear_asset(asset: &DeipAssetIdOf<T>, asset_id: &AssetsAssetIdOf<T>) {
+        AssetIdByDeipAssetIdV1::<T>::remove(asset, asset_id);
+        DeipAssetIdByAssetIdV1::<T>::remove(asset_id, asset);
+        if let Some(project) = ProjectIdByAssetId::<T>::take(asset) {
+            AssetIdByProjectId::<T>::mutate_exists(project, |maybe_assets| {
+                if let Some(assets) = maybe_assets.as_mut() {
+                    assets.retain(|a| a != asset);
+                    if assets.is_empty() {
+                        *maybe_assets = None;
+                    }
+                }
+            });
+        }
+        AssetMetadataMap::<T>::remove(asset);
+        SupplyLimits::<T>::remove(asset);
+        SecurityMetadataByAsset::<T>::remove(asset);
+        SecurityMetadataHistory::<T>::remove_prefix(asset, None);
+        TransferRulesByAsset::<T>::remove(asset);
+        Allowlist::<T>::remove_prefix(asset, None);
+        DestroyingAssets::<T>::remove(asset);
+        DestroyedAssets::<T>::insert(asset, ());
+        Self::clear_holders(asset);
+    }
+}
@@ -0,0 +1,206 @@
+use codec::{Decode, Encode};
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    storage::{with_transaction, TransactionOutcome},
+    traits::{Currency, ExistenceRequirement, Get, WithdrawReasons},
+};
+use frame_system::RawOrigin;
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    SaturatedConversion,
+};
+
+use crate::{
+    AccountIdOf, AssetsBalanceOf, CheckpointId, ClaimsInBlock, Config, DeipAssetIdOf,
+    DistributionOf, Distributions, DividendClaims, Error, Event, Pallet, PolicyCheck,
+};
+
+impl<T: Config> Pallet<T> {
+    /// Account which holds the deposit of the distribution until it's claimed.
+    pub fn dividends_key(asset: &DeipAssetIdOf<T>, checkpoint: CheckpointId) -> AccountIdOf<T> {
+        let entropy = (b"deip/dividends/", asset, checkpoint)
+            .using_encoded(sp_io::hashing::blake2_256);
+        AccountIdOf::<T>::decode(&mut &entropy[..]).unwrap_or_default()
+    }
+
+    pub(crate) fn distribute_impl(
+        distributor: AccountIdOf<T>,
+        asset: DeipAssetIdOf<T>,
+        payment_asset: DeipAssetIdOf<T>,
+        amount: AssetsBalanceOf<T>,
+    ) -> DispatchResult {
+        Self::ensure_asset_team(&asset, &distributor)?;
+        ensure!(asset != payment_asset, Error::<T>::DistributionWrongAsset);
+        ensure!(!amount.is_zero(), Error::<T>::DistributionAmountMustBePositive);
+        ensure!(!Self::total_supply(&asset).is_zero(), Error::<T>::DistributionNoHolders);
+
+        let checkpoint = Self::create_checkpoint(asset)?;
+        let pool = Self::dividends_key(&asset, checkpoint);
+
+        // The pool should exist to hold the deposit, see `deip_transactionally_reserve`.
+        let existential = T::Currency::withdraw(
+            &distributor,
+            T::Currency::minimum_balance(),
+            WithdrawReasons::RESERVE,
+            ExistenceRequirement::AllowDeath,
+        )
+        .map_err(|_| Error::<T>::DistributionBalanceIsNotEnough)?;
+        T::Currency::resolve_creating(&pool, existential);
+
+        Self::deip_transfer_impl(
+            RawOrigin::Signed(distributor.clone()).into(),
+            payment_asset,
+            pool,
+            amount,
+            PolicyCheck::Deposit,
+        )
+        .map_err(|e| e.error)?;
+
+        Distributions::<T>::insert(
+            asset,
+            checkpoint,
+            DistributionOf::<T> {
+                distributor,
+                payment_asset,
+                amount,
+                total_supply: Self::total_supply_at(&asset, checkpoint)
+                    .expect("created above"),
+                claimed_supply: Zero::zero(),
+                claims: 0,
+            },
+        );
+
+        Self::deposit_event(Event::<T>::DividendsDistributed(
+            asset,
+            checkpoint,
+            payment_asset,
+            amount,
+        ));
+
+        Ok(())
+    }
+
+    pub(crate) fn claim_impl(
+        account: AccountIdOf<T>,
+        asset: DeipAssetIdOf<T>,
+        checkpoint: CheckpointId,
+    ) -> DispatchResult {
+        let now = frame_system::Pallet::<T>::block_number();
+        let (block, claims) = ClaimsInBlock::<T>::get(asset);
+        let claims = if block == now { claims } else { 0 };
+        ensure!(claims < T::MaxDividendClaimsPerBlock::get(), Error::<T>::TooManyClaimsInBlock);
+
+        let mut distribution =
+            Distributions::<T>::get(asset, checkpoint).ok_or(Error::<T>::DistributionNotFound)?;
+        ensure!(
+            !DividendClaims::<T>::contains_key((asset, checkpoint), &account),
+            Error::<T>::DividendsAlreadyClaimed
+        );
+
+        let balance = Self::balance_at(&asset, &account, checkpoint)
+            .ok_or(Error::<T>::DistributionNotFound)?;
+
+        // similiar to frame_support::traits::Imbalance::ration
+        let share = balance
+            .saturated_into::<u128>()
+            .saturating_mul(distribution.amount.saturated_into()) /
+            distribution.total_supply.saturated_into::<u128>();
+        let share: AssetsBalanceOf<T> = share.saturated_into();
+
+        let pool = Self::dividends_key(&asset, checkpoint);
+        let available = Self::account_balance(&pool, &distribution.payment_asset);
+        // the pool may be drained earlier by the dust of previous claims
+        let share = share.min(available);
+        ensure!(!share.is_zero(), Error::<T>::NothingToClaim);
+
+        Self::deip_transfer_impl(
+            RawOrigin::Signed(pool.clone()).into(),
+            distribution.payment_asset,
+            account.clone(),
+            share,
+            PolicyCheck::Payout,
+        )
+        .map_err(|e| e.error)?;
+
+        DividendClaims::<T>::insert((asset, checkpoint), &account, share);
+        ClaimsInBlock::<T>::insert(asset, (now, claims.saturating_add(1)));
+
+        distribution.claimed_supply = distribution.claimed_supply.saturating_add(balance);
+        distribution.claims = distribution.claims.saturating_add(1);
+        let all_claimed = distribution.claimed_supply >= distribution.total_supply;
+        Distributions::<T>::insert(asset, checkpoint, &distribution);
+
+        Self::deposit_event(Event::<T>::DividendsClaimed(asset, checkpoint, account, share));
+
+        if all_claimed {
+            // only the rounding dust is left, the claim doesn't fail if it can't be
+            // returned now, the distributor reclaims it after the checkpoint expires
+            let _ = with_transaction(|| match Self::sweep_pool(asset, checkpoint, &distribution) {
+                Ok(()) => TransactionOutcome::Commit(Ok(())),
+                Err(e) => TransactionOutcome::Rollback(Err(e)),
+            });
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn reclaim_dividends_impl(
+        who: AccountIdOf<T>,
+        asset: DeipAssetIdOf<T>,
+        checkpoint: CheckpointId,
+        claims: u32,
+    ) -> DispatchResult {
+        let distribution =
+            Distributions::<T>::get(asset, checkpoint).ok_or(Error::<T>::DistributionNotFound)?;
+        ensure!(who == distribution.distributor, Error::<T>::NoPermission);
+        ensure!(
+            !Self::is_checkpoint_retained(&asset, checkpoint),
+            Error::<T>::DistributionNotExpired
+        );
+        ensure!(claims >= distribution.claims, Error::<T>::DistributionBadWitness);
+
+        Self::sweep_pool(asset, checkpoint, &distribution)?;
+
+        Distributions::<T>::remove(asset, checkpoint);
+        DividendClaims::<T>::remove_prefix((asset, checkpoint), None);
+
+        Ok(())
+    }
+
+    /// Returns the remainder of the distribution and the existential deposit
+    /// of its pool to the distributor.
+    fn sweep_pool(
+        asset: DeipAssetIdOf<T>,
+        checkpoint: CheckpointId,
+        distribution: &DistributionOf<T>,
+    ) -> DispatchResult {
+        let pool = Self::dividends_key(&asset, checkpoint);
+
+        let remainder = Self::account_balance(&pool, &distribution.payment_asset);
+        if !remainder.is_zero() {
+            Self::deip_transfer_impl(
+                RawOrigin::Signed(pool.clone()).into(),
+                distribution.payment_asset,
+                distribution.distributor.clone(),
+                remainder,
+                PolicyCheck::Refund,
+            )
+            .map_err(|e| e.error)?;
+        }
+
+        let existential = T::Currency::free_balance(&pool);
+        if !existential.is_zero() {
+            T::Currency::transfer(
+                &pool,
+                &distribution.distributor,
+                existential,
+                ExistenceRequirement::AllowDeath,
+            )?;
+        }
+
+        Self::deposit_event(Event::<T>::DividendsReclaimed(asset, checkpoint, remainder));
+
+        Ok(())
+    }
+}
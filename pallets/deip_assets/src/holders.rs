@@ -0,0 +1,146 @@
+use codec::Decode;
+use frame_support::{
+    storage::storage_prefix,
+    traits::{Get, PalletInfoAccess},
+    weights::Weight,
+};
+use sp_runtime::traits::Zero;
+
+use crate::{
+    AccountIdOf, AssetsAssetIdOf, Config, DeipAssetIdByAssetIdV1, DeipAssetIdOf,
+    AssetsByHolder, HolderCountByAsset, HoldersBackfillCursor, HoldersByAsset,
+    HoldingCountByAccount, Pallet, V3,
+};
+
+impl<T: Config> Pallet<T> {
+    /// Number of accounts with non-zero balance of the asset.
+    pub fn holder_count(asset: &DeipAssetIdOf<T>) -> u32 {
+        HolderCountByAsset::<T>::get(asset)
+    }
+
+    /// Number of DEIP assets `account` has non-zero balance of.
+    pub fn holding_count(account: &AccountIdOf<T>) -> u32 {
+        HoldingCountByAccount::<T>::get(account)
+    }
+
+    /// Whether the index of holders is complete, i.e. its backfill has finished.
+    pub fn holders_indexed() -> bool {
+        !HoldersBackfillCursor::<T>::exists()
+    }
+
+    /// Should be called after any change of the account balance.
+    pub(crate) fn update_holder(asset: &DeipAssetIdOf<T>, account: &AccountIdOf<T>) {
+        let is_holder = !Self::account_balance(account, asset).is_zero();
+        // holders indexed before `V3` aren't in `AssetsByHolder` till the backfill
+        Self::update_holding(asset, account, is_holder);
+        if is_holder == HoldersByAsset::<T>::contains_key(asset, account) {
+            return
+        }
+
+        if is_holder {
+            HoldersByAsset::<T>::insert(asset, account, ());
+            HolderCountByAsset::<T>::mutate(asset, |count| *count = count.saturating_add(1));
+        } else {
+            HoldersByAsset::<T>::remove(asset, account);
+            Self::decrease_holder_count(asset);
+        }
+    }
+
+    fn update_holding(asset: &DeipAssetIdOf<T>, account: &AccountIdOf<T>, is_holder: bool) {
+        if is_holder == AssetsByHolder::<T>::contains_key(account, asset) {
+            return
+        }
+
+        if is_holder {
+            AssetsByHolder::<T>::insert(account, asset, ());
+            HoldingCountByAccount::<T>::mutate(account, |count| *count = count.saturating_add(1));
+        } else {
+            AssetsByHolder::<T>::remove(account, asset);
+            HoldingCountByAccount::<T>::mutate(account, |count| *count = count.saturating_sub(1));
+        }
+    }
+
+    /// Removes `account` from holders of the asset.
+    pub(crate) fn remove_holder(asset: &DeipAssetIdOf<T>, account: &AccountIdOf<T>) {
+        Self::update_holding(asset, account, false);
+        if HoldersByAsset::<T>::contains_key(asset, account) {
+            HoldersByAsset::<T>::remove(asset, account);
+            Self::decrease_holder_count(asset);
+        }
+    }
+
+    pub(crate) fn decrease_holder_count(asset: &DeipAssetIdOf<T>) {
+        HolderCountByAsset::<T>::mutate(asset, |count| *count = count.saturating_sub(1));
+    }
+
+    /// Removes all holders of the asset. Their number is bounded by the witness
+    /// of destroying the asset.
+    pub(crate) fn clear_holders(asset: &DeipAssetIdOf<T>) {
+        for account in HoldersByAsset::<T>::iter_key_prefix(asset) {
+            Self::update_holding(asset, &account, false);
+        }
+        HoldersByAsset::<T>::remove_prefix(asset, None);
+        HolderCountByAsset::<T>::remove(asset);
+    }
+
+    /// Starts the backfill of the index of holders from accounts of `pallet_assets`,
+    /// see [`backfill_holders`](Self::backfill_holders).
+    pub(crate) fn start_holders_backfill() {
+        HoldersBackfillCursor::<T>::put(Self::assets_accounts_prefix().to_vec());
+    }
+
+    /// Adds up to `limit` accounts of `pallet_assets` to the index of holders
+    /// in the order of their storage keys. Sets the storage version `V3` when finished.
+    /// `update_holder` is idempotent so changes of balances during the backfill are safe.
+    pub(crate) fn backfill_holders(limit: u32) -> Weight {
+        let mut cursor = match HoldersBackfillCursor::<T>::get() {
+            None => return T::DbWeight::get().reads(1),
+            Some(c) => c,
+        };
+
+        // accounts are stored in `pallet_assets::Account` keyed by (asset id, account)
+        let prefix = Self::assets_accounts_prefix();
+        let mut reads: Weight = 1;
+        let mut writes: Weight = 1;
+
+        for _ in 0..limit {
+            reads += 1;
+            let key = match sp_io::storage::next_key(&cursor) {
+                Some(key) if key.starts_with(&prefix) => key,
+                _ => {
+                    HoldersBackfillCursor::<T>::kill();
+                    V3.put::<Pallet<T>>();
+                    return T::DbWeight::get().reads_writes(reads, writes.saturating_add(1))
+                },
+            };
+
+            if let Some((asset_id, account)) = Self::decode_assets_account_key(&key[32..]) {
+                reads += 1;
+                if let Some((asset, _)) = DeipAssetIdByAssetIdV1::<T>::iter_prefix(asset_id).next()
+                {
+                    reads += 2;
+                    writes += 2;
+                    Self::update_holder(&asset, &account);
+                }
+            }
+
+            cursor = key;
+        }
+
+        HoldersBackfillCursor::<T>::put(cursor);
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+
+    fn assets_accounts_prefix() -> [u8; 32] {
+        storage_prefix(pallet_assets::Pallet::<T>::name().as_bytes(), b"Account")
+    }
+
+    fn decode_assets_account_key(key: &[u8]) -> Option<(AssetsAssetIdOf<T>, AccountIdOf<T>)> {
+        // skip hashes of Blake2_128Concat
+        let mut input = key.get(16..)?;
+        let asset_id = AssetsAssetIdOf::<T>::decode(&mut input).ok()?;
+        let mut input = input.get(16..)?;
+        let account = AccountIdOf::<T>::decode(&mut input).ok()?;
+        Some((asset_id, account))
+    }
+}
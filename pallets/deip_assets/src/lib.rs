@@ -13,9 +13,13 @@
 //!
 //! * [`create_asset`](./enum.Call.html#variant.create_asset)
 //! * [`destroy`](./enum.Call.html#variant.destroy)
+//! * [`start_destroy`](./enum.Call.html#variant.start_destroy)
 //! * [`issue_asset`](./enum.Call.html#variant.issue_asset)
 //! * [`burn`](./enum.Call.html#variant.burn)
 //! * [`transfer`](./enum.Call.html#variant.transfer)
+//! * [`deip_approve_transfer`](./enum.Call.html#variant.deip_approve_transfer)
+//! * [`deip_cancel_approval`](./enum.Call.html#variant.deip_cancel_approval)
+//! * [`deip_transfer_approved`](./enum.Call.html#variant.deip_transfer_approved)
 //! * [`freeze`](./enum.Call.html#variant.freeze)
 //! * [`thaw`](./enum.Call.html#variant.thaw)
 //! * [`freeze_asset`](./enum.Call.html#variant.freeze_asset)
@@ -23,6 +27,15 @@
 //! * [`transfer_ownership`](./enum.Call.html#variant.transfer_ownership)
 //! * [`set_team`](./enum.Call.html#variant.set_team)
 //! * [`set_metadata`](./enum.Call.html#variant.set_metadata)
+//! * [`checkpoint`](./enum.Call.html#variant.checkpoint)
+//! * [`distribute`](./enum.Call.html#variant.distribute)
+//! * [`claim`](./enum.Call.html#variant.claim)
+//! * [`reclaim_dividends`](./enum.Call.html#variant.reclaim_dividends)
+//! * [`set_transfer_rules`](./enum.Call.html#variant.set_transfer_rules)
+//! * [`allowlist_add`](./enum.Call.html#variant.allowlist_add)
+//! * [`allowlist_remove`](./enum.Call.html#variant.allowlist_remove)
+//! * [`set_security_metadata`](./enum.Call.html#variant.set_security_metadata)
+//! * [`attest_security_metadata`](./enum.Call.html#variant.attest_security_metadata)
 //!
 //! [`Config`]: ./trait.Config.html
 
@@ -32,7 +45,23 @@
 // Re-export to use implementation details in dependent crates:
 pub use pallet_assets;
 
+pub mod api;
+mod checkpoint;
+mod destroy;
+mod dividends;
+mod holders;
 mod impl_fungibles;
+mod policy;
+mod security;
+mod supply;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub use checkpoint::RetainedCheckpointsLimit;
+pub use policy::{OnChainTransferPolicy, TransferPolicy};
+pub(crate) use policy::PolicyCheck;
 
 pub use deip_serializable_u128::SerializableAtLeast32BitUnsigned as SerializableAssetBalance;
 
@@ -45,20 +74,31 @@ pub mod pallet {
     use frame_support::{
         pallet_prelude::{
             ensure, Blake2_128Concat, Decode, DispatchResultWithPostInfo, Encode, Get, Hooks,
-            Identity, MaxEncodedLen, Member, OptionQuery, Parameter, Pays, StorageDoubleMap,
+            Identity, IsType, MaxEncodedLen, Member, OptionQuery, Parameter, StorageDoubleMap,
             StorageMap, StorageValue, ValueQuery,
         },
-        traits::{Currency, ExistenceRequirement, UnfilteredDispatchable, WithdrawReasons},
-        transactional, RuntimeDebug,
+        storage::{storage_prefix, unhashed},
+        traits::{
+            Currency, ExistenceRequirement, PalletInfoAccess, UnfilteredDispatchable,
+            WithdrawReasons,
+        },
+        transactional, BoundedVec, RuntimeDebug, StorageHasher,
     };
     use frame_system::{
+        ensure_signed,
         pallet_prelude::{BlockNumberFor, OriginFor},
         RawOrigin,
     };
     use scale_info::TypeInfo;
-    use sp_runtime::traits::{CheckedAdd, One, StaticLookup, Zero};
+    use sp_runtime::{
+        traits::{AtLeast32BitUnsigned, CheckedAdd, One, StaticLookup, Zero},
+        DispatchResult,
+    };
     use sp_std::prelude::*;
 
+    #[cfg(feature = "std")]
+    use serde::{Deserialize, Serialize};
+
     use codec::HasCompact;
     use frame_support::dispatch::Weight;
 
@@ -70,7 +110,9 @@ pub mod pallet {
     use deip_asset_system::AssetIdInitT;
     use deip_projects_info::DeipProjectsInfo;
 
-    type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+    use super::{PolicyCheck, RetainedCheckpointsLimit, SerializableAssetBalance, TransferPolicy};
+
+    pub(crate) type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
     pub type ProjectsInfoOf<T> = <T as Config>::ProjectsInfo;
     pub type DeipProjectIdOf<T> =
         <<T as Config>::ProjectsInfo as DeipProjectsInfo<AccountIdOf<T>>>::ProjectId;
@@ -80,6 +122,32 @@ pub mod pallet {
     pub(crate) type AssetsBalanceOf<T> = <T as pallet_assets::Config>::Balance;
     pub type DeipAssetIdOf<T> = <T as Config>::AssetId;
     type AssetsWeightInfoOf<T> = <T as pallet_assets::Config>::WeightInfo;
+    type DepositBalanceOf<T> =
+        <<T as pallet_assets::Config>::Currency as Currency<AccountIdOf<T>>>::Balance;
+    pub type DistributionOf<T> = Distribution<AccountIdOf<T>, DeipAssetIdOf<T>, AssetsBalanceOf<T>>;
+    pub type IssuanceScheduleOf<T> = IssuanceSchedule<AssetsBalanceOf<T>, BlockNumberFor<T>>;
+    pub type SupplyLimitOf<T> = SupplyLimit<AssetsBalanceOf<T>, BlockNumberFor<T>>;
+    pub type SecurityMetadataOf<T> =
+        SecurityMetadata<DeipProjectIdOf<T>, <T as frame_system::Config>::Hash>;
+    pub type VersionedSecurityMetadataOf<T> = VersionedSecurityMetadata<
+        DeipProjectIdOf<T>,
+        <T as frame_system::Config>::Hash,
+        AccountIdOf<T>,
+    >;
+
+    /// Sequential number of the asset checkpoint. Starts from 1.
+    pub type CheckpointId = u32;
+
+    /// Version of the security metadata of the asset. Starts from 1.
+    pub type MetadataVersion = u32;
+
+    /// Code of the jurisdiction, e.g. ISO 3166-1 alpha-2.
+    pub type Jurisdiction = [u8; 2];
+
+    pub const MAX_JURISDICTIONS: usize = 50;
+
+    /// Number of accounts of `pallet_assets` processed by the backfill of holders in a block.
+    pub const MAX_BACKFILL_ACCOUNTS_PER_BLOCK: u32 = 100;
 
     #[pallet::config]
     pub trait Config:
@@ -110,20 +178,56 @@ pub mod pallet {
             + TypeInfo;
         type AssetIdInit: AssetIdInitT<<Self as Config>::AssetId>;
 
-        /// Period of check for accounts with zero FTs
+        /// Id reserved for the native currency in `DeipAssetSystem`.
+        /// Assets can't be created with it.
+        #[pallet::constant]
+        type NativeAssetId: Get<<Self as Config>::AssetId>;
+
+        /// Period between the start of the asset destruction and wiping of its accounts.
         #[pallet::constant]
         type WipePeriod: Get<Self::BlockNumber>;
+
+        /// Max number of accounts of destroyed assets wiped in a block.
+        #[pallet::constant]
+        type MaxWipeAccountsPerBlock: Get<u32>;
+
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+        /// Max number of dividend claims of an asset in a block.
+        #[pallet::constant]
+        type MaxDividendClaimsPerBlock: Get<u32>;
+
+        /// Number of latest checkpoints of an asset which balances are kept.
+        /// Dividends distributed at older checkpoints can't be claimed and
+        /// the remainder is returned with [`reclaim_dividends`](Pallet::reclaim_dividends).
+        #[pallet::constant]
+        type CheckpointRetention: Get<u32>;
+
+        /// Max number of checkpoints of an asset pinned at once,
+        /// see [`pin_checkpoint`](Pallet::pin_checkpoint).
+        #[pallet::constant]
+        type MaxPinnedCheckpoints: Get<u32>;
+
+        /// Checks transfers of assets, see [`OnChainTransferPolicy`](crate::OnChainTransferPolicy).
+        type TransferPolicy: TransferPolicy<
+            Self::AccountId,
+            <Self as Config>::AssetId,
+            <Self as pallet_assets::Config>::Balance,
+        >;
     }
 
     use frame_support::traits::{GetStorageVersion, StorageVersion};
 
     pub const V0: StorageVersion = StorageVersion::new(0);
     pub const V1: StorageVersion = StorageVersion::new(1);
+    pub const V2: StorageVersion = StorageVersion::new(2);
+    /// Indexes of assets by holder and of investments by creator.
+    pub const V3: StorageVersion = StorageVersion::new(3);
 
     #[doc(hidden)]
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
-    #[pallet::storage_version(V1)]
+    #[pallet::storage_version(V3)]
     pub struct Pallet<T>(_);
 
     fn count_items(pallet_name: &[u8], storage_name: &[u8]) -> usize {
@@ -135,12 +239,21 @@ pub mod pallet {
     #[doc(hidden)]
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            Self::wipe_destroying_assets(n)
+                .saturating_add(Self::backfill_holders(MAX_BACKFILL_ACCOUNTS_PER_BLOCK))
+        }
+
         fn on_runtime_upgrade() -> Weight {
             use core::convert::TryInto;
-            use frame_support::storage::migration::move_storage_from_pallet;
-            if Pallet::<T>::on_chain_storage_version() == V0 &&
-                Pallet::<T>::current_storage_version() == V1
-            {
+            use frame_support::storage::migration::{
+                move_storage_from_pallet, remove_storage_prefix,
+            };
+
+            let on_chain_version = Pallet::<T>::on_chain_storage_version();
+            let mut weight: Weight = 0;
+
+            if on_chain_version == V0 {
                 let mut reads: usize = 0;
                 for x in &[
                     "AssetIdByDeipAssetId",
@@ -150,7 +263,6 @@ pub mod pallet {
                     "ProjectIdByAssetId",
                     "InvestmentByAssetId",
                     "InvestmentMap",
-                    "FtBalanceMap",
                     "AssetMetadataMap",
                 ] {
                     reads += count_items(b"Assets", x.as_bytes());
@@ -186,7 +298,8 @@ pub mod pallet {
                         x
                     })
                     .for_each(|(k, v)| InvestmentMapV1::<T>::insert(k, v));
-                reads += FtBalanceMap::<T>::drain().count();
+                reads += count_items(b"Assets", b"FtBalanceMap");
+                remove_storage_prefix(b"Assets", b"FtBalanceMap", &[]);
                 reads += AssetMetadataMap::<T>::drain().count();
 
                 for x in &["Asset", "Account", "Approvals", "Metadata"] {
@@ -198,9 +311,28 @@ pub mod pallet {
                     );
                 }
                 let reads: Weight = reads.try_into().unwrap_or(Weight::MAX);
-                return T::DbWeight::get().reads_writes(reads, reads)
+                weight = weight.saturating_add(T::DbWeight::get().reads_writes(reads, reads));
+            }
+
+            if on_chain_version < V3 {
+                let mut reads: Weight = 0;
+                for investment in InvestmentMapV1::<T>::iter_values() {
+                    reads += 1;
+                    InvestmentCountByCreator::<T>::mutate(&investment.creator, |count| {
+                        *count = count.saturating_add(1)
+                    });
+                }
+                weight = weight.saturating_add(T::DbWeight::get().reads_writes(reads, reads));
+            }
+
+            if on_chain_version < V3 && !HoldersBackfillCursor::<T>::exists() {
+                // the version is set when the backfill is finished in `on_initialize`,
+                // holders indexed before `V3` are added to the index of assets by holder
+                Self::start_holders_backfill();
+                weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
             }
-            0
+
+            weight
         }
     }
 
@@ -214,10 +346,145 @@ pub mod pallet {
         FtBalanceNotFound,
         AssetIdOverflow,
         DeipAssetIdExists,
+        /// The id is reserved for the native currency.
+        DeipAssetIdIsNative,
+        /// The asset with the id has been destroyed, the id can't be reused.
+        DeipAssetIdDestroyed,
         /// Asset with DeipAssetId wasn't created.
         DeipAssetIdDoesNotExist,
+        /// Access Forbidden
+        NoPermission,
+        CheckpointIdOverflow,
+        /// Dividends can't be paid in the asset they are distributed for.
+        DistributionWrongAsset,
+        DistributionAmountMustBePositive,
+        /// The asset has no supply to distribute dividends to.
+        DistributionNoHolders,
+        DistributionBalanceIsNotEnough,
+        DistributionNotFound,
+        DividendsAlreadyClaimed,
+        NothingToClaim,
+        /// Limit of dividend claims of the asset in the block is reached, try in the next one.
+        TooManyClaimsInBlock,
+        /// The checkpoint of the distribution is still retained, holders may claim dividends.
+        DistributionNotExpired,
+        /// Number of claims passed as the witness is less than the actual one.
+        DistributionBadWitness,
+        /// Too many jurisdictions in transfer rules.
+        TooMuchJurisdictions,
+        /// The asset is locked up until the moment set in transfer rules.
+        TransferLockedUp,
+        /// Only accounts in the allowlist of the asset may receive it.
+        TransferRecipientNotAllowlisted,
+        /// Jurisdiction of the recipient isn't allowed by transfer rules.
+        TransferJurisdictionNotAllowed,
+        /// Max number of holders of the asset is reached.
+        TransferMaxHoldersReached,
+        ReservedAssetCannotBeDestroyed,
+        /// The asset is being destroyed and can't be changed.
+        AssetIsBeingDestroyed,
+        /// Max supply and amount and period of the issuance schedule must be positive.
+        SupplyLimitInvalid,
+        /// The amount exceeds max supply or the amount released by the issuance schedule.
+        SupplyLimitExceeded,
+        SecurityMetadataNotFound,
+        SecurityMetadataVersionOverflow,
+        /// Only the latest version of the security metadata can be attested.
+        SecurityMetadataVersionMismatch,
+        SecurityMetadataAlreadyAttested,
+        /// Holders are being indexed, new holders of assets with max number of holders
+        /// aren't accepted till then.
+        HoldersBeingIndexed,
+        /// Max number of pinned checkpoints of the asset is reached.
+        TooManyPinnedCheckpoints,
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Event emitted when a checkpoint of the asset has been created: asset, checkpoint.
+        CheckpointCreated(DeipAssetIdOf<T>, CheckpointId),
+        /// Event emitted when dividends have been distributed to holders of the asset
+        /// at the checkpoint: asset, checkpoint, payment asset, amount.
+        DividendsDistributed(DeipAssetIdOf<T>, CheckpointId, DeipAssetIdOf<T>, AssetsBalanceOf<T>),
+        /// Event emitted when a holder has claimed dividends: asset, checkpoint, holder, amount.
+        DividendsClaimed(DeipAssetIdOf<T>, CheckpointId, AccountIdOf<T>, AssetsBalanceOf<T>),
+        /// Event emitted when the remainder of dividends has been returned to the distributor:
+        /// asset, checkpoint, amount.
+        DividendsReclaimed(DeipAssetIdOf<T>, CheckpointId, AssetsBalanceOf<T>),
+        /// Event emitted when transfer rules of the asset have been set or removed.
+        TransferRulesUpdated(DeipAssetIdOf<T>),
+        /// Event emitted when an account has been added to the allowlist of the asset.
+        AllowlistAdded(DeipAssetIdOf<T>, AccountIdOf<T>),
+        /// Event emitted when an account has been removed from the allowlist of the asset.
+        AllowlistRemoved(DeipAssetIdOf<T>, AccountIdOf<T>),
+        /// Event emitted when the asset has been frozen for destruction: asset,
+        /// block since which its accounts are wiped.
+        DestroyStarted(DeipAssetIdOf<T>, T::BlockNumber),
+        /// Event emitted when accounts of the destroyed asset have been wiped:
+        /// asset, wiped accounts, remaining holders. When no holders remain
+        /// the owner destroys the asset with `deip_destroy`.
+        AccountsWiped(DeipAssetIdOf<T>, u32, u32),
+        /// Event emitted when the asset has been destroyed.
+        Destroyed(DeipAssetIdOf<T>),
+        /// Event emitted when a new version of the security metadata of the asset
+        /// has been set: asset, version.
+        SecurityMetadataSet(DeipAssetIdOf<T>, MetadataVersion),
+        /// Event emitted when the issuer has attested the security metadata of the asset:
+        /// asset, version, issuer.
+        SecurityMetadataAttested(DeipAssetIdOf<T>, MetadataVersion, AccountIdOf<T>),
     }
 
+    /// Mirror of `pallet_assets::AssetDetails` which isn't accessible outside the pallet.
+    /// The layout is pinned by the `asset_details_layout` test.
+    #[derive(Decode)]
+    #[allow(dead_code)]
+    pub(crate) struct AssetDetails<AccountId, Balance, DepositBalance> {
+        pub owner: AccountId,
+        pub issuer: AccountId,
+        pub admin: AccountId,
+        pub freezer: AccountId,
+        pub supply: Balance,
+        pub deposit: DepositBalance,
+        pub min_balance: Balance,
+        pub is_sufficient: bool,
+        /// Number of accounts holding the asset.
+        pub accounts: u32,
+        pub sufficients: u32,
+        pub approvals: u32,
+        pub is_frozen: bool,
+    }
+
+    #[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub struct TransferRules {
+        /// Only accounts in the allowlist may receive the asset.
+        pub allowlist_only: bool,
+        /// Jurisdictions of recipients set in the allowlist. Empty means any.
+        pub jurisdictions: Vec<Jurisdiction>,
+        pub max_holders: Option<u32>,
+        /// Unix time in milliseconds until holders can't send the asset.
+        /// Doesn't apply to the owner and the issuer of the asset.
+        pub lockup_until: Option<u64>,
+    }
+
+    /// Transfer rules configured by the asset team.
+    #[pallet::storage]
+    pub(super) type TransferRulesByAsset<T: Config> =
+        StorageMap<_, Blake2_128Concat, DeipAssetIdOf<T>, TransferRules, OptionQuery>;
+
+    /// Accounts allowed to receive the asset with their jurisdictions.
+    #[pallet::storage]
+    pub(super) type Allowlist<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DeipAssetIdOf<T>,
+        Blake2_128Concat,
+        AccountIdOf<T>,
+        Option<Jurisdiction>,
+        OptionQuery,
+    >;
+
     #[pallet::storage]
     pub(super) type AssetIdByDeipAssetId<T: Config> = StorageDoubleMap<
         _,
@@ -284,10 +551,12 @@ pub mod pallet {
         StorageMap<_, Blake2_128Concat, DeipAssetIdOf<T>, Vec<DeipInvestmentIdOf<T>>, OptionQuery>;
 
     #[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq, TypeInfo)]
-    pub(super) struct Investment<AccountId, AssetId> {
-        creator: AccountId,
-        assets: Vec<AssetId>,
-        asset_id: AssetId,
+    pub struct Investment<AccountId, AssetId> {
+        pub creator: AccountId,
+        /// Reserved assets.
+        pub assets: Vec<AssetId>,
+        /// Asset to raise.
+        pub asset_id: AssetId,
     }
 
     #[pallet::storage]
@@ -308,10 +577,49 @@ pub mod pallet {
         OptionQuery,
     >;
 
-    /// Deprecated
+    /// Accounts with non-zero balance of the asset.
+    #[pallet::storage]
+    pub(super) type HoldersByAsset<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DeipAssetIdOf<T>,
+        Blake2_128Concat,
+        AccountIdOf<T>,
+        (),
+        OptionQuery,
+    >;
+
+    /// Number of holders of the asset.
+    #[pallet::storage]
+    pub(super) type HolderCountByAsset<T: Config> =
+        StorageMap<_, Blake2_128Concat, DeipAssetIdOf<T>, u32, ValueQuery>;
+
+    /// DEIP assets the account has non-zero balance of, the reverse of [`HoldersByAsset`].
+    #[pallet::storage]
+    pub(super) type AssetsByHolder<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        AccountIdOf<T>,
+        Blake2_128Concat,
+        DeipAssetIdOf<T>,
+        (),
+        OptionQuery,
+    >;
+
+    /// Number of DEIP assets the account has non-zero balance of.
+    #[pallet::storage]
+    pub(super) type HoldingCountByAccount<T: Config> =
+        StorageMap<_, Blake2_128Concat, AccountIdOf<T>, u32, ValueQuery>;
+
+    /// Number of investments with assets reserved by the account.
+    #[pallet::storage]
+    pub(super) type InvestmentCountByCreator<T: Config> =
+        StorageMap<_, Blake2_128Concat, AccountIdOf<T>, u32, ValueQuery>;
+
+    /// Storage key of the last account of `pallet_assets` added to [`HoldersByAsset`]
+    /// while the index is backfilled after the upgrade to `V3`.
     #[pallet::storage]
-    pub(super) type FtBalanceMap<T: Config> =
-        StorageMap<_, Identity, DeipAssetIdOf<T>, Vec<AccountIdOf<T>>, OptionQuery>;
+    pub(super) type HoldersBackfillCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
 
     #[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq, TypeInfo)]
     pub(super) struct AssetMetadata<U8> {
@@ -325,6 +633,222 @@ pub mod pallet {
     pub(super) type AssetMetadataMap<T: Config> =
         StorageMap<_, Identity, DeipAssetIdOf<T>, AssetMetadata<u8>, OptionQuery>;
 
+    /// Last checkpoint of the asset.
+    #[pallet::storage]
+    pub(super) type CheckpointIdByAsset<T: Config> =
+        StorageMap<_, Blake2_128Concat, DeipAssetIdOf<T>, CheckpointId, ValueQuery>;
+
+    /// Latest checkpoints of the asset kept by `CheckpointRetention`, pinned ones aren't
+    /// included.
+    #[pallet::storage]
+    pub(super) type RingCheckpoints<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        DeipAssetIdOf<T>,
+        BoundedVec<CheckpointId, T::CheckpointRetention>,
+        ValueQuery,
+    >;
+
+    /// Pinned checkpoints of the asset, kept till they are unpinned.
+    #[pallet::storage]
+    pub(super) type PinnedCheckpoints<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        DeipAssetIdOf<T>,
+        BoundedVec<CheckpointId, T::MaxPinnedCheckpoints>,
+        ValueQuery,
+    >;
+
+    /// Number of pins of the checkpoint.
+    #[pallet::storage]
+    pub(super) type CheckpointPins<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DeipAssetIdOf<T>,
+        Blake2_128Concat,
+        CheckpointId,
+        u32,
+        ValueQuery,
+    >;
+
+    /// Last pinned checkpoint of the asset with the block it's been created in.
+    #[pallet::storage]
+    pub(super) type LastPinnedCheckpoint<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        DeipAssetIdOf<T>,
+        (BlockNumberFor<T>, CheckpointId),
+        OptionQuery,
+    >;
+
+    /// Total supply of the asset at the checkpoint.
+    #[pallet::storage]
+    pub(super) type TotalSupplyAt<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DeipAssetIdOf<T>,
+        Blake2_128Concat,
+        CheckpointId,
+        AssetsBalanceOf<T>,
+        OptionQuery,
+    >;
+
+    /// Balances of the account before the first change after retained checkpoints,
+    /// sorted by checkpoint.
+    #[pallet::storage]
+    pub(super) type BalanceSnapshots<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DeipAssetIdOf<T>,
+        Blake2_128Concat,
+        AccountIdOf<T>,
+        BoundedVec<(CheckpointId, AssetsBalanceOf<T>), RetainedCheckpointsLimit<T>>,
+        ValueQuery,
+    >;
+
+    #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+    pub struct Distribution<AccountId, AssetId, Balance> {
+        pub distributor: AccountId,
+        pub payment_asset: AssetId,
+        pub amount: Balance,
+        /// Total supply of the distributed asset at the checkpoint.
+        pub total_supply: Balance,
+        /// Sum of balances at the checkpoint of holders who have claimed their shares.
+        pub claimed_supply: Balance,
+        /// Number of holders who have claimed their shares.
+        pub claims: u32,
+    }
+
+    /// Dividends distributed to holders of the asset at the checkpoint.
+    #[pallet::storage]
+    pub(super) type Distributions<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DeipAssetIdOf<T>,
+        Blake2_128Concat,
+        CheckpointId,
+        DistributionOf<T>,
+        OptionQuery,
+    >;
+
+    /// Amounts of dividends claimed by holders.
+    #[pallet::storage]
+    pub(super) type DividendClaims<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        (DeipAssetIdOf<T>, CheckpointId),
+        Blake2_128Concat,
+        AccountIdOf<T>,
+        AssetsBalanceOf<T>,
+        OptionQuery,
+    >;
+
+    /// Assets being destroyed with blocks since their accounts are wiped.
+    #[pallet::storage]
+    pub(super) type DestroyingAssets<T: Config> =
+        StorageMap<_, Blake2_128Concat, DeipAssetIdOf<T>, T::BlockNumber, OptionQuery>;
+
+    /// Assets being destroyed in the order of `start_destroy`, i.e. by the block
+    /// since which their accounts are wiped. Keyed by the position in the queue.
+    #[pallet::storage]
+    pub(super) type WipeQueue<T: Config> =
+        StorageMap<_, Identity, u32, DeipAssetIdOf<T>, OptionQuery>;
+
+    /// Positions of the head and the end of [`WipeQueue`].
+    #[pallet::storage]
+    pub(super) type WipeQueueRange<T> = StorageValue<_, (u32, u32), ValueQuery>;
+
+    /// Ids of destroyed assets which can't be reused: holders may claim dividends
+    /// distributed at retained checkpoints of the asset after it's destroyed.
+    #[pallet::storage]
+    pub(super) type DestroyedAssets<T: Config> =
+        StorageMap<_, Blake2_128Concat, DeipAssetIdOf<T>, (), OptionQuery>;
+
+    #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub struct IssuanceSchedule<Balance: Clone + AtLeast32BitUnsigned, BlockNumber> {
+        /// Amount released for minting at the start of each period.
+        pub amount: SerializableAssetBalance<Balance>,
+        pub period: BlockNumber,
+    }
+
+    #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+    pub struct SupplyLimit<Balance: Clone + AtLeast32BitUnsigned, BlockNumber> {
+        pub max_supply: Option<Balance>,
+        pub schedule: Option<IssuanceSchedule<Balance, BlockNumber>>,
+        /// Block since which periods of the schedule are counted.
+        pub start: BlockNumber,
+        /// Total amount minted since the asset creation. Burning doesn't decrease it.
+        pub issued: Balance,
+    }
+
+    /// Limits of minting set on the asset creation.
+    #[pallet::storage]
+    pub(super) type SupplyLimits<T: Config> =
+        StorageMap<_, Blake2_128Concat, DeipAssetIdOf<T>, SupplyLimitOf<T>, OptionQuery>;
+
+    #[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub enum AssetClass {
+        Equity,
+        RevenueShare,
+        Utility,
+    }
+
+    #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct SecurityMetadata<ProjectId, Hash> {
+        pub class: AssetClass,
+        /// Project the asset is issued for.
+        pub project: Option<ProjectId>,
+        /// Hash of the offering document, e.g. a prospectus or a term sheet.
+        pub offering_document: Option<Hash>,
+        pub jurisdiction: Option<Jurisdiction>,
+    }
+
+    #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+    pub struct VersionedSecurityMetadata<ProjectId, Hash, AccountId> {
+        pub version: MetadataVersion,
+        pub metadata: SecurityMetadata<ProjectId, Hash>,
+        /// Issuer of the asset who has attested the version.
+        pub attested_by: Option<AccountId>,
+    }
+
+    /// Latest version of the security metadata of the asset.
+    #[pallet::storage]
+    pub(super) type SecurityMetadataByAsset<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        DeipAssetIdOf<T>,
+        VersionedSecurityMetadataOf<T>,
+        OptionQuery,
+    >;
+
+    /// Previous versions of the security metadata of the asset.
+    #[pallet::storage]
+    pub(super) type SecurityMetadataHistory<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DeipAssetIdOf<T>,
+        Blake2_128Concat,
+        MetadataVersion,
+        VersionedSecurityMetadataOf<T>,
+        OptionQuery,
+    >;
+
+    /// Number of dividend claims of the asset in the block.
+    #[pallet::storage]
+    pub(super) type ClaimsInBlock<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        DeipAssetIdOf<T>,
+        (T::BlockNumber, u32),
+        ValueQuery,
+    >;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T> {
         pub _marker: std::marker::PhantomData<T>,
@@ -355,6 +879,24 @@ pub mod pallet {
             T::AccountId::decode(&mut &entropy[..]).unwrap_or_default()
         }
 
+        pub fn investment(
+            id: &DeipInvestmentIdOf<T>,
+        ) -> Option<Investment<AccountIdOf<T>, DeipAssetIdOf<T>>> {
+            InvestmentMapV1::<T>::get(id)
+        }
+
+        /// Whether `creator` has investments with reserved assets, i.e. not finished yet.
+        pub fn has_investments(creator: &AccountIdOf<T>) -> bool {
+            InvestmentCountByCreator::<T>::get(creator) > 0
+        }
+
+        /// Non-zero balances of DEIP assets of `account`.
+        pub fn holdings(account: &AccountIdOf<T>) -> Vec<(DeipAssetIdOf<T>, T::Balance)> {
+            AssetsByHolder::<T>::iter_key_prefix(account)
+                .map(|asset| (asset, Self::account_balance(account, &asset)))
+                .collect()
+        }
+
         pub fn account_balance(account: &AccountIdOf<T>, asset: &DeipAssetIdOf<T>) -> T::Balance {
             match AssetIdByDeipAssetIdV1::<T>::iter_prefix(*asset).next() {
                 None => Default::default(),
@@ -362,6 +904,54 @@ pub mod pallet {
             }
         }
 
+        /// Id of the asset in `pallet_assets`.
+        pub(crate) fn assets_asset_id(
+            asset: &DeipAssetIdOf<T>,
+        ) -> Result<AssetsAssetIdOf<T>, Error<T>> {
+            AssetIdByDeipAssetIdV1::<T>::iter_prefix(asset)
+                .next()
+                .map(|(id, _)| id)
+                .ok_or(Error::<T>::DeipAssetIdDoesNotExist)
+        }
+
+        /// Details of the asset in `pallet_assets`.
+        pub(crate) fn asset_details(
+            asset: &DeipAssetIdOf<T>,
+        ) -> Option<AssetDetails<AccountIdOf<T>, AssetsBalanceOf<T>, DepositBalanceOf<T>>> {
+            let asset_id = AssetIdByDeipAssetIdV1::<T>::iter_prefix(asset).next()?.0;
+            let prefix = storage_prefix(pallet_assets::Pallet::<T>::name().as_bytes(), b"Asset");
+            let hashed = Blake2_128Concat::hash(&asset_id.encode());
+            let key = [&prefix[..], &hashed[..]].concat();
+            unhashed::get(&key)
+        }
+
+        /// Checks if `who` is the owner or the admin of the asset.
+        pub(crate) fn ensure_asset_team(
+            asset: &DeipAssetIdOf<T>,
+            who: &AccountIdOf<T>,
+        ) -> DispatchResult {
+            let details = Self::asset_details(asset).ok_or(Error::<T>::DeipAssetIdDoesNotExist)?;
+            ensure!(*who == details.owner || *who == details.admin, Error::<T>::NoPermission);
+            Ok(())
+        }
+
+        /// Checks that an asset may be created with the id. Callers which pay
+        /// for the creation in advance should check it first.
+        pub fn ensure_asset_id_available(id: &DeipAssetIdOf<T>) -> Result<(), Error<T>> {
+            ensure!(*id != T::NativeAssetId::get(), Error::<T>::DeipAssetIdIsNative);
+            ensure!(
+                AssetIdByDeipAssetIdV1::<T>::iter_prefix(*id).next().is_none(),
+                Error::<T>::DeipAssetIdExists
+            );
+            ensure!(!DestroyedAssets::<T>::contains_key(id), Error::<T>::DeipAssetIdDestroyed);
+            Ok(())
+        }
+
+        /// Checks if the asset of `pallet_assets` is created via `deip_create`.
+        pub fn is_deip_asset(id: &AssetsAssetIdOf<T>) -> bool {
+            DeipAssetIdByAssetIdV1::<T>::iter_prefix(id).next().is_some()
+        }
+
         pub fn total_supply(asset: &DeipAssetIdOf<T>) -> T::Balance {
             match AssetIdByDeipAssetIdV1::<T>::iter_prefix(*asset).next() {
                 None => Zero::zero(),
@@ -369,33 +959,41 @@ pub mod pallet {
             }
         }
 
+        /// Minimum balance of the asset, zero if the asset doesn't exist.
+        pub fn minimum_balance(asset: &DeipAssetIdOf<T>) -> AssetsBalanceOf<T> {
+            Self::asset_details(asset).map_or_else(Zero::zero, |details| details.min_balance)
+        }
+
         #[transactional]
         pub fn transactionally_transfer(
             from: &AccountIdOf<T>,
             asset: DeipAssetIdOf<T>,
             transfers: &[(AssetsBalanceOf<T>, AccountIdOf<T>)],
-        ) -> Result<(), ()> {
+        ) -> DispatchResult {
             for (amount, to) in transfers {
-                let result = Self::deip_transfer_impl(
+                Self::deip_transfer_impl(
                     RawOrigin::Signed(from.clone()).into(),
                     asset,
                     to.clone(),
                     *amount,
-                );
-                if result.is_err() {
-                    return Err(())
-                }
+                    PolicyCheck::Full,
+                )
+                .map_err(|e| e.error)?;
             }
 
             Ok(())
         }
 
+        /// Reserves `shares` of `account` for the investment `id` raising `asset_to_raise`.
+        /// Reserved assets can't be frozen or destroyed, as well as `asset_to_raise`
+        /// if `lock_asset_to_raise` is set.
         #[transactional]
         pub fn deip_transactionally_reserve(
             account: &T::AccountId,
             id: DeipInvestmentIdOf<T>,
             shares: &[(DeipAssetIdOf<T>, AssetsBalanceOf<T>)],
             asset_to_raise: DeipAssetIdOf<T>,
+            lock_asset_to_raise: bool,
         ) -> Result<(), deip_assets_error::ReserveError<DeipAssetIdOf<T>>> {
             use deip_assets_error::ReserveError;
 
@@ -421,6 +1019,12 @@ pub mod pallet {
                     .next()
                     .ok_or(ReserveError::AssetTransferFailed(*asset))?
                     .0;
+                T::TransferPolicy::ensure_can_send(asset, account, *amount)
+                    .map_err(|_| ReserveError::AssetTransferFailed(*asset))?;
+
+                Self::update_balance_snapshot(asset, account);
+                Self::update_balance_snapshot(asset, &id_account);
+
                 let call = pallet_assets::Call::<T>::transfer {
                     id: asset_id,
                     target: id_source.clone(),
@@ -431,6 +1035,9 @@ pub mod pallet {
                     return Err(ReserveError::AssetTransferFailed(*asset))
                 }
 
+                Self::update_holder(asset, account);
+                Self::update_holder(asset, &id_account);
+
                 assets_to_reserve.push(*asset);
 
                 InvestmentByAssetIdV1::<T>::mutate_exists(*asset, |investments| {
@@ -441,13 +1048,18 @@ pub mod pallet {
                 });
             }
 
-            InvestmentByAssetIdV1::<T>::mutate_exists(asset_to_raise, |investments| {
-                match investments.as_mut() {
-                    None => *investments = Some(vec![id.clone()]),
-                    Some(c) => c.push(id.clone()),
-                };
-            });
+            if lock_asset_to_raise {
+                InvestmentByAssetIdV1::<T>::mutate_exists(asset_to_raise, |investments| {
+                    match investments.as_mut() {
+                        None => *investments = Some(vec![id.clone()]),
+                        Some(c) => c.push(id.clone()),
+                    };
+                });
+            }
 
+            InvestmentCountByCreator::<T>::mutate(account, |count| {
+                *count = count.saturating_add(1)
+            });
             InvestmentMapV1::<T>::insert(
                 id.clone(),
                 Investment {
@@ -470,6 +1082,9 @@ pub mod pallet {
                 Some(i) => i,
                 None => return Err(UnreserveError::NoSuchInvestment),
             };
+            InvestmentCountByCreator::<T>::mutate(&info.creator, |count| {
+                *count = count.saturating_sub(1)
+            });
 
             let deposited =
                 T::Currency::deposit_creating(&info.creator, T::Currency::minimum_balance());
@@ -477,16 +1092,13 @@ pub mod pallet {
             let id_account = Self::investment_key(&id);
 
             for asset_id in info.assets.iter().chain(&[info.asset_id]) {
+                // the asset to raise isn't registered for escrows
                 InvestmentByAssetIdV1::<T>::mutate_exists(*asset_id, |maybe_investments| {
-                    let investments =
-                        maybe_investments.as_mut().expect("checked in transactionally_reserve");
-                    let index = investments
-                        .iter()
-                        .position(|a| *a == id)
-                        .expect("checked in transactionally_reserve");
-                    investments.remove(index);
-                    if investments.is_empty() {
-                        *maybe_investments = None;
+                    if let Some(investments) = maybe_investments.as_mut() {
+                        investments.retain(|a| *a != id);
+                        if investments.is_empty() {
+                            *maybe_investments = None;
+                        }
                     }
                 });
 
@@ -500,6 +1112,7 @@ pub mod pallet {
                     *asset_id,
                     info.creator.clone(),
                     amount,
+                    PolicyCheck::Refund,
                 );
                 if result.is_err() {
                     return Err(UnreserveError::AssetTransferFailed(*asset_id))
@@ -537,6 +1150,7 @@ pub mod pallet {
                 asset,
                 who.clone(),
                 amount,
+                PolicyCheck::Payout,
             );
             if result.is_err() {
                 return Err(UnreserveError::AssetTransferFailed(asset))
@@ -562,8 +1176,20 @@ pub mod pallet {
                 .ok_or(UnreserveError::AssetTransferFailed(info.asset_id))?
                 .0;
 
+            T::TransferPolicy::ensure_can_send(&info.asset_id, who, amount)
+                .map_err(|_| UnreserveError::AssetTransferFailed(info.asset_id))?;
+            // shares are distributed from the reserve after the investment is finished
+            for share in &info.assets {
+                T::TransferPolicy::ensure_can_receive(share, who, Zero::zero())
+                    .map_err(|_| UnreserveError::AssetTransferFailed(*share))?;
+            }
+
             let id_account = Self::investment_key(&id);
-            let id_source = <T::Lookup as StaticLookup>::unlookup(id_account);
+
+            Self::update_balance_snapshot(&info.asset_id, who);
+            Self::update_balance_snapshot(&info.asset_id, &id_account);
+
+            let id_source = <T::Lookup as StaticLookup>::unlookup(id_account.clone());
 
             let call =
                 pallet_assets::Call::<T>::transfer { id: asset_id, target: id_source, amount };
@@ -572,24 +1198,45 @@ pub mod pallet {
                 return Err(UnreserveError::AssetTransferFailed(info.asset_id))
             }
 
+            Self::update_holder(&info.asset_id, who);
+            Self::update_holder(&info.asset_id, &id_account);
+
             Ok(())
         }
 
         // stores `to` in the map of FT-balances if the asset tokenizes some active
-        fn deip_transfer_impl(
+        pub(crate) fn deip_transfer_impl(
             from: OriginFor<T>,
             id: DeipAssetIdOf<T>,
             to: AccountIdOf<T>,
             amount: AssetsBalanceOf<T>,
+            check: PolicyCheck,
         ) -> DispatchResultWithPostInfo {
-            let target_source = <T::Lookup as StaticLookup>::unlookup(to);
             let asset_id = AssetIdByDeipAssetIdV1::<T>::iter_prefix(id)
                 .next()
                 .ok_or(Error::<T>::DeipAssetIdDoesNotExist)?
                 .0;
+
+            let who = ensure_signed(from.clone())?;
+            Self::ensure_not_destroying(&id)?;
+            if matches!(check, PolicyCheck::Full | PolicyCheck::Deposit) {
+                T::TransferPolicy::ensure_can_send(&id, &who, amount)?;
+            }
+            if matches!(check, PolicyCheck::Full | PolicyCheck::Payout) {
+                T::TransferPolicy::ensure_can_receive(&id, &to, amount)?;
+            }
+            Self::update_balance_snapshot(&id, &who);
+            Self::update_balance_snapshot(&id, &to);
+
+            let target_source = <T::Lookup as StaticLookup>::unlookup(to.clone());
             let call =
                 pallet_assets::Call::<T>::transfer { id: asset_id, target: target_source, amount };
-            call.dispatch_bypass_filter(from)
+            let post_dispatch_info = call.dispatch_bypass_filter(from)?;
+
+            Self::update_holder(&id, &who);
+            Self::update_holder(&id, &to);
+
+            Ok(post_dispatch_info)
         }
 
         fn deip_create_impl(
@@ -597,11 +1244,11 @@ pub mod pallet {
             id: DeipAssetIdOf<T>,
             admin: T::AccountId,
             min_balance: AssetsBalanceOf<T>,
+            max_supply: Option<AssetsBalanceOf<T>>,
+            schedule: Option<IssuanceScheduleOf<T>>,
         ) -> DispatchResultWithPostInfo {
-            ensure!(
-                AssetIdByDeipAssetIdV1::<T>::iter_prefix(id).next().is_none(),
-                Error::<T>::DeipAssetIdExists
-            );
+            Self::ensure_asset_id_available(&id)?;
+            Self::ensure_supply_limit_valid(&max_supply, &schedule)?;
 
             let asset_id = NextAssetId::<T>::get();
             let next_asset_id =
@@ -615,6 +1262,7 @@ pub mod pallet {
             NextAssetId::<T>::put(next_asset_id);
             AssetIdByDeipAssetIdV1::<T>::insert(id, asset_id, ());
             DeipAssetIdByAssetIdV1::<T>::insert(asset_id, id, ());
+            Self::set_supply_limit(id, max_supply, schedule);
 
             Ok(post_dispatch_info)
         }
@@ -625,18 +1273,29 @@ pub mod pallet {
             beneficiary: T::AccountId,
             amount: AssetsBalanceOf<T>,
         ) -> DispatchResultWithPostInfo {
-            let beneficiary_source = <T::Lookup as StaticLookup>::unlookup(beneficiary);
-
             let asset_id = AssetIdByDeipAssetIdV1::<T>::iter_prefix(id)
                 .next()
                 .ok_or(Error::<T>::DeipAssetIdDoesNotExist)?
                 .0;
+
+            Self::ensure_not_destroying(&id)?;
+            Self::ensure_mintable(&id, amount)?;
+            T::TransferPolicy::ensure_can_receive(&id, &beneficiary, amount)?;
+
+            Self::update_balance_snapshot(&id, &beneficiary);
+
+            let beneficiary_source = <T::Lookup as StaticLookup>::unlookup(beneficiary.clone());
             let call = pallet_assets::Call::<T>::mint {
                 id: asset_id,
                 beneficiary: beneficiary_source,
                 amount,
             };
-            call.dispatch_bypass_filter(origin)
+            let post_dispatch_info = call.dispatch_bypass_filter(origin)?;
+
+            Self::note_issued(&id, amount);
+            Self::update_holder(&id, &beneficiary);
+
+            Ok(post_dispatch_info)
         }
 
         fn deip_set_metadata_impl(
@@ -658,17 +1317,25 @@ pub mod pallet {
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        #[pallet::weight(AssetsWeightInfoOf::<T>::create())]
+        /// Creates the asset.
+        ///
+        /// - `max_supply`: total amount which can ever be minted, can't be changed.
+        /// - `schedule`: amount released for minting each period since the creation.
+        #[pallet::weight(
+            AssetsWeightInfoOf::<T>::create().saturating_add(T::DbWeight::get().writes(1))
+        )]
         pub fn deip_create(
             origin: OriginFor<T>,
             id: DeipAssetIdOf<T>,
             admin: T::DeipAccountId,
             min_balance: AssetsBalanceOf<T>,
+            max_supply: Option<AssetsBalanceOf<T>>,
+            schedule: Option<IssuanceScheduleOf<T>>,
         ) -> DispatchResultWithPostInfo {
-            Self::deip_create_impl(origin, id, admin.into(), min_balance)
+            Self::deip_create_impl(origin, id, admin.into(), min_balance, max_supply, schedule)
         }
 
-        #[pallet::weight((10_000, Pays::No))]
+        #[pallet::weight(Pallet::<T>::destroy_weight(witness))]
         pub fn deip_destroy(
             origin: OriginFor<T>,
             id: DeipAssetIdOf<T>,
@@ -680,7 +1347,33 @@ pub mod pallet {
                 .0;
 
             let call = pallet_assets::Call::<T>::destroy { id: asset_id, witness };
-            call.dispatch_bypass_filter(origin)
+            let post_dispatch_info = call.dispatch_bypass_filter(origin)?;
+
+            Self::clear_asset(&id, &asset_id);
+            Self::deposit_event(Event::<T>::Destroyed(id));
+
+            Ok(post_dispatch_info)
+        }
+
+        /// Starts staged destruction of the asset for which the witness of
+        /// [`deip_destroy`](Self::deip_destroy) can't be provided.
+        ///
+        /// The asset is frozen. After `WipePeriod` its accounts are wiped
+        /// by `MaxWipeAccountsPerBlock` in a block. Then the owner destroys the asset
+        /// with [`deip_destroy`](Self::deip_destroy) and the witness of no accounts.
+        ///
+        /// The origin for this call must be _Signed_ by the owner of the asset.
+        #[pallet::weight(
+            AssetsWeightInfoOf::<T>::freeze_asset()
+                .saturating_add(T::DbWeight::get().reads_writes(5, 3))
+        )]
+        pub fn start_destroy(
+            origin: OriginFor<T>,
+            id: DeipAssetIdOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::start_destroy_impl(who, id)?;
+            Ok(().into())
         }
 
         #[pallet::weight(AssetsWeightInfoOf::<T>::mint())]
@@ -705,9 +1398,16 @@ pub mod pallet {
                 .ok_or(Error::<T>::DeipAssetIdDoesNotExist)?
                 .0;
 
-            let who_source = <T::Lookup as StaticLookup>::unlookup(who.into());
+            let who = who.into();
+            Self::update_balance_snapshot(&id, &who);
+
+            let who_source = <T::Lookup as StaticLookup>::unlookup(who.clone());
             let call = pallet_assets::Call::<T>::burn { id: asset_id, who: who_source, amount };
-            call.dispatch_bypass_filter(origin)
+            let post_dispatch_info = call.dispatch_bypass_filter(origin)?;
+
+            Self::update_holder(&id, &who);
+
+            Ok(post_dispatch_info)
         }
 
         #[pallet::weight(AssetsWeightInfoOf::<T>::transfer())]
@@ -717,7 +1417,89 @@ pub mod pallet {
             target: T::DeipAccountId,
             #[pallet::compact] amount: AssetsBalanceOf<T>,
         ) -> DispatchResultWithPostInfo {
-            Self::deip_transfer_impl(origin, id, target.into(), amount)
+            Self::deip_transfer_impl(origin, id, target.into(), amount, PolicyCheck::Full)
+        }
+
+        /// Approves `delegate` to transfer up to `amount` of the asset
+        /// from the account of the caller.
+        #[pallet::weight(AssetsWeightInfoOf::<T>::approve_transfer())]
+        pub fn deip_approve_transfer(
+            origin: OriginFor<T>,
+            id: DeipAssetIdOf<T>,
+            delegate: T::DeipAccountId,
+            #[pallet::compact] amount: AssetsBalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let asset_id = AssetIdByDeipAssetIdV1::<T>::iter_prefix(id)
+                .next()
+                .ok_or(Error::<T>::DeipAssetIdDoesNotExist)?
+                .0;
+
+            Self::ensure_not_destroying(&id)?;
+
+            let delegate_source = <T::Lookup as StaticLookup>::unlookup(delegate.into());
+            let call = pallet_assets::Call::<T>::approve_transfer {
+                id: asset_id,
+                delegate: delegate_source,
+                amount,
+            };
+            call.dispatch_bypass_filter(origin)
+        }
+
+        #[pallet::weight(AssetsWeightInfoOf::<T>::cancel_approval())]
+        pub fn deip_cancel_approval(
+            origin: OriginFor<T>,
+            id: DeipAssetIdOf<T>,
+            delegate: T::DeipAccountId,
+        ) -> DispatchResultWithPostInfo {
+            let asset_id = AssetIdByDeipAssetIdV1::<T>::iter_prefix(id)
+                .next()
+                .ok_or(Error::<T>::DeipAssetIdDoesNotExist)?
+                .0;
+
+            let delegate_source = <T::Lookup as StaticLookup>::unlookup(delegate.into());
+            let call =
+                pallet_assets::Call::<T>::cancel_approval { id: asset_id, delegate: delegate_source };
+            call.dispatch_bypass_filter(origin)
+        }
+
+        /// Transfers `amount` of the asset from `owner` to `destination`
+        /// within the amount approved to the caller by `owner`.
+        #[pallet::weight(AssetsWeightInfoOf::<T>::transfer_approved())]
+        pub fn deip_transfer_approved(
+            origin: OriginFor<T>,
+            id: DeipAssetIdOf<T>,
+            owner: T::DeipAccountId,
+            destination: T::DeipAccountId,
+            #[pallet::compact] amount: AssetsBalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let asset_id = AssetIdByDeipAssetIdV1::<T>::iter_prefix(id)
+                .next()
+                .ok_or(Error::<T>::DeipAssetIdDoesNotExist)?
+                .0;
+
+            let owner = owner.into();
+            let destination = destination.into();
+            Self::ensure_not_destroying(&id)?;
+            T::TransferPolicy::ensure_can_send(&id, &owner, amount)?;
+            T::TransferPolicy::ensure_can_receive(&id, &destination, amount)?;
+
+            Self::update_balance_snapshot(&id, &owner);
+            Self::update_balance_snapshot(&id, &destination);
+
+            let owner_source = <T::Lookup as StaticLookup>::unlookup(owner.clone());
+            let destination_source = <T::Lookup as StaticLookup>::unlookup(destination.clone());
+            let call = pallet_assets::Call::<T>::transfer_approved {
+                id: asset_id,
+                owner: owner_source,
+                destination: destination_source,
+                amount,
+            };
+            let post_dispatch_info = call.dispatch_bypass_filter(origin)?;
+
+            Self::update_holder(&id, &owner);
+            Self::update_holder(&id, &destination);
+
+            Ok(post_dispatch_info)
         }
 
         #[pallet::weight(AssetsWeightInfoOf::<T>::freeze())]
@@ -836,6 +1618,187 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             Self::deip_set_metadata_impl(origin, id, name, symbol, decimals)
         }
+
+        /// Records a checkpoint of the asset. Balances of holders at the checkpoint
+        /// are available with [`balance_at`](Self::balance_at).
+        ///
+        /// The origin for this call must be _Signed_ by the owner or the admin of the asset.
+        #[pallet::weight(T::DbWeight::get().reads_writes(5, 4))]
+        pub fn checkpoint(
+            origin: OriginFor<T>,
+            asset: DeipAssetIdOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::ensure_asset_team(&asset, &who)?;
+
+            Self::create_checkpoint(asset)?;
+            Ok(().into())
+        }
+
+        /// Distributes `amount` of `payment_asset` to holders of `asset` pro rata
+        /// to their balances at a new checkpoint.
+        ///
+        /// The origin for this call must be _Signed_ by the owner or the admin of the asset.
+        ///
+        /// Holders get their shares with [`claim`](Self::claim). Rounding dust is
+        /// returned after the last claim.
+        #[pallet::weight(
+            AssetsWeightInfoOf::<T>::transfer()
+                .saturating_add(T::DbWeight::get().reads_writes(8, 8))
+        )]
+        #[transactional]
+        pub fn distribute(
+            origin: OriginFor<T>,
+            asset: DeipAssetIdOf<T>,
+            payment_asset: DeipAssetIdOf<T>,
+            #[pallet::compact] amount: AssetsBalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::distribute_impl(who, asset, payment_asset, amount)?;
+            Ok(().into())
+        }
+
+        /// Claims the share of dividends distributed to holders of `asset` at `checkpoint`.
+        ///
+        /// The origin for this call must be _Signed_.
+        ///
+        /// Number of claims of the asset in a block is limited by `MaxDividendClaimsPerBlock`.
+        #[pallet::weight(
+            AssetsWeightInfoOf::<T>::transfer()
+                .saturating_add(T::DbWeight::get().reads_writes(9, 4))
+        )]
+        #[transactional]
+        pub fn claim(
+            origin: OriginFor<T>,
+            asset: DeipAssetIdOf<T>,
+            checkpoint: CheckpointId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::claim_impl(who, asset, checkpoint)?;
+            Ok(().into())
+        }
+
+        /// Returns unclaimed dividends distributed at `checkpoint` to the distributor
+        /// after the checkpoint is no longer retained.
+        ///
+        /// The origin for this call must be _Signed_ by the distributor.
+        ///
+        /// - `claims`: number of claims of the distribution.
+        #[pallet::weight(
+            AssetsWeightInfoOf::<T>::transfer()
+                .saturating_add(T::DbWeight::get().reads_writes(4, 3))
+                .saturating_add(T::DbWeight::get().writes(*claims as Weight))
+        )]
+        #[transactional]
+        pub fn reclaim_dividends(
+            origin: OriginFor<T>,
+            asset: DeipAssetIdOf<T>,
+            checkpoint: CheckpointId,
+            claims: u32,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::reclaim_dividends_impl(who, asset, checkpoint, claims)?;
+            Ok(().into())
+        }
+
+        /// Sets transfer rules of the asset checked by `OnChainTransferPolicy`.
+        /// `None` removes the rules.
+        ///
+        /// The origin for this call must be _Signed_ by the owner or the admin of the asset.
+        #[pallet::weight(T::DbWeight::get().reads_writes(3, 1))]
+        pub fn set_transfer_rules(
+            origin: OriginFor<T>,
+            asset: DeipAssetIdOf<T>,
+            rules: Option<TransferRules>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::ensure_asset_team(&asset, &who)?;
+
+            match rules {
+                None => TransferRulesByAsset::<T>::remove(asset),
+                Some(rules) => {
+                    ensure!(
+                        rules.jurisdictions.len() <= MAX_JURISDICTIONS,
+                        Error::<T>::TooMuchJurisdictions
+                    );
+                    TransferRulesByAsset::<T>::insert(asset, rules);
+                },
+            };
+
+            Self::deposit_event(Event::<T>::TransferRulesUpdated(asset));
+            Ok(().into())
+        }
+
+        /// Allows `who` to receive the asset if its transfer rules require the allowlist.
+        ///
+        /// The origin for this call must be _Signed_ by the owner or the admin of the asset.
+        #[pallet::weight(T::DbWeight::get().reads_writes(3, 1))]
+        pub fn allowlist_add(
+            origin: OriginFor<T>,
+            asset: DeipAssetIdOf<T>,
+            who: T::DeipAccountId,
+            jurisdiction: Option<Jurisdiction>,
+        ) -> DispatchResultWithPostInfo {
+            let account = ensure_signed(origin)?;
+            Self::ensure_asset_team(&asset, &account)?;
+
+            let who = who.into();
+            Allowlist::<T>::insert(asset, &who, jurisdiction);
+
+            Self::deposit_event(Event::<T>::AllowlistAdded(asset, who));
+            Ok(().into())
+        }
+
+        /// Removes `who` from the allowlist of the asset. Balance of the account
+        /// isn't affected.
+        ///
+        /// The origin for this call must be _Signed_ by the owner or the admin of the asset.
+        #[pallet::weight(T::DbWeight::get().reads_writes(3, 1))]
+        pub fn allowlist_remove(
+            origin: OriginFor<T>,
+            asset: DeipAssetIdOf<T>,
+            who: T::DeipAccountId,
+        ) -> DispatchResultWithPostInfo {
+            let account = ensure_signed(origin)?;
+            Self::ensure_asset_team(&asset, &account)?;
+
+            let who = who.into();
+            Allowlist::<T>::remove(asset, &who);
+
+            Self::deposit_event(Event::<T>::AllowlistRemoved(asset, who));
+            Ok(().into())
+        }
+
+        /// Sets a new version of the security metadata of the asset.
+        /// The previous version is kept in the history, the attestation is reset.
+        ///
+        /// The origin for this call must be _Signed_ by the owner or the admin of the asset.
+        /// The linked project must belong to the owner or the admin of the asset.
+        #[pallet::weight(T::DbWeight::get().reads_writes(4, 2))]
+        pub fn set_security_metadata(
+            origin: OriginFor<T>,
+            asset: DeipAssetIdOf<T>,
+            metadata: SecurityMetadataOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::set_security_metadata_impl(who, asset, metadata)?;
+            Ok(().into())
+        }
+
+        /// Attests the latest version of the security metadata of the asset.
+        /// `version` must be the latest one so the issuer attests exactly what it has seen.
+        ///
+        /// The origin for this call must be _Signed_ by the issuer of the asset.
+        #[pallet::weight(T::DbWeight::get().reads_writes(3, 1))]
+        pub fn attest_security_metadata(
+            origin: OriginFor<T>,
+            asset: DeipAssetIdOf<T>,
+            version: MetadataVersion,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::attest_security_metadata_impl(who, asset, version)?;
+            Ok(().into())
+        }
     }
 }
 
@@ -0,0 +1,112 @@
+use frame_support::{dispatch::DispatchResult, ensure, traits::UnixTime};
+use sp_runtime::traits::Zero;
+use sp_std::marker::PhantomData;
+
+use crate::{
+    AccountIdOf, Allowlist, AssetsBalanceOf, Config, DeipAssetIdOf, Error, Pallet,
+    TransferRulesByAsset,
+};
+
+/// Compliance rules checked before assets are moved via `deip_transfer`, `deip_mint`,
+/// reserving for investments and other transfers. Transfers from and to internal accounts
+/// of the pallet are checked partially, see `PolicyCheck`.
+pub trait TransferPolicy<AccountId, AssetId, Balance> {
+    /// Checks if `who` may send `amount` of `asset`.
+    fn ensure_can_send(asset: &AssetId, who: &AccountId, amount: Balance) -> DispatchResult;
+
+    /// Checks if `who` may receive `amount` of `asset`.
+    fn ensure_can_receive(asset: &AssetId, who: &AccountId, amount: Balance) -> DispatchResult;
+}
+
+/// Side of the transfer which is checked by [`TransferPolicy`] in `deip_transfer_impl`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PolicyCheck {
+    /// Transfer between accounts, both sides are checked.
+    Full,
+    /// Deposit to an internal account of the pallet (investment reserve, dividends pool).
+    /// Only the sender is checked.
+    Deposit,
+    /// Payout from an internal account. Only the recipient is checked:
+    /// the sender was checked when the assets were deposited.
+    Payout,
+    /// Return of the assets to the account which deposited them to an internal account.
+    /// Not checked so the assets don't get stuck if the rules have changed meanwhile.
+    Refund,
+}
+
+/// Allows everything.
+impl<AccountId, AssetId, Balance> TransferPolicy<AccountId, AssetId, Balance> for () {
+    fn ensure_can_send(_asset: &AssetId, _who: &AccountId, _amount: Balance) -> DispatchResult {
+        Ok(())
+    }
+
+    fn ensure_can_receive(_asset: &AssetId, _who: &AccountId, _amount: Balance) -> DispatchResult {
+        Ok(())
+    }
+}
+
+/// Checks rules configured by the asset team with
+/// [`set_transfer_rules`](crate::Call::set_transfer_rules) and the allowlist of the asset.
+/// Assets without rules are not restricted.
+pub struct OnChainTransferPolicy<T, Time>(PhantomData<(T, Time)>);
+
+impl<T: Config, Time: UnixTime> TransferPolicy<AccountIdOf<T>, DeipAssetIdOf<T>, AssetsBalanceOf<T>>
+    for OnChainTransferPolicy<T, Time>
+{
+    fn ensure_can_send(
+        asset: &DeipAssetIdOf<T>,
+        who: &AccountIdOf<T>,
+        _amount: AssetsBalanceOf<T>,
+    ) -> DispatchResult {
+        let rules = match TransferRulesByAsset::<T>::get(asset) {
+            None => return Ok(()),
+            Some(r) => r,
+        };
+
+        if let Some(until) = rules.lockup_until {
+            // the team distributes the asset during the lockup
+            let is_team = Pallet::<T>::asset_details(asset)
+                .map_or(false, |d| *who == d.owner || *who == d.issuer);
+            ensure!(
+                is_team || Time::now().as_millis() >= until as u128,
+                Error::<T>::TransferLockedUp
+            );
+        }
+
+        Ok(())
+    }
+
+    fn ensure_can_receive(
+        asset: &DeipAssetIdOf<T>,
+        who: &AccountIdOf<T>,
+        _amount: AssetsBalanceOf<T>,
+    ) -> DispatchResult {
+        let rules = match TransferRulesByAsset::<T>::get(asset) {
+            None => return Ok(()),
+            Some(r) => r,
+        };
+
+        let entry = Allowlist::<T>::get(asset, who);
+        if rules.allowlist_only {
+            ensure!(entry.is_some(), Error::<T>::TransferRecipientNotAllowlisted);
+        }
+
+        if !rules.jurisdictions.is_empty() {
+            let allowed = entry
+                .flatten()
+                .map_or(false, |jurisdiction| rules.jurisdictions.contains(&jurisdiction));
+            ensure!(allowed, Error::<T>::TransferJurisdictionNotAllowed);
+        }
+
+        if let Some(max_holders) = rules.max_holders {
+            if Pallet::<T>::account_balance(who, asset).is_zero() {
+                // the number of holders isn't known till they are indexed
+                ensure!(Pallet::<T>::holders_indexed(), Error::<T>::HoldersBeingIndexed);
+                let holders = Pallet::<T>::holder_count(asset);
+                ensure!(holders < max_holders, Error::<T>::TransferMaxHoldersReached);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use deip_projects_info::DeipProjectsInfo;
+use frame_support::{dispatch::DispatchResult, ensure};
+use sp_runtime::traits::One;
+
+use crate::{
+    AccountIdOf, Config, DeipAssetIdOf, Error, Event, MetadataVersion, Pallet,
+    SecurityMetadataByAsset, SecurityMetadataHistory, SecurityMetadataOf,
+    VersionedSecurityMetadata, VersionedSecurityMetadataOf,
+};
+
+impl<T: Config> Pallet<T> {
+    /// Latest version of the security metadata of the asset.
+    pub fn security_metadata(asset: &DeipAssetIdOf<T>) -> Option<VersionedSecurityMetadataOf<T>> {
+        SecurityMetadataByAsset::<T>::get(asset)
+    }
+
+    pub(crate) fn set_security_metadata_impl(
+        who: AccountIdOf<T>,
+        asset: DeipAssetIdOf<T>,
+        metadata: SecurityMetadataOf<T>,
+    ) -> DispatchResult {
+        Self::ensure_not_destroying(&asset)?;
+        let details = Self::asset_details(&asset).ok_or(Error::<T>::DeipAssetIdDoesNotExist)?;
+        ensure!(who == details.owner || who == details.admin, Error::<T>::NoPermission);
+
+        if let Some(ref project) = metadata.project {
+            let team = T::ProjectsInfo::try_get_project_team(project)
+                .ok_or(Error::<T>::ProjectDoesNotExist)?;
+            ensure!(
+                team == details.owner || team == details.admin,
+                Error::<T>::ProjectDoesNotBelongToTeam
+            );
+        }
+
+        let version = match SecurityMetadataByAsset::<T>::take(asset) {
+            None => One::one(),
+            Some(previous) => {
+                let version = previous
+                    .version
+                    .checked_add(One::one())
+                    .ok_or(Error::<T>::SecurityMetadataVersionOverflow)?;
+                SecurityMetadataHistory::<T>::insert(asset, previous.version, previous);
+                version
+            },
+        };
+
+        SecurityMetadataByAsset::<T>::insert(
+            asset,
+            VersionedSecurityMetadata { version, metadata, attested_by: None },
+        );
+
+        Self::deposit_event(Event::<T>::SecurityMetadataSet(asset, version));
+
+        Ok(())
+    }
+
+    pub(crate) fn attest_security_metadata_impl(
+        who: AccountIdOf<T>,
+        asset: DeipAssetIdOf<T>,
+        version: MetadataVersion,
+    ) -> DispatchResult {
+        let details = Self::asset_details(&asset).ok_or(Error::<T>::DeipAssetIdDoesNotExist)?;
+        ensure!(who == details.issuer, Error::<T>::NoPermission);
+
+        SecurityMetadataByAsset::<T>::try_mutate(asset, |maybe_metadata| -> DispatchResult {
+            let metadata = maybe_metadata.as_mut().ok_or(Error::<T>::SecurityMetadataNotFound)?;
+            ensure!(metadata.version == version, Error::<T>::SecurityMetadataVersionMismatch);
+            ensure!(metadata.attested_by.is_none(), Error::<T>::SecurityMetadataAlreadyAttested);
+            metadata.attested_by = Some(who.clone());
+            Ok(())
+        })?;
+
+        Self::deposit_event(Event::<T>::SecurityMetadataAttested(asset, version, who));
+
+        Ok(())
+    }
+}
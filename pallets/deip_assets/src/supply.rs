@@ -0,0 +1,90 @@
+use frame_support::ensure;
+use sp_runtime::{
+    traits::{Bounded, One, Saturating, Zero},
+    SaturatedConversion,
+};
+
+use crate::{
+    AssetsBalanceOf, Config, DeipAssetIdOf, Error, IssuanceScheduleOf, Pallet, SupplyLimit,
+    SupplyLimitOf, SupplyLimits,
+};
+
+impl<T: Config> Pallet<T> {
+    /// Amount of the asset which can be minted at the moment.
+    /// Returns `None` if minting of the asset isn't limited.
+    pub fn remaining_supply(asset: &DeipAssetIdOf<T>) -> Option<AssetsBalanceOf<T>> {
+        SupplyLimits::<T>::get(asset).map(|limit| Self::mintable(&limit))
+    }
+
+    pub(crate) fn ensure_supply_limit_valid(
+        max_supply: &Option<AssetsBalanceOf<T>>,
+        schedule: &Option<IssuanceScheduleOf<T>>,
+    ) -> Result<(), Error<T>> {
+        if let Some(max_supply) = max_supply {
+            ensure!(!max_supply.is_zero(), Error::<T>::SupplyLimitInvalid);
+        }
+        if let Some(schedule) = schedule {
+            ensure!(
+                !schedule.amount.0.is_zero() && !schedule.period.is_zero(),
+                Error::<T>::SupplyLimitInvalid
+            );
+        }
+        Ok(())
+    }
+
+    pub(crate) fn set_supply_limit(
+        asset: DeipAssetIdOf<T>,
+        max_supply: Option<AssetsBalanceOf<T>>,
+        schedule: Option<IssuanceScheduleOf<T>>,
+    ) {
+        if max_supply.is_none() && schedule.is_none() {
+            return
+        }
+
+        SupplyLimits::<T>::insert(
+            asset,
+            SupplyLimit {
+                max_supply,
+                schedule,
+                start: frame_system::Pallet::<T>::block_number(),
+                issued: Zero::zero(),
+            },
+        );
+    }
+
+    pub(crate) fn ensure_mintable(
+        asset: &DeipAssetIdOf<T>,
+        amount: AssetsBalanceOf<T>,
+    ) -> Result<(), Error<T>> {
+        if let Some(limit) = SupplyLimits::<T>::get(asset) {
+            ensure!(amount <= Self::mintable(&limit), Error::<T>::SupplyLimitExceeded);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn note_issued(asset: &DeipAssetIdOf<T>, amount: AssetsBalanceOf<T>) {
+        SupplyLimits::<T>::mutate(asset, |maybe_limit| {
+            if let Some(limit) = maybe_limit.as_mut() {
+                limit.issued = limit.issued.saturating_add(amount);
+            }
+        });
+    }
+
+    fn mintable(limit: &SupplyLimitOf<T>) -> AssetsBalanceOf<T> {
+        // the amount of the first period is released on the creation
+        let released = limit.schedule.as_ref().map(|schedule| {
+            let elapsed = frame_system::Pallet::<T>::block_number().saturating_sub(limit.start);
+            let periods: AssetsBalanceOf<T> =
+                (elapsed / schedule.period).saturated_into::<u128>().saturated_into();
+            schedule.amount.0.saturating_mul(periods.saturating_add(One::one()))
+        });
+
+        let cap = match (limit.max_supply, released) {
+            (Some(max_supply), Some(released)) => max_supply.min(released),
+            (Some(max_supply), None) => max_supply,
+            (None, Some(released)) => released,
+            (None, None) => Bounded::max_value(),
+        };
+        cap.saturating_sub(limit.issued)
+    }
+}
@@ -0,0 +1,372 @@
+use crate::{
+    destroy::DestroyWitness, mock::*, AssetsByHolder, BalanceSnapshots, Distributions,
+    DividendClaims, Error, HolderCountByAsset, HoldersBackfillCursor, HoldersByAsset,
+    HoldingCountByAccount, TransferRules, WipeQueueRange, V3,
+};
+use codec::{Decode, Encode};
+use frame_support::traits::{Get, GetStorageVersion, Hooks};
+use sp_core::H160;
+
+fn payment() -> H160 {
+    asset(0)
+}
+
+fn balance(who: AccountId, id: H160) -> Balance {
+    DeipAssets::account_balance(&who, &id)
+}
+
+#[test]
+fn distribute_requires_asset_team() {
+    new_test_ext().execute_with(|| {
+        create_asset(PROJECT_TEAM, asset(1), &[(ALICE, 1)]);
+        create_asset(BOB, payment(), &[(BOB, 100)]);
+
+        assert_noop!(
+            DeipAssets::distribute(Origin::signed(BOB), asset(1), payment(), 100),
+            Error::<Test>::NoPermission
+        );
+    });
+}
+
+#[test]
+fn claims_are_limited_per_asset_and_dust_is_returned() {
+    new_test_ext().execute_with(|| {
+        create_asset(PROJECT_TEAM, asset(1), &[(ALICE, 1), (BOB, 1), (CHARLIE, 1)]);
+        create_asset(PROJECT_TEAM, asset(2), &[(ALICE, 1)]);
+        create_asset(PROJECT_TEAM, payment(), &[(PROJECT_TEAM, 200)]);
+
+        assert_ok!(DeipAssets::distribute(Origin::signed(PROJECT_TEAM), asset(1), payment(), 100));
+        assert_ok!(DeipAssets::distribute(Origin::signed(PROJECT_TEAM), asset(2), payment(), 10));
+
+        assert_ok!(DeipAssets::claim(Origin::signed(ALICE), asset(1), 1));
+        assert_ok!(DeipAssets::claim(Origin::signed(BOB), asset(1), 1));
+        assert_noop!(
+            DeipAssets::claim(Origin::signed(CHARLIE), asset(1), 1),
+            Error::<Test>::TooManyClaimsInBlock
+        );
+        // the limit of another asset isn't affected
+        assert_ok!(DeipAssets::claim(Origin::signed(ALICE), asset(2), 1));
+
+        System::set_block_number(2);
+        assert_ok!(DeipAssets::claim(Origin::signed(CHARLIE), asset(1), 1));
+        assert_eq!(
+            last_event(),
+            Event::DeipAssets(crate::Event::DividendsReclaimed(asset(1), 1, 1))
+        );
+
+        assert_eq!(balance(ALICE, payment()), 33 + 10);
+        assert_eq!(balance(BOB, payment()), 33);
+        assert_eq!(balance(CHARLIE, payment()), 33);
+        assert_eq!(balance(PROJECT_TEAM, payment()), 200 - 110 + 1);
+        // existential deposits of both pools are returned
+        assert_eq!(Balances::free_balance(PROJECT_TEAM), 1000);
+    });
+}
+
+#[test]
+fn expired_distribution_is_reclaimed() {
+    new_test_ext().execute_with(|| {
+        create_asset(PROJECT_TEAM, asset(1), &[(ALICE, 2), (BOB, 1)]);
+        create_asset(PROJECT_TEAM, payment(), &[(PROJECT_TEAM, 30)]);
+
+        assert_ok!(DeipAssets::distribute(Origin::signed(PROJECT_TEAM), asset(1), payment(), 30));
+        assert_ok!(DeipAssets::claim(Origin::signed(ALICE), asset(1), 1));
+        assert_noop!(
+            DeipAssets::reclaim_dividends(Origin::signed(PROJECT_TEAM), asset(1), 1, 1),
+            Error::<Test>::DistributionNotExpired
+        );
+
+        for _ in 0..2 {
+            assert_ok!(DeipAssets::deip_transfer(Origin::signed(ALICE), asset(1), BOB, 1));
+            assert_ok!(DeipAssets::checkpoint(Origin::signed(PROJECT_TEAM), asset(1)));
+        }
+        assert_ok!(DeipAssets::deip_transfer(Origin::signed(BOB), asset(1), ALICE, 1));
+
+        assert_eq!(DeipAssets::balance_at(&asset(1), &BOB, 1), None);
+        assert_eq!(DeipAssets::balance_at(&asset(1), &BOB, 2), Some(2));
+        assert_eq!(DeipAssets::total_supply_at(&asset(1), 1), None);
+        assert!(BalanceSnapshots::<Test>::get(asset(1), BOB).len() <= 2);
+        assert_noop!(
+            DeipAssets::claim(Origin::signed(BOB), asset(1), 1),
+            Error::<Test>::DistributionNotFound
+        );
+
+        assert_noop!(
+            DeipAssets::reclaim_dividends(Origin::signed(ALICE), asset(1), 1, 1),
+            Error::<Test>::NoPermission
+        );
+        assert_noop!(
+            DeipAssets::reclaim_dividends(Origin::signed(PROJECT_TEAM), asset(1), 1, 0),
+            Error::<Test>::DistributionBadWitness
+        );
+        assert_ok!(DeipAssets::reclaim_dividends(Origin::signed(PROJECT_TEAM), asset(1), 1, 1));
+
+        assert_eq!(balance(PROJECT_TEAM, payment()), 10);
+        assert_eq!(Balances::free_balance(PROJECT_TEAM), 1000);
+        assert!(Distributions::<Test>::get(asset(1), 1).is_none());
+        assert!(DividendClaims::<Test>::iter_prefix((asset(1), 1)).next().is_none());
+    });
+}
+
+#[test]
+fn pinned_checkpoints_dont_evict_latest_ones() {
+    new_test_ext().execute_with(|| {
+        create_asset(PROJECT_TEAM, asset(1), &[(ALICE, 2), (BOB, 1)]);
+        assert_ok!(DeipAssets::checkpoint(Origin::signed(PROJECT_TEAM), asset(1)));
+
+        // checkpoints pinned in one block are shared
+        assert_eq!(DeipAssets::pin_checkpoint(asset(1)), Ok(2));
+        assert_eq!(DeipAssets::pin_checkpoint(asset(1)), Ok(2));
+        System::set_block_number(2);
+        assert_eq!(DeipAssets::pin_checkpoint(asset(1)), Ok(3));
+        System::set_block_number(3);
+        assert_noop!(DeipAssets::pin_checkpoint(asset(1)), Error::<Test>::TooManyPinnedCheckpoints);
+
+        assert_ok!(DeipAssets::deip_transfer(Origin::signed(ALICE), asset(1), BOB, 1));
+        assert_ok!(DeipAssets::checkpoint(Origin::signed(PROJECT_TEAM), asset(1)));
+        assert_ok!(DeipAssets::deip_transfer(Origin::signed(ALICE), asset(1), BOB, 1));
+
+        assert!(DeipAssets::is_checkpoint_retained(&asset(1), 1));
+        assert_eq!(DeipAssets::balance_at(&asset(1), &BOB, 1), Some(1));
+        assert_eq!(DeipAssets::balance_at(&asset(1), &BOB, 3), Some(1));
+        assert_eq!(DeipAssets::balance_at(&asset(1), &BOB, 4), Some(2));
+
+        DeipAssets::unpin_checkpoint(&asset(1), 2);
+        assert_eq!(DeipAssets::balance_at(&asset(1), &BOB, 2), Some(1));
+        DeipAssets::unpin_checkpoint(&asset(1), 2);
+        assert_eq!(DeipAssets::balance_at(&asset(1), &BOB, 2), None);
+        assert_eq!(DeipAssets::total_supply_at(&asset(1), 2), None);
+        assert_eq!(DeipAssets::balance_at(&asset(1), &BOB, 3), Some(1));
+        assert!(BalanceSnapshots::<Test>::get(asset(1), BOB).len() <= 4);
+    });
+}
+
+fn witness(accounts: u32) -> pallet_assets::DestroyWitness {
+    let mirror = DestroyWitness { accounts, sufficients: 0, approvals: 0 };
+    let witness =
+        pallet_assets::DestroyWitness::decode(&mut &mirror.encode()[..]).expect("same layout");
+    assert_eq!(witness.encode(), mirror.encode());
+    witness
+}
+
+#[test]
+fn destroy_witness_layout() {
+    new_test_ext().execute_with(|| {
+        create_asset(PROJECT_TEAM, asset(1), &[(ALICE, 1), (BOB, 1)]);
+
+        assert!(DeipAssets::deip_destroy(Origin::signed(PROJECT_TEAM), asset(1), witness(1))
+            .is_err());
+        assert_ok!(DeipAssets::deip_destroy(Origin::signed(PROJECT_TEAM), asset(1), witness(2)));
+    });
+}
+
+#[test]
+fn staged_destroy_wipes_accounts_in_batches() {
+    new_test_ext().execute_with(|| {
+        create_asset(PROJECT_TEAM, asset(1), &[(ALICE, 1), (BOB, 1), (CHARLIE, 1)]);
+        create_asset(PROJECT_TEAM, payment(), &[(PROJECT_TEAM, 30)]);
+        assert_ok!(DeipAssets::distribute(Origin::signed(PROJECT_TEAM), asset(1), payment(), 30));
+
+        assert_ok!(DeipAssets::start_destroy(Origin::signed(PROJECT_TEAM), asset(1)));
+        assert_noop!(
+            DeipAssets::deip_mint(Origin::signed(PROJECT_TEAM), asset(1), ALICE, 1),
+            Error::<Test>::AssetIsBeingDestroyed
+        );
+
+        DeipAssets::on_initialize(10);
+        assert_eq!(DeipAssets::holder_count(&asset(1)), 3);
+
+        DeipAssets::on_initialize(11);
+        assert_eq!(last_event(), Event::DeipAssets(crate::Event::AccountsWiped(asset(1), 2, 1)));
+        DeipAssets::on_initialize(12);
+        assert_eq!(last_event(), Event::DeipAssets(crate::Event::AccountsWiped(asset(1), 1, 0)));
+        assert_eq!(WipeQueueRange::<Test>::get(), (1, 1));
+
+        assert_ok!(DeipAssets::deip_destroy(Origin::signed(PROJECT_TEAM), asset(1), witness(0)));
+        assert_eq!(last_event(), Event::DeipAssets(crate::Event::Destroyed(asset(1))));
+        assert_noop!(
+            DeipAssets::deip_create(
+                Origin::signed(PROJECT_TEAM),
+                asset(1),
+                PROJECT_TEAM,
+                1,
+                None,
+                None
+            ),
+            Error::<Test>::DeipAssetIdDestroyed
+        );
+
+        // dividends distributed before the destruction are still claimable
+        assert_ok!(DeipAssets::claim(Origin::signed(ALICE), asset(1), 1));
+        assert_eq!(balance(ALICE, payment()), 10);
+    });
+}
+
+#[test]
+fn asset_details_layout() {
+    new_test_ext().execute_with(|| {
+        create_asset(PROJECT_TEAM, asset(1), &[(ALICE, 3), (BOB, 4)]);
+        assert_ok!(DeipAssets::deip_set_team(
+            Origin::signed(PROJECT_TEAM),
+            asset(1),
+            ALICE,
+            BOB,
+            CHARLIE
+        ));
+        assert_ok!(DeipAssets::deip_freeze_asset(Origin::signed(CHARLIE), asset(1)));
+
+        let details = DeipAssets::asset_details(&asset(1)).expect("asset exists");
+        assert_eq!(details.owner, PROJECT_TEAM);
+        assert_eq!(details.issuer, ALICE);
+        assert_eq!(details.admin, BOB);
+        assert_eq!(details.freezer, CHARLIE);
+        assert_eq!(details.supply, 7);
+        assert_eq!(details.deposit, 0);
+        assert_eq!(details.min_balance, 1);
+        assert!(!details.is_sufficient);
+        assert_eq!(details.accounts, 2);
+        assert_eq!(details.sufficients, 0);
+        assert_eq!(details.approvals, 0);
+        assert!(details.is_frozen);
+    });
+}
+
+fn allowlist_only() -> Option<TransferRules> {
+    Some(TransferRules { allowlist_only: true, ..Default::default() })
+}
+
+#[test]
+fn internal_transfers_follow_transfer_rules() {
+    new_test_ext().execute_with(|| {
+        create_asset(PROJECT_TEAM, asset(1), &[(ALICE, 1), (BOB, 1)]);
+        create_asset(PROJECT_TEAM, payment(), &[(PROJECT_TEAM, 10)]);
+        assert_ok!(DeipAssets::allowlist_add(
+            Origin::signed(PROJECT_TEAM),
+            payment(),
+            ALICE,
+            None
+        ));
+        assert_ok!(DeipAssets::set_transfer_rules(
+            Origin::signed(PROJECT_TEAM),
+            payment(),
+            allowlist_only()
+        ));
+
+        assert!(DeipAssets::transactionally_transfer(&PROJECT_TEAM, payment(), &[(1, BOB)])
+            .is_err());
+        assert_ok!(DeipAssets::transactionally_transfer(&PROJECT_TEAM, payment(), &[(1, ALICE)]));
+
+        // the pool isn't in the allowlist
+        assert_ok!(DeipAssets::distribute(Origin::signed(PROJECT_TEAM), asset(1), payment(), 8));
+        assert_ok!(DeipAssets::claim(Origin::signed(ALICE), asset(1), 1));
+        assert_noop!(
+            DeipAssets::claim(Origin::signed(BOB), asset(1), 1),
+            Error::<Test>::TransferRecipientNotAllowlisted
+        );
+        assert_eq!(balance(ALICE, payment()), 1 + 4);
+    });
+}
+
+#[test]
+fn holders_are_backfilled_in_batches() {
+    new_test_ext().execute_with(|| {
+        create_asset(PROJECT_TEAM, asset(1), &[(ALICE, 1), (BOB, 1), (CHARLIE, 1)]);
+        HoldersByAsset::<Test>::remove_prefix(asset(1), None);
+        HolderCountByAsset::<Test>::remove(asset(1));
+        for who in [ALICE, BOB, CHARLIE] {
+            AssetsByHolder::<Test>::remove(who, asset(1));
+            HoldingCountByAccount::<Test>::remove(who);
+        }
+
+        DeipAssets::start_holders_backfill();
+        DeipAssets::backfill_holders(2);
+        assert_eq!(DeipAssets::holder_count(&asset(1)), 2);
+
+        // changes of balances during the backfill are counted once
+        assert_ok!(DeipAssets::deip_transfer(Origin::signed(ALICE), asset(1), BOB, 1));
+        DeipAssets::backfill_holders(2);
+
+        assert_eq!(DeipAssets::holder_count(&asset(1)), 2);
+        assert!(HoldersByAsset::<Test>::contains_key(asset(1), BOB));
+        assert!(HoldersByAsset::<Test>::contains_key(asset(1), CHARLIE));
+        assert_eq!(DeipAssets::holdings(&BOB), vec![(asset(1), 2)]);
+        assert_eq!(DeipAssets::holding_count(&ALICE), 0);
+        assert_eq!(DeipAssets::holding_count(&CHARLIE), 1);
+        assert!(!HoldersBackfillCursor::<Test>::exists());
+        assert_eq!(DeipAssets::on_chain_storage_version(), V3);
+    });
+}
+
+#[test]
+fn capped_assets_reject_new_holders_during_backfill() {
+    new_test_ext().execute_with(|| {
+        create_asset(PROJECT_TEAM, asset(1), &[(ALICE, 2), (BOB, 1)]);
+        let rules = TransferRules { max_holders: Some(3), ..Default::default() };
+        assert_ok!(DeipAssets::set_transfer_rules(
+            Origin::signed(PROJECT_TEAM),
+            asset(1),
+            Some(rules)
+        ));
+
+        DeipAssets::start_holders_backfill();
+        assert_noop!(
+            DeipAssets::deip_transfer(Origin::signed(ALICE), asset(1), CHARLIE, 1),
+            Error::<Test>::HoldersBeingIndexed
+        );
+        // existing holders still receive the asset
+        assert_ok!(DeipAssets::deip_transfer(Origin::signed(ALICE), asset(1), BOB, 1));
+
+        DeipAssets::backfill_holders(10);
+        assert_ok!(DeipAssets::deip_transfer(Origin::signed(BOB), asset(1), CHARLIE, 1));
+    });
+}
+
+#[test]
+fn holdings_and_investments_are_indexed_by_account() {
+    new_test_ext().execute_with(|| {
+        create_asset(PROJECT_TEAM, asset(1), &[(ALICE, 2)]);
+        create_asset(PROJECT_TEAM, asset(2), &[(ALICE, 1)]);
+        assert_eq!(DeipAssets::holding_count(&ALICE), 2);
+
+        assert_ok!(DeipAssets::deip_transfer(Origin::signed(ALICE), asset(2), BOB, 1));
+        assert_eq!(DeipAssets::holdings(&ALICE), vec![(asset(1), 2)]);
+        assert_eq!(DeipAssets::holdings(&BOB), vec![(asset(2), 1)]);
+
+        let id = H160::repeat_byte(7);
+        assert!(!DeipAssets::has_investments(&ALICE));
+        let shares = [(asset(1), 1)];
+        assert!(
+            DeipAssets::deip_transactionally_reserve(&ALICE, id, &shares, payment(), false).is_ok()
+        );
+        assert!(DeipAssets::has_investments(&ALICE));
+        assert!(DeipAssets::transactionally_unreserve(id).is_ok());
+        assert!(!DeipAssets::has_investments(&ALICE));
+    });
+}
+
+#[test]
+fn native_asset_id_is_reserved() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            DeipAssets::deip_create(
+                Origin::signed(PROJECT_TEAM),
+                NativeAssetId::get(),
+                PROJECT_TEAM,
+                1,
+                None,
+                None
+            ),
+            Error::<Test>::DeipAssetIdIsNative
+        );
+        assert!(matches!(
+            DeipAssets::ensure_asset_id_available(&NativeAssetId::get()),
+            Err(Error::<Test>::DeipAssetIdIsNative)
+        ));
+
+        create_asset(PROJECT_TEAM, asset(1), &[]);
+        assert!(matches!(
+            DeipAssets::ensure_asset_id_available(&asset(1)),
+            Err(Error::<Test>::DeipAssetIdExists)
+        ));
+    });
+}
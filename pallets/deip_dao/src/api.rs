@@ -14,6 +14,13 @@ sp_api::decl_runtime_apis! {
         fn get(id: DaoId) -> GetResult<AccountId>;
         fn get_multi(ids: Vec<DaoId>) -> GetMultiResult<AccountId>;
     }
+
+    pub trait DeipDaoBudgetRuntimeApi<AssetId, Balance>
+        where AssetId: Codec, Balance: Codec
+    {
+        /// Remaining budget of `asset`, `None` if spending of the asset isn't limited
+        fn remaining_budget(id: DaoId, asset: AssetId) -> Option<Balance>;
+    }
 }
 
 use super::{Pallet, Config, DaoRepository};
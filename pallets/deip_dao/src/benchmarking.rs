@@ -2,11 +2,23 @@
 
 use super::{dao::*, *};
 use core::convert::TryInto;
+use deip_asset_system::DeipCheckpoints;
 use frame_benchmarking::{account, benchmarks, whitelist_account, whitelisted_caller};
-use frame_support::{ensure, traits::Get};
+use frame_support::{
+    ensure,
+    traits::{Currency, Get},
+};
 use frame_system::{Config as Sys, EventRecord, RawOrigin};
+use sp_runtime::{
+    traits::{Bounded, Hash},
+    Perbill,
+};
 use sp_std::prelude::*;
 
+use crate::budget::{Budget, BudgetOf, DaoSpending, SpendingAssetIdOf, SpendingBalanceOf};
+use crate::dissolution::{DaoDissolution, DissolveWitness};
+use crate::governance::{AssetIdOf, ProposalId, TokenGovernance, TokenGovernanceOf};
+use crate::roles::RoleId;
 use crate::Pallet;
 use frame_support::weights::Weight;
 
@@ -86,6 +98,109 @@ fn replace_authority<T: Config>(dao: &DaoOf<T>) -> AlterAuthority<T::AccountId>
     AlterAuthority::<T::AccountId>::ReplaceAuthority { authority_key, authority }
 }
 
+fn remark_call<T: Config>(len: u32) -> <T as Config>::Call {
+    // the encoded remark takes up to 7 bytes more than its content
+    let remark = vec![0; len.saturating_sub(7) as usize];
+    frame_system::Call::<T>::remark { remark }.into()
+}
+
+fn token_governance<T: Config>(asset: AssetIdOf<T>) -> TokenGovernanceOf<T> {
+    TokenGovernance {
+        asset,
+        quorum: Perbill::from_percent(50),
+        approval: Perbill::from_percent(50),
+        voting_period: 10u32.into(),
+    }
+}
+
+/// Gives tokens of the governing asset to `holder` able to pay deposits.
+fn init_holder<T: Config>(index: u32) -> (T::AccountId, AssetIdOf<T>) {
+    let holder = init_member::<T>(index);
+    let asset = T::Checkpoints::benchmark_mint(&holder, 100u32.into());
+    T::Currency::make_free_balance_be(&holder, DepositBalanceOf::<T>::max_value() / 2u32.into());
+    (holder, asset)
+}
+
+/// Makes `dao` governed by the asset of the returned holder.
+fn govern_dao<T: Config>(dao: &DaoOf<T>) -> T::AccountId {
+    let (holder, asset) = init_holder::<T>(1_000);
+    let origin = RawOrigin::Signed(dao.dao_key().clone()).into();
+    Pallet::<T>::set_token_governance(origin, Some(token_governance::<T>(asset))).unwrap();
+    holder
+}
+
+fn propose<T: Config>(dao: &DaoOf<T>, proposer: &T::AccountId, len: u32) -> ProposalId {
+    let id = ProposalId::repeat_byte(1);
+    let call = Box::new(remark_call::<T>(len));
+    Pallet::<T>::propose(RawOrigin::Signed(proposer.clone()).into(), *dao.id(), id, call)
+        .unwrap();
+    id
+}
+
+/// Defines a role with the benchmark permission.
+fn define_role<T: Config>(dao: &DaoOf<T>) -> RoleId {
+    let role = RoleId::repeat_byte(1);
+    let (permission, _) = T::BenchmarkPermission::get();
+    let origin = RawOrigin::Signed(dao.dao_key().clone()).into();
+    Pallet::<T>::define_role(origin, role, vec![permission]).unwrap();
+    role
+}
+
+/// Budget not reached by the benchmarks.
+fn max_budget<T: Config>() -> BudgetOf<T> {
+    Budget { limit: SpendingBalanceOf::<T>::max_value(), period: 10u32.into() }
+}
+
+/// Asset spent by the call of the benchmark permission.
+fn spent_asset<T: Config>(dao: &DaoOf<T>) -> SpendingAssetIdOf<T> {
+    let (_, call) = T::BenchmarkPermission::get();
+    let (asset, _) = T::Spending::spending(dao.dao_key(), &call).unwrap().pop().unwrap();
+    asset
+}
+
+/// Lets `role` spend assets moved by `call` without reaching its budgets.
+fn budget_role<T: Config>(dao: &DaoOf<T>, role: RoleId, call: &<T as Config>::Call) {
+    for (asset, _) in T::Spending::spending(dao.dao_key(), call).unwrap() {
+        let origin = RawOrigin::Signed(dao.dao_key().clone()).into();
+        Pallet::<T>::set_role_budget(origin, role, asset, Some(max_budget::<T>())).unwrap();
+    }
+}
+
+fn grant_role<T: Config>(dao: &DaoOf<T>, role: RoleId, member: &T::AccountId) {
+    let origin = RawOrigin::Signed(dao.dao_key().clone()).into();
+    Pallet::<T>::grant_role(origin, role, member.clone()).unwrap();
+}
+
+/// Limits spending of the benchmark asset and appoints the returned budget guardian.
+fn guard_budget<T: Config>(dao: &DaoOf<T>) -> T::AccountId {
+    let guardian = init_member::<T>(2_000);
+    let asset = spent_asset::<T>(dao);
+    let origin = RawOrigin::Signed(dao.dao_key().clone());
+    Pallet::<T>::set_budget(origin.clone().into(), asset, Some(max_budget::<T>())).unwrap();
+    Pallet::<T>::set_budget_guardian(origin.into(), *dao.id(), Some(guardian.clone())).unwrap();
+    guardian
+}
+
+/// Lets `dao` pay deposits.
+fn fund_dao<T: Config>(dao: &DaoOf<T>) {
+    let balance = DepositBalanceOf::<T>::max_value() / 2u32.into();
+    T::Currency::make_free_balance_be(dao.dao_key(), balance);
+}
+
+fn invite<T: Config>(dao: &DaoOf<T>, member: &T::AccountId) {
+    let origin = RawOrigin::Signed(dao.dao_key().clone()).into();
+    Pallet::<T>::invite_member(origin, member.clone(), true).unwrap();
+}
+
+fn request_to_join<T: Config>(dao: &DaoOf<T>, member: &T::AccountId) {
+    T::Currency::make_free_balance_be(member, DepositBalanceOf::<T>::max_value() / 2u32.into());
+    Pallet::<T>::request_to_join(RawOrigin::Signed(member.clone()).into(), *dao.id()).unwrap();
+}
+
+fn membership_request_expires<T: Config>() -> T::BlockNumber {
+    frame_system::Pallet::<T>::block_number() + T::MembershipRequestTtl::get()
+}
+
 benchmarks! {
     create {
         let m in 1 .. T::MaxSignatories::get().try_into().unwrap();
@@ -168,4 +283,270 @@ benchmarks! {
         let dao = create_dao::<T>(dao);
         let call = frame_system::Call::<T>::remark{ remark: vec![] }.into();
     }: _(RawOrigin::Signed(dao.authority_key().clone()), dao.id().clone(), Box::new(call))
+
+    set_token_governance {
+        let dao = create_dao::<T>(init_dao::<T>(1));
+        let (_, asset) = init_holder::<T>(1_000);
+        let governance = Some(token_governance::<T>(asset));
+    }: _(RawOrigin::Signed(dao.dao_key().clone()), governance.clone())
+    verify {
+        assert_last_event::<T>(Event::TokenGovernanceSet(*dao.id(), governance).into())
+    }
+
+    propose {
+        let l in 0 .. T::MaxProposalLength::get();
+        let dao = create_dao::<T>(init_dao::<T>(1));
+        let proposer = govern_dao::<T>(&dao);
+        let id = ProposalId::repeat_byte(1);
+        let call = Box::new(remark_call::<T>(l));
+    }: _(RawOrigin::Signed(proposer.clone()), *dao.id(), id, call)
+    verify {
+        assert_last_event::<T>(Event::Proposed(*dao.id(), id, proposer).into())
+    }
+
+    vote {
+        let dao = create_dao::<T>(init_dao::<T>(1));
+        let proposer = govern_dao::<T>(&dao);
+        let (voter, _) = init_holder::<T>(1_001);
+        let id = propose::<T>(&dao, &proposer, T::MaxProposalLength::get());
+    }: _(RawOrigin::Signed(voter.clone()), id, true)
+    verify {
+        assert_last_event::<T>(Event::Voted(id, voter, true, 100u32.into()).into())
+    }
+
+    close_proposal {
+        let dao = create_dao::<T>(init_dao::<T>(1));
+        let proposer = govern_dao::<T>(&dao);
+        let id = propose::<T>(&dao, &proposer, T::MaxProposalLength::get());
+        Pallet::<T>::vote(RawOrigin::Signed(proposer.clone()).into(), id, true).unwrap();
+    }: _(RawOrigin::Signed(proposer), id, Weight::max_value() / 2)
+    verify {
+        assert_last_event::<T>(Event::ProposalExecuted(id, Ok(())).into())
+    }
+
+    define_role {
+        let p in 0 .. T::MaxRolePermissions::get() as u32;
+        let dao = create_dao::<T>(init_dao::<T>(1));
+        let (permission, _) = T::BenchmarkPermission::get();
+        let role = RoleId::repeat_byte(1);
+    }: _(RawOrigin::Signed(dao.dao_key().clone()), role, vec![permission; p as usize])
+    verify {
+        assert_last_event::<T>(Event::RoleDefined(*dao.id(), role).into())
+    }
+
+    remove_role {
+        let dao = create_dao::<T>(init_dao::<T>(T::MaxSignatories::get()));
+        let role = define_role::<T>(&dao);
+        let (_, call) = T::BenchmarkPermission::get();
+        budget_role::<T>(&dao, role, &call);
+        for member in dao.authority().signatories.iter() {
+            grant_role::<T>(&dao, role, member);
+        }
+    }: _(RawOrigin::Signed(dao.dao_key().clone()), role)
+    verify {
+        assert_last_event::<T>(Event::RoleRemoved(*dao.id(), role).into())
+    }
+
+    grant_role {
+        let dao = create_dao::<T>(init_dao::<T>(T::MaxSignatories::get()));
+        let role = define_role::<T>(&dao);
+        let member = dao.authority().signatories.last().unwrap().clone();
+    }: _(RawOrigin::Signed(dao.dao_key().clone()), role, member.clone())
+    verify {
+        assert_last_event::<T>(Event::RoleGranted(*dao.id(), role, member).into())
+    }
+
+    revoke_role {
+        let dao = create_dao::<T>(init_dao::<T>(T::MaxSignatories::get()));
+        let role = define_role::<T>(&dao);
+        let member = dao.authority().signatories.last().unwrap().clone();
+        grant_role::<T>(&dao, role, &member);
+    }: _(RawOrigin::Signed(dao.dao_key().clone()), role, member.clone())
+    verify {
+        assert_last_event::<T>(Event::RoleRevoked(*dao.id(), role, member).into())
+    }
+
+    on_behalf_as_role {
+        let dao = create_dao::<T>(init_dao::<T>(T::MaxSignatories::get()));
+        let role = define_role::<T>(&dao);
+        let (_, call) = T::BenchmarkPermission::get();
+        budget_role::<T>(&dao, role, &call);
+        let member = dao.authority().signatories.last().unwrap().clone();
+        grant_role::<T>(&dao, role, &member);
+        let balance = DepositBalanceOf::<T>::max_value() / 2u32.into();
+        T::Currency::make_free_balance_be(dao.dao_key(), balance);
+    }: _(RawOrigin::Signed(member), *dao.id(), role, Box::new(call))
+
+    set_role_budget {
+        let dao = create_dao::<T>(init_dao::<T>(1));
+        let role = define_role::<T>(&dao);
+        let asset = spent_asset::<T>(&dao);
+        let budget = Some(max_budget::<T>());
+    }: _(RawOrigin::Signed(dao.dao_key().clone()), role, asset.clone(), budget.clone())
+    verify {
+        assert_last_event::<T>(Event::RoleBudgetSet(*dao.id(), role, asset, budget).into())
+    }
+
+    set_budget {
+        let dao = create_dao::<T>(init_dao::<T>(1));
+        guard_budget::<T>(&dao);
+        let asset = spent_asset::<T>(&dao);
+        let budget = Some(max_budget::<T>());
+    }: _(RawOrigin::Signed(dao.dao_key().clone()), asset.clone(), budget.clone())
+    verify {
+        assert_last_event::<T>(Event::BudgetSet(*dao.id(), asset, budget).into())
+    }
+
+    set_budget_guardian {
+        let dao = create_dao::<T>(init_dao::<T>(1));
+        let guardian = Some(init_member::<T>(2_000));
+    }: _(RawOrigin::Signed(dao.dao_key().clone()), *dao.id(), guardian.clone())
+    verify {
+        assert_last_event::<T>(Event::BudgetGuardianSet(*dao.id(), guardian).into())
+    }
+
+    approve_over_budget {
+        let dao = create_dao::<T>(init_dao::<T>(1));
+        let guardian = guard_budget::<T>(&dao);
+        let (_, call) = T::BenchmarkPermission::get();
+        let call_hash = T::Hashing::hash_of(&call);
+    }: _(RawOrigin::Signed(guardian), *dao.id(), call_hash)
+    verify {
+        assert_last_event::<T>(Event::OverBudgetApproved(*dao.id(), call_hash).into())
+    }
+
+    on_behalf_over_budget {
+        let dao = create_dao::<T>(init_dao::<T>(1));
+        let guardian = guard_budget::<T>(&dao);
+        let (_, call) = T::BenchmarkPermission::get();
+        let origin = RawOrigin::Signed(guardian).into();
+        Pallet::<T>::approve_over_budget(origin, *dao.id(), T::Hashing::hash_of(&call)).unwrap();
+        let balance = DepositBalanceOf::<T>::max_value() / 2u32.into();
+        T::Currency::make_free_balance_be(dao.dao_key(), balance);
+    }: _(RawOrigin::Signed(dao.authority_key().clone()), *dao.id(), Box::new(call))
+    verify {
+        assert_last_event::<T>(Event::BudgetOverridden(*dao.id()).into())
+    }
+
+    dissolve {
+        let r in 0 .. 100;
+        let h in 0 .. 50;
+        let m in 0 .. T::MaxMembershipRequests::get();
+        let dao = create_dao::<T>(init_dao::<T>(T::MaxSignatories::get()));
+        let (permission, call) = T::BenchmarkPermission::get();
+        let origin = RawOrigin::Signed(dao.dao_key().clone());
+        for i in 0 .. r {
+            let role = RoleId::from_low_u64_be(i.into());
+            let permissions = vec![permission.clone()];
+            Pallet::<T>::define_role(origin.clone().into(), role, permissions).unwrap();
+            budget_role::<T>(&dao, role, &call);
+            for member in dao.authority().signatories.iter() {
+                grant_role::<T>(&dao, role, member);
+            }
+        }
+        T::Dissolution::setup_holdings(dao.dao_key(), h);
+        fund_dao::<T>(&dao);
+        for i in 0 .. m {
+            let member = init_member::<T>(3_000 + i);
+            if i % 2 == 0 {
+                invite::<T>(&dao, &member);
+            } else {
+                request_to_join::<T>(&dao, &member);
+            }
+        }
+        let beneficiary = init_member::<T>(5_000);
+        let witness = DissolveWitness { roles: r, holdings: h, membership_requests: m };
+    }: _(origin, beneficiary.clone(), witness)
+    verify {
+        assert_last_event::<T>(Event::DaoDissolved(*dao.id(), beneficiary).into())
+    }
+
+    invite_member {
+        let dao = create_dao::<T>(init_dao::<T>(1));
+        fund_dao::<T>(&dao);
+        let member = init_member::<T>(3_000);
+        // the previous invitation is replaced
+        invite::<T>(&dao, &member);
+        let expires = membership_request_expires::<T>();
+    }: _(RawOrigin::Signed(dao.dao_key().clone()), member.clone(), true)
+    verify {
+        assert_last_event::<T>(Event::MemberInvited(*dao.id(), member, expires).into())
+    }
+
+    accept_invitation {
+        let dao = create_dao::<T>(init_dao::<T>(T::MaxSignatories::get() - 1));
+        fund_dao::<T>(&dao);
+        let member = init_member::<T>(3_000);
+        invite::<T>(&dao, &member);
+        request_to_join::<T>(&dao, &member);
+    }: _(RawOrigin::Signed(member.clone()), *dao.id())
+    verify {
+        assert_last_event::<T>(Event::MemberJoined(*dao.id(), member).into())
+    }
+
+    reject_invitation {
+        let dao = create_dao::<T>(init_dao::<T>(1));
+        fund_dao::<T>(&dao);
+        let member = init_member::<T>(3_000);
+        invite::<T>(&dao, &member);
+    }: _(RawOrigin::Signed(member.clone()), *dao.id())
+    verify {
+        assert_last_event::<T>(Event::InvitationRejected(*dao.id(), member).into())
+    }
+
+    request_to_join {
+        let dao = create_dao::<T>(init_dao::<T>(T::MaxSignatories::get()));
+        let member = init_member::<T>(3_000);
+        // the expired request is replaced
+        request_to_join::<T>(&dao, &member);
+        let expired = membership_request_expires::<T>() + 1u32.into();
+        frame_system::Pallet::<T>::set_block_number(expired);
+        let expires = membership_request_expires::<T>();
+    }: _(RawOrigin::Signed(member.clone()), *dao.id())
+    verify {
+        assert_last_event::<T>(Event::JoinRequested(*dao.id(), member, expires).into())
+    }
+
+    accept_join_request {
+        let dao = create_dao::<T>(init_dao::<T>(T::MaxSignatories::get() - 1));
+        fund_dao::<T>(&dao);
+        let member = init_member::<T>(3_000);
+        invite::<T>(&dao, &member);
+        request_to_join::<T>(&dao, &member);
+    }: _(RawOrigin::Signed(dao.dao_key().clone()), member.clone(), true)
+    verify {
+        assert_last_event::<T>(Event::MemberJoined(*dao.id(), member).into())
+    }
+
+    reject_join_request {
+        let dao = create_dao::<T>(init_dao::<T>(1));
+        let member = init_member::<T>(3_000);
+        request_to_join::<T>(&dao, &member);
+    }: _(RawOrigin::Signed(dao.dao_key().clone()), member.clone())
+    verify {
+        assert_last_event::<T>(Event::JoinRequestRejected(*dao.id(), member).into())
+    }
+
+    prune_membership_requests {
+        let dao = create_dao::<T>(init_dao::<T>(1));
+        fund_dao::<T>(&dao);
+        let member = init_member::<T>(3_000);
+        invite::<T>(&dao, &member);
+        request_to_join::<T>(&dao, &member);
+        let expired = membership_request_expires::<T>() + 1u32.into();
+        frame_system::Pallet::<T>::set_block_number(expired);
+        let caller: T::AccountId = whitelisted_caller();
+    }: _(RawOrigin::Signed(caller), *dao.id(), member.clone())
+    verify {
+        assert_last_event::<T>(Event::JoinRequestExpired(*dao.id(), member).into())
+    }
+
+    consent_to_nesting {
+        let dao = create_dao::<T>(init_dao::<T>(1));
+        let parent = Some(DaoId::repeat_byte(2));
+        let origin = RawOrigin::Signed(dao.dao_key().clone());
+    }: _(origin, parent)
+    verify {
+        assert_last_event::<T>(Event::NestingConsented(*dao.id(), parent).into())
+    }
 }
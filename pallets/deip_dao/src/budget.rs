@@ -0,0 +1,349 @@
+//! Budgets of DAO treasuries.
+//!
+//! A budget caps the amount of an asset the DAO may spend within a period.
+//! Calls dispatched on behalf of the DAO are charged by the funds they move
+//! out of its own key as reported by the runtime. Calls moving funds the
+//! runtime can't measure are rejected once the DAO has any budget.
+//!
+//! Calls exceeding the budgets are rejected unless the authority overrides
+//! them via `on_behalf_over_budget`.
+//!
+//! A DAO may appoint a budget guardian. While it's appointed, the DAO may only
+//! add or tighten its budgets, loosening them is up to the guardian, and
+//! the authority may only override budgets for calls the guardian has approved.
+//!
+//! Calls dispatched by a role are charged by budgets of the role as well.
+//! A role may spend only assets it has budgets of, and the authority can't
+//! override them.
+
+use codec::{Decode, Encode};
+use frame_support::{
+    dispatch::{DispatchResult, DispatchResultWithPostInfo},
+    ensure,
+    storage::{with_transaction, TransactionOutcome},
+    Parameter, RuntimeDebug,
+};
+use sp_runtime::traits::Hash;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::traits::{AtLeast32BitUnsigned, Saturating, Zero};
+use sp_std::prelude::*;
+
+use crate::{
+    dao::{load_dao, DaoId, LoadBy},
+    roles::RoleId,
+    BudgetGuardians, BudgetRepository, BudgetSpent, Config, Error, Event, OverBudgetApprovals,
+    Pallet, RoleBudgetSpent, RoleBudgets, RoleRepository,
+};
+
+/// Funds moved by calls, provided by the runtime.
+pub trait DaoSpending<AccountId, Call> {
+    type AssetId: Parameter;
+    type Balance: Parameter + AtLeast32BitUnsigned + Copy;
+
+    /// Amounts of assets `call` moves out of `spender` account,
+    /// `None` if the call may move funds which can't be measured.
+    fn spending(spender: &AccountId, call: &Call) -> Option<Vec<(Self::AssetId, Self::Balance)>>;
+}
+
+pub type SpendingAssetIdOf<T> = <<T as Config>::Spending as DaoSpending<
+    <T as frame_system::Config>::AccountId,
+    <T as Config>::Call,
+>>::AssetId;
+pub type SpendingBalanceOf<T> = <<T as Config>::Spending as DaoSpending<
+    <T as frame_system::Config>::AccountId,
+    <T as Config>::Call,
+>>::Balance;
+
+pub type BudgetOf<T> = Budget<SpendingBalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+pub type SpentOf<T> = Spent<SpendingBalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
+/// Limit of spending of an asset per period.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Budget<Balance, BlockNumber> {
+    pub limit: Balance,
+    /// Length of the period in blocks
+    pub period: BlockNumber,
+}
+
+/// Amount spent within the period starting at `since`.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct Spent<Balance, BlockNumber> {
+    pub since: BlockNumber,
+    pub amount: Balance,
+}
+
+impl<T: Config> Pallet<T> {
+    pub fn budget(dao: &DaoId, asset: &SpendingAssetIdOf<T>) -> Option<BudgetOf<T>> {
+        BudgetRepository::<T>::get(dao, asset)
+    }
+
+    /// Amount of `asset` the DAO may spend till the end of the current period,
+    /// `None` if its spending isn't limited.
+    pub fn remaining_budget(
+        dao: &DaoId,
+        asset: &SpendingAssetIdOf<T>,
+    ) -> Option<SpendingBalanceOf<T>> {
+        let budget = BudgetRepository::<T>::get(dao, asset)?;
+        let spent = Self::current_spent(&budget, BudgetSpent::<T>::get(dao, asset));
+        Some(budget.limit.saturating_sub(spent.amount))
+    }
+
+    pub fn role_budget(
+        dao: &DaoId,
+        role: &RoleId,
+        asset: &SpendingAssetIdOf<T>,
+    ) -> Option<BudgetOf<T>> {
+        RoleBudgets::<T>::get((dao, role), asset)
+    }
+
+    /// Amount of `asset` the role may spend till the end of the current period,
+    /// `None` if the role has no budget of the asset, i.e. may not spend it.
+    pub fn remaining_role_budget(
+        dao: &DaoId,
+        role: &RoleId,
+        asset: &SpendingAssetIdOf<T>,
+    ) -> Option<SpendingBalanceOf<T>> {
+        let budget = RoleBudgets::<T>::get((dao, role), asset)?;
+        let spent = Self::current_spent(&budget, RoleBudgetSpent::<T>::get((dao, role), asset));
+        Some(budget.limit.saturating_sub(spent.amount))
+    }
+
+    /// `spent` if it belongs to the current period of `budget`, nothing spent otherwise.
+    fn current_spent(budget: &BudgetOf<T>, spent: Option<SpentOf<T>>) -> SpentOf<T> {
+        let now = frame_system::Pallet::<T>::block_number();
+        let since = now - now % budget.period;
+        match spent {
+            Some(spent) if spent.since == since => spent,
+            _ => Spent { since, amount: Zero::zero() },
+        }
+    }
+
+    /// Charges budgets of the DAO and of `role` by funds moved by `call`.
+    /// Budgets of the DAO aren't checked if `overridden`.
+    ///
+    /// Calls moving unmeasured funds are denied to roles and,
+    /// unless `overridden`, to DAOs having any budget.
+    fn spend_budget(
+        dao: &DaoId,
+        role: Option<&RoleId>,
+        dao_key: &T::AccountId,
+        call: &<T as Config>::Call,
+        overridden: bool,
+    ) -> DispatchResult {
+        let spending = match T::Spending::spending(dao_key, call) {
+            Some(spending) => spending,
+            None => {
+                ensure!(role.is_none(), Error::<T>::UnmeasuredSpending);
+                ensure!(
+                    overridden || BudgetRepository::<T>::iter_prefix(dao).next().is_none(),
+                    Error::<T>::UnmeasuredSpending
+                );
+                return Ok(())
+            },
+        };
+        for (asset, amount) in spending {
+            if amount.is_zero() {
+                continue
+            }
+            if let Some(role) = role {
+                let key = (*dao, *role);
+                let budget = RoleBudgets::<T>::get(key, &asset)
+                    .ok_or(Error::<T>::RoleBudgetExceeded)?;
+                let mut spent =
+                    Self::current_spent(&budget, RoleBudgetSpent::<T>::get(key, &asset));
+                spent.amount = spent.amount.saturating_add(amount);
+                ensure!(spent.amount <= budget.limit, Error::<T>::RoleBudgetExceeded);
+                RoleBudgetSpent::<T>::insert(key, &asset, spent);
+            }
+            let budget = match BudgetRepository::<T>::get(dao, &asset) {
+                Some(budget) => budget,
+                None => continue,
+            };
+            let mut spent = Self::current_spent(&budget, BudgetSpent::<T>::get(dao, &asset));
+            spent.amount = spent.amount.saturating_add(amount);
+            ensure!(overridden || spent.amount <= budget.limit, Error::<T>::BudgetExceeded);
+            BudgetSpent::<T>::insert(dao, &asset, spent);
+        }
+        Ok(())
+    }
+
+    /// Dispatches `call` on behalf of the DAO charging its budgets.
+    /// Nothing is charged if the call fails.
+    pub(crate) fn dispatch_within_budget(
+        dao: &DaoId,
+        dao_key: T::AccountId,
+        call: <T as Config>::Call,
+        overridden: bool,
+    ) -> DispatchResultWithPostInfo {
+        Self::dispatch_charged(dao, None, dao_key, call, overridden)
+    }
+
+    /// Dispatches `call` on behalf of the DAO charging budgets of the DAO and of `role`.
+    /// Nothing is charged if the call fails.
+    pub(crate) fn dispatch_within_role_budget(
+        dao: &DaoId,
+        role: &RoleId,
+        dao_key: T::AccountId,
+        call: <T as Config>::Call,
+    ) -> DispatchResultWithPostInfo {
+        Self::dispatch_charged(dao, Some(role), dao_key, call, false)
+    }
+
+    fn dispatch_charged(
+        dao: &DaoId,
+        role: Option<&RoleId>,
+        dao_key: T::AccountId,
+        call: <T as Config>::Call,
+        overridden: bool,
+    ) -> DispatchResultWithPostInfo {
+        with_transaction(|| {
+            let result = Self::spend_budget(dao, role, &dao_key, &call, overridden)
+                .map_err(Into::into)
+                .and_then(|_| Self::dispatch_on_behalf(dao_key, call));
+            if result.is_ok() {
+                TransactionOutcome::Commit(result)
+            } else {
+                TransactionOutcome::Rollback(result)
+            }
+        })
+    }
+
+    /// Dispatches `call` regardless of budgets of the DAO. If the DAO has a budget
+    /// guardian, the call must be approved by it and the approval is consumed
+    /// if the call succeeds.
+    pub(crate) fn dispatch_over_budget(
+        dao: &DaoId,
+        dao_key: T::AccountId,
+        call: <T as Config>::Call,
+    ) -> DispatchResultWithPostInfo {
+        let guarded = BudgetGuardians::<T>::contains_key(dao);
+        ensure!(
+            !guarded || OverBudgetApprovals::<T>::get(dao) == Some(T::Hashing::hash_of(&call)),
+            Error::<T>::OverBudgetNotApproved
+        );
+        let result = Self::dispatch_within_budget(dao, dao_key, call, true)?;
+        if guarded {
+            OverBudgetApprovals::<T>::remove(dao);
+        }
+        Ok(result)
+    }
+
+    pub(crate) fn set_budget_impl(
+        who: T::AccountId,
+        asset: SpendingAssetIdOf<T>,
+        budget: Option<BudgetOf<T>>,
+    ) -> DispatchResult {
+        let dao = load_dao::<T>(LoadBy::DaoKey { dao_key: &who })?;
+        let id = *dao.id();
+
+        if BudgetGuardians::<T>::contains_key(id) {
+            let tightened = match (BudgetRepository::<T>::get(id, &asset), &budget) {
+                (None, Some(_)) => true,
+                (Some(old), Some(new)) => new.period == old.period && new.limit <= old.limit,
+                _ => false,
+            };
+            ensure!(tightened, Error::<T>::BudgetGuarded);
+        }
+
+        Self::put_budget(id, asset, budget)
+    }
+
+    pub(crate) fn set_budget_by_guardian_impl(
+        who: T::AccountId,
+        name: DaoId,
+        asset: SpendingAssetIdOf<T>,
+        budget: Option<BudgetOf<T>>,
+    ) -> DispatchResult {
+        ensure!(BudgetGuardians::<T>::get(name) == Some(who), Error::<T>::Forbidden);
+        Self::put_budget(name, asset, budget)
+    }
+
+    fn put_budget(
+        id: DaoId,
+        asset: SpendingAssetIdOf<T>,
+        budget: Option<BudgetOf<T>>,
+    ) -> DispatchResult {
+        match budget {
+            Some(ref budget) => {
+                ensure!(!budget.period.is_zero(), Error::<T>::ZeroBudgetPeriod);
+                BudgetRepository::<T>::insert(id, &asset, budget);
+            },
+            None => {
+                BudgetRepository::<T>::remove(id, &asset);
+                BudgetSpent::<T>::remove(id, &asset);
+            },
+        }
+
+        Self::deposit_event(Event::<T>::BudgetSet(id, asset, budget));
+
+        Ok(())
+    }
+
+    /// Appoints `guardian` or, if `None`, dismisses the current one.
+    /// The guardian is replaced by itself, the first one is appointed by the DAO.
+    pub(crate) fn set_budget_guardian_impl(
+        who: T::AccountId,
+        name: DaoId,
+        guardian: Option<T::AccountId>,
+    ) -> DispatchResult {
+        match BudgetGuardians::<T>::get(name) {
+            Some(current) => ensure!(who == current, Error::<T>::Forbidden),
+            None => {
+                let dao = load_dao::<T>(LoadBy::DaoKey { dao_key: &who })?;
+                ensure!(*dao.id() == name, Error::<T>::Forbidden);
+            },
+        }
+
+        match guardian {
+            Some(ref guardian) => BudgetGuardians::<T>::insert(name, guardian),
+            None => {
+                BudgetGuardians::<T>::remove(name);
+                OverBudgetApprovals::<T>::remove(name);
+            },
+        }
+
+        Self::deposit_event(Event::<T>::BudgetGuardianSet(name, guardian));
+
+        Ok(())
+    }
+
+    pub(crate) fn approve_over_budget_impl(
+        who: T::AccountId,
+        name: DaoId,
+        call_hash: T::Hash,
+    ) -> DispatchResult {
+        ensure!(BudgetGuardians::<T>::get(name) == Some(who), Error::<T>::Forbidden);
+        OverBudgetApprovals::<T>::insert(name, call_hash);
+        Self::deposit_event(Event::<T>::OverBudgetApproved(name, call_hash));
+        Ok(())
+    }
+
+    pub(crate) fn set_role_budget_impl(
+        who: T::AccountId,
+        role: RoleId,
+        asset: SpendingAssetIdOf<T>,
+        budget: Option<BudgetOf<T>>,
+    ) -> DispatchResult {
+        let dao = load_dao::<T>(LoadBy::DaoKey { dao_key: &who })?;
+        let id = *dao.id();
+        ensure!(RoleRepository::<T>::contains_key(id, role), Error::<T>::RoleNotFound);
+
+        match budget {
+            Some(ref budget) => {
+                ensure!(!budget.period.is_zero(), Error::<T>::ZeroBudgetPeriod);
+                RoleBudgets::<T>::insert((id, role), &asset, budget);
+            },
+            None => {
+                RoleBudgets::<T>::remove((id, role), &asset);
+                RoleBudgetSpent::<T>::remove((id, role), &asset);
+            },
+        }
+
+        Self::deposit_event(Event::<T>::RoleBudgetSet(id, role, asset, budget));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+//! Dissolution of DAOs.
+//!
+//! A DAO may be dissolved when nothing depends on its own key anymore,
+//! its balances are swept to a beneficiary and its records are removed.
+//! Deposits of pending invitations and join requests are refunded before the sweep.
+//! The caller witnesses the number of roles, holdings and pending invitations
+//! and join requests of the DAO to bound the weight of the call.
+//! The name of a dissolved DAO is never reused. A DAO nested in authorities
+//! of other DAOs isn't dissolved, so they aren't left with a dead signatory.
+
+use codec::{Decode, Encode};
+use frame_support::{
+    dispatch::{DispatchError, DispatchResult},
+    ensure, RuntimeDebug,
+};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_std::prelude::*;
+
+use crate::{
+    dao::{load_dao, LoadBy},
+    storage_ops::StorageOps,
+    AuthorityDepth, BudgetGuardians, BudgetRepository, BudgetSpent, Config, DaoLookup,
+    DissolvedDaos, Error, Event, Invitations, JoinRequests, MembershipRequestCountByDao,
+    NestingConsents, OverBudgetApprovals, Pallet, ParentDaos, ProposalCountByDao, RoleBudgetSpent,
+    RoleBudgets, RoleCountByDao, RoleGrants, RoleRepository, TokenGovernanceByDao,
+};
+use deip_storage_ops::StorageOpsTransaction;
+
+/// Upper bounds of data removed on dissolution.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct DissolveWitness {
+    /// Number of roles defined by DAO
+    pub roles: u32,
+    /// Number of assets held by DAO
+    pub holdings: u32,
+    /// Number of pending invitations and join requests of DAO
+    pub membership_requests: u32,
+}
+
+/// Reasons the runtime refuses to dissolve a DAO.
+pub enum DissolveError {
+    ActiveProjects,
+    UnfinishedSales,
+    PendingProposals,
+    HoldingsNotIndexed,
+}
+
+impl<T: Config> From<DissolveError> for Error<T> {
+    fn from(source: DissolveError) -> Self {
+        match source {
+            DissolveError::ActiveProjects => Error::<T>::HasActiveProjects,
+            DissolveError::UnfinishedSales => Error::<T>::HasUnfinishedSales,
+            DissolveError::PendingProposals => Error::<T>::HasPendingProposals,
+            DissolveError::HoldingsNotIndexed => Error::<T>::HoldingsNotIndexed,
+        }
+    }
+}
+
+/// Obligations and balances of DAOs, provided by the runtime.
+pub trait DaoDissolution<AccountId> {
+    /// Checks nothing owned by `dao_key` prevents dissolution.
+    fn ensure_dissolvable(dao_key: &AccountId) -> Result<(), DissolveError>;
+
+    /// Number of assets held by `dao_key`, i.e. transfers done by `sweep`.
+    fn holding_count(dao_key: &AccountId) -> u32;
+
+    /// Transfers all balances of `dao_key` to `beneficiary`.
+    fn sweep(dao_key: &AccountId, beneficiary: &AccountId) -> DispatchResult;
+
+    /// Makes `dao_key` hold `count` assets.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn setup_holdings(dao_key: &AccountId, count: u32);
+}
+
+impl<AccountId> DaoDissolution<AccountId> for () {
+    fn ensure_dissolvable(_dao_key: &AccountId) -> Result<(), DissolveError> {
+        Ok(())
+    }
+
+    fn holding_count(_dao_key: &AccountId) -> u32 {
+        0
+    }
+
+    fn sweep(_dao_key: &AccountId, _beneficiary: &AccountId) -> DispatchResult {
+        Ok(())
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn setup_holdings(_dao_key: &AccountId, _count: u32) {}
+}
+
+impl<T: Config> Pallet<T> {
+    /// Returns the actual witness of dissolved DAO.
+    pub(crate) fn dissolve_impl(
+        who: T::AccountId,
+        beneficiary: T::AccountId,
+        witness: DissolveWitness,
+    ) -> Result<DissolveWitness, DispatchError> {
+        let dao = load_dao::<T>(LoadBy::DaoKey { dao_key: &who })?;
+        let id = *dao.id();
+
+        let actual = DissolveWitness {
+            roles: RoleCountByDao::<T>::get(id),
+            holdings: T::Dissolution::holding_count(&who),
+            membership_requests: MembershipRequestCountByDao::<T>::get(id),
+        };
+        ensure!(
+            actual.roles <= witness.roles &&
+                actual.holdings <= witness.holdings &&
+                actual.membership_requests <= witness.membership_requests,
+            Error::<T>::WrongDissolveWitness
+        );
+        ensure!(ProposalCountByDao::<T>::get(id) == 0, Error::<T>::HasPendingProposals);
+        T::Dissolution::ensure_dissolvable(&who).map_err(Error::<T>::from)?;
+        ensure!(ParentDaos::<T>::iter_key_prefix(id).next().is_none(), Error::<T>::IsMemberOfDao);
+
+        let invitees = Invitations::<T>::iter_key_prefix(id).collect::<Vec<_>>();
+        for member in invitees {
+            Self::remove_invitation(&id, &member);
+        }
+        let requesters = JoinRequests::<T>::iter_key_prefix(id).collect::<Vec<_>>();
+        for member in requesters {
+            Self::remove_join_request(&id, &member);
+        }
+        MembershipRequestCountByDao::<T>::remove(id);
+        T::Dissolution::sweep(&who, &beneficiary)?;
+
+        for role in RoleRepository::<T>::iter_key_prefix(id) {
+            RoleGrants::<T>::remove_prefix((id, role), None);
+            RoleBudgets::<T>::remove_prefix((id, role), None);
+            RoleBudgetSpent::<T>::remove_prefix((id, role), None);
+        }
+        RoleRepository::<T>::remove_prefix(id, None);
+        RoleCountByDao::<T>::remove(id);
+        BudgetRepository::<T>::remove_prefix(id, None);
+        BudgetSpent::<T>::remove_prefix(id, None);
+        BudgetGuardians::<T>::remove(id);
+        OverBudgetApprovals::<T>::remove(id);
+        TokenGovernanceByDao::<T>::remove(id);
+        AuthorityDepth::<T>::remove(id);
+        NestingConsents::<T>::remove(id);
+        for sub in dao.authority().signatories.iter().filter_map(DaoLookup::<T>::get) {
+            ParentDaos::<T>::remove(sub, id);
+        }
+        DissolvedDaos::<T>::insert(id, ());
+
+        StorageOpsTransaction::<StorageOps<T>>::new().commit(move |ops| {
+            ops.push_op(StorageOps::DissolveDao(dao));
+            ops.push_op(StorageOps::DepositEvent(Event::<T>::DaoDissolved(id, beneficiary)));
+        });
+
+        Ok(actual)
+    }
+}
@@ -0,0 +1,294 @@
+//! Governance of DAOs by holders of the designated asset.
+//!
+//! Calls proposed to the DAO are dispatched on behalf of it when holders
+//! approve them by votes weighted by their balances at the checkpoint
+//! taken when the proposal is created. The checkpoint is pinned till the proposal
+//! is closed, so other checkpoints of the asset aren't evicted by proposals.
+//! The proposer reserves `ProposalDeposit` till the proposal is closed.
+//!
+//! Votes of a closed proposal are removed by `MAX_VOTES_REMOVED_PER_BLOCK`
+//! on close and then in `on_initialize` of the following blocks.
+
+use codec::{Decode, Encode};
+use deip_asset_system::DeipCheckpoints;
+use frame_support::{
+    dispatch::{DispatchResult, DispatchResultWithPostInfo},
+    ensure,
+    traits::{Get, ReservableCurrency},
+    weights::{GetDispatchInfo, Weight},
+    RuntimeDebug,
+};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_io::KillStorageResult;
+use sp_runtime::{
+    traits::{AtLeast32BitUnsigned, Dispatchable, Saturating, Zero},
+    Perbill,
+};
+use sp_std::prelude::*;
+
+use crate::{
+    dao::{load_dao, DaoId, LoadBy},
+    ClosedProposals, Config, DepositBalanceOf, Error, Event, Pallet, ProposalCountByDao,
+    ProposalVotes, Proposals, TokenGovernanceByDao,
+};
+
+pub type ProposalId = sp_core::H160;
+
+/// Max number of votes of closed proposals removed on close and in a block.
+pub const MAX_VOTES_REMOVED_PER_BLOCK: u32 = 100;
+
+pub type AssetIdOf<T> = <<T as Config>::Checkpoints as DeipCheckpoints<
+    <T as frame_system::Config>::AccountId,
+>>::AssetId;
+pub type BalanceOf<T> = <<T as Config>::Checkpoints as DeipCheckpoints<
+    <T as frame_system::Config>::AccountId,
+>>::Balance;
+pub type CheckpointIdOf<T> = <<T as Config>::Checkpoints as DeipCheckpoints<
+    <T as frame_system::Config>::AccountId,
+>>::CheckpointId;
+
+pub type TokenGovernanceOf<T> =
+    TokenGovernance<AssetIdOf<T>, <T as frame_system::Config>::BlockNumber>;
+pub type ProposalOf<T> = Proposal<
+    <T as frame_system::Config>::AccountId,
+    Box<<T as Config>::Call>,
+    AssetIdOf<T>,
+    CheckpointIdOf<T>,
+    BalanceOf<T>,
+    <T as frame_system::Config>::BlockNumber,
+    DepositBalanceOf<T>,
+>;
+
+/// Authority mode of the DAO where its calls are approved by holders of `asset`.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct TokenGovernance<AssetId, BlockNumber> {
+    /// Asset which balances are the weights of votes
+    pub asset: AssetId,
+    /// Minimal share of the total supply which should vote for a proposal to pass
+    pub quorum: Perbill,
+    /// Minimal share of "aye" votes among all votes for a proposal to pass
+    pub approval: Perbill,
+    /// Number of blocks the proposal is open for voting
+    pub voting_period: BlockNumber,
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct Proposal<AccountId, Call, AssetId, CheckpointId, Balance, BlockNumber, Deposit> {
+    pub dao: DaoId,
+    pub proposer: AccountId,
+    /// Reserved from the proposer till the proposal is closed
+    pub deposit: Deposit,
+    pub call: Call,
+    /// Governance of the DAO when the proposal has been created
+    pub governance: TokenGovernance<AssetId, BlockNumber>,
+    /// Checkpoint of balances of the asset taken when the proposal has been created
+    pub checkpoint: CheckpointId,
+    pub total_supply: Balance,
+    pub ayes: Balance,
+    pub nays: Balance,
+    /// Last block of voting
+    pub end: BlockNumber,
+}
+
+impl<AccountId, Call, AssetId, CheckpointId, Balance, BlockNumber, Deposit>
+    Proposal<AccountId, Call, AssetId, CheckpointId, Balance, BlockNumber, Deposit>
+where
+    Balance: AtLeast32BitUnsigned + Copy,
+{
+    fn quorum_reached(&self) -> bool {
+        self.ayes.saturating_add(self.nays) >= self.governance.quorum.mul_ceil(self.total_supply)
+    }
+
+    fn approved(&self) -> bool {
+        let turnout = self.ayes.saturating_add(self.nays);
+        !turnout.is_zero() && self.ayes >= self.governance.approval.mul_ceil(turnout)
+    }
+
+    /// Remaining votes can't turn the proposal down.
+    fn decided(&self) -> bool {
+        self.ayes >= self.governance.quorum.mul_ceil(self.total_supply) &&
+            self.ayes >= self.governance.approval.mul_ceil(self.total_supply)
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    pub fn token_governance(dao: &DaoId) -> Option<TokenGovernanceOf<T>> {
+        TokenGovernanceByDao::<T>::get(dao)
+    }
+
+    pub fn proposal(id: &ProposalId) -> Option<ProposalOf<T>> {
+        Proposals::<T>::get(id)
+    }
+
+    /// Dispatches `call` signed by the own key of the DAO.
+    pub(crate) fn dispatch_on_behalf(
+        dao_key: T::AccountId,
+        call: <T as Config>::Call,
+    ) -> DispatchResultWithPostInfo {
+        call.dispatch(frame_system::RawOrigin::Signed(dao_key).into())
+    }
+
+    pub(crate) fn set_token_governance_impl(
+        who: T::AccountId,
+        governance: Option<TokenGovernanceOf<T>>,
+    ) -> DispatchResult {
+        let dao = load_dao::<T>(LoadBy::DaoKey { dao_key: &who })?;
+        let id = *dao.id();
+
+        match governance {
+            Some(ref governance) => {
+                ensure!(!governance.voting_period.is_zero(), Error::<T>::ZeroVotingPeriod);
+                TokenGovernanceByDao::<T>::insert(id, governance);
+            },
+            None => TokenGovernanceByDao::<T>::remove(id),
+        }
+
+        Self::deposit_event(Event::<T>::TokenGovernanceSet(id, governance));
+
+        Ok(())
+    }
+
+    pub(crate) fn propose_impl(
+        who: T::AccountId,
+        dao: DaoId,
+        id: ProposalId,
+        call: Box<<T as Config>::Call>,
+    ) -> DispatchResult {
+        ensure!(
+            !Proposals::<T>::contains_key(id) && !ClosedProposals::<T>::contains_key(id),
+            Error::<T>::ProposalExists
+        );
+        ensure!(
+            call.encoded_size() <= T::MaxProposalLength::get() as usize,
+            Error::<T>::ProposalTooLong
+        );
+        let governance = TokenGovernanceByDao::<T>::get(dao).ok_or(Error::<T>::NotTokenGoverned)?;
+
+        let checkpoint = T::Checkpoints::pin_checkpoint(governance.asset)?;
+        let balance = T::Checkpoints::balance_at(&governance.asset, &who, checkpoint)
+            .unwrap_or_else(Zero::zero);
+        ensure!(!balance.is_zero(), Error::<T>::NotTokenHolder);
+        let total_supply = T::Checkpoints::total_supply_at(&governance.asset, checkpoint)
+            .unwrap_or_else(Zero::zero);
+
+        let deposit = T::ProposalDeposit::get();
+        T::Currency::reserve(&who, deposit)?;
+
+        let end =
+            frame_system::Pallet::<T>::block_number().saturating_add(governance.voting_period);
+        let proposal = Proposal {
+            dao,
+            proposer: who.clone(),
+            deposit,
+            call,
+            governance,
+            checkpoint,
+            total_supply,
+            ayes: Zero::zero(),
+            nays: Zero::zero(),
+            end,
+        };
+        Proposals::<T>::insert(id, proposal);
+        ProposalCountByDao::<T>::mutate(dao, |count| *count = count.saturating_add(1));
+
+        Self::deposit_event(Event::<T>::Proposed(dao, id, who));
+
+        Ok(())
+    }
+
+    pub(crate) fn vote_impl(who: T::AccountId, id: ProposalId, aye: bool) -> DispatchResult {
+        Proposals::<T>::try_mutate(id, |maybe_proposal| -> DispatchResult {
+            let proposal = maybe_proposal.as_mut().ok_or(Error::<T>::ProposalNotFound)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() <= proposal.end,
+                Error::<T>::VotingClosed
+            );
+            ensure!(!ProposalVotes::<T>::contains_key(id, &who), Error::<T>::AlreadyVoted);
+
+            let weight =
+                T::Checkpoints::balance_at(&proposal.governance.asset, &who, proposal.checkpoint)
+                    .unwrap_or_else(Zero::zero);
+            ensure!(!weight.is_zero(), Error::<T>::NotTokenHolder);
+
+            if aye {
+                proposal.ayes = proposal.ayes.saturating_add(weight);
+            } else {
+                proposal.nays = proposal.nays.saturating_add(weight);
+            }
+            ProposalVotes::<T>::insert(id, &who, aye);
+
+            Self::deposit_event(Event::<T>::Voted(id, who, aye, weight));
+
+            Ok(())
+        })
+    }
+
+    pub(crate) fn close_proposal_impl(
+        id: ProposalId,
+        proposal_weight_bound: Weight,
+    ) -> DispatchResultWithPostInfo {
+        let proposal = Proposals::<T>::get(id).ok_or(Error::<T>::ProposalNotFound)?;
+        let voting = frame_system::Pallet::<T>::block_number() <= proposal.end;
+        ensure!(!voting || proposal.decided(), Error::<T>::VotingNotClosed);
+
+        let passed = proposal.quorum_reached() && proposal.approved();
+        if passed {
+            let call_weight = proposal.call.get_dispatch_info().weight;
+            ensure!(call_weight <= proposal_weight_bound, Error::<T>::WrongProposalWeight);
+        }
+
+        Proposals::<T>::remove(id);
+        ProposalCountByDao::<T>::mutate(proposal.dao, |count| *count = count.saturating_sub(1));
+        if let KillStorageResult::SomeRemaining(_) =
+            ProposalVotes::<T>::remove_prefix(id, Some(MAX_VOTES_REMOVED_PER_BLOCK))
+        {
+            ClosedProposals::<T>::insert(id, ());
+        }
+        T::Checkpoints::unpin_checkpoint(&proposal.governance.asset, proposal.checkpoint);
+        T::Currency::unreserve(&proposal.proposer, proposal.deposit);
+
+        if !passed {
+            Self::deposit_event(Event::<T>::ProposalRejected(id));
+            return Ok(Some(0).into())
+        }
+
+        let dao = Self::get_dao(proposal.dao).ok_or(Error::<T>::NotFound)?;
+        let dao_key = dao.dao_key().clone();
+        let result = Self::dispatch_within_budget(&proposal.dao, dao_key, *proposal.call, false);
+
+        Self::deposit_event(Event::<T>::ProposalExecuted(
+            id,
+            result.map(|_| ()).map_err(|e| e.error),
+        ));
+
+        Ok(None.into())
+    }
+
+    /// Removes up to `limit` remaining votes of closed proposals.
+    pub(crate) fn clear_closed_proposals(limit: u32) -> Weight {
+        let mut weight = T::DbWeight::get().reads(1);
+        let mut budget = limit;
+
+        for id in ClosedProposals::<T>::iter_keys() {
+            if budget.is_zero() {
+                break
+            }
+            let removed = match ProposalVotes::<T>::remove_prefix(id, Some(budget)) {
+                KillStorageResult::AllRemoved(removed) => {
+                    ClosedProposals::<T>::remove(id);
+                    weight = weight.saturating_add(T::DbWeight::get().writes(1));
+                    removed
+                },
+                KillStorageResult::SomeRemaining(removed) => removed,
+            };
+            // removing the prefix with no votes left costs a read too
+            budget = budget.saturating_sub(removed.max(1));
+            weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, removed as Weight));
+        }
+
+        weight
+    }
+}
@@ -14,6 +14,34 @@
 //! * `create` - Create a DAO.
 //! * `alter_authority` - Alter DAO's authority.
 //! * `on_behalf` - Perform action on behalf of a DAO.
+//! * `set_token_governance` - Make a DAO governed by holders of an asset.
+//! * `propose` - Propose a call to a DAO governed by holders of an asset.
+//! * `vote` - Vote for a proposal by the balance at the moment it was proposed.
+//! * `close_proposal` - Close voting and dispatch the call of the passed proposal.
+//! * `define_role` - Define a role of DAO members by permissions to dispatch calls.
+//! * `remove_role` - Remove a role with all its grants.
+//! * `grant_role` - Grant a role to a member.
+//! * `revoke_role` - Revoke a role from a member.
+//! * `on_behalf_as_role` - Perform action permitted to a role on behalf of a DAO.
+//! * `set_role_budget` - Limit spending of an asset by a role per period.
+//! * `set_budget` - Limit spending of an asset by a DAO per period.
+//! * `on_behalf_over_budget` - Perform action approved by the budget guardian regardless
+//!   of budgets of a DAO.
+//! * `set_budget_guardian` - Appoint or dismiss the guardian of budgets of a DAO.
+//! * `set_budget_by_guardian` - Set or remove a budget of a DAO by its guardian.
+//! * `approve_over_budget` - Approve a call to be dispatched regardless of budgets.
+//! * `dissolve` - Sweep balances of a DAO to a beneficiary and remove the DAO.
+//! * `invite_member` - Invite an account to join the authority of a DAO.
+//! * `accept_invitation` - Join the authority of a DAO by invitation.
+//! * `reject_invitation` - Decline an invitation.
+//! * `request_to_join` - Ask a DAO to join its authority.
+//! * `accept_join_request` - Add the requester to the authority of a DAO.
+//! * `reject_join_request` - Decline a join request.
+//! * `prune_membership_requests` - Remove expired invitation and join request of an account
+//!   refunding their deposits.
+//! * `consent_to_nesting` - Consent to be a signatory of the authority of another DAO.
+//!
+//! A signatory of DAO authority may be another DAO, see [`nesting`].
 //!
 //! [`Call`]: ./enum.Call.html
 //! [`Config`]: ./trait.Config.html
@@ -29,6 +57,12 @@ mod tests;
 
 pub mod api;
 pub mod benchmarking;
+pub mod budget;
+pub mod dissolution;
+pub mod governance;
+pub mod membership;
+pub mod nesting;
+pub mod roles;
 pub mod weights;
 
 #[doc(inline)]
@@ -41,11 +75,14 @@ pub mod pallet {
 
     use frame_support::{
         pallet_prelude::*,
+        transactional,
         weights::{GetDispatchInfo, PostDispatchInfo},
         Hashable,
     };
 
-    use frame_support::traits::{Get, IsSubType, UnfilteredDispatchable};
+    use frame_support::traits::{
+        Currency, Get, InstanceFilter, IsSubType, ReservableCurrency, UnfilteredDispatchable,
+    };
 
     use sp_std::{collections::btree_map::BTreeMap, iter::FromIterator, prelude::*};
 
@@ -59,8 +96,19 @@ pub mod pallet {
 
     use deip_storage_ops::StorageOpsTransaction;
 
+    use crate::governance::{
+        BalanceOf, ProposalId, ProposalOf, TokenGovernanceOf, MAX_VOTES_REMOVED_PER_BLOCK,
+    };
+    use crate::membership::{InvitationOf, JoinRequestOf};
+    use crate::budget::{BudgetOf, SpendingAssetIdOf, SpentOf};
+    use crate::dissolution::DissolveWitness;
+    use crate::roles::RoleId;
     use crate::weights::WeightInfo;
 
+    pub type DepositBalanceOf<T> = <<T as Config>::Currency as Currency<
+        <T as frame_system::Config>::AccountId,
+    >>::Balance;
+
     /// Configuration trait
     #[pallet::config]
     pub trait Config: frame_system::Config {
@@ -81,32 +129,110 @@ pub mod pallet {
         /// Max signatories in DAO Authority
         #[pallet::constant]
         type MaxSignatories: Get<u16>;
+        /// Balance checkpoints of assets governing DAOs
+        type Checkpoints: deip_asset_system::DeipCheckpoints<Self::AccountId>;
+        /// Permission of a role to dispatch calls on behalf of DAO
+        type Permission: Parameter + Member + InstanceFilter<<Self as Config>::Call>;
+        /// Max permissions of a role
+        #[pallet::constant]
+        type MaxRolePermissions: Get<u16>;
+        /// Funds moved by calls dispatched on behalf of DAO
+        type Spending: crate::budget::DaoSpending<Self::AccountId, <Self as Config>::Call>;
+        /// Obligations and balances checked and swept on dissolution of DAO
+        type Dissolution: crate::dissolution::DaoDissolution<Self::AccountId>;
+        /// Number of blocks invitations and join requests stay valid
+        #[pallet::constant]
+        type MembershipRequestTtl: Get<Self::BlockNumber>;
+        /// Max levels of DAOs nested in authorities of each other
+        #[pallet::constant]
+        type MaxAuthorityDepth: Get<u16>;
+        /// Currency of deposits reserved for pending requests
+        type Currency: ReservableCurrency<Self::AccountId>;
+        /// Deposit reserved from the proposer till the proposal is closed
+        #[pallet::constant]
+        type ProposalDeposit: Get<DepositBalanceOf<Self>>;
+        /// Deposit reserved for an invitation or a join request till it's resolved
+        #[pallet::constant]
+        type MembershipRequestDeposit: Get<DepositBalanceOf<Self>>;
+        /// Max pending invitations and join requests of DAO
+        #[pallet::constant]
+        type MaxMembershipRequests: Get<u32>;
+        /// Max length of the encoded call of a proposal
+        #[pallet::constant]
+        type MaxProposalLength: Get<u32>;
+        /// Permission of a role and a call it permits, used by benchmarks
+        #[cfg(feature = "runtime-benchmarks")]
+        type BenchmarkPermission: Get<(Self::Permission, <Self as Config>::Call)>;
     }
 
     use frame_support::traits::{StorageVersion, GetStorageVersion};
 
     pub const V0: StorageVersion = StorageVersion::new(0);
     pub const V1: StorageVersion = StorageVersion::new(1);
+    pub const V2: StorageVersion = StorageVersion::new(2);
+    /// Counters of proposals and roles of DAOs.
+    pub const V3: StorageVersion = StorageVersion::new(3);
+    /// Depths of authorities of DAOs.
+    pub const V4: StorageVersion = StorageVersion::new(4);
 
     #[doc(hidden)]
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
-    #[pallet::storage_version(V1)]
+    #[pallet::storage_version(V4)]
     pub struct Pallet<T>(_);
 
     #[doc(hidden)]
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+            Self::clear_closed_proposals(MAX_VOTES_REMOVED_PER_BLOCK)
+        }
+
         fn on_runtime_upgrade() -> Weight {
-            if Self::on_chain_storage_version() == V0
-                && Self::current_storage_version() == V1
-            {
+            let mut weight = 0;
+            if Self::on_chain_storage_version() == V0 {
                 let id = DaoId::zero();
                 DaoLookup::<T>::remove(dao_key2::<T>(&id));
                 DaoRepository::<T>::remove(id);
-                return T::DbWeight::get().writes(2);
+                weight = T::DbWeight::get().writes(2);
+            }
+            if Self::on_chain_storage_version() < V2 {
+                // backfill the index of DAOs by signatories
+                let (mut reads, mut writes) = (0, 1);
+                for (id, dao) in DaoRepository::<T>::iter() {
+                    reads += 1;
+                    for member in &dao.authority().signatories {
+                        DaoIdByMember::<T>::insert(member, id, ());
+                        writes += 1;
+                    }
+                }
+                V2.put::<Self>();
+                weight = weight.saturating_add(T::DbWeight::get().reads_writes(reads, writes));
+            }
+            if Self::on_chain_storage_version() < V3 {
+                // count proposals and roles of DAOs
+                let (mut reads, mut writes) = (0, 1);
+                for proposal in Proposals::<T>::iter_values() {
+                    ProposalCountByDao::<T>::mutate(proposal.dao, |count| {
+                        *count = count.saturating_add(1)
+                    });
+                    reads += 1;
+                    writes += 1;
+                }
+                for (dao, _) in RoleRepository::<T>::iter_keys() {
+                    RoleCountByDao::<T>::mutate(dao, |count| *count = count.saturating_add(1));
+                    reads += 1;
+                    writes += 1;
+                }
+                V3.put::<Self>();
+                weight = weight.saturating_add(T::DbWeight::get().reads_writes(reads, writes));
+            }
+            if Self::on_chain_storage_version() < V4 {
+                weight = weight.saturating_add(Self::backfill_authority_depth());
+                V4.put::<Self>();
+                weight = weight.saturating_add(T::DbWeight::get().writes(1));
             }
-            0
+            weight
         }
     }
 
@@ -120,6 +246,86 @@ pub mod pallet {
         Forbidden,
         ///
         AuthorityMismatch,
+        /// DAO is governed by holders of an asset
+        TokenGoverned,
+        /// DAO isn't governed by holders of an asset
+        NotTokenGoverned,
+        /// Voting period must be positive
+        ZeroVotingPeriod,
+        /// Account holds none of the governing asset
+        NotTokenHolder,
+        /// Proposal already exists
+        ProposalExists,
+        /// Proposal not found
+        ProposalNotFound,
+        /// Voting on the proposal is closed
+        VotingClosed,
+        /// Voting on the proposal isn't closed yet
+        VotingNotClosed,
+        /// Account already voted for the proposal
+        AlreadyVoted,
+        /// Weight of the call exceeds the given bound
+        WrongProposalWeight,
+        /// Encoded call exceeds `MaxProposalLength`
+        ProposalTooLong,
+        /// Role not found
+        RoleNotFound,
+        /// Role has more permissions than allowed
+        TooMuchPermissions,
+        /// Account doesn't have the role
+        NoRole,
+        /// Call isn't permitted to the role
+        CallNotPermitted,
+        /// Budget period is zero
+        ZeroBudgetPeriod,
+        /// Call spends more than remains of the budget
+        BudgetExceeded,
+        /// Call spends an asset the role has no budget of or more than remains of it
+        RoleBudgetExceeded,
+        /// Account isn't a signatory of the DAO authority
+        NotMember,
+        /// Call may move funds which can't be charged to budgets
+        UnmeasuredSpending,
+        /// Only the budget guardian may loosen budgets
+        BudgetGuarded,
+        /// Budget guardian hasn't approved the call
+        OverBudgetNotApproved,
+        /// DAO owns projects without final results
+        HasActiveProjects,
+        /// DAO has sales not finished yet
+        HasUnfinishedSales,
+        /// DAO takes part in pending proposals
+        HasPendingProposals,
+        /// Holdings of DAOs are being indexed, DAOs can't be dissolved till then
+        HoldingsNotIndexed,
+        /// Witness of dissolution understates roles or holdings of DAO
+        WrongDissolveWitness,
+        /// DAO with the name has been dissolved, the name can't be reused
+        Dissolved,
+        /// Account is a member of DAO already
+        AlreadyMember,
+        /// Invitation not found
+        InvitationNotFound,
+        /// Join request not found
+        JoinRequestNotFound,
+        /// Join request is pending already
+        JoinRequestExists,
+        /// Invitation or join request has expired
+        MembershipRequestExpired,
+        /// Neither invitation nor join request of the account has expired
+        MembershipRequestNotExpired,
+        /// Nested DAO authorities exceed the max depth
+        AuthorityTooDeep,
+        /// DAO can't be a signatory of itself at any level of nested authorities
+        AuthorityCycle,
+        /// DAO hasn't consented to be nested in the authority
+        NoNestingConsent,
+        /// Authority of DAO nested in others can't get deeper
+        NestedAuthorityDeepened,
+        /// DAO has `MaxMembershipRequests` pending invitations and join requests
+        TooManyMembershipRequests,
+        /// DAO is nested in authorities of other DAOs
+        IsMemberOfDao,
     }
 
     #[pallet::event]
@@ -130,6 +336,53 @@ pub mod pallet {
         /// Emits when authority alteration
         DaoAlterAuthority(DaoOf<T>),
         DaoMetadataUpdated(DaoOf<T>),
+        /// Emits when DAO becomes governed by holders of an asset or stops to be
+        TokenGovernanceSet(DaoId, Option<TokenGovernanceOf<T>>),
+        /// Emits when a call is proposed to DAO: dao, proposal, proposer
+        Proposed(DaoId, ProposalId, T::AccountId),
+        /// Emits when a holder votes: proposal, voter, aye, weight of the vote
+        Voted(ProposalId, T::AccountId, bool, BalanceOf<T>),
+        /// Emits when a proposal hasn't passed
+        ProposalRejected(ProposalId),
+        /// Emits when the call of the passed proposal has been dispatched
+        ProposalExecuted(ProposalId, DispatchResult),
+        /// Emits when a role has been defined or redefined
+        RoleDefined(DaoId, RoleId),
+        /// Emits when a role has been removed with all its grants
+        RoleRemoved(DaoId, RoleId),
+        /// Emits when a role has been granted: dao, role, member
+        RoleGranted(DaoId, RoleId, T::AccountId),
+        /// Emits when a role has been revoked: dao, role, member
+        RoleRevoked(DaoId, RoleId, T::AccountId),
+        /// Emits when a budget of an asset has been set or removed
+        BudgetSet(DaoId, SpendingAssetIdOf<T>, Option<BudgetOf<T>>),
+        /// Emits when the authority has dispatched a call regardless of budgets
+        BudgetOverridden(DaoId),
+        /// Emits when the budget guardian has been appointed or dismissed
+        BudgetGuardianSet(DaoId, Option<T::AccountId>),
+        /// Emits when the budget guardian has approved a call: dao, hash of the call
+        OverBudgetApproved(DaoId, T::Hash),
+        /// Emits when a budget of a role has been set or removed: dao, role, asset, budget
+        RoleBudgetSet(DaoId, RoleId, SpendingAssetIdOf<T>, Option<BudgetOf<T>>),
+        /// Emits when DAO has been dissolved: dao, beneficiary of its balances
+        DaoDissolved(DaoId, T::AccountId),
+        /// Emits when an account has been invited: dao, invitee, last block to accept
+        MemberInvited(DaoId, T::AccountId, T::BlockNumber),
+        /// Emits when the invitee has declined an invitation
+        InvitationRejected(DaoId, T::AccountId),
+        /// Emits when an account has requested to join: dao, requester, last block to accept
+        JoinRequested(DaoId, T::AccountId, T::BlockNumber),
+        /// Emits when DAO has declined a join request
+        JoinRequestRejected(DaoId, T::AccountId),
+        /// Emits when an account has joined the authority of DAO
+        MemberJoined(DaoId, T::AccountId),
+        /// Emits when an expired invitation has been pruned
+        InvitationExpired(DaoId, T::AccountId),
+        /// Emits when an expired join request has been pruned
+        JoinRequestExpired(DaoId, T::AccountId),
+        /// Emits when DAO has consented to be nested in the authority of a parent or withdrawn
+        /// its consent: dao, parent
+        NestingConsented(DaoId, Option<DaoId>),
     }
 
     #[doc(hidden)]
@@ -462,8 +715,10 @@ pub mod pallet {
                 authority.assert::<T>(&authority_key).map_err::<Error<T>, _>(Into::into)?;
             ensure!(!name.is_zero(), Error::<T>::Exists);
             ensure!(!DaoRepository::<T>::contains_key(&name), Error::<T>::Exists);
+            ensure!(!DissolvedDaos::<T>::contains_key(&name), Error::<T>::Dissolved);
             let dao_key = Self::dao_key(&name);
             let dao = DaoOf::<T>::new(authority_key, authority, name, dao_key, metadata);
+            Self::update_authority_nesting(&dao, None, None)?;
             StorageOpsTransaction::<StorageOps<T>>::new().commit(move |ops| {
                 ops.push_op(StorageOps::CreateDao(dao.clone()));
                 ops.push_op(StorageOps::DepositEvent(Event::<T>::DaoCreate(dao)));
@@ -481,8 +736,10 @@ pub mod pallet {
             authority: AlterAuthority<T::AccountId>,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
-            let mut dao = load_dao::<T>(LoadBy::DaoKey { dao_key: &who })?;
-            dao = dao.alter_authoriry::<T>(authority).map_err::<Error<T>, _>(Into::into)?;
+            let old = load_dao::<T>(LoadBy::DaoKey { dao_key: &who })?;
+            let dao =
+                old.clone().alter_authoriry::<T>(authority).map_err::<Error<T>, _>(Into::into)?;
+            Self::update_authority_nesting(&dao, Some(&old), None)?;
             StorageOpsTransaction::<StorageOps<T>>::new().commit(move |ops| {
                 ops.push_op(StorageOps::UpdateDao(dao.clone()));
                 ops.push_op(StorageOps::DepositEvent(Event::<T>::DaoAlterAuthority(dao)));
@@ -523,7 +780,467 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
             let dao = load_dao::<T>(LoadBy::DaoId { id: &name, who: KeyType::Members(&who) })?;
-            call.dispatch(RawOrigin::Signed(dao.dao_key().clone()).into())
+            ensure!(!TokenGovernanceByDao::<T>::contains_key(&name), Error::<T>::TokenGoverned);
+            Self::dispatch_within_budget(&name, dao.dao_key().clone(), *call, false)
+        }
+
+        /// Performs `call` on behalf of DAO regardless of its budgets.
+        /// Funds moved by the call are still charged.
+        /// If DAO has a budget guardian, the call must be approved by it via
+        /// `approve_over_budget`, the approval is consumed.
+        ///
+        /// The origin for this call must be _Signed_ by the authority of DAO.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::on_behalf_over_budget()
+                + call.get_dispatch_info().weight,
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn on_behalf_over_budget(
+            origin: OriginFor<T>,
+            name: DaoId,
+            call: Box<<T as Config>::Call>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let dao = load_dao::<T>(LoadBy::DaoId { id: &name, who: KeyType::Members(&who) })?;
+            ensure!(!TokenGovernanceByDao::<T>::contains_key(&name), Error::<T>::TokenGoverned);
+            let result = Self::dispatch_over_budget(&name, dao.dao_key().clone(), *call)?;
+            Self::deposit_event(Event::<T>::BudgetOverridden(name));
+            Ok(result)
+        }
+
+        /// Makes DAO governed by holders of `governance.asset` or, if `None`,
+        /// returns the control to its authority.
+        ///
+        /// The origin for this call must be _Signed_ by the own key of DAO,
+        /// i.e. dispatched via `on_behalf` or a passed proposal.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::set_token_governance(),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn set_token_governance(
+            origin: OriginFor<T>,
+            governance: Option<TokenGovernanceOf<T>>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::set_token_governance_impl(who, governance)?;
+            Ok(Some(0).into())
+        }
+
+        /// Proposes `call` to be dispatched on behalf of DAO governed by holders
+        /// of an asset. Balances of the asset are recorded for voting.
+        /// `ProposalDeposit` is reserved till the proposal is closed.
+        ///
+        /// The origin for this call must be _Signed_ by a holder of the asset.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::propose(call.encoded_size() as u32),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        #[transactional]
+        pub fn propose(
+            origin: OriginFor<T>,
+            dao: DaoId,
+            id: ProposalId,
+            call: Box<<T as Config>::Call>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::propose_impl(who, dao, id, call)?;
+            Ok(Some(0).into())
+        }
+
+        /// Votes for the proposal with the balance at the moment of proposing.
+        ///
+        /// The origin for this call must be _Signed_ by a holder of the asset.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::vote(),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn vote(origin: OriginFor<T>, id: ProposalId, aye: bool) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::vote_impl(who, id, aye)?;
+            Ok(Some(0).into())
+        }
+
+        /// Closes voting on the proposal after its voting period or earlier when
+        /// remaining votes can't turn it down. Passed proposal is dispatched
+        /// on behalf of DAO the same way as `on_behalf` does.
+        ///
+        /// The origin for this call must be _Signed_.
+        /// - `proposal_weight_bound` - maximal weight of the proposed call
+        ///
+        /// Up to `MAX_VOTES_REMOVED_PER_BLOCK` votes are removed, the rest are
+        /// removed in the following blocks.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::close_proposal()
+                .saturating_add(T::DbWeight::get().writes(MAX_VOTES_REMOVED_PER_BLOCK as Weight))
+                .saturating_add(*proposal_weight_bound),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        #[transactional]
+        pub fn close_proposal(
+            origin: OriginFor<T>,
+            id: ProposalId,
+            #[pallet::compact] proposal_weight_bound: Weight,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            Self::close_proposal_impl(id, proposal_weight_bound)
+        }
+
+        /// Defines `role` of DAO members permitted to dispatch calls matched
+        /// by any of `permissions`. Redefines the role if it exists.
+        ///
+        /// The origin for this call must be _Signed_ by the own key of DAO.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::define_role(permissions.len() as u32),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn define_role(
+            origin: OriginFor<T>,
+            role: RoleId,
+            permissions: Vec<T::Permission>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::define_role_impl(who, role, permissions)?;
+            Ok(Some(0).into())
+        }
+
+        /// Removes `role` with its budgets and revokes it from all members.
+        ///
+        /// The origin for this call must be _Signed_ by the own key of DAO.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::remove_role(T::MaxSignatories::get() as u32),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn remove_role(origin: OriginFor<T>, role: RoleId) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::remove_role_impl(who, role)?;
+            Ok(Some(0).into())
+        }
+
+        /// Grants `role` to `member` of the authority.
+        ///
+        /// The origin for this call must be _Signed_ by the own key of DAO.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::grant_role(),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn grant_role(
+            origin: OriginFor<T>,
+            role: RoleId,
+            member: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::grant_role_impl(who, role, member)?;
+            Ok(Some(0).into())
+        }
+
+        /// Revokes `role` from `member`.
+        ///
+        /// The origin for this call must be _Signed_ by the own key of DAO.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::revoke_role(),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn revoke_role(
+            origin: OriginFor<T>,
+            role: RoleId,
+            member: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::revoke_role_impl(who, role, member)?;
+            Ok(Some(0).into())
+        }
+
+        /// Performs `call` on behalf of DAO if it's permitted to `role`.
+        /// Funds moved by the call are charged to budgets of the role and of DAO.
+        ///
+        /// The origin for this call must be _Signed_ by a member with the role.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::on_behalf_as_role()
+                + call.get_dispatch_info().weight,
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn on_behalf_as_role(
+            origin: OriginFor<T>,
+            name: DaoId,
+            role: RoleId,
+            call: Box<<T as Config>::Call>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::on_behalf_as_role_impl(who, name, role, *call)
+        }
+
+        /// Limits spending of `asset` by `budget` or, if `None`, removes the limit.
+        /// While DAO has a budget guardian, a budget may only be added or lowered
+        /// within the same period.
+        ///
+        /// The origin for this call must be _Signed_ by the own key of DAO.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::set_budget(),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn set_budget(
+            origin: OriginFor<T>,
+            asset: SpendingAssetIdOf<T>,
+            budget: Option<BudgetOf<T>>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::set_budget_impl(who, asset, budget)?;
+            Ok(Some(0).into())
+        }
+
+        /// Dissolves DAO transferring all its balances to `beneficiary`.
+        /// Refused while DAO owns active projects, unfinished sales
+        /// or takes part in pending proposals.
+        /// `witness` bounds the number of roles, holdings and pending invitations
+        /// and join requests of DAO, deposits of the latter are refunded.
+        ///
+        /// The origin for this call must be _Signed_ by the own key of DAO,
+        /// i.e. dispatched by its authority via `on_behalf`.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::dissolve(
+                witness.roles,
+                witness.holdings,
+                witness.membership_requests
+            ),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        #[transactional]
+        pub fn dissolve(
+            origin: OriginFor<T>,
+            beneficiary: T::AccountId,
+            witness: DissolveWitness,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let actual = Self::dissolve_impl(who, beneficiary, witness)?;
+            let weight = T::DeipDaoWeightInfo::dissolve(
+                actual.roles,
+                actual.holdings,
+                actual.membership_requests,
+            );
+            Ok(Some(weight).into())
+        }
+
+        /// Invites `member` to join the authority. The threshold of the authority
+        /// is kept or incremented on joining according to `preserve_threshold`.
+        ///
+        /// The origin for this call must be _Signed_ by the own key of DAO.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::invite_member(),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn invite_member(
+            origin: OriginFor<T>,
+            member: T::AccountId,
+            preserve_threshold: bool,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::invite_member_impl(who, member, preserve_threshold)?;
+            Ok(Some(0).into())
+        }
+
+        /// Joins the authority of DAO `name` by its invitation.
+        ///
+        /// The origin for this call must be _Signed_ by the invitee.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::accept_invitation(),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        #[transactional]
+        pub fn accept_invitation(origin: OriginFor<T>, name: DaoId) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::accept_invitation_impl(who, name)?;
+            Ok(Some(0).into())
+        }
+
+        /// Declines the invitation of DAO `name`.
+        ///
+        /// The origin for this call must be _Signed_ by the invitee.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::reject_invitation(),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn reject_invitation(origin: OriginFor<T>, name: DaoId) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::reject_invitation_impl(who, name)?;
+            Ok(Some(0).into())
+        }
+
+        /// Asks DAO `name` to add the caller to its authority.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::request_to_join(),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn request_to_join(origin: OriginFor<T>, name: DaoId) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::request_to_join_impl(who, name)?;
+            Ok(Some(0).into())
+        }
+
+        /// Adds the requester `member` to the authority. The threshold of the authority
+        /// is kept or incremented according to `preserve_threshold`.
+        ///
+        /// The origin for this call must be _Signed_ by the own key of DAO.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::accept_join_request(),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        #[transactional]
+        pub fn accept_join_request(
+            origin: OriginFor<T>,
+            member: T::AccountId,
+            preserve_threshold: bool,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::accept_join_request_impl(who, member, preserve_threshold)?;
+            Ok(Some(0).into())
+        }
+
+        /// Declines the join request of `member`.
+        ///
+        /// The origin for this call must be _Signed_ by the own key of DAO.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::reject_join_request(),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn reject_join_request(
+            origin: OriginFor<T>,
+            member: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::reject_join_request_impl(who, member)?;
+            Ok(Some(0).into())
+        }
+
+        /// Limits spending of `asset` by `role` per period or, if `None`,
+        /// forbids the role to spend the asset.
+        ///
+        /// The origin for this call must be _Signed_ by the own key of DAO.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::set_role_budget(),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn set_role_budget(
+            origin: OriginFor<T>,
+            role: RoleId,
+            asset: SpendingAssetIdOf<T>,
+            budget: Option<BudgetOf<T>>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::set_role_budget_impl(who, role, asset, budget)?;
+            Ok(Some(0).into())
+        }
+
+        /// Appoints `guardian` of budgets of DAO `name` or, if `None`, dismisses it.
+        ///
+        /// The origin for this call must be _Signed_ by the current guardian or,
+        /// if there is none, by the own key of DAO.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::set_budget_guardian(),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn set_budget_guardian(
+            origin: OriginFor<T>,
+            name: DaoId,
+            guardian: Option<T::AccountId>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::set_budget_guardian_impl(who, name, guardian)?;
+            Ok(Some(0).into())
+        }
+
+        /// Sets any `budget` of `asset` for DAO `name` or, if `None`, removes it.
+        ///
+        /// The origin for this call must be _Signed_ by the budget guardian of DAO.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::set_budget(),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn set_budget_by_guardian(
+            origin: OriginFor<T>,
+            name: DaoId,
+            asset: SpendingAssetIdOf<T>,
+            budget: Option<BudgetOf<T>>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::set_budget_by_guardian_impl(who, name, asset, budget)?;
+            Ok(Some(0).into())
+        }
+
+        /// Approves the call with `call_hash` to be dispatched by the authority
+        /// of DAO `name` via `on_behalf_over_budget`. Replaces the previous approval.
+        ///
+        /// The origin for this call must be _Signed_ by the budget guardian of DAO.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::approve_over_budget(),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn approve_over_budget(
+            origin: OriginFor<T>,
+            name: DaoId,
+            call_hash: T::Hash,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::approve_over_budget_impl(who, name, call_hash)?;
+            Ok(Some(0).into())
+        }
+
+        /// Removes expired invitation and join request of `member` to DAO `name`
+        /// refunding their deposits.
+        ///
+        /// The origin for this call must be _Signed_ by any account.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::prune_membership_requests(),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn prune_membership_requests(
+            origin: OriginFor<T>,
+            name: DaoId,
+            member: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            Self::prune_membership_requests_impl(name, member)?;
+            Ok(Some(0).into())
+        }
+
+        /// Consents to be nested in the authority of DAO `parent` or, if `None`,
+        /// withdraws the consent. The consent is used up by nesting.
+        ///
+        /// The origin for this call must be _Signed_ by the own key of DAO.
+        #[pallet::weight((
+            T::DeipDaoWeightInfo::consent_to_nesting(),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn consent_to_nesting(
+            origin: OriginFor<T>,
+            parent: Option<DaoId>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::consent_to_nesting_impl(who, parent)?;
+            Ok(Some(0).into())
         }
     }
 
@@ -534,15 +1251,191 @@ pub mod pallet {
     pub(super) type DaoRepository<T: Config> =
         StorageMap<_, Blake2_128Concat, DaoId, DaoOf<T>, OptionQuery>;
 
+    /// DAOs by signatories of their authorities
+    #[pallet::storage]
+    pub(super) type DaoIdByMember<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        DaoId,
+        (),
+        OptionQuery,
+    >;
+
     #[pallet::storage]
     #[pallet::getter(fn lookup_dao)]
     pub(super) type DaoLookup<T: Config> =
         StorageMap<_, Blake2_128Concat, T::AccountId, DaoId, OptionQuery>;
 
+    /// DAOs governed by holders of an asset
+    #[pallet::storage]
+    pub(super) type TokenGovernanceByDao<T: Config> =
+        StorageMap<_, Blake2_128Concat, DaoId, TokenGovernanceOf<T>, OptionQuery>;
+
+    #[pallet::storage]
+    pub(super) type Proposals<T: Config> =
+        StorageMap<_, Blake2_128Concat, ProposalId, ProposalOf<T>, OptionQuery>;
+
+    /// Number of pending proposals of DAOs
+    #[pallet::storage]
+    pub(super) type ProposalCountByDao<T: Config> =
+        StorageMap<_, Blake2_128Concat, DaoId, u32, ValueQuery>;
+
+    /// Permissions of roles of DAO members
+    #[pallet::storage]
+    pub(super) type RoleRepository<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DaoId,
+        Blake2_128Concat,
+        RoleId,
+        Vec<T::Permission>,
+        OptionQuery,
+    >;
+
+    /// Number of roles defined by DAOs
+    #[pallet::storage]
+    pub(super) type RoleCountByDao<T: Config> =
+        StorageMap<_, Blake2_128Concat, DaoId, u32, ValueQuery>;
+
+    #[pallet::storage]
+    pub(super) type RoleGrants<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        (DaoId, RoleId),
+        Blake2_128Concat,
+        T::AccountId,
+        (),
+        OptionQuery,
+    >;
+
+    #[pallet::storage]
+    pub(super) type BudgetRepository<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DaoId,
+        Blake2_128Concat,
+        SpendingAssetIdOf<T>,
+        BudgetOf<T>,
+        OptionQuery,
+    >;
+
+    /// Amounts spent by DAOs within current periods of their budgets
+    #[pallet::storage]
+    pub(super) type BudgetSpent<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DaoId,
+        Blake2_128Concat,
+        SpendingAssetIdOf<T>,
+        SpentOf<T>,
+        OptionQuery,
+    >;
+
+    /// Spending limits of roles
+    #[pallet::storage]
+    pub(super) type RoleBudgets<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        (DaoId, RoleId),
+        Blake2_128Concat,
+        SpendingAssetIdOf<T>,
+        BudgetOf<T>,
+        OptionQuery,
+    >;
+
+    /// Amounts spent by roles within current periods of their budgets
+    #[pallet::storage]
+    pub(super) type RoleBudgetSpent<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        (DaoId, RoleId),
+        Blake2_128Concat,
+        SpendingAssetIdOf<T>,
+        SpentOf<T>,
+        OptionQuery,
+    >;
+
+    /// Accounts approving loosening of budgets of DAOs and spending over them
+    #[pallet::storage]
+    pub(super) type BudgetGuardians<T: Config> =
+        StorageMap<_, Blake2_128Concat, DaoId, T::AccountId, OptionQuery>;
+
+    /// Hashes of calls approved by budget guardians to be dispatched regardless of budgets
+    #[pallet::storage]
+    pub(super) type OverBudgetApprovals<T: Config> =
+        StorageMap<_, Blake2_128Concat, DaoId, T::Hash, OptionQuery>;
+
+    #[pallet::storage]
+    pub(super) type Invitations<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DaoId,
+        Blake2_128Concat,
+        T::AccountId,
+        InvitationOf<T>,
+        OptionQuery,
+    >;
+
+    #[pallet::storage]
+    pub(super) type JoinRequests<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DaoId,
+        Blake2_128Concat,
+        T::AccountId,
+        JoinRequestOf<T>,
+        OptionQuery,
+    >;
+
+    /// Number of pending invitations and join requests of DAOs
+    #[pallet::storage]
+    pub(super) type MembershipRequestCountByDao<T: Config> =
+        StorageMap<_, Blake2_128Concat, DaoId, u32, ValueQuery>;
+
+    /// Levels of DAOs nested in authorities of DAOs
+    #[pallet::storage]
+    pub(super) type AuthorityDepth<T: Config> =
+        StorageMap<_, Blake2_128Concat, DaoId, u16, ValueQuery>;
+
+    /// Parents DAOs have consented to be nested in
+    #[pallet::storage]
+    pub(super) type NestingConsents<T: Config> =
+        StorageMap<_, Blake2_128Concat, DaoId, DaoId, OptionQuery>;
+
+    /// Parents DAOs are nested in, keys added to authorities before their DAOs
+    /// have been created aren't nested
+    #[pallet::storage]
+    pub(super) type ParentDaos<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, DaoId, Blake2_128Concat, DaoId, (), OptionQuery>;
+
+    /// Names of dissolved DAOs
+    #[pallet::storage]
+    pub(super) type DissolvedDaos<T: Config> =
+        StorageMap<_, Blake2_128Concat, DaoId, (), OptionQuery>;
+
+    /// Votes of holders: "aye" or "nay"
+    #[pallet::storage]
+    pub(super) type ProposalVotes<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        ProposalId,
+        Blake2_128Concat,
+        T::AccountId,
+        bool,
+        OptionQuery,
+    >;
+
+    /// Closed proposals which votes are still being removed
+    #[pallet::storage]
+    pub(super) type ClosedProposals<T: Config> =
+        StorageMap<_, Blake2_128Concat, ProposalId, (), OptionQuery>;
+
     use storage_ops::*;
     /// Module contains abstractions over pallet storage operations
     pub mod storage_ops {
-        use super::{Config, DaoLookup, DaoOf, DaoRepository, Event, Pallet};
+        use super::{Config, DaoIdByMember, DaoLookup, DaoOf, DaoRepository, Event, Pallet};
         use deip_storage_ops::StorageOp;
         use sp_std::prelude::*;
 
@@ -554,6 +1447,17 @@ pub mod pallet {
             CreateDao(DaoOf<T>),
             /// Update DAO
             UpdateDao(DaoOf<T>),
+            /// Remove DAO
+            DissolveDao(DaoOf<T>),
+        }
+        fn index_members<T: Config>(dao: &DaoOf<T>, insert: bool) {
+            for member in &dao.authority().signatories {
+                if insert {
+                    DaoIdByMember::<T>::insert(member, dao.id(), ());
+                } else {
+                    DaoIdByMember::<T>::remove(member, dao.id());
+                }
+            }
         }
         impl<T: Config> StorageOp for StorageOps<T> {
             fn exec(self) {
@@ -561,11 +1465,21 @@ pub mod pallet {
                     Self::DepositEvent(e) => Pallet::<T>::deposit_event(e),
                     Self::CreateDao(dao) => {
                         DaoLookup::<T>::insert(dao.dao_key().clone(), dao.id().clone());
+                        index_members::<T>(&dao, true);
                         DaoRepository::<T>::insert(*dao.id(), dao);
                     },
                     Self::UpdateDao(dao) => {
+                        if let Some(old) = DaoRepository::<T>::get(dao.id()) {
+                            index_members::<T>(&old, false);
+                        }
+                        index_members::<T>(&dao, true);
                         DaoRepository::<T>::insert(*dao.id(), dao);
                     },
+                    Self::DissolveDao(dao) => {
+                        DaoLookup::<T>::remove(dao.dao_key());
+                        index_members::<T>(&dao, false);
+                        DaoRepository::<T>::remove(dao.id());
+                    },
                 }
             }
         }
@@ -0,0 +1,240 @@
+//! Invitations and join requests of DAO members.
+//!
+//! A member is added to the authority of DAO only when both sides agree:
+//! DAO invites an account and the account accepts the invitation,
+//! or an account requests to join and DAO accepts the request.
+//! Pending invitations and requests expire after `MembershipRequestTtl` blocks.
+//! DAO may have up to `MaxMembershipRequests` of them, it frees the slots
+//! taken by join requests by rejecting them.
+//! `MembershipRequestDeposit` is reserved from DAO for an invitation and from the requester
+//! for a join request till it's accepted, declined, pruned after expiration
+//! or DAO is dissolved.
+
+use codec::{Decode, Encode};
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    traits::{Get, ReservableCurrency},
+    RuntimeDebug,
+};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::traits::Saturating;
+use sp_std::prelude::*;
+
+use crate::{
+    dao::{load_dao, AlterAuthority, DaoId, DaoOf, LoadBy},
+    storage_ops::StorageOps,
+    Config, DepositBalanceOf, Error, Event, Invitations, JoinRequests,
+    MembershipRequestCountByDao, Pallet,
+};
+use deip_storage_ops::StorageOpsTransaction;
+
+pub type InvitationOf<T> =
+    Invitation<<T as frame_system::Config>::BlockNumber, DepositBalanceOf<T>>;
+pub type JoinRequestOf<T> =
+    JoinRequest<<T as frame_system::Config>::BlockNumber, DepositBalanceOf<T>>;
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Invitation<BlockNumber, Balance> {
+    /// Keep the threshold of the authority when the invitee joins
+    pub preserve_threshold: bool,
+    /// Last block the invitation may be accepted
+    pub expires: BlockNumber,
+    /// Deposit reserved from DAO
+    pub deposit: Balance,
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct JoinRequest<BlockNumber, Balance> {
+    /// Last block the request may be accepted
+    pub expires: BlockNumber,
+    /// Deposit reserved from the requester
+    pub deposit: Balance,
+}
+
+impl<T: Config> Pallet<T> {
+    fn membership_request_expires() -> T::BlockNumber {
+        frame_system::Pallet::<T>::block_number().saturating_add(T::MembershipRequestTtl::get())
+    }
+
+    fn is_expired(expires: T::BlockNumber) -> bool {
+        frame_system::Pallet::<T>::block_number() > expires
+    }
+
+    fn insert_invitation(dao: &DaoOf<T>, member: &T::AccountId, invitation: InvitationOf<T>) {
+        if let Some(old) = Invitations::<T>::get(dao.id(), member) {
+            T::Currency::unreserve(dao.dao_key(), old.deposit);
+        } else {
+            MembershipRequestCountByDao::<T>::mutate(dao.id(), |count| {
+                *count = count.saturating_add(1)
+            });
+        }
+        Invitations::<T>::insert(dao.id(), member, invitation);
+    }
+
+    /// Removes the invitation refunding its deposit to DAO.
+    pub(crate) fn remove_invitation(
+        id: &DaoId,
+        member: &T::AccountId,
+    ) -> Option<InvitationOf<T>> {
+        let invitation = Invitations::<T>::take(id, member)?;
+        T::Currency::unreserve(&Self::dao_key(id), invitation.deposit);
+        MembershipRequestCountByDao::<T>::mutate(id, |count| *count = count.saturating_sub(1));
+        Some(invitation)
+    }
+
+    /// Removes the join request refunding its deposit to the requester.
+    pub(crate) fn remove_join_request(
+        id: &DaoId,
+        member: &T::AccountId,
+    ) -> Option<JoinRequestOf<T>> {
+        let request = JoinRequests::<T>::take(id, member)?;
+        T::Currency::unreserve(member, request.deposit);
+        MembershipRequestCountByDao::<T>::mutate(id, |count| *count = count.saturating_sub(1));
+        Some(request)
+    }
+
+    fn ensure_membership_request_slot(id: &DaoId) -> DispatchResult {
+        ensure!(
+            MembershipRequestCountByDao::<T>::get(id) < T::MaxMembershipRequests::get(),
+            Error::<T>::TooManyMembershipRequests
+        );
+        Ok(())
+    }
+
+    fn ensure_not_member(dao: &DaoOf<T>, who: &T::AccountId) -> DispatchResult {
+        ensure!(!dao.authority().signatories.contains(who), Error::<T>::AlreadyMember);
+        Ok(())
+    }
+
+    fn join(dao: DaoOf<T>, member: T::AccountId, preserve_threshold: bool) -> DispatchResult {
+        let id = *dao.id();
+        let op = AlterAuthority::AddMember { member: member.clone(), preserve_threshold };
+        let old = dao.clone();
+        let dao = dao.alter_authoriry::<T>(op).map_err::<Error<T>, _>(Into::into)?;
+        Self::update_authority_nesting(&dao, Some(&old), Some(&member))?;
+        StorageOpsTransaction::<StorageOps<T>>::new().commit(move |ops| {
+            ops.push_op(StorageOps::UpdateDao(dao.clone()));
+            ops.push_op(StorageOps::DepositEvent(Event::<T>::DaoAlterAuthority(dao)));
+            ops.push_op(StorageOps::DepositEvent(Event::<T>::MemberJoined(id, member)));
+        });
+        Ok(())
+    }
+
+    pub(crate) fn invite_member_impl(
+        who: T::AccountId,
+        member: T::AccountId,
+        preserve_threshold: bool,
+    ) -> DispatchResult {
+        let dao = load_dao::<T>(LoadBy::DaoKey { dao_key: &who })?;
+        Self::ensure_not_member(&dao, &member)?;
+        if !Invitations::<T>::contains_key(dao.id(), &member) {
+            Self::ensure_membership_request_slot(dao.id())?;
+        }
+
+        let expires = Self::membership_request_expires();
+        let deposit = T::MembershipRequestDeposit::get();
+        T::Currency::reserve(dao.dao_key(), deposit)?;
+        let invitation = Invitation { preserve_threshold, expires, deposit };
+        Self::insert_invitation(&dao, &member, invitation);
+
+        Self::deposit_event(Event::<T>::MemberInvited(*dao.id(), member, expires));
+
+        Ok(())
+    }
+
+    pub(crate) fn accept_invitation_impl(who: T::AccountId, name: DaoId) -> DispatchResult {
+        let invitation = Invitations::<T>::get(name, &who).ok_or(Error::<T>::InvitationNotFound)?;
+        ensure!(!Self::is_expired(invitation.expires), Error::<T>::MembershipRequestExpired);
+        let dao = Self::get_dao(name).ok_or(Error::<T>::NotFound)?;
+        Self::ensure_not_member(&dao, &who)?;
+
+        Self::remove_invitation(&name, &who);
+        Self::remove_join_request(&name, &who);
+        Self::join(dao, who, invitation.preserve_threshold)
+    }
+
+    pub(crate) fn reject_invitation_impl(who: T::AccountId, name: DaoId) -> DispatchResult {
+        Self::remove_invitation(&name, &who).ok_or(Error::<T>::InvitationNotFound)?;
+
+        Self::deposit_event(Event::<T>::InvitationRejected(name, who));
+
+        Ok(())
+    }
+
+    pub(crate) fn request_to_join_impl(who: T::AccountId, name: DaoId) -> DispatchResult {
+        let dao = Self::get_dao(name).ok_or(Error::<T>::NotFound)?;
+        Self::ensure_not_member(&dao, &who)?;
+        match JoinRequests::<T>::get(name, &who) {
+            Some(request) =>
+                ensure!(Self::is_expired(request.expires), Error::<T>::JoinRequestExists),
+            None => Self::ensure_membership_request_slot(&name)?,
+        }
+
+        let expires = Self::membership_request_expires();
+        let deposit = T::MembershipRequestDeposit::get();
+        T::Currency::reserve(&who, deposit)?;
+        Self::remove_join_request(&name, &who);
+        JoinRequests::<T>::insert(name, &who, JoinRequest { expires, deposit });
+        MembershipRequestCountByDao::<T>::mutate(name, |count| *count = count.saturating_add(1));
+
+        Self::deposit_event(Event::<T>::JoinRequested(name, who, expires));
+
+        Ok(())
+    }
+
+    pub(crate) fn accept_join_request_impl(
+        who: T::AccountId,
+        member: T::AccountId,
+        preserve_threshold: bool,
+    ) -> DispatchResult {
+        let dao = load_dao::<T>(LoadBy::DaoKey { dao_key: &who })?;
+        let id = *dao.id();
+        let request = JoinRequests::<T>::get(id, &member).ok_or(Error::<T>::JoinRequestNotFound)?;
+        ensure!(!Self::is_expired(request.expires), Error::<T>::MembershipRequestExpired);
+        Self::ensure_not_member(&dao, &member)?;
+
+        Self::remove_join_request(&id, &member);
+        Self::remove_invitation(&id, &member);
+        Self::join(dao, member, preserve_threshold)
+    }
+
+    pub(crate) fn reject_join_request_impl(
+        who: T::AccountId,
+        member: T::AccountId,
+    ) -> DispatchResult {
+        let dao = load_dao::<T>(LoadBy::DaoKey { dao_key: &who })?;
+        let id = *dao.id();
+        Self::remove_join_request(&id, &member).ok_or(Error::<T>::JoinRequestNotFound)?;
+
+        Self::deposit_event(Event::<T>::JoinRequestRejected(id, member));
+
+        Ok(())
+    }
+
+    pub(crate) fn prune_membership_requests_impl(
+        name: DaoId,
+        member: T::AccountId,
+    ) -> DispatchResult {
+        let invitation_expired =
+            Invitations::<T>::get(name, &member).map_or(false, |x| Self::is_expired(x.expires));
+        let request_expired =
+            JoinRequests::<T>::get(name, &member).map_or(false, |x| Self::is_expired(x.expires));
+        ensure!(invitation_expired || request_expired, Error::<T>::MembershipRequestNotExpired);
+
+        if invitation_expired {
+            Self::remove_invitation(&name, &member);
+            Self::deposit_event(Event::<T>::InvitationExpired(name, member.clone()));
+        }
+        if request_expired {
+            Self::remove_join_request(&name, &member);
+            Self::deposit_event(Event::<T>::JoinRequestExpired(name, member));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+//! Nested authorities of DAOs.
+//!
+//! A signatory of DAO authority may be the own key of another DAO, e.g. a department
+//! inside a company. The sub-DAO signs on behalf of itself, so its approval resolved
+//! by its own authority counts as one signature in the parent.
+//!
+//! A DAO is nested only with its consent: it approves the parent by `consent_to_nesting`
+//! before being added to the authority of the parent, or joins the parent by itself.
+//!
+//! The depth of each DAO, i.e. levels of DAOs nested in its authority, is stored
+//! and limited by `MaxAuthorityDepth`. The depth of a new authority is computed from
+//! the stored depths of its signatories. A DAO nested in others can't get deeper,
+//! so depths of its parents stay valid without being updated, and cycles can't be formed.
+//!
+//! The own key of a DAO added to an authority before the DAO is created is a plain
+//! account there, so it doesn't make the DAO nested. A nested DAO can't be dissolved
+//! till it's removed from authorities of its parents.
+
+use frame_support::{dispatch::DispatchResult, ensure, traits::Get, weights::Weight};
+use sp_std::prelude::*;
+
+use crate::{
+    dao::{load_dao, DaoId, DaoOf, LoadBy},
+    AuthorityDepth, Config, DaoLookup, DaoRepository, Error, Event, NestingConsents, Pallet,
+    ParentDaos,
+};
+
+impl<T: Config> Pallet<T> {
+    /// Levels of DAOs nested in the authority of DAO.
+    pub fn authority_depth(dao: &DaoId) -> u16 {
+        AuthorityDepth::<T>::get(dao)
+    }
+
+    /// Checks the authority of `dao` being created or altered from `old` and stores its depth.
+    /// DAOs added to the authority must have consented to it, except `joined` which has
+    /// joined the authority by itself.
+    pub(crate) fn update_authority_nesting(
+        dao: &DaoOf<T>,
+        old: Option<&DaoOf<T>>,
+        joined: Option<&T::AccountId>,
+    ) -> DispatchResult {
+        let mut depth = 0;
+        let mut added = Vec::new();
+        let mut consents = Vec::new();
+        for who in &dao.authority().signatories {
+            ensure!(who != dao.dao_key(), Error::<T>::AuthorityCycle);
+            let sub = match DaoLookup::<T>::get(who) {
+                Some(sub) => sub,
+                None => continue,
+            };
+            depth = depth.max(AuthorityDepth::<T>::get(sub).saturating_add(1));
+            if old.map_or(true, |old| !old.authority().signatories.contains(who)) {
+                added.push(sub);
+                if joined != Some(who) {
+                    consents.push(sub);
+                }
+            }
+        }
+        ensure!(depth <= T::MaxAuthorityDepth::get(), Error::<T>::AuthorityTooDeep);
+
+        // a new DAO isn't nested yet
+        if let Some(old) = old {
+            if ParentDaos::<T>::iter_key_prefix(dao.id()).next().is_some() {
+                ensure!(
+                    depth <= AuthorityDepth::<T>::get(old.id()),
+                    Error::<T>::NestedAuthorityDeepened
+                );
+            }
+        }
+
+        for sub in &consents {
+            ensure!(
+                NestingConsents::<T>::get(sub) == Some(*dao.id()),
+                Error::<T>::NoNestingConsent
+            );
+        }
+        for sub in consents {
+            NestingConsents::<T>::remove(sub);
+        }
+        for sub in added {
+            ParentDaos::<T>::insert(sub, dao.id(), ());
+        }
+        if let Some(old) = old {
+            let removed = old
+                .authority()
+                .signatories
+                .iter()
+                .filter(|who| !dao.authority().signatories.contains(*who))
+                .filter_map(DaoLookup::<T>::get);
+            for sub in removed {
+                ParentDaos::<T>::remove(sub, dao.id());
+            }
+        }
+        AuthorityDepth::<T>::insert(dao.id(), depth);
+
+        Ok(())
+    }
+
+    pub(crate) fn consent_to_nesting_impl(
+        who: T::AccountId,
+        parent: Option<DaoId>,
+    ) -> DispatchResult {
+        let dao = load_dao::<T>(LoadBy::DaoKey { dao_key: &who })?;
+
+        match parent {
+            Some(parent) => NestingConsents::<T>::insert(dao.id(), parent),
+            None => NestingConsents::<T>::remove(dao.id()),
+        }
+
+        Self::deposit_event(Event::<T>::NestingConsented(*dao.id(), parent));
+
+        Ok(())
+    }
+
+    /// Stores depths and parents of all DAOs computed from their authorities.
+    pub(crate) fn backfill_authority_depth() -> Weight {
+        let (mut reads, mut writes) = (0, 0);
+        for (id, dao) in DaoRepository::<T>::iter() {
+            let depth = Self::compute_depth(&dao.authority().signatories, 0, &mut reads);
+            AuthorityDepth::<T>::insert(id, depth);
+            reads += 1;
+            writes += 1;
+            for sub in dao.authority().signatories.iter().filter_map(DaoLookup::<T>::get) {
+                ParentDaos::<T>::insert(sub, id, ());
+                writes += 1;
+            }
+        }
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+
+    /// Levels of DAOs among `signatories` and their authorities, cut above `MaxAuthorityDepth`.
+    fn compute_depth(signatories: &[T::AccountId], level: u16, reads: &mut Weight) -> u16 {
+        let mut depth = level;
+        if level > T::MaxAuthorityDepth::get() {
+            return depth
+        }
+        for who in signatories {
+            *reads += 1;
+            let sub = match DaoLookup::<T>::get(who).and_then(DaoRepository::<T>::get) {
+                Some(sub) => sub,
+                None => continue,
+            };
+            depth = depth.max(Self::compute_depth(&sub.authority().signatories, level + 1, reads));
+        }
+        depth
+    }
+}
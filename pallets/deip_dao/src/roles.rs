@@ -0,0 +1,115 @@
+//! Roles of DAO members.
+//!
+//! A role is a set of permissions defined by the runtime, each one filters
+//! calls which a member with the role may dispatch on behalf of DAO.
+//! Roles are defined, granted and revoked by DAO itself, i.e. via its authority.
+//! Roles are granted only to signatories of the authority and spend assets
+//! within their own budgets, see [`crate::budget`].
+
+use frame_support::{
+    dispatch::{DispatchResult, DispatchResultWithPostInfo},
+    ensure,
+    traits::{Get, InstanceFilter},
+};
+use sp_std::prelude::*;
+
+use crate::{
+    dao::{load_dao, DaoId, LoadBy},
+    Config, Error, Event, Pallet, RoleBudgetSpent, RoleBudgets, RoleCountByDao, RoleGrants,
+    RoleRepository,
+};
+
+pub type RoleId = sp_core::H160;
+
+impl<T: Config> Pallet<T> {
+    pub fn role(dao: &DaoId, role: &RoleId) -> Option<Vec<T::Permission>> {
+        RoleRepository::<T>::get(dao, role)
+    }
+
+    pub fn has_role(dao: &DaoId, role: &RoleId, who: &T::AccountId) -> bool {
+        RoleGrants::<T>::contains_key((dao, role), who)
+    }
+
+    pub(crate) fn define_role_impl(
+        who: T::AccountId,
+        role: RoleId,
+        permissions: Vec<T::Permission>,
+    ) -> DispatchResult {
+        let dao = load_dao::<T>(LoadBy::DaoKey { dao_key: &who })?;
+        ensure!(
+            permissions.len() <= T::MaxRolePermissions::get() as usize,
+            Error::<T>::TooMuchPermissions
+        );
+
+        if !RoleRepository::<T>::contains_key(dao.id(), role) {
+            RoleCountByDao::<T>::mutate(dao.id(), |count| *count = count.saturating_add(1));
+        }
+        RoleRepository::<T>::insert(dao.id(), role, permissions);
+
+        Self::deposit_event(Event::<T>::RoleDefined(*dao.id(), role));
+
+        Ok(())
+    }
+
+    pub(crate) fn remove_role_impl(who: T::AccountId, role: RoleId) -> DispatchResult {
+        let dao = load_dao::<T>(LoadBy::DaoKey { dao_key: &who })?;
+        ensure!(RoleRepository::<T>::contains_key(dao.id(), role), Error::<T>::RoleNotFound);
+
+        RoleRepository::<T>::remove(dao.id(), role);
+        RoleCountByDao::<T>::mutate(dao.id(), |count| *count = count.saturating_sub(1));
+        RoleGrants::<T>::remove_prefix((dao.id(), role), None);
+        RoleBudgets::<T>::remove_prefix((dao.id(), role), None);
+        RoleBudgetSpent::<T>::remove_prefix((dao.id(), role), None);
+
+        Self::deposit_event(Event::<T>::RoleRemoved(*dao.id(), role));
+
+        Ok(())
+    }
+
+    pub(crate) fn grant_role_impl(
+        who: T::AccountId,
+        role: RoleId,
+        member: T::AccountId,
+    ) -> DispatchResult {
+        let dao = load_dao::<T>(LoadBy::DaoKey { dao_key: &who })?;
+        ensure!(RoleRepository::<T>::contains_key(dao.id(), role), Error::<T>::RoleNotFound);
+        ensure!(dao.authority().signatories.contains(&member), Error::<T>::NotMember);
+
+        RoleGrants::<T>::insert((dao.id(), role), &member, ());
+
+        Self::deposit_event(Event::<T>::RoleGranted(*dao.id(), role, member));
+
+        Ok(())
+    }
+
+    pub(crate) fn revoke_role_impl(
+        who: T::AccountId,
+        role: RoleId,
+        member: T::AccountId,
+    ) -> DispatchResult {
+        let dao = load_dao::<T>(LoadBy::DaoKey { dao_key: &who })?;
+        ensure!(Self::has_role(dao.id(), &role, &member), Error::<T>::NoRole);
+
+        RoleGrants::<T>::remove((dao.id(), role), &member);
+
+        Self::deposit_event(Event::<T>::RoleRevoked(*dao.id(), role, member));
+
+        Ok(())
+    }
+
+    pub(crate) fn on_behalf_as_role_impl(
+        who: T::AccountId,
+        name: DaoId,
+        role: RoleId,
+        call: <T as Config>::Call,
+    ) -> DispatchResultWithPostInfo {
+        let dao = Self::get_dao(name).ok_or(Error::<T>::NotFound)?;
+        ensure!(Self::has_role(&name, &role, &who), Error::<T>::NoRole);
+        // grants outlive members removed from the authority
+        ensure!(dao.authority().signatories.contains(&who), Error::<T>::NotMember);
+        let permissions = RoleRepository::<T>::get(name, role).ok_or(Error::<T>::RoleNotFound)?;
+        ensure!(permissions.iter().any(|p| p.filter(&call)), Error::<T>::CallNotPermitted);
+
+        Self::dispatch_within_role_budget(&name, &role, dao.dao_key().clone(), call)
+    }
+}
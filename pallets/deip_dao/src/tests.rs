@@ -17,6 +17,7 @@ frame_support::construct_runtime!(
         UncheckedExtrinsic = UncheckedExtrinsic,
     {
         System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
         DeipDao: pallet_deip_dao::{Pallet, Call, Storage, Event<T>, Config},
     }
 );
@@ -45,7 +46,7 @@ impl frame_system::Config for TestRuntime {
     type DbWeight = ();
     type Version = ();
     type PalletInfo = PalletInfo;
-    type AccountData = ();
+    type AccountData = pallet_balances::AccountData<u64>;
     type OnNewAccount = ();
     type OnKilledAccount = ();
     type SystemWeightInfo = ();
@@ -53,20 +54,198 @@ impl frame_system::Config for TestRuntime {
     type OnSetCode = ();
 }
 
+frame_support::parameter_types! {
+    pub const ExistentialDeposit: u64 = 1;
+    pub const MaxLocks: u32 = 50;
+    pub const MaxReserves: u32 = 50;
+}
+
+impl pallet_balances::Config for TestRuntime {
+    type MaxLocks = MaxLocks;
+    type MaxReserves = MaxReserves;
+    type ReserveIdentifier = [u8; 8];
+    type Balance = u64;
+    type DustRemoval = ();
+    type Event = Event;
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
 impl crate::Config for TestRuntime {
     type Event = Event;
     type Call = Call;
     type DaoId = ();
     type DeipDaoWeightInfo = weights::Weights<Self>;
-    type MaxSignatories = ();
+    type MaxSignatories = MaxSignatories;
+    type Checkpoints = Checkpoints;
+    type Permission = Permission;
+    type MaxRolePermissions = MaxRolePermissions;
+    type Spending = Spending;
+    type Dissolution = ();
+    type MembershipRequestTtl = MembershipRequestTtl;
+    type MaxAuthorityDepth = MaxAuthorityDepth;
+    type Currency = Balances;
+    type ProposalDeposit = ProposalDeposit;
+    type MembershipRequestDeposit = MembershipRequestDeposit;
+    type MaxMembershipRequests = MaxMembershipRequests;
+    type MaxProposalLength = MaxProposalLength;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkPermission = BenchmarkPermission;
+}
+
+/// Remarks spend the asset `0` by their length, remarks with events spend unmeasured funds.
+pub struct Spending;
+
+impl crate::budget::DaoSpending<u64, Call> for Spending {
+    type AssetId = u32;
+    type Balance = u64;
+
+    fn spending(_spender: &u64, call: &Call) -> Option<Vec<(u32, u64)>> {
+        match call {
+            Call::System(frame_system::Call::remark { remark }) => {
+                Some(vec![(0, remark.len() as u64)])
+            },
+            Call::System(frame_system::Call::remark_with_event { .. }) => None,
+            _ => Some(vec![]),
+        }
+    }
+}
+
+frame_support::parameter_types! {
+    pub const MaxSignatories: u16 = 10;
+    pub const MaxRolePermissions: u16 = 2;
+    pub const MembershipRequestTtl: u64 = 10;
+    pub const MaxAuthorityDepth: u16 = 2;
+    pub const ProposalDeposit: u64 = 5;
+    pub const MembershipRequestDeposit: u64 = 3;
+    pub const MaxMembershipRequests: u32 = 2;
+    pub const MaxProposalLength: u32 = 64;
+    pub BenchmarkPermission: (Permission, Call) =
+        (Permission::Remark, Call::System(frame_system::Call::remark { remark: vec![1] }));
+}
+
+#[derive(
+    Clone,
+    Eq,
+    PartialEq,
+    codec::Encode,
+    codec::Decode,
+    sp_runtime::RuntimeDebug,
+    scale_info::TypeInfo,
+)]
+pub enum Permission {
+    Remark,
+}
+
+impl frame_support::traits::InstanceFilter<Call> for Permission {
+    fn filter(&self, c: &Call) -> bool {
+        match self {
+            Permission::Remark => matches!(
+                c,
+                Call::System(
+                    frame_system::Call::remark { .. } |
+                        frame_system::Call::remark_with_event { .. }
+                )
+            ),
+        }
+    }
+}
+
+thread_local! {
+    static BALANCES: std::cell::RefCell<Vec<(u64, u64)>> = Default::default();
+    static SNAPSHOTS: std::cell::RefCell<Vec<Vec<(u64, u64)>>> = Default::default();
+    static PINNED: std::cell::RefCell<Vec<u32>> = Default::default();
+}
+
+/// Balances of the single asset with checkpoints kept in memory.
+pub struct Checkpoints;
+
+impl Checkpoints {
+    fn set_balance(who: u64, amount: u64) {
+        BALANCES.with(|b| {
+            let mut b = b.borrow_mut();
+            b.retain(|(a, _)| *a != who);
+            b.push((who, amount));
+        });
+    }
+
+    fn pinned() -> Vec<u32> {
+        PINNED.with(|p| p.borrow().clone())
+    }
+}
+
+impl deip_asset_system::DeipCheckpoints<u64> for Checkpoints {
+    type Balance = u64;
+    type AssetId = u32;
+    type CheckpointId = u32;
+
+    fn create_checkpoint(
+        _asset: Self::AssetId,
+    ) -> Result<Self::CheckpointId, sp_runtime::DispatchError> {
+        let balances = BALANCES.with(|b| b.borrow().clone());
+        Ok(SNAPSHOTS.with(|s| {
+            s.borrow_mut().push(balances);
+            s.borrow().len() as u32
+        }))
+    }
+
+    fn pin_checkpoint(
+        asset: Self::AssetId,
+    ) -> Result<Self::CheckpointId, sp_runtime::DispatchError> {
+        let checkpoint = Self::create_checkpoint(asset)?;
+        PINNED.with(|p| p.borrow_mut().push(checkpoint));
+        Ok(checkpoint)
+    }
+
+    fn unpin_checkpoint(_asset: &Self::AssetId, checkpoint: Self::CheckpointId) {
+        PINNED.with(|p| p.borrow_mut().retain(|c| *c != checkpoint));
+    }
+
+    fn balance_at(
+        _asset: &Self::AssetId,
+        account: &u64,
+        checkpoint: Self::CheckpointId,
+    ) -> Option<Self::Balance> {
+        SNAPSHOTS.with(|s| {
+            let s = s.borrow();
+            let balances = s.get(checkpoint.checked_sub(1)? as usize)?;
+            Some(balances.iter().find(|(a, _)| a == account).map_or(0, |(_, b)| *b))
+        })
+    }
+
+    fn total_supply_at(
+        _asset: &Self::AssetId,
+        checkpoint: Self::CheckpointId,
+    ) -> Option<Self::Balance> {
+        SNAPSHOTS.with(|s| {
+            let s = s.borrow();
+            let balances = s.get(checkpoint.checked_sub(1)? as usize)?;
+            Some(balances.iter().map(|(_, b)| b).sum())
+        })
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn benchmark_mint(account: &u64, amount: Self::Balance) -> Self::AssetId {
+        let balance = BALANCES
+            .with(|b| b.borrow().iter().find(|(a, _)| a == account).map(|(_, b)| *b))
+            .unwrap_or(0);
+        Self::set_balance(*account, balance + amount);
+        0
+    }
 }
 
 pub struct ExtBuilder;
 
 impl ExtBuilder {
     pub fn build() -> sp_io::TestExternalities {
-        let storage =
+        let mut storage =
             frame_system::GenesisConfig::default().build_storage::<TestRuntime>().unwrap();
+        pallet_balances::GenesisConfig::<TestRuntime> {
+            balances: (1..=10).map(|who| (who, 100)).collect(),
+        }
+        .assimilate_storage(&mut storage)
+        .unwrap();
         sp_io::TestExternalities::from(storage)
     }
 }
@@ -95,6 +274,12 @@ fn plain_key_source(who: u64) -> InputAuthority<u64> {
     InputAuthority { signatories: vec![who], threshold: 0 }
 }
 
+/// Authority key of sorted `signatories` with its source.
+fn multisig_key_source(signatories: Vec<u64>, threshold: u16) -> (u64, InputAuthority<u64>) {
+    let key = Authority { signatories: signatories.clone(), threshold }.authority_key();
+    (key, InputAuthority { signatories, threshold })
+}
+
 #[test]
 #[ignore]
 fn fake_test_example() {
@@ -228,6 +413,774 @@ fn dao_on_behalf() {
     })
 }
 
+#[test]
+fn dao_token_governance() {
+    use crate::governance::{ProposalId, TokenGovernance};
+    use sp_runtime::Perbill;
+
+    with_test_ext(|| {
+        System::set_block_number(1);
+        let who = 1;
+        let id = DaoId::from_slice("test_dao\0\0\0\0\0\0\0\0\0\0\0\0".as_bytes());
+        DeipDao::create(Origin::signed(who), id, plain_key_source(who), None).expect("create OK");
+        let governance = TokenGovernance {
+            asset: 0,
+            quorum: Perbill::from_percent(50),
+            approval: Perbill::from_percent(60),
+            voting_period: 10,
+        };
+        assert_ok!(DeipDao::on_behalf(
+            Origin::signed(who),
+            id,
+            Box::new(Call::DeipDao(RawCall::set_token_governance {
+                governance: Some(governance)
+            }))
+        ));
+        let remark = Box::new(Call::System(frame_system::Call::remark { remark: vec![] }));
+        assert_noop!(
+            DeipDao::on_behalf(Origin::signed(who), id, remark.clone()),
+            Error::<TestRuntime>::TokenGoverned,
+        );
+
+        Checkpoints::set_balance(2, 30);
+        Checkpoints::set_balance(3, 70);
+        let proposal = ProposalId::repeat_byte(1);
+        assert_noop!(
+            DeipDao::propose(Origin::signed(who), id, proposal, remark.clone()),
+            Error::<TestRuntime>::NotTokenHolder,
+        );
+        assert_ok!(DeipDao::propose(Origin::signed(2), id, proposal, remark));
+        assert_eq!(Balances::reserved_balance(2), ProposalDeposit::get());
+
+        // balances changed after the proposal don't affect votes
+        Checkpoints::set_balance(2, 100);
+        assert_ok!(DeipDao::vote(Origin::signed(2), proposal, true));
+        assert_noop!(
+            DeipDao::vote(Origin::signed(2), proposal, true),
+            Error::<TestRuntime>::AlreadyVoted,
+        );
+        assert_noop!(
+            DeipDao::close_proposal(Origin::signed(who), proposal, 1_000_000_000),
+            Error::<TestRuntime>::VotingNotClosed,
+        );
+
+        assert_ok!(DeipDao::vote(Origin::signed(3), proposal, true));
+        assert_ok!(DeipDao::close_proposal(Origin::signed(who), proposal, 1_000_000_000));
+        expect_event(RawEvent::<TestRuntime>::ProposalExecuted(proposal, Ok(())));
+        assert_eq!(Balances::reserved_balance(2), 0);
+    })
+}
+
+#[test]
+fn dao_proposal_deposit_and_length() {
+    use crate::governance::{ProposalId, TokenGovernance};
+    use sp_runtime::Perbill;
+
+    with_test_ext(|| {
+        System::set_block_number(1);
+        let who = 1;
+        let poor = 11;
+        let id = DaoId::from_slice("test_dao\0\0\0\0\0\0\0\0\0\0\0\0".as_bytes());
+        DeipDao::create(Origin::signed(who), id, plain_key_source(who), None).expect("create OK");
+        let governance = TokenGovernance {
+            asset: 0,
+            quorum: Perbill::from_percent(50),
+            approval: Perbill::from_percent(50),
+            voting_period: 10,
+        };
+        assert_ok!(DeipDao::on_behalf(
+            Origin::signed(who),
+            id,
+            Box::new(Call::DeipDao(RawCall::set_token_governance {
+                governance: Some(governance)
+            }))
+        ));
+        Checkpoints::set_balance(2, 50);
+        Checkpoints::set_balance(poor, 50);
+        let remark = |len: usize| {
+            Box::new(Call::System(frame_system::Call::remark { remark: vec![0; len] }))
+        };
+        let proposal = ProposalId::repeat_byte(1);
+
+        assert_noop!(
+            DeipDao::propose(Origin::signed(2), id, proposal, remark(64)),
+            Error::<TestRuntime>::ProposalTooLong,
+        );
+        assert_noop!(
+            DeipDao::propose(Origin::signed(poor), id, proposal, remark(0)),
+            pallet_balances::Error::<TestRuntime>::InsufficientBalance,
+        );
+
+        assert_ok!(DeipDao::propose(Origin::signed(2), id, proposal, remark(0)));
+        assert_eq!(Balances::reserved_balance(2), ProposalDeposit::get());
+
+        // the deposit is returned for rejected proposals too
+        System::set_block_number(12);
+        assert_ok!(DeipDao::close_proposal(Origin::signed(who), proposal, 1_000_000_000));
+        expect_event(RawEvent::<TestRuntime>::ProposalRejected(proposal));
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert_eq!(Balances::free_balance(2), 100);
+    })
+}
+
+#[test]
+fn dao_proposal_checkpoint_and_votes_released_on_close() {
+    use crate::governance::{ProposalId, TokenGovernance, MAX_VOTES_REMOVED_PER_BLOCK};
+    use frame_support::traits::Hooks;
+    use sp_runtime::Perbill;
+
+    let who = 1;
+    let id = DaoId::from_slice("test_dao\0\0\0\0\0\0\0\0\0\0\0\0".as_bytes());
+    let proposal = ProposalId::repeat_byte(1);
+    let remark = Box::new(Call::System(frame_system::Call::remark { remark: vec![] }));
+    let voters = 2..MAX_VOTES_REMOVED_PER_BLOCK as u64 + 12;
+
+    let mut ext = ExtBuilder::build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        DeipDao::create(Origin::signed(who), id, plain_key_source(who), None).expect("create OK");
+        let governance = TokenGovernance {
+            asset: 0,
+            quorum: Perbill::from_percent(50),
+            approval: Perbill::from_percent(50),
+            voting_period: 10,
+        };
+        assert_ok!(DeipDao::on_behalf(
+            Origin::signed(who),
+            id,
+            Box::new(Call::DeipDao(RawCall::set_token_governance {
+                governance: Some(governance)
+            }))
+        ));
+        voters.clone().for_each(|voter| Checkpoints::set_balance(voter, 1));
+
+        assert_ok!(DeipDao::propose(Origin::signed(2), id, proposal, remark.clone()));
+        let checkpoint = DeipDao::proposal(&proposal).unwrap().checkpoint;
+        assert_eq!(Checkpoints::pinned(), vec![checkpoint]);
+        for voter in voters.clone() {
+            assert_ok!(DeipDao::vote(Origin::signed(voter), proposal, true));
+        }
+    });
+    // votes are removed from the overlay without a limit
+    ext.commit_all().unwrap();
+
+    ext.execute_with(|| {
+        assert_ok!(DeipDao::close_proposal(Origin::signed(who), proposal, 1_000_000_000));
+        assert!(Checkpoints::pinned().is_empty());
+        assert_eq!(ProposalVotes::<TestRuntime>::iter_prefix(proposal).count(), 10);
+        // the id isn't reused till the votes are removed
+        assert_noop!(
+            DeipDao::propose(Origin::signed(2), id, proposal, remark.clone()),
+            Error::<TestRuntime>::ProposalExists,
+        );
+
+        System::set_block_number(2);
+        DeipDao::on_initialize(2);
+        assert_eq!(ProposalVotes::<TestRuntime>::iter_prefix(proposal).count(), 0);
+        assert!(!ClosedProposals::<TestRuntime>::contains_key(proposal));
+        assert_ok!(DeipDao::propose(Origin::signed(2), id, proposal, remark));
+    });
+}
+
+#[test]
+fn dao_roles() {
+    use crate::budget::Budget;
+    use crate::roles::RoleId;
+
+    with_test_ext(|| {
+        System::set_block_number(1);
+        let who = 1;
+        let member = 2;
+        let outsider = 3;
+        let (key, authority) = multisig_key_source(vec![who, member], 1);
+        let id = DaoId::from_slice("test_dao\0\0\0\0\0\0\0\0\0\0\0\0".as_bytes());
+        DeipDao::create(Origin::signed(key), id, authority, None).expect("create OK");
+        let role = RoleId::repeat_byte(1);
+        let remark = |len: usize| {
+            Box::new(Call::System(frame_system::Call::remark { remark: vec![0; len] }))
+        };
+        let on_behalf = |call: RawCall<TestRuntime>| {
+            DeipDao::on_behalf(Origin::signed(key), id, Box::new(Call::DeipDao(call)))
+        };
+
+        assert_noop!(
+            on_behalf(RawCall::grant_role { role, member }),
+            Error::<TestRuntime>::RoleNotFound,
+        );
+        assert_noop!(
+            on_behalf(RawCall::define_role { role, permissions: vec![Permission::Remark; 3] }),
+            Error::<TestRuntime>::TooMuchPermissions,
+        );
+        assert_ok!(on_behalf(RawCall::define_role { role, permissions: vec![Permission::Remark] }));
+        assert_noop!(
+            DeipDao::on_behalf_as_role(Origin::signed(member), id, role, remark(0)),
+            Error::<TestRuntime>::NoRole,
+        );
+        assert_noop!(
+            on_behalf(RawCall::grant_role { role, member: outsider }),
+            Error::<TestRuntime>::NotMember,
+        );
+
+        assert_ok!(on_behalf(RawCall::grant_role { role, member }));
+        expect_event(RawEvent::<TestRuntime>::RoleGranted(id, role, member));
+        assert_ok!(DeipDao::on_behalf_as_role(Origin::signed(member), id, role, remark(0)));
+        assert_noop!(
+            DeipDao::on_behalf_as_role(
+                Origin::signed(member),
+                id,
+                role,
+                Box::new(Call::DeipDao(RawCall::remove_role { role }))
+            ),
+            Error::<TestRuntime>::CallNotPermitted,
+        );
+
+        // roles spend only within their own budgets
+        assert_noop!(
+            DeipDao::on_behalf_as_role(
+                Origin::signed(member),
+                id,
+                role,
+                Box::new(Call::System(frame_system::Call::remark_with_event { remark: vec![] }))
+            ),
+            Error::<TestRuntime>::UnmeasuredSpending,
+        );
+        assert_noop!(
+            DeipDao::on_behalf_as_role(Origin::signed(member), id, role, remark(3)),
+            Error::<TestRuntime>::RoleBudgetExceeded,
+        );
+        let budget = Some(Budget { limit: 5, period: 10 });
+        assert_ok!(on_behalf(RawCall::set_role_budget { role, asset: 0, budget }));
+        assert_ok!(DeipDao::on_behalf_as_role(Origin::signed(member), id, role, remark(3)));
+        assert_eq!(DeipDao::remaining_role_budget(&id, &role, &0), Some(2));
+        assert_noop!(
+            DeipDao::on_behalf_as_role(Origin::signed(member), id, role, remark(3)),
+            Error::<TestRuntime>::RoleBudgetExceeded,
+        );
+        System::set_block_number(10);
+        assert_ok!(DeipDao::on_behalf_as_role(Origin::signed(member), id, role, remark(3)));
+
+        assert_ok!(on_behalf(RawCall::revoke_role { role, member }));
+        assert_noop!(
+            DeipDao::on_behalf_as_role(Origin::signed(member), id, role, remark(0)),
+            Error::<TestRuntime>::NoRole,
+        );
+
+        assert_ok!(on_behalf(RawCall::remove_role { role }));
+        assert_eq!(DeipDao::role_budget(&id, &role, &0), None);
+    })
+}
+
+#[test]
+fn dao_role_of_removed_member() {
+    use crate::dao::AlterAuthority;
+    use crate::roles::RoleId;
+
+    with_test_ext(|| {
+        System::set_block_number(1);
+        let who = 1;
+        let member = 2;
+        let (key, authority) = multisig_key_source(vec![who, member], 1);
+        let id = DaoId::from_slice("test_dao\0\0\0\0\0\0\0\0\0\0\0\0".as_bytes());
+        DeipDao::create(Origin::signed(key), id, authority, None).expect("create OK");
+        let role = RoleId::repeat_byte(1);
+        let remark = Box::new(Call::System(frame_system::Call::remark { remark: vec![] }));
+        let on_behalf = |call: RawCall<TestRuntime>| {
+            DeipDao::on_behalf(Origin::signed(key), id, Box::new(Call::DeipDao(call)))
+        };
+        assert_ok!(on_behalf(RawCall::define_role { role, permissions: vec![Permission::Remark] }));
+        assert_ok!(on_behalf(RawCall::grant_role { role, member }));
+
+        let authority = AlterAuthority::ReplaceAuthority {
+            authority_key: who,
+            authority: plain_key_source(who),
+        };
+        assert_ok!(on_behalf(RawCall::alter_authority { authority }));
+        assert_noop!(
+            DeipDao::on_behalf_as_role(Origin::signed(member), id, role, remark),
+            Error::<TestRuntime>::NotMember,
+        );
+    })
+}
+
+#[test]
+fn dao_budget() {
+    use crate::budget::Budget;
+    use sp_runtime::traits::{BlakeTwo256, Hash};
+
+    with_test_ext(|| {
+        System::set_block_number(1);
+        let who = 1;
+        let guardian = 3;
+        let id = DaoId::from_slice("test_dao\0\0\0\0\0\0\0\0\0\0\0\0".as_bytes());
+        DeipDao::create(Origin::signed(who), id, plain_key_source(who), None).expect("create OK");
+        let remark = |len: usize| {
+            Box::new(Call::System(frame_system::Call::remark { remark: vec![0; len] }))
+        };
+        let unmeasured =
+            || Box::new(Call::System(frame_system::Call::remark_with_event { remark: vec![] }));
+        let on_behalf = |call: RawCall<TestRuntime>| {
+            DeipDao::on_behalf(Origin::signed(who), id, Box::new(Call::DeipDao(call)))
+        };
+        assert_eq!(DeipDao::remaining_budget(&id, &0), None);
+        assert_ok!(DeipDao::on_behalf(Origin::signed(who), id, unmeasured()));
+
+        let budget = Some(Budget { limit: 5, period: 10 });
+        assert_ok!(on_behalf(RawCall::set_budget { asset: 0, budget }));
+        assert_ok!(DeipDao::on_behalf(Origin::signed(who), id, remark(3)));
+        assert_eq!(DeipDao::remaining_budget(&id, &0), Some(2));
+        assert_noop!(
+            DeipDao::on_behalf(Origin::signed(who), id, remark(3)),
+            Error::<TestRuntime>::BudgetExceeded,
+        );
+        assert_noop!(
+            DeipDao::on_behalf(Origin::signed(who), id, unmeasured()),
+            Error::<TestRuntime>::UnmeasuredSpending,
+        );
+        assert_noop!(
+            DeipDao::on_behalf_over_budget(Origin::signed(2), id, remark(3)),
+            Error::<TestRuntime>::Forbidden,
+        );
+        // the authority overrides budgets on its own while there's no guardian
+        assert_ok!(DeipDao::on_behalf_over_budget(Origin::signed(who), id, unmeasured()));
+        expect_event(RawEvent::<TestRuntime>::BudgetOverridden(id));
+
+        assert_ok!(on_behalf(RawCall::set_budget_guardian { name: id, guardian: Some(guardian) }));
+        expect_event(RawEvent::<TestRuntime>::BudgetGuardianSet(id, Some(guardian)));
+        assert_noop!(
+            on_behalf(RawCall::set_budget_guardian { name: id, guardian: None }),
+            Error::<TestRuntime>::Forbidden,
+        );
+        let budget = Some(Budget { limit: 10, period: 10 });
+        assert_noop!(
+            on_behalf(RawCall::set_budget { asset: 0, budget }),
+            Error::<TestRuntime>::BudgetGuarded,
+        );
+        assert_noop!(
+            on_behalf(RawCall::set_budget { asset: 0, budget: None }),
+            Error::<TestRuntime>::BudgetGuarded,
+        );
+        let budget = Some(Budget { limit: 4, period: 10 });
+        assert_ok!(on_behalf(RawCall::set_budget { asset: 0, budget }));
+        assert_eq!(DeipDao::remaining_budget(&id, &0), Some(1));
+        let budget = Some(Budget { limit: 5, period: 10 });
+        assert_noop!(
+            DeipDao::set_budget_by_guardian(Origin::signed(who), id, 0, budget.clone()),
+            Error::<TestRuntime>::Forbidden,
+        );
+        assert_ok!(DeipDao::set_budget_by_guardian(Origin::signed(guardian), id, 0, budget));
+        assert_eq!(DeipDao::remaining_budget(&id, &0), Some(2));
+
+        assert_noop!(
+            DeipDao::on_behalf_over_budget(Origin::signed(who), id, remark(3)),
+            Error::<TestRuntime>::OverBudgetNotApproved,
+        );
+        let call_hash = BlakeTwo256::hash_of(&*remark(3));
+        assert_noop!(
+            DeipDao::approve_over_budget(Origin::signed(who), id, call_hash),
+            Error::<TestRuntime>::Forbidden,
+        );
+        assert_ok!(DeipDao::approve_over_budget(Origin::signed(guardian), id, call_hash));
+        expect_event(RawEvent::<TestRuntime>::OverBudgetApproved(id, call_hash));
+        assert_ok!(DeipDao::on_behalf_over_budget(Origin::signed(who), id, remark(3)));
+        expect_event(RawEvent::<TestRuntime>::BudgetOverridden(id));
+        assert_eq!(DeipDao::remaining_budget(&id, &0), Some(0));
+        assert_noop!(
+            DeipDao::on_behalf_over_budget(Origin::signed(who), id, remark(3)),
+            Error::<TestRuntime>::OverBudgetNotApproved,
+        );
+
+        let call_hash = BlakeTwo256::hash_of(&*unmeasured());
+        assert_ok!(DeipDao::approve_over_budget(Origin::signed(guardian), id, call_hash));
+        assert_ok!(DeipDao::on_behalf_over_budget(Origin::signed(who), id, unmeasured()));
+
+        System::set_block_number(10);
+        assert_eq!(DeipDao::remaining_budget(&id, &0), Some(5));
+        assert_ok!(DeipDao::on_behalf(Origin::signed(who), id, remark(5)));
+
+        assert_ok!(DeipDao::set_budget_guardian(Origin::signed(guardian), id, None));
+        assert_ok!(on_behalf(RawCall::set_budget { asset: 0, budget: None }));
+        assert_ok!(DeipDao::on_behalf(Origin::signed(who), id, unmeasured()));
+    })
+}
+
+#[test]
+fn dao_member_index() {
+    use crate::dao::AlterAuthority;
+
+    with_test_ext(|| {
+        System::set_block_number(1);
+        let who = 1;
+        let member = 2;
+        let id = DaoId::from_slice("test_dao\0\0\0\0\0\0\0\0\0\0\0\0".as_bytes());
+        DeipDao::create(Origin::signed(who), id, plain_key_source(who), None).expect("create OK");
+        assert!(DaoIdByMember::<TestRuntime>::contains_key(who, id));
+
+        let authority = AlterAuthority::ReplaceAuthority {
+            authority_key: member,
+            authority: plain_key_source(member),
+        };
+        assert_ok!(DeipDao::on_behalf(
+            Origin::signed(who),
+            id,
+            Box::new(Call::DeipDao(RawCall::alter_authority { authority }))
+        ));
+        assert!(!DaoIdByMember::<TestRuntime>::contains_key(who, id));
+        assert!(DaoIdByMember::<TestRuntime>::contains_key(member, id));
+    })
+}
+
+#[test]
+fn dao_membership() {
+    with_test_ext(|| {
+        System::set_block_number(1);
+        let who = 1;
+        let invitee = 2;
+        let requester = 3;
+        let id = DaoId::from_slice("test_dao\0\0\0\0\0\0\0\0\0\0\0\0".as_bytes());
+        DeipDao::create(Origin::signed(who), id, plain_key_source(who), None).expect("create OK");
+        let dao_key = DeipDao::dao_key(&id);
+        Balances::make_free_balance_be(&dao_key, 100);
+        let deposit = MembershipRequestDeposit::get();
+        let on_behalf = |call: RawCall<TestRuntime>| {
+            DeipDao::on_behalf(Origin::signed(who), id, Box::new(Call::DeipDao(call)))
+        };
+
+        assert_noop!(
+            DeipDao::request_to_join(Origin::signed(who), id),
+            Error::<TestRuntime>::AlreadyMember,
+        );
+        assert_ok!(DeipDao::request_to_join(Origin::signed(requester), id));
+        assert_eq!(Balances::reserved_balance(requester), deposit);
+        assert_noop!(
+            DeipDao::request_to_join(Origin::signed(requester), id),
+            Error::<TestRuntime>::JoinRequestExists,
+        );
+        assert_ok!(on_behalf(RawCall::reject_join_request { member: requester }));
+        expect_event(RawEvent::<TestRuntime>::JoinRequestRejected(id, requester));
+        assert_eq!(Balances::reserved_balance(requester), 0);
+        assert_noop!(
+            on_behalf(RawCall::accept_join_request { member: requester, preserve_threshold: true }),
+            Error::<TestRuntime>::JoinRequestNotFound,
+        );
+        assert_ok!(DeipDao::request_to_join(Origin::signed(requester), id));
+
+        assert_ok!(on_behalf(RawCall::invite_member { member: invitee, preserve_threshold: true }));
+        expect_event(RawEvent::<TestRuntime>::MemberInvited(id, invitee, 11));
+        assert_eq!(Balances::reserved_balance(dao_key), deposit);
+        assert_eq!(MembershipRequestCountByDao::<TestRuntime>::get(id), 2);
+        assert_noop!(
+            DeipDao::request_to_join(Origin::signed(4), id),
+            Error::<TestRuntime>::TooManyMembershipRequests,
+        );
+        assert_noop!(
+            on_behalf(RawCall::invite_member { member: 4, preserve_threshold: true }),
+            Error::<TestRuntime>::TooManyMembershipRequests,
+        );
+        assert_noop!(
+            DeipDao::accept_invitation(Origin::signed(requester), id),
+            Error::<TestRuntime>::InvitationNotFound,
+        );
+        assert_noop!(
+            DeipDao::prune_membership_requests(Origin::signed(who), id, invitee),
+            Error::<TestRuntime>::MembershipRequestNotExpired,
+        );
+
+        System::set_block_number(12);
+        assert_noop!(
+            on_behalf(RawCall::accept_join_request { member: requester, preserve_threshold: true }),
+            Error::<TestRuntime>::MembershipRequestExpired,
+        );
+        assert_noop!(
+            DeipDao::accept_invitation(Origin::signed(invitee), id),
+            Error::<TestRuntime>::MembershipRequestExpired,
+        );
+
+        // anyone prunes expired requests refunding their deposits
+        assert_ok!(DeipDao::prune_membership_requests(Origin::signed(who), id, requester));
+        expect_event(RawEvent::<TestRuntime>::JoinRequestExpired(id, requester));
+        assert_eq!(Balances::reserved_balance(requester), 0);
+        assert!(!JoinRequests::<TestRuntime>::contains_key(id, requester));
+
+        // inviting again replaces the expired invitation
+        assert_ok!(on_behalf(RawCall::invite_member { member: invitee, preserve_threshold: true }));
+        assert_eq!(Balances::reserved_balance(dao_key), deposit);
+        assert_eq!(MembershipRequestCountByDao::<TestRuntime>::get(id), 1);
+        assert_ok!(DeipDao::accept_invitation(Origin::signed(invitee), id));
+        expect_event(RawEvent::<TestRuntime>::MemberJoined(id, invitee));
+        assert_eq!(Balances::reserved_balance(dao_key), 0);
+        assert_eq!(MembershipRequestCountByDao::<TestRuntime>::get(id), 0);
+        assert!(DeipDao::get_dao(id).unwrap().authority().signatories.contains(&invitee));
+        assert!(DaoIdByMember::<TestRuntime>::contains_key(invitee, id));
+        assert_noop!(
+            DeipDao::accept_invitation(Origin::signed(invitee), id),
+            Error::<TestRuntime>::InvitationNotFound,
+        );
+    })
+}
+
+#[test]
+fn dao_dissolve() {
+    use crate::dissolution::DissolveWitness;
+    use crate::roles::RoleId;
+
+    with_test_ext(|| {
+        System::set_block_number(1);
+        let who = 1;
+        let beneficiary = 2;
+        let id = DaoId::from_slice("test_dao\0\0\0\0\0\0\0\0\0\0\0\0".as_bytes());
+        DeipDao::create(Origin::signed(who), id, plain_key_source(who), None).expect("create OK");
+        let on_behalf = |call: RawCall<TestRuntime>| {
+            DeipDao::on_behalf(Origin::signed(who), id, Box::new(Call::DeipDao(call)))
+        };
+        let dao_key = DeipDao::dao_key(&id);
+        Balances::make_free_balance_be(&dao_key, 100);
+        let witness = DissolveWitness { roles: 2, holdings: 0, membership_requests: 2 };
+        assert_noop!(
+            DeipDao::dissolve(Origin::signed(who), beneficiary, witness),
+            Error::<TestRuntime>::NotFound,
+        );
+
+        for role in vec![RoleId::repeat_byte(1), RoleId::repeat_byte(2)] {
+            let permissions = vec![Permission::Remark];
+            assert_ok!(on_behalf(RawCall::define_role { role, permissions }));
+        }
+        // redefinition isn't counted
+        let role = RoleId::repeat_byte(1);
+        assert_ok!(on_behalf(RawCall::define_role { role, permissions: vec![] }));
+        assert_eq!(RoleCountByDao::<TestRuntime>::get(id), 2);
+        assert_ok!(on_behalf(RawCall::invite_member { member: 3, preserve_threshold: true }));
+        assert_ok!(DeipDao::request_to_join(Origin::signed(4), id));
+
+        for understated in vec![
+            DissolveWitness { roles: 1, ..witness },
+            DissolveWitness { membership_requests: 1, ..witness },
+        ] {
+            assert_noop!(
+                on_behalf(RawCall::dissolve { beneficiary, witness: understated }),
+                Error::<TestRuntime>::WrongDissolveWitness,
+            );
+        }
+
+        assert_ok!(on_behalf(RawCall::dissolve { beneficiary, witness }));
+        expect_event(RawEvent::<TestRuntime>::DaoDissolved(id, beneficiary));
+        assert!(DeipDao::get_dao(id).is_none());
+        assert!(DeipDao::lookup_dao(DeipDao::dao_key(&id)).is_none());
+        assert_eq!(RoleCountByDao::<TestRuntime>::get(id), 0);
+        assert_eq!(RoleRepository::<TestRuntime>::iter_prefix(id).count(), 0);
+        assert!(!Invitations::<TestRuntime>::contains_key(id, 3));
+        assert!(!JoinRequests::<TestRuntime>::contains_key(id, 4));
+        assert_eq!(MembershipRequestCountByDao::<TestRuntime>::get(id), 0);
+        // deposits are refunded before balances of DAO are swept
+        assert_eq!(Balances::reserved_balance(4), 0);
+        assert_eq!(Balances::reserved_balance(dao_key), 0);
+        assert_eq!(Balances::free_balance(dao_key), 100);
+        assert_noop!(
+            on_behalf(RawCall::dissolve { beneficiary, witness }),
+            Error::<TestRuntime>::NotFound,
+        );
+        assert_noop!(
+            DeipDao::create(Origin::signed(who), id, plain_key_source(who), None),
+            Error::<TestRuntime>::Dissolved,
+        );
+    })
+}
+
+#[test]
+fn dao_dissolve_with_pending_proposals() {
+    use crate::dissolution::DissolveWitness;
+    use crate::governance::{ProposalId, TokenGovernance};
+    use sp_runtime::Perbill;
+
+    with_test_ext(|| {
+        System::set_block_number(1);
+        let who = 1;
+        let holder = 2;
+        let id = DaoId::from_slice("test_dao\0\0\0\0\0\0\0\0\0\0\0\0".as_bytes());
+        DeipDao::create(Origin::signed(who), id, plain_key_source(who), None).expect("create OK");
+        let governance = TokenGovernance {
+            asset: 0,
+            quorum: Perbill::from_percent(50),
+            approval: Perbill::from_percent(60),
+            voting_period: 10,
+        };
+        assert_ok!(DeipDao::on_behalf(
+            Origin::signed(who),
+            id,
+            Box::new(Call::DeipDao(RawCall::set_token_governance {
+                governance: Some(governance)
+            }))
+        ));
+        Checkpoints::set_balance(holder, 100);
+
+        let remark = ProposalId::repeat_byte(1);
+        let dissolve = ProposalId::repeat_byte(2);
+        let witness = DissolveWitness::default();
+        let calls = vec![
+            (remark, Call::System(frame_system::Call::remark { remark: vec![] })),
+            (dissolve, Call::DeipDao(RawCall::dissolve { beneficiary: holder, witness })),
+        ];
+        for (proposal, call) in calls {
+            assert_ok!(DeipDao::propose(Origin::signed(holder), id, proposal, Box::new(call)));
+            assert_ok!(DeipDao::vote(Origin::signed(holder), proposal, true));
+        }
+        assert_eq!(ProposalCountByDao::<TestRuntime>::get(id), 2);
+
+        System::set_block_number(12);
+        assert_ok!(DeipDao::close_proposal(Origin::signed(who), dissolve, 1_000_000_000));
+        let error = Error::<TestRuntime>::HasPendingProposals.into();
+        expect_event(RawEvent::<TestRuntime>::ProposalExecuted(dissolve, Err(error)));
+        assert!(DeipDao::get_dao(id).is_some());
+        assert_eq!(ProposalCountByDao::<TestRuntime>::get(id), 1);
+
+        assert_ok!(DeipDao::close_proposal(Origin::signed(who), remark, 1_000_000_000));
+        assert_eq!(ProposalCountByDao::<TestRuntime>::get(id), 0);
+    })
+}
+
+#[test]
+fn dao_nested_authority() {
+    use crate::dao::AlterAuthority;
+    use crate::dissolution::DissolveWitness;
+
+    with_test_ext(|| {
+        System::set_block_number(1);
+        let who = 1;
+        let company = DaoId::from_slice("company\0\0\0\0\0\0\0\0\0\0\0\0\0".as_bytes());
+        let department = DaoId::from_slice("department\0\0\0\0\0\0\0\0\0\0".as_bytes());
+        let team = DaoId::from_slice("team\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0".as_bytes());
+        let group = DaoId::from_slice("group\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0".as_bytes());
+        let company_key = DeipDao::dao_key(&company);
+        let department_key = DeipDao::dao_key(&department);
+        let team_key = DeipDao::dao_key(&team);
+        let on_behalf = |name: DaoId, call: Call| {
+            Call::DeipDao(RawCall::on_behalf { name, call: Box::new(call) })
+        };
+        let create = |name: DaoId, authority_key: u64| {
+            let authority = plain_key_source(authority_key);
+            Call::DeipDao(RawCall::create { name, authority, metadata: None })
+        };
+        let consent = |parent: DaoId| {
+            Call::DeipDao(RawCall::consent_to_nesting { parent: Some(parent) })
+        };
+        DeipDao::create(Origin::signed(who), company, plain_key_source(who), None)
+            .expect("create OK");
+
+        // The company has to consent to be nested in the department
+        assert_noop!(
+            DeipDao::on_behalf(
+                Origin::signed(who),
+                company,
+                Box::new(create(department, company_key))
+            ),
+            Error::<TestRuntime>::NoNestingConsent,
+        );
+        assert_ok!(DeipDao::on_behalf(Origin::signed(who), company, Box::new(consent(department))));
+        assert_ok!(DeipDao::on_behalf(
+            Origin::signed(who),
+            company,
+            Box::new(create(department, company_key))
+        ));
+        assert!(DaoIdByMember::<TestRuntime>::contains_key(company_key, department));
+        assert_eq!(NestingConsents::<TestRuntime>::get(company), None);
+        assert_eq!(DeipDao::authority_depth(&company), 0);
+        assert_eq!(DeipDao::authority_depth(&department), 1);
+        assert!(ParentDaos::<TestRuntime>::contains_key(company, department));
+
+        // The company can't be dissolved while it's a signatory of the department
+        let dissolve =
+            RawCall::dissolve { beneficiary: who, witness: DissolveWitness::default() };
+        assert_noop!(
+            DeipDao::on_behalf(Origin::signed(who), company, Box::new(Call::DeipDao(dissolve))),
+            Error::<TestRuntime>::IsMemberOfDao,
+        );
+
+        // Approval of the company counts as a signature in the department
+        let update = Call::DeipDao(RawCall::update_dao { new_metadata: None });
+        assert_ok!(DeipDao::on_behalf(
+            Origin::signed(who),
+            company,
+            Box::new(on_behalf(department, update))
+        ));
+
+        let authority = AlterAuthority::ReplaceAuthority {
+            authority_key: department_key,
+            authority: plain_key_source(department_key),
+        };
+        assert_noop!(
+            DeipDao::on_behalf(
+                Origin::signed(who),
+                company,
+                Box::new(Call::DeipDao(RawCall::alter_authority { authority }))
+            ),
+            Error::<TestRuntime>::NestedAuthorityDeepened,
+        );
+
+        assert_ok!(DeipDao::on_behalf(
+            Origin::signed(who),
+            company,
+            Box::new(on_behalf(department, consent(team)))
+        ));
+        assert_ok!(DeipDao::on_behalf(
+            Origin::signed(who),
+            company,
+            Box::new(on_behalf(department, create(team, department_key)))
+        ));
+        assert_eq!(DeipDao::authority_depth(&team), 2);
+        assert_noop!(
+            DeipDao::on_behalf(
+                Origin::signed(who),
+                company,
+                Box::new(on_behalf(department, on_behalf(team, create(group, team_key))))
+            ),
+            Error::<TestRuntime>::AuthorityTooDeep,
+        );
+    })
+}
+
+#[test]
+fn dao_nesting_of_preregistered_key() {
+    use crate::dao::AlterAuthority;
+
+    with_test_ext(|| {
+        System::set_block_number(1);
+        let who = 1;
+        let squatter = DaoId::from_slice("squatter\0\0\0\0\0\0\0\0\0\0\0\0".as_bytes());
+        let victim = DaoId::from_slice("victim\0\0\0\0\0\0\0\0\0\0\0\0\0\0".as_bytes());
+        let sub = DaoId::from_slice("sub\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0".as_bytes());
+        let victim_key = DeipDao::dao_key(&victim);
+        let sub_key = DeipDao::dao_key(&sub);
+
+        // The key of the victim is added to an authority before the victim is created
+        let mut signatories = vec![5, victim_key];
+        signatories.sort();
+        let (squatter_key, authority) = multisig_key_source(signatories, 1);
+        assert_ok!(DeipDao::create(Origin::signed(squatter_key), squatter, authority, None));
+        assert!(DaoIdByMember::<TestRuntime>::contains_key(victim_key, squatter));
+
+        assert_ok!(DeipDao::create(Origin::signed(who), victim, plain_key_source(who), None));
+        assert!(!ParentDaos::<TestRuntime>::contains_key(victim, squatter));
+
+        // The victim isn't nested in the squatter, so its authority may get deeper
+        assert_ok!(DeipDao::create(Origin::signed(who), sub, plain_key_source(who), None));
+        assert_ok!(DeipDao::on_behalf(
+            Origin::signed(who),
+            sub,
+            Box::new(Call::DeipDao(RawCall::consent_to_nesting { parent: Some(victim) }))
+        ));
+        let authority = AlterAuthority::ReplaceAuthority {
+            authority_key: sub_key,
+            authority: plain_key_source(sub_key),
+        };
+        assert_ok!(DeipDao::on_behalf(
+            Origin::signed(who),
+            victim,
+            Box::new(Call::DeipDao(RawCall::alter_authority { authority }))
+        ));
+        assert_eq!(DeipDao::authority_depth(&victim), 1);
+        assert!(ParentDaos::<TestRuntime>::contains_key(sub, victim));
+    })
+}
+
 // #[test]
 // fn dao_on_behalf_not_found() {
 //     with_test_ext(|| {
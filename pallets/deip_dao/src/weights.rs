@@ -42,6 +42,29 @@ pub trait WeightInfo {
     fn alter_authority_replace_authority(m: u32) -> Weight;
     fn update_dao() -> Weight;
     fn on_behalf() -> Weight;
+    fn set_token_governance() -> Weight;
+    fn propose(l: u32) -> Weight;
+    fn vote() -> Weight;
+    fn close_proposal() -> Weight;
+    fn define_role(p: u32) -> Weight;
+    fn remove_role(m: u32) -> Weight;
+    fn grant_role() -> Weight;
+    fn revoke_role() -> Weight;
+    fn on_behalf_as_role() -> Weight;
+    fn set_budget() -> Weight;
+    fn set_role_budget() -> Weight;
+    fn set_budget_guardian() -> Weight;
+    fn approve_over_budget() -> Weight;
+    fn on_behalf_over_budget() -> Weight;
+    fn dissolve(r: u32, h: u32, m: u32) -> Weight;
+    fn invite_member() -> Weight;
+    fn accept_invitation() -> Weight;
+    fn reject_invitation() -> Weight;
+    fn request_to_join() -> Weight;
+    fn accept_join_request() -> Weight;
+    fn reject_join_request() -> Weight;
+    fn prune_membership_requests() -> Weight;
+    fn consent_to_nesting() -> Weight;
 }
 
 /// Weight functions for pallet_deip_dao.
@@ -52,35 +75,37 @@ impl<T: frame_system::Config> WeightInfo for Weights<T> {
         (82_598_000 as Weight)
             // Standard Error: 4_000
             .saturating_add((479_000 as Weight).saturating_mul(m as Weight))
-            .saturating_add(T::DbWeight::get().reads(1 as Weight))
-            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(m as Weight)))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
     }
     fn alter_authority_add_member() -> Weight {
         (135_686_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(2 as Weight))
-            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
     }
     fn alter_authority_add_member_preserve_threshold() -> Weight {
         (135_061_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(2 as Weight))
-            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
     }
     fn alter_authority_remove_member() -> Weight {
         (130_946_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(2 as Weight))
-            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
     }
     fn alter_authority_remove_member_preserve_threshold() -> Weight {
         (131_643_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(2 as Weight))
-            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
     }
     fn alter_authority_replace_authority(m: u32) -> Weight {
         (96_851_000 as Weight)
             // Standard Error: 7_000
             .saturating_add((715_000 as Weight).saturating_mul(m as Weight))
-            .saturating_add(T::DbWeight::get().reads(2 as Weight))
-            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(m as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
     }
     fn update_dao() -> Weight {
         (89_137_000 as Weight)
@@ -91,4 +116,133 @@ impl<T: frame_system::Config> WeightInfo for Weights<T> {
         (23_231_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(1 as Weight))
     }
+    // Estimated by storage accesses including the checkpoint of the asset
+    // till the weights below are regenerated from `benchmarking.rs`.
+    fn set_token_governance() -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn propose(l: u32) -> Weight {
+        (90_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(T::DbWeight::get().reads(12 as Weight))
+            .saturating_add(T::DbWeight::get().writes(9 as Weight))
+    }
+    fn vote() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn close_proposal() -> Weight {
+        (70_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(8 as Weight))
+    }
+    fn define_role(p: u32) -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add((500_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn remove_role(m: u32) -> Weight {
+        (50_000_000 as Weight)
+            .saturating_add((2_000_000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(m as Weight))
+    }
+    fn grant_role() -> Weight {
+        (45_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn revoke_role() -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn on_behalf_as_role() -> Weight {
+        (45_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn set_budget() -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn set_role_budget() -> Weight {
+        (40_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn set_budget_guardian() -> Weight {
+        (35_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn approve_over_budget() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn on_behalf_over_budget() -> Weight {
+        (45_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn dissolve(r: u32, h: u32, m: u32) -> Weight {
+        (150_000_000 as Weight)
+            .saturating_add((10_000_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add((40_000_000 as Weight).saturating_mul(h as Weight))
+            .saturating_add((15_000_000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(T::DbWeight::get().reads(14 as Weight))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(h as Weight)))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(m as Weight)))
+            .saturating_add(T::DbWeight::get().writes(18 as Weight))
+            .saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(h as Weight)))
+            .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(m as Weight)))
+    }
+    fn invite_member() -> Weight {
+        (55_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn accept_invitation() -> Weight {
+        (90_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(10 as Weight))
+            .saturating_add(T::DbWeight::get().writes(11 as Weight))
+    }
+    fn reject_invitation() -> Weight {
+        (45_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn request_to_join() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn accept_join_request() -> Weight {
+        (90_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(11 as Weight))
+            .saturating_add(T::DbWeight::get().writes(11 as Weight))
+    }
+    fn reject_join_request() -> Weight {
+        (50_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn prune_membership_requests() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    fn consent_to_nesting() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
 }
@@ -196,6 +196,8 @@ fn _add_balance<T: Config + AssetsConfig + DeipAssetsConfig + BalancesConfig>(
         asset_id.clone(),
         asset_admin.clone(),
         min_balance.clone(),
+        None,
+        None,
     ).unwrap();
 
     DeipAssets::<T>::deip_mint(
@@ -228,6 +230,8 @@ fn _create_asset<T: Config + AssetsConfig + DeipAssetsConfig + BalancesConfig>(
         asset_id,
         asset_admin,
         min_balance,
+        None,
+        None,
     )
 }
 
@@ -271,7 +275,8 @@ fn init_funding_model<T: Config>(investment: &Investment<T>) -> FundingModelOf<T
         start_time,
         end_time,
         soft_cap,
-        hard_cap
+        hard_cap,
+        perks: None,
     }
 }
 
@@ -423,6 +428,7 @@ fn pre_simple_crowdfunding<T: Config + DeipAssetsConfig + BalancesConfig>(
         end_time,
         soft_cap: DeipAsset::<T>::new(asset_id, soft_cap.0),
         hard_cap: DeipAsset::<T>::new(asset_id, hard_cap.0),
+        perks: None,
     };
     PreSimpleCrowdfunding::<T> {
         investment,
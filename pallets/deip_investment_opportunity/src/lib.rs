@@ -58,10 +58,14 @@ pub mod pallet {
     };
 
     use sp_core::H256;
-    use crate::module::{InvestmentId, FundingModelOf, DeipAsset, DeipAssetBalance, DeipAssetId};
+    use crate::module::{
+        InvestmentId, FundingModelOf, DeipAsset, DeipAssetBalance, DeipAssetId, NftClassIdOf,
+        NftInstanceIdOf,
+    };
 
     use crate::weights::WeightInfo;
-    use deip_asset_system::DeipAssetSystem;
+    use deip_asset_system::{DeipAssetSystem, DeipNftSystem};
+    use deip_projects_info::DeipProjectsInfo;
     use deip_transaction_ctx::{PortalCtxT, TransactionCtxId};
 
     /// Configuration trait
@@ -87,6 +91,18 @@ pub mod pallet {
 
         type SourceId: Member + Parameter;
 
+        /// Additional project info.
+        type ProjectsInfo: DeipProjectsInfo<Self::AccountId, ProjectId = Self::SourceId>;
+
+        /// NFT system contribution perks are minted with.
+        type NftSystem: DeipNftSystem<Self::AccountId>;
+
+        #[pallet::constant]
+        type MaxContributionTiers: Get<u16>;
+
+        /// Max number of contribution perks minted in a block.
+        #[pallet::constant]
+        type MaxPerkMintsPerBlock: Get<u32>;
     }
 
     use frame_support::traits::StorageVersion;
@@ -102,6 +118,10 @@ pub mod pallet {
     #[doc(hidden)]
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+            Self::mint_pending_perks()
+        }
+
         fn offchain_worker(_n: BlockNumberFor<T>) {
             if !sp_io::offchain::is_validator() {
                 return;
@@ -193,6 +213,14 @@ pub mod pallet {
         InvestingWrongAsset,
         /// Access Forbdden
         NoPermission,
+        // Possible errors when contribution perks are specified
+        TooMuchTiers,
+        TiersNotSpecified,
+        TierWrongAsset,
+        TierThresholdMustBePositive,
+        ProjectDoesNotExist,
+        ProjectDoesNotBelongToTeam,
+        TierClassDoesNotBelongToTeam,
     }
 
     #[pallet::event]
@@ -208,6 +236,12 @@ pub mod pallet {
         SimpleCrowdfundingExpired(InvestmentId),
         /// Event emitted when DAO invested to an opportunity
         Invested(InvestmentId, T::AccountId),
+        /// Event emitted when a contribution perk has been minted:
+        /// investment, investor, class, instance.
+        ContributionPerkMinted(InvestmentId, T::AccountId, NftClassIdOf<T>, NftInstanceIdOf<T>),
+        /// Event emitted when a contribution perk couldn't be minted:
+        /// investment, investor, class.
+        ContributionPerkMintFailed(InvestmentId, T::AccountId, NftClassIdOf<T>),
     }
 
     #[doc(hidden)]
@@ -308,7 +342,7 @@ pub mod pallet {
 
     // ==== Storage ====:
 
-    use crate::module::{Investment, SimpleCrowdfundingOf};
+    use crate::module::{Investment, SimpleCrowdfundingOf, ContributionPerksOf};
 
     #[pallet::storage]
     pub type InvestmentMapV1<T: Config> = StorageMap<_,
@@ -323,4 +357,20 @@ pub mod pallet {
         InvestmentId,
         SimpleCrowdfundingOf<T>,
     >;
+
+    /// Contribution perks of crowdfundings, tiers are sorted by threshold descending.
+    #[pallet::storage]
+    pub type ContributionPerksMap<T: Config> = StorageMap<_,
+        Blake2_128Concat,
+        InvestmentId,
+        ContributionPerksOf<T>,
+    >;
+
+    /// Contribution perks of finished crowdfundings waiting to be minted.
+    #[pallet::storage]
+    pub type PendingContributionPerks<T: Config> = StorageMap<_,
+        Blake2_128Concat,
+        InvestmentId,
+        Vec<(T::AccountId, NftClassIdOf<T>)>,
+    >;
 }
@@ -15,13 +15,17 @@ use frame_support::{ensure, RuntimeDebug};
 use frame_support::dispatch::{DispatchResult, DispatchResultWithPostInfo};
 use frame_support::log::{debug};
 use frame_support::traits::{Get};
+use frame_support::weights::Weight;
 use scale_info::TypeInfo;
 use sp_std::prelude::*;
 use crate::{Config, Error, Event, Call, Pallet};
-use deip_asset_system::{DeipAssetSystem, ReserveError, UnreserveError};
+use deip_asset_system::{DeipAssetSystem, DeipNftSystem, ReserveError, UnreserveError};
+use deip_projects_info::DeipProjectsInfo;
 pub use deip_asset_system::investment_opportunity::*;
 pub use deip_asset_system::asset::*;
-use crate::{SimpleCrowdfundingMapV1, InvestmentMapV1};
+use crate::{
+    SimpleCrowdfundingMapV1, InvestmentMapV1, ContributionPerksMap, PendingContributionPerks,
+};
 use crate::weights::WeightInfo;
 
 pub type DeipAssetId<T: Config> =
@@ -32,7 +36,17 @@ pub type DeipAssetBalance<T: Config> =
 
 pub type DeipAsset<T: Config> = Asset<DeipAssetId<T>, DeipAssetBalance<T>>;
 
-pub type FundingModelOf<T: Config> = FundingModel<T::Moment, DeipAsset<T>>;
+pub type NftClassIdOf<T: Config> =
+    <<T as Config>::NftSystem as DeipNftSystem<T::AccountId>>::ClassId;
+
+pub type NftInstanceIdOf<T: Config> =
+    <<T as Config>::NftSystem as DeipNftSystem<T::AccountId>>::InstanceId;
+
+pub type FundingModelOf<T: Config> =
+    FundingModel<T::Moment, DeipAsset<T>, T::SourceId, NftClassIdOf<T>>;
+
+pub type ContributionPerksOf<T: Config> =
+    ContributionPerks<DeipAsset<T>, T::SourceId, NftClassIdOf<T>>;
 
 pub type SimpleCrowdfundingOf<T: Config> = SimpleCrowdfunding<
     T::Moment,
@@ -62,7 +76,18 @@ impl<T: Config> Pallet<T> {
         );
 
         match funding_model {
-            FundingModel::SimpleCrowdfunding { start_time, end_time, soft_cap, hard_cap } =>
+            FundingModel::SimpleCrowdfunding {
+                start_time,
+                end_time,
+                soft_cap,
+                hard_cap,
+                perks,
+            } => {
+                let perks = match perks {
+                    Some(p) => Some(Self::ensure_contribution_perks(&account, soft_cap.id(), p)?),
+                    None => None,
+                };
+
                 Self::create_simple_crowdfunding(
                     account,
                     external_id,
@@ -71,8 +96,48 @@ impl<T: Config> Pallet<T> {
                     soft_cap,
                     hard_cap,
                     shares,
-                ),
+                )?;
+
+                if let Some(p) = perks {
+                    ContributionPerksMap::<T>::insert(external_id, p);
+                }
+
+                Ok(())
+            },
+        }
+    }
+
+    /// Checks tiers of `perks` and sorts them by threshold descending.
+    fn ensure_contribution_perks(
+        account: &T::AccountId,
+        asset_id: &DeipAssetId<T>,
+        mut perks: ContributionPerksOf<T>,
+    ) -> Result<ContributionPerksOf<T>, Error<T>> {
+        ensure!(!perks.tiers.is_empty(), Error::<T>::TiersNotSpecified);
+        ensure!(
+            perks.tiers.len() <= T::MaxContributionTiers::get() as usize,
+            Error::<T>::TooMuchTiers
+        );
+
+        let team = T::ProjectsInfo::try_get_project_team(&perks.project_id)
+            .ok_or(Error::<T>::ProjectDoesNotExist)?;
+        ensure!(&team == account, Error::<T>::ProjectDoesNotBelongToTeam);
+
+        for tier in &perks.tiers {
+            ensure!(tier.threshold.id() == asset_id, Error::<T>::TierWrongAsset);
+            ensure!(
+                tier.threshold.amount() > &Zero::zero(),
+                Error::<T>::TierThresholdMustBePositive
+            );
+            ensure!(
+                T::NftSystem::class_owner(&tier.class).as_ref() == Some(&team),
+                Error::<T>::TierClassDoesNotBelongToTeam
+            );
         }
+
+        perks.tiers.sort_by(|a, b| b.threshold.amount().cmp(a.threshold.amount()));
+
+        Ok(perks)
     }
 
     pub(super) fn create_simple_crowdfunding(
@@ -298,6 +363,8 @@ impl<T: Config> Pallet<T> {
         T::transactionally_unreserve(sale.external_id)
             .unwrap_or_else(|_| panic!("assets should be reserved earlier"));
 
+        ContributionPerksMap::<T>::remove(sale.external_id);
+
         Self::deposit_event(Event::SimpleCrowdfundingExpired(sale.external_id));
     }
 
@@ -349,6 +416,13 @@ impl<T: Config> Pallet<T> {
         T::transactionally_unreserve(sale.external_id)
             .unwrap_or_else(|_| panic!("remaining assets should be reserved earlier"));
 
+        if let Some(perks) = ContributionPerksMap::<T>::take(sale.external_id) {
+            let pending = Self::contribution_perks(&perks, &contributions);
+            if !pending.is_empty() {
+                PendingContributionPerks::<T>::insert(sale.external_id, pending);
+            }
+        }
+
         for (_, ref contribution) in contributions {
             frame_system::Pallet::<T>::dec_consumers(&contribution.owner);
         }
@@ -357,6 +431,65 @@ impl<T: Config> Pallet<T> {
         Self::deposit_event(Event::SimpleCrowdfundingFinished(sale.external_id));
     }
 
+    /// Class of the highest reached tier for every qualifying investor.
+    fn contribution_perks(
+        perks: &ContributionPerksOf<T>,
+        contributions: &[(T::AccountId, Investment<T>)],
+    ) -> Vec<(T::AccountId, NftClassIdOf<T>)> {
+        contributions
+            .iter()
+            .filter_map(|(_, contribution)| {
+                perks
+                    .tiers
+                    .iter()
+                    .find(|t| t.threshold.amount() <= &contribution.amount)
+                    .map(|t| (contribution.owner.clone(), t.class))
+            })
+            .collect()
+    }
+
+    /// Mints pending contribution perks on behalf of the issuers of their classes.
+    /// At most `MaxPerkMintsPerBlock` perks are minted in a block.
+    pub(super) fn mint_pending_perks() -> Weight {
+        let mut weight = T::DbWeight::get().reads(1);
+        let mut budget = T::MaxPerkMintsPerBlock::get() as usize;
+
+        while budget > 0 {
+            let (id, mut pending) = match PendingContributionPerks::<T>::iter().next() {
+                Some(entry) => entry,
+                None => break,
+            };
+            let rest = pending.split_off(budget.min(pending.len()));
+            budget -= pending.len();
+
+            for (owner, class) in pending {
+                let minted = T::NftSystem::class_issuer(&class)
+                    .ok_or(())
+                    .and_then(|issuer| T::NftSystem::mint(&issuer, class, &owner));
+                match minted {
+                    Ok(instance) => Self::deposit_event(Event::ContributionPerkMinted(
+                        id, owner, class, instance,
+                    )),
+                    Err(_) => {
+                        Self::deposit_event(Event::ContributionPerkMintFailed(id, owner, class))
+                    },
+                }
+                weight = weight
+                    .saturating_add(T::NftSystem::mint_weight())
+                    .saturating_add(T::DbWeight::get().reads(2));
+            }
+
+            if rest.is_empty() {
+                PendingContributionPerks::<T>::remove(id);
+            } else {
+                PendingContributionPerks::<T>::insert(id, rest);
+            }
+            weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+        }
+
+        weight
+    }
+
     pub(super) fn invest_to_crowdfunding_impl(
         account: T::AccountId,
         sale_id: InvestmentId,
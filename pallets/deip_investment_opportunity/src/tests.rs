@@ -2,10 +2,18 @@
 #![allow(dead_code)]
 #![allow(unused_variables)]
 
-use super::{Call as RawCall, *};
+use super::{Event as RawEvent, *};
 use crate as pallet_deip_investment_opportunity;
+use crate::module::{ContributionPerks, ContributionTier, DeipAsset, FundingModel, InvestmentId};
 
-use sp_std::prelude::*;
+use deip_asset_system::{
+    AssetIdInitT, DeipAssetSystem, DeipNftSystem, ReserveError, UnreserveError,
+};
+use deip_projects_info::DeipProjectsInfo;
+use deip_transaction_ctx::{TransactionCtxId, TransactionCtxT};
+use sp_core::H160;
+use sp_runtime::{traits::Dispatchable, DispatchError, DispatchResult, DispatchResultWithInfo};
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap, prelude::*};
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
 type Block = frame_system::mocking::MockBlock<TestRuntime>;
@@ -17,14 +25,29 @@ frame_support::construct_runtime!(
         UncheckedExtrinsic = UncheckedExtrinsic,
     {
         System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-        DeipDao: pallet_deip_investment_opportunity::{Pallet, Call, Storage, Config},
+        Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+        DeipInvestmentOpportunity: pallet_deip_investment_opportunity::{
+            Pallet, Call, Storage, Event<T>, ValidateUnsigned
+        },
     }
 );
 
+impl<C> SendTransactionTypes<C> for TestRuntime
+where
+    Call: From<C>,
+{
+    type Extrinsic = UncheckedExtrinsic;
+    type OverarchingCall = Call;
+}
+
 frame_support::parameter_types! {
     pub const BlockHashCount: u64 = 250;
     pub BlockWeights: frame_system::limits::BlockWeights =
         frame_system::limits::BlockWeights::simple_max(1024);
+    pub const MinimumPeriod: u64 = 1;
+    pub const MaxInvestmentShares: u16 = 10;
+    pub const MaxContributionTiers: u16 = 3;
+    pub const MaxPerkMintsPerBlock: u32 = 1;
 }
 
 impl frame_system::Config for TestRuntime {
@@ -53,9 +76,303 @@ impl frame_system::Config for TestRuntime {
     type OnSetCode = ();
 }
 
+impl pallet_timestamp::Config for TestRuntime {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = MinimumPeriod;
+    type WeightInfo = ();
+}
+
 impl crate::Config for TestRuntime {
-    // type Event = Event;
-    // type Call = Call;
+    type DeipInvestmentWeightInfo = crate::weights::Weights<Self>;
+    type Event = Event;
+    type TransactionCtx = TestCtx;
+    type DeipAccountId = u64;
+    type MaxInvestmentShares = MaxInvestmentShares;
+    type SourceId = H160;
+    type ProjectsInfo = Self;
+    type NftSystem = TestNfts;
+    type MaxContributionTiers = MaxContributionTiers;
+    type MaxPerkMintsPerBlock = MaxPerkMintsPerBlock;
+}
+
+#[derive(Clone, Default, Eq, PartialEq, scale_info::TypeInfo)]
+pub struct TestCtx;
+
+impl TransactionCtxT for TestCtx {
+    type BlockNumber = u64;
+    type ExtrinsicId = u32;
+
+    fn current() -> Self {
+        Self
+    }
+
+    fn block_number(&self) -> Self::BlockNumber {
+        System::block_number()
+    }
+
+    fn extrinsic_id(&self) -> Self::ExtrinsicId {
+        0
+    }
+
+    fn id(&self) -> TransactionCtxId<Self> {
+        TransactionCtxId { block_number: self.block_number(), extrinsic_id: self.extrinsic_id() }
+    }
+
+    fn extrinsic_data(&self) -> Vec<u8> {
+        Vec::new()
+    }
+}
+
+impl<LocalCall> deip_transaction_ctx::PortalCtxT<LocalCall> for TestCtx {
+    type PortalId = ();
+
+    fn portal_id(_ctx: &TransactionCtxId<Self>) -> Self::PortalId {}
+
+    fn dispatch<D: Dispatchable>(
+        &self,
+        _portal_id: Self::PortalId,
+        call: D,
+        origin: D::Origin,
+    ) -> DispatchResultWithInfo<D::PostInfo> {
+        call.dispatch(origin)
+    }
+
+    fn submit_postponed(_call: LocalCall, _ctx: TransactionCtxId<Self>) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+const TEAM: u64 = 1;
+const ISSUER: u64 = 2;
+const ALICE: u64 = 3;
+const BOB: u64 = 4;
+const CHARLIE: u64 = 5;
+
+const GOLD: u32 = 1;
+const SILVER: u32 = 2;
+
+fn project() -> H160 {
+    H160::from_low_u64_be(1)
+}
+
+fn usd() -> H160 {
+    H160::from_low_u64_be(10)
+}
+
+fn token() -> H160 {
+    H160::from_low_u64_be(11)
+}
+
+impl DeipProjectsInfo<u64> for TestRuntime {
+    type ProjectId = H160;
+    type InvestmentId = H160;
+
+    fn try_get_project_team(id: &Self::ProjectId) -> Option<u64> {
+        if *id == project() {
+            Some(TEAM)
+        } else {
+            None
+        }
+    }
+
+    fn project_id(source: &[u8]) -> Self::ProjectId {
+        H160::from_slice(&sp_io::hashing::blake2_256(source)[..20])
+    }
+}
+
+thread_local! {
+    static BALANCES: RefCell<BTreeMap<(u64, H160), u64>> = RefCell::new(BTreeMap::new());
+    static RESERVES: RefCell<BTreeMap<InvestmentId, (u64, H160)>> = RefCell::new(BTreeMap::new());
+    static RESERVED: RefCell<BTreeMap<(InvestmentId, H160), u64>> = RefCell::new(BTreeMap::new());
+    /// class -> (owner, issuer)
+    static CLASSES: RefCell<BTreeMap<u32, (u64, u64)>> = RefCell::new(BTreeMap::new());
+    /// (class, instance) -> owner
+    static INSTANCES: RefCell<BTreeMap<(u32, u32), u64>> = RefCell::new(BTreeMap::new());
+}
+
+fn balance(account: u64, asset: H160) -> u64 {
+    BALANCES.with(|b| b.borrow().get(&(account, asset)).copied().unwrap_or_default())
+}
+
+fn set_balance(account: u64, asset: H160, amount: u64) {
+    BALANCES.with(|b| b.borrow_mut().insert((account, asset), amount));
+}
+
+fn withdraw(account: u64, asset: H160, amount: u64) -> Result<(), ()> {
+    let free = balance(account, asset);
+    if free < amount {
+        return Err(())
+    }
+    set_balance(account, asset, free - amount);
+    Ok(())
+}
+
+fn reserved(id: InvestmentId, asset: H160) -> u64 {
+    RESERVED.with(|r| r.borrow().get(&(id, asset)).copied().unwrap_or_default())
+}
+
+fn set_reserved(id: InvestmentId, asset: H160, amount: u64) {
+    RESERVED.with(|r| r.borrow_mut().insert((id, asset), amount));
+}
+
+impl AssetIdInitT<H160> for TestRuntime {
+    fn asset_id(raw: &[u8]) -> H160 {
+        H160::from_slice(raw)
+    }
+}
+
+impl DeipAssetSystem<u64, H160, InvestmentId> for TestRuntime {
+    type Balance = u64;
+    type AssetId = H160;
+
+    fn account_balance(account: &u64, asset: &Self::AssetId) -> Self::Balance {
+        balance(*account, *asset)
+    }
+
+    fn total_supply(asset: &Self::AssetId) -> Self::Balance {
+        BALANCES.with(|b| {
+            b.borrow().iter().filter(|((_, a), _)| a == asset).map(|(_, amount)| amount).sum()
+        })
+    }
+
+    fn minimum_balance(_asset: &Self::AssetId) -> Self::Balance {
+        0
+    }
+
+    fn transactionally_transfer(
+        from: &u64,
+        asset: Self::AssetId,
+        transfers: &[(Self::Balance, u64)],
+    ) -> DispatchResult {
+        let total = transfers.iter().map(|(amount, _)| amount).sum();
+        withdraw(*from, asset, total).map_err(|_| DispatchError::Other("BalanceLow"))?;
+        for (amount, to) in transfers {
+            set_balance(*to, asset, balance(*to, asset) + amount);
+        }
+        Ok(())
+    }
+
+    fn transactionally_reserve(
+        account: &u64,
+        id: InvestmentId,
+        shares: &[(Self::AssetId, Self::Balance)],
+        asset: Self::AssetId,
+    ) -> Result<(), ReserveError<Self::AssetId>> {
+        if RESERVES.with(|r| r.borrow().contains_key(&id)) {
+            return Err(ReserveError::AlreadyReserved)
+        }
+        if shares.iter().any(|(share, amount)| balance(*account, *share) < *amount) {
+            return Err(ReserveError::NotEnoughBalance)
+        }
+        for (share, amount) in shares {
+            withdraw(*account, *share, *amount).expect("checked above");
+            set_reserved(id, *share, *amount);
+        }
+        RESERVES.with(|r| r.borrow_mut().insert(id, (*account, asset)));
+        Ok(())
+    }
+
+    fn transactionally_reserve_escrow(
+        account: &u64,
+        id: InvestmentId,
+        shares: &[(Self::AssetId, Self::Balance)],
+        asset: Self::AssetId,
+    ) -> Result<(), ReserveError<Self::AssetId>> {
+        Self::transactionally_reserve(account, id, shares, asset)
+    }
+
+    fn transactionally_unreserve(id: InvestmentId) -> Result<(), UnreserveError<Self::AssetId>> {
+        let (owner, _) = RESERVES
+            .with(|r| r.borrow_mut().remove(&id))
+            .ok_or(UnreserveError::NoSuchInvestment)?;
+        let assets: Vec<_> = RESERVED.with(|r| {
+            let mut r = r.borrow_mut();
+            let keys: Vec<_> = r.keys().filter(|(i, _)| *i == id).copied().collect();
+            keys.into_iter().map(|key| (key.1, r.remove(&key).unwrap())).collect()
+        });
+        for (asset, amount) in assets {
+            set_balance(owner, asset, balance(owner, asset) + amount);
+        }
+        Ok(())
+    }
+
+    fn transfer_from_reserved(
+        id: InvestmentId,
+        who: &u64,
+        asset: Self::AssetId,
+        amount: Self::Balance,
+    ) -> Result<(), UnreserveError<Self::AssetId>> {
+        let left = reserved(id, asset)
+            .checked_sub(amount)
+            .ok_or(UnreserveError::AssetTransferFailed(asset))?;
+        set_reserved(id, asset, left);
+        set_balance(*who, asset, balance(*who, asset) + amount);
+        Ok(())
+    }
+
+    fn transfer_to_reserved(
+        who: &u64,
+        id: InvestmentId,
+        amount: Self::Balance,
+    ) -> Result<(), UnreserveError<Self::AssetId>> {
+        let (_, asset) = RESERVES
+            .with(|r| r.borrow().get(&id).copied())
+            .ok_or(UnreserveError::NoSuchInvestment)?;
+        withdraw(*who, asset, amount).map_err(|_| UnreserveError::AssetTransferFailed(asset))?;
+        set_reserved(id, asset, reserved(id, asset) + amount);
+        Ok(())
+    }
+}
+
+pub struct TestNfts;
+
+impl TestNfts {
+    fn create_class(class: u32, owner: u64, issuer: u64) {
+        CLASSES.with(|c| c.borrow_mut().insert(class, (owner, issuer)));
+    }
+
+    fn destroy_class(class: u32) {
+        CLASSES.with(|c| c.borrow_mut().remove(&class));
+    }
+}
+
+impl DeipNftSystem<u64> for TestNfts {
+    type ClassId = u32;
+    type InstanceId = u32;
+
+    fn class_owner(class: &Self::ClassId) -> Option<u64> {
+        CLASSES.with(|c| c.borrow().get(class).map(|(owner, _)| *owner))
+    }
+
+    fn class_issuer(class: &Self::ClassId) -> Option<u64> {
+        CLASSES.with(|c| c.borrow().get(class).map(|(_, issuer)| *issuer))
+    }
+
+    fn holder(class: &Self::ClassId, instance: &Self::InstanceId) -> Option<u64> {
+        INSTANCES.with(|i| i.borrow().get(&(*class, *instance)).copied())
+    }
+
+    /// Like `pallet_uniques::mint`, only the issuer of the class may mint.
+    fn mint(issuer: &u64, class: Self::ClassId, owner: &u64) -> Result<Self::InstanceId, ()> {
+        if Self::class_issuer(&class).as_ref() != Some(issuer) {
+            return Err(())
+        }
+        INSTANCES.with(|i| {
+            let mut i = i.borrow_mut();
+            let instance = i.keys().filter(|(c, _)| *c == class).count() as u32;
+            i.insert((class, instance), *owner);
+            Ok(instance)
+        })
+    }
+
+    fn mint_weight() -> Weight {
+        1
+    }
+
+    fn is_holder(class: &Self::ClassId, account: &u64) -> bool {
+        INSTANCES.with(|i| i.borrow().iter().any(|((c, _), o)| c == class && o == account))
+    }
 }
 
 pub struct ExtBuilder;
@@ -72,9 +389,12 @@ fn with_test_ext<R>(t: impl FnOnce() -> R) -> R {
     ExtBuilder::build().execute_with(t)
 }
 
-use frame_support::{assert_noop, assert_ok, traits::Everything};
-use frame_system::RawOrigin;
-use sp_std::str::FromStr;
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{Everything, Hooks},
+    weights::Weight,
+};
+use frame_system::{offchain::SendTransactionTypes, RawOrigin};
 
 fn last_event() -> Event {
     frame_system::Pallet::<TestRuntime>::events()
@@ -87,10 +407,130 @@ fn expect_event<E: Into<Event>>(e: E) {
     assert_eq!(last_event(), e.into());
 }
 
+fn tier(threshold: u64, class: u32) -> ContributionTier<DeipAsset<TestRuntime>, u32> {
+    ContributionTier { threshold: DeipAsset::<TestRuntime>::new(usd(), threshold), class }
+}
+
+/// Creates an active crowdfunding of 1000 tokens raising from 100 to 300 USD
+/// with silver perks from 50 USD and gold perks from 150 USD.
+fn create_crowdfunding(id: InvestmentId) {
+    TestNfts::create_class(GOLD, TEAM, ISSUER);
+    TestNfts::create_class(SILVER, TEAM, ISSUER);
+    set_balance(TEAM, token(), 1000);
+
+    assert_ok!(DeipInvestmentOpportunity::create_investment_opportunity(
+        Origin::signed(TEAM),
+        id,
+        TEAM,
+        vec![DeipAsset::<TestRuntime>::new(token(), 1000)],
+        FundingModel::SimpleCrowdfunding {
+            start_time: 0,
+            end_time: 100,
+            soft_cap: DeipAsset::<TestRuntime>::new(usd(), 100),
+            hard_cap: DeipAsset::<TestRuntime>::new(usd(), 300),
+            perks: Some(ContributionPerks {
+                project_id: project(),
+                tiers: vec![tier(50, SILVER), tier(150, GOLD)],
+            }),
+        },
+    ));
+    assert_ok!(DeipInvestmentOpportunity::activate_crowdfunding(RawOrigin::None.into(), id));
+}
+
+/// Reaches the hard cap: Alice qualifies for gold, Bob for nothing, Charlie for silver.
+fn invest_to_hard_cap(id: InvestmentId) {
+    for (who, amount) in [(ALICE, 160), (BOB, 40), (CHARLIE, 100)] {
+        set_balance(who, usd(), amount);
+        assert_ok!(DeipInvestmentOpportunity::invest(
+            Origin::signed(who),
+            id,
+            DeipAsset::<TestRuntime>::new(usd(), amount),
+        ));
+    }
+}
+
+#[test]
+fn contribution_perks_are_minted_by_issuer_in_next_blocks() {
+    with_test_ext(|| {
+        System::set_block_number(1);
+        let id = H160::from_low_u64_be(100);
+        create_crowdfunding(id);
+        invest_to_hard_cap(id);
+
+        expect_event(RawEvent::SimpleCrowdfundingFinished(id));
+        assert!(!ContributionPerksMap::<TestRuntime>::contains_key(id));
+        assert_eq!(
+            PendingContributionPerks::<TestRuntime>::get(id),
+            Some(vec![(ALICE, GOLD), (CHARLIE, SILVER)])
+        );
+        assert_eq!(TestNfts::owner(&GOLD, &0), None);
+
+        // one perk per block
+        System::set_block_number(2);
+        DeipInvestmentOpportunity::on_initialize(2);
+        expect_event(RawEvent::ContributionPerkMinted(id, ALICE, GOLD, 0));
+        assert_eq!(TestNfts::owner(&GOLD, &0), Some(ALICE));
+        assert_eq!(
+            PendingContributionPerks::<TestRuntime>::get(id),
+            Some(vec![(CHARLIE, SILVER)])
+        );
+
+        System::set_block_number(3);
+        DeipInvestmentOpportunity::on_initialize(3);
+        expect_event(RawEvent::ContributionPerkMinted(id, CHARLIE, SILVER, 0));
+        assert_eq!(TestNfts::owner(&SILVER, &0), Some(CHARLIE));
+        assert!(!PendingContributionPerks::<TestRuntime>::contains_key(id));
+        assert!(!TestNfts::is_holder(&GOLD, &BOB));
+        assert!(!TestNfts::is_holder(&SILVER, &BOB));
+    })
+}
+
 #[test]
-#[ignore]
-fn fake_test_example() {
+fn contribution_perk_mint_failure_is_recorded() {
     with_test_ext(|| {
-        // ...test conditions...
+        System::set_block_number(1);
+        let id = H160::from_low_u64_be(100);
+        create_crowdfunding(id);
+        invest_to_hard_cap(id);
+
+        TestNfts::destroy_class(GOLD);
+
+        System::set_block_number(2);
+        DeipInvestmentOpportunity::on_initialize(2);
+        expect_event(RawEvent::ContributionPerkMintFailed(id, ALICE, GOLD));
+
+        System::set_block_number(3);
+        DeipInvestmentOpportunity::on_initialize(3);
+        expect_event(RawEvent::ContributionPerkMinted(id, CHARLIE, SILVER, 0));
+        assert!(!PendingContributionPerks::<TestRuntime>::contains_key(id));
+    })
+}
+
+#[test]
+fn contribution_perks_require_team_classes() {
+    with_test_ext(|| {
+        let id = H160::from_low_u64_be(100);
+        TestNfts::create_class(GOLD, ISSUER, ISSUER);
+        set_balance(TEAM, token(), 1000);
+
+        assert_noop!(
+            DeipInvestmentOpportunity::create_investment_opportunity(
+                Origin::signed(TEAM),
+                id,
+                TEAM,
+                vec![DeipAsset::<TestRuntime>::new(token(), 1000)],
+                FundingModel::SimpleCrowdfunding {
+                    start_time: 0,
+                    end_time: 100,
+                    soft_cap: DeipAsset::<TestRuntime>::new(usd(), 100),
+                    hard_cap: DeipAsset::<TestRuntime>::new(usd(), 300),
+                    perks: Some(ContributionPerks {
+                        project_id: project(),
+                        tiers: vec![tier(150, GOLD)],
+                    }),
+                },
+            ),
+            Error::<TestRuntime>::TierClassDoesNotBelongToTeam,
+        );
     })
 }
@@ -114,14 +114,38 @@ pub mod pallet {
 
     pub type WeightInfoOf<T> = <T as crate::Config>::WeightInfo;
 
+    use frame_support::traits::{GetStorageVersion, StorageVersion};
+
+    pub const V0: StorageVersion = StorageVersion::new(0);
+    /// Index of pending proposals by their authors and members.
+    pub const V1: StorageVersion = StorageVersion::new(1);
+
     #[doc(hidden)]
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::storage_version(V1)]
     pub struct Pallet<T>(_);
 
     #[doc(hidden)]
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if Self::on_chain_storage_version() >= V1 {
+                return 0
+            }
+            // backfill the index of pending proposals by accounts
+            let (mut reads, mut writes) = (0, 1);
+            for proposal in ProposalRepository::<T>::iter_values() {
+                reads += 1;
+                for account in proposal.accounts() {
+                    PendingProposalsByAccount::<T>::insert(account, proposal.id, ());
+                    writes += 1;
+                }
+            }
+            V1.put::<Self>();
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+
         fn offchain_worker(n: T::BlockNumber) {
             // RuntimeLogger::init();
             if !sp_io::offchain::is_validator() {
@@ -310,6 +334,11 @@ pub mod pallet {
     pub(crate) type BatchItemDispatchResult = (DispatchResultWithPostInfo, DispatchInfo);
 
     impl<T: Config> Pallet<T> {
+        /// Whether `member` is the author or a member of pending proposals.
+        pub fn has_pending_proposals(member: &T::AccountId) -> bool {
+            PendingProposalsByAccount::<T>::iter_prefix(member).next().is_some()
+        }
+
         /// Execute batch as an atomic transaction
         #[frame_support::transactional]
         fn exec_batch(batch: ProposalBatch<T>) -> Result<BatchExecResult, BatchExecResult> {
@@ -344,4 +373,16 @@ pub mod pallet {
     #[pallet::storage]
     pub(super) type ProposalRepository<T: Config> =
         StorageMap<_, Blake2_128Concat, ProposalId, DeipProposal<T>, OptionQuery>;
+
+    /// Pending proposals by their authors and members
+    #[pallet::storage]
+    pub(super) type PendingProposalsByAccount<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        ProposalId,
+        (),
+        OptionQuery,
+    >;
 }
@@ -278,6 +278,11 @@ impl<T: Config> DeipProposal<T> {
         approved && matches!(self.state, ProposalState::Pending)
     }
 
+    /// Author and members of the proposal
+    pub(crate) fn accounts(&self) -> impl Iterator<Item = &T::AccountId> {
+        sp_std::iter::once(&self.author).chain(self.decisions.keys().filter(|x| **x != self.author))
+    }
+
     pub(crate) fn expired(&self, now: T::Moment) -> bool {
         (self.created_at + T::Ttl::get()) <= now && matches!(self.state, ProposalState::Pending)
     }
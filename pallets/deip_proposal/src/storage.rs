@@ -4,7 +4,7 @@ pub use deip_storage_ops::*;
 
 use crate::proposal::DeipProposal;
 
-use super::{Config, Event, ProposalRepository, Pallet};
+use super::{Config, Event, PendingProposalsByAccount, ProposalRepository, Pallet};
 
 
 pub type StorageWrite<T> = StorageOpsTransaction<StorageOps<T>>;
@@ -28,12 +28,18 @@ impl<T: Config> StorageOp for StorageOps<T> {
                 <Pallet<T>>::deposit_event(event);
             },
             StorageOps::CreateProposal(proposal) => {
+                for account in proposal.accounts() {
+                    <PendingProposalsByAccount<T>>::insert(account, proposal.id, ());
+                }
                 <ProposalRepository<T>>::insert(proposal.id, proposal);
             },
             StorageOps::UpdateProposal(proposal) => {
                 <ProposalRepository<T>>::insert(proposal.id, proposal)
             },
             StorageOps::DeleteProposal(proposal) => {
+                for account in proposal.accounts() {
+                    <PendingProposalsByAccount<T>>::remove(account, proposal.id);
+                }
                 <ProposalRepository<T>>::remove(proposal.id);
             },
         }
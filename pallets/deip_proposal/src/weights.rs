@@ -52,7 +52,8 @@ impl<T: frame_system::Config> WeightInfo for CallWeight<T> {
             // Standard Error: 44_000
             .saturating_add((2_173_000 as Weight).saturating_mul(c as Weight))
             .saturating_add(T::DbWeight::get().reads(3 as Weight))
-            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(c as Weight))
     }
     fn decide_reject() -> Weight {
         (62_706_000 as Weight)
@@ -0,0 +1,72 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use deip_asset_system::DeipAssetSystemBenchmarking;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_system::{EventRecord, RawOrigin};
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::Event) {
+    let events = frame_system::Pallet::<T>::events();
+    let system_event: <T as frame_system::Config>::Event = generic_event.into();
+    let EventRecord { event, .. } = &events[events.len() - 1];
+    assert_eq!(event, &system_event);
+}
+
+/// Creates an offer of 100 of the first benchmark asset for 100 of the second one.
+fn create_offer<T>(maker: &T::AccountId) -> OfferId
+where
+    T: Config + DeipAssetSystemBenchmarking<T::AccountId, T::SourceId, OfferId>,
+{
+    let give = T::benchmark_mint(1, maker, 1_000u32.into());
+    let want = T::benchmark_mint(2, maker, 1_000u32.into());
+    let id = Pallet::<T>::offer_id(OfferNonce::<T>::get());
+    Pallet::<T>::create_offer(
+        RawOrigin::Signed(maker.clone()).into(),
+        give,
+        100u32.into(),
+        want,
+        100u32.into(),
+        None,
+        None,
+    )
+    .unwrap();
+    id
+}
+
+benchmarks! {
+    where_clause {
+        where T: DeipAssetSystemBenchmarking<T::AccountId, T::SourceId, OfferId>
+    }
+
+    create_offer {
+        let maker: T::AccountId = whitelisted_caller();
+        let give = T::benchmark_mint(1, &maker, 1_000u32.into());
+        let want = T::benchmark_mint(2, &maker, 1_000u32.into());
+        let id = Pallet::<T>::offer_id(OfferNonce::<T>::get());
+    }: _(RawOrigin::Signed(maker.clone()), give, 100u32.into(), want, 100u32.into(), None, None)
+    verify {
+        assert_last_event::<T>(Event::<T>::OfferCreated(id, maker).into());
+    }
+
+    // the whole offer is filled, so it's closed and unreserved
+    fill_offer {
+        let maker = account::<T::AccountId>("maker", 0, SEED);
+        let id = create_offer::<T>(&maker);
+        let taker: T::AccountId = whitelisted_caller();
+        T::benchmark_mint(2, &taker, 1_000u32.into());
+    }: _(RawOrigin::Signed(taker), id, 100u32.into())
+    verify {
+        assert_last_event::<T>(Event::<T>::OfferClosed(id).into());
+    }
+
+    cancel_offer {
+        let maker: T::AccountId = whitelisted_caller();
+        let id = create_offer::<T>(&maker);
+    }: _(RawOrigin::Signed(maker), id)
+    verify {
+        assert_last_event::<T>(Event::<T>::OfferCancelled(id).into());
+    }
+}
@@ -0,0 +1,213 @@
+//! # DEIP Swap Module
+//! A module provides atomic swaps of DEIP assets.
+//!
+//! - [`Config`](./trait.Config.html)
+//!
+//! ## Overview
+//! A maker locks an amount of one asset in an offer and requests an amount of another one.
+//! The offer may be restricted to a single taker and limited in time.
+//! Takers fill the offer, fully or partially, in a single transaction.
+//!
+//! Offers are reserved via [`DeipAssetSystem`](deip_asset_system::DeipAssetSystem)
+//! the same way as investment opportunities, so their ids share the namespace
+//! of investment ids. Ids of offers are derived from a nonce, so they can't be
+//! chosen to take ids of investments.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! * [`create_offer`](./enum.Call.html#variant.create_offer)
+//! * [`fill_offer`](./enum.Call.html#variant.fill_offer)
+//! * [`cancel_offer`](./enum.Call.html#variant.cancel_offer)
+//!
+//! [`Config`]: ./trait.Config.html
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod offer;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub mod benchmarking;
+pub mod weights;
+
+pub use offer::*;
+
+#[doc(inline)]
+pub use pallet::*;
+
+#[frame_support::pallet]
+#[doc(hidden)]
+pub mod pallet {
+    use frame_support::{
+        pallet_prelude::{
+            Blake2_128Concat, DispatchResultWithPostInfo, IsType, Member, OptionQuery, Parameter,
+            StorageDoubleMap, StorageMap, StorageValue, ValueQuery,
+        },
+        transactional,
+    };
+    use frame_system::{ensure_signed, pallet_prelude::OriginFor};
+
+    use deip_asset_system::DeipAssetSystem;
+
+    use crate::{weights::WeightInfo, OfferId, SwapOffer};
+
+    pub(crate) type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+    pub type DeipAssetIdOf<T> =
+        <T as DeipAssetSystem<AccountIdOf<T>, <T as Config>::SourceId, OfferId>>::AssetId;
+    pub type DeipAssetBalanceOf<T> =
+        <T as DeipAssetSystem<AccountIdOf<T>, <T as Config>::SourceId, OfferId>>::Balance;
+    pub type SwapOfferOf<T> = SwapOffer<
+        AccountIdOf<T>,
+        DeipAssetIdOf<T>,
+        DeipAssetBalanceOf<T>,
+        <T as pallet_timestamp::Config>::Moment,
+    >;
+
+    #[pallet::config]
+    pub trait Config:
+        frame_system::Config
+        + pallet_timestamp::Config
+        + DeipAssetSystem<Self::AccountId, Self::SourceId, OfferId>
+    {
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+        type DeipAccountId: Into<Self::AccountId> + From<Self::AccountId> + Parameter + Member;
+
+        type SourceId: Member + Parameter;
+
+        type DeipSwapWeightInfo: WeightInfo;
+    }
+
+    #[doc(hidden)]
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(super) trait Store)]
+    pub struct Pallet<T>(_);
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Ids derived for the offer are used by investments.
+        OfferAlreadyExists,
+        OfferNotFound,
+        /// An asset can't be swapped for itself.
+        OfferSameAssets,
+        OfferAmountMustBePositive,
+        /// Expiration time must be later than the current moment.
+        OfferExpirationInPast,
+        OfferExpired,
+        /// The offer may be filled by its taker only.
+        NotOfferTaker,
+        /// Only the maker may cancel the offer until it expires.
+        NotOfferMaker,
+        /// The amount exceeds the remaining requested amount.
+        FillAmountTooBig,
+        /// The amount is too small to receive anything for it.
+        FillAmountTooSmall,
+        BalanceIsNotEnough,
+        AssetTransferFailed,
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Event emitted when an offer has been created: id, maker.
+        OfferCreated(OfferId, AccountIdOf<T>),
+        /// Event emitted when an offer has been filled: id, taker,
+        /// amount of the offered asset, amount of the requested asset.
+        OfferFilled(OfferId, AccountIdOf<T>, DeipAssetBalanceOf<T>, DeipAssetBalanceOf<T>),
+        /// Event emitted when an offer has been filled completely.
+        OfferClosed(OfferId),
+        /// Event emitted when an offer has been cancelled and remaining assets
+        /// have been returned to the maker.
+        OfferCancelled(OfferId),
+    }
+
+    /// Nonce from which the id of the next offer is derived.
+    #[pallet::storage]
+    pub(super) type OfferNonce<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Open offers.
+    #[pallet::storage]
+    pub(super) type Offers<T: Config> =
+        StorageMap<_, Blake2_128Concat, OfferId, SwapOfferOf<T>, OptionQuery>;
+
+    /// Open offers by pair (offered asset, requested asset).
+    #[pallet::storage]
+    pub(super) type OffersByPair<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        (DeipAssetIdOf<T>, DeipAssetIdOf<T>),
+        Blake2_128Concat,
+        OfferId,
+        (),
+        OptionQuery,
+    >;
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Locks `give_amount` of `give_asset` and offers it for `want_amount` of `want_asset`.
+        ///
+        /// The origin for this call must be _Signed_.
+        ///
+        /// The id of the offer is derived from a nonce and emitted with `OfferCreated`.
+        ///
+        /// - `taker`: the only account which may fill the offer. Anyone if not set.
+        /// - `expires`: moment since which the offer can't be filled.
+        #[pallet::weight(T::DeipSwapWeightInfo::create_offer())]
+        #[transactional]
+        pub fn create_offer(
+            origin: OriginFor<T>,
+            give_asset: DeipAssetIdOf<T>,
+            give_amount: DeipAssetBalanceOf<T>,
+            want_asset: DeipAssetIdOf<T>,
+            want_amount: DeipAssetBalanceOf<T>,
+            taker: Option<T::DeipAccountId>,
+            expires: Option<T::Moment>,
+        ) -> DispatchResultWithPostInfo {
+            let maker = ensure_signed(origin)?;
+            Self::create_offer_impl(
+                maker,
+                give_asset,
+                give_amount,
+                want_asset,
+                want_amount,
+                taker.map(Into::into),
+                expires,
+            )?;
+            Ok(().into())
+        }
+
+        /// Pays `amount` of the requested asset to the maker and receives
+        /// the offered asset at the price of the offer. The offer is closed
+        /// when the whole requested amount is paid.
+        ///
+        /// The origin for this call must be _Signed_ by the taker if the offer has one.
+        #[pallet::weight(T::DeipSwapWeightInfo::fill_offer())]
+        #[transactional]
+        pub fn fill_offer(
+            origin: OriginFor<T>,
+            id: OfferId,
+            amount: DeipAssetBalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let taker = ensure_signed(origin)?;
+            Self::fill_offer_impl(taker, id, amount)?;
+            Ok(().into())
+        }
+
+        /// Cancels the offer and returns remaining assets to the maker.
+        ///
+        /// The origin for this call must be _Signed_ by the maker.
+        /// Anyone may cancel the offer after it expires.
+        #[pallet::weight(T::DeipSwapWeightInfo::cancel_offer())]
+        #[transactional]
+        pub fn cancel_offer(origin: OriginFor<T>, id: OfferId) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::cancel_offer_impl(who, id)?;
+            Ok(().into())
+        }
+    }
+}
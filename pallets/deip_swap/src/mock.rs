@@ -0,0 +1,274 @@
+use deip_asset_system::{AssetIdInitT, DeipAssetSystem, ReserveError, UnreserveError};
+use frame_support::{parameter_types, traits::Everything};
+use sp_core::{H160, H256};
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    DispatchError, DispatchResult,
+};
+use sp_std::{
+    cell::RefCell,
+    collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+};
+
+use crate as pallet_deip_swap;
+use crate::OfferId;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub type AccountId = u64;
+pub type Balance = u64;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+        DeipSwap: pallet_deip_swap::{Pallet, Call, Storage, Event<T>},
+    }
+);
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const MinimumPeriod: u64 = 1;
+}
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type Origin = Origin;
+    type Call = Call;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = Event;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+}
+
+impl pallet_timestamp::Config for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = MinimumPeriod;
+    type WeightInfo = ();
+}
+
+impl pallet_deip_swap::Config for Test {
+    type Event = Event;
+    type DeipAccountId = AccountId;
+    type SourceId = H160;
+    type DeipSwapWeightInfo = crate::weights::Weights<Self>;
+}
+
+thread_local! {
+    static BALANCES: RefCell<BTreeMap<(AccountId, H160), Balance>> = RefCell::new(BTreeMap::new());
+    /// reserve -> (owner, asset to raise)
+    static RESERVES: RefCell<BTreeMap<OfferId, (AccountId, H160)>> =
+        RefCell::new(BTreeMap::new());
+    static RESERVED: RefCell<BTreeMap<(OfferId, H160), Balance>> = RefCell::new(BTreeMap::new());
+    /// Assets which can't be frozen or destroyed by their teams.
+    static LOCKED: RefCell<BTreeSet<H160>> = RefCell::new(BTreeSet::new());
+}
+
+pub fn balance(account: AccountId, asset: H160) -> Balance {
+    BALANCES.with(|b| b.borrow().get(&(account, asset)).copied().unwrap_or_default())
+}
+
+pub fn set_balance(account: AccountId, asset: H160, amount: Balance) {
+    BALANCES.with(|b| b.borrow_mut().insert((account, asset), amount));
+}
+
+pub fn is_locked(asset: H160) -> bool {
+    LOCKED.with(|l| l.borrow().contains(&asset))
+}
+
+fn withdraw(account: AccountId, asset: H160, amount: Balance) -> Result<(), ()> {
+    let free = balance(account, asset);
+    if free < amount {
+        return Err(())
+    }
+    set_balance(account, asset, free - amount);
+    Ok(())
+}
+
+fn reserved(id: OfferId, asset: H160) -> Balance {
+    RESERVED.with(|r| r.borrow().get(&(id, asset)).copied().unwrap_or_default())
+}
+
+fn set_reserved(id: OfferId, asset: H160, amount: Balance) {
+    RESERVED.with(|r| r.borrow_mut().insert((id, asset), amount));
+}
+
+impl AssetIdInitT<H160> for Test {
+    fn asset_id(raw: &[u8]) -> H160 {
+        H160::from_slice(raw)
+    }
+}
+
+impl DeipAssetSystem<AccountId, H160, OfferId> for Test {
+    type Balance = Balance;
+    type AssetId = H160;
+
+    fn account_balance(account: &AccountId, asset: &Self::AssetId) -> Self::Balance {
+        balance(*account, *asset)
+    }
+
+    fn total_supply(asset: &Self::AssetId) -> Self::Balance {
+        BALANCES.with(|b| {
+            b.borrow().iter().filter(|((_, a), _)| a == asset).map(|(_, amount)| amount).sum()
+        })
+    }
+
+    fn minimum_balance(_asset: &Self::AssetId) -> Self::Balance {
+        0
+    }
+
+    fn transactionally_transfer(
+        from: &AccountId,
+        asset: Self::AssetId,
+        transfers: &[(Self::Balance, AccountId)],
+    ) -> DispatchResult {
+        let total = transfers.iter().map(|(amount, _)| amount).sum();
+        withdraw(*from, asset, total).map_err(|_| DispatchError::Other("BalanceLow"))?;
+        for (amount, to) in transfers {
+            set_balance(*to, asset, balance(*to, asset) + amount);
+        }
+        Ok(())
+    }
+
+    fn transactionally_reserve(
+        account: &AccountId,
+        id: OfferId,
+        shares: &[(Self::AssetId, Self::Balance)],
+        asset: Self::AssetId,
+    ) -> Result<(), ReserveError<Self::AssetId>> {
+        Self::transactionally_reserve_escrow(account, id, shares, asset)?;
+        LOCKED.with(|l| l.borrow_mut().insert(asset));
+        Ok(())
+    }
+
+    fn transactionally_reserve_escrow(
+        account: &AccountId,
+        id: OfferId,
+        shares: &[(Self::AssetId, Self::Balance)],
+        asset: Self::AssetId,
+    ) -> Result<(), ReserveError<Self::AssetId>> {
+        if RESERVES.with(|r| r.borrow().contains_key(&id)) {
+            return Err(ReserveError::AlreadyReserved)
+        }
+        if shares.iter().any(|(share, amount)| balance(*account, *share) < *amount) {
+            return Err(ReserveError::NotEnoughBalance)
+        }
+        for (share, amount) in shares {
+            withdraw(*account, *share, *amount).expect("checked above");
+            set_reserved(id, *share, *amount);
+            LOCKED.with(|l| l.borrow_mut().insert(*share));
+        }
+        RESERVES.with(|r| r.borrow_mut().insert(id, (*account, asset)));
+        Ok(())
+    }
+
+    fn transactionally_unreserve(id: OfferId) -> Result<(), UnreserveError<Self::AssetId>> {
+        let (owner, _) = RESERVES
+            .with(|r| r.borrow_mut().remove(&id))
+            .ok_or(UnreserveError::NoSuchInvestment)?;
+        let assets: Vec<_> = RESERVED.with(|r| {
+            let mut r = r.borrow_mut();
+            let keys: Vec<_> = r.keys().filter(|(i, _)| *i == id).copied().collect();
+            keys.into_iter().map(|key| (key.1, r.remove(&key).unwrap())).collect()
+        });
+        for (asset, amount) in assets {
+            LOCKED.with(|l| l.borrow_mut().remove(&asset));
+            set_balance(owner, asset, balance(owner, asset) + amount);
+        }
+        Ok(())
+    }
+
+    fn transfer_from_reserved(
+        id: OfferId,
+        who: &AccountId,
+        asset: Self::AssetId,
+        amount: Self::Balance,
+    ) -> Result<(), UnreserveError<Self::AssetId>> {
+        let left = reserved(id, asset)
+            .checked_sub(amount)
+            .ok_or(UnreserveError::AssetTransferFailed(asset))?;
+        set_reserved(id, asset, left);
+        set_balance(*who, asset, balance(*who, asset) + amount);
+        Ok(())
+    }
+
+    fn transfer_to_reserved(
+        who: &AccountId,
+        id: OfferId,
+        amount: Self::Balance,
+    ) -> Result<(), UnreserveError<Self::AssetId>> {
+        let (_, asset) = RESERVES
+            .with(|r| r.borrow().get(&id).copied())
+            .ok_or(UnreserveError::NoSuchInvestment)?;
+        withdraw(*who, asset, amount).map_err(|_| UnreserveError::AssetTransferFailed(asset))?;
+        set_reserved(id, asset, reserved(id, asset) + amount);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl deip_asset_system::DeipAssetSystemBenchmarking<AccountId, H160, OfferId> for Test {
+    fn benchmark_mint(index: u8, account: &AccountId, amount: Balance) -> H160 {
+        let asset = H160::repeat_byte(index);
+        set_balance(*account, asset, balance(*account, asset) + amount);
+        asset
+    }
+}
+
+pub const MAKER: AccountId = 1;
+pub const ALICE: AccountId = 2;
+pub const BOB: AccountId = 3;
+
+pub fn gold() -> H160 {
+    H160::from_low_u64_be(1)
+}
+
+pub fn usd() -> H160 {
+    H160::from_low_u64_be(2)
+}
+
+/// Id of the `n`th offer created in a test.
+pub fn offer_id(n: u64) -> OfferId {
+    DeipSwap::offer_id(n - 1)
+}
+
+/// Reserves `id` as an investment of `account`.
+pub fn reserve_investment(account: AccountId, id: OfferId) {
+    RESERVES.with(|r| r.borrow_mut().insert(id, (account, usd())));
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+pub fn last_event() -> Event {
+    System::events().pop().expect("Event expected").event
+}
+
+pub use frame_support::{assert_noop, assert_ok};
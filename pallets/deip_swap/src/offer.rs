@@ -0,0 +1,201 @@
+use codec::{Decode, Encode};
+use frame_support::{dispatch::DispatchResult, ensure, RuntimeDebug};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{
+    helpers_128bit::multiply_by_rational,
+    traits::{AtLeast32BitUnsigned, Saturating, Zero},
+    SaturatedConversion,
+};
+
+use deip_asset_system::{DeipAssetSystem, ReserveError};
+use deip_serializable_u128::SerializableAtLeast32BitUnsigned;
+
+use crate::{
+    AccountIdOf, Config, DeipAssetBalanceOf, DeipAssetIdOf, Error, Event, OfferNonce, Offers,
+    OffersByPair, Pallet, SwapOfferOf,
+};
+
+/// Offers are reserved as investments in `DeipAssetSystem`, so they share ids.
+pub type OfferId = deip_asset_system::investment_opportunity::InvestmentId;
+
+/// Number of derived ids tried for an offer if previous ones are used by investments.
+const OFFER_ID_ATTEMPTS: u32 = 3;
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct SwapOffer<AccountId, AssetId, Balance: Clone + AtLeast32BitUnsigned, Moment> {
+    pub maker: AccountId,
+    /// The only account which may fill the offer.
+    pub taker: Option<AccountId>,
+    pub give_asset: AssetId,
+    /// Remaining amount of the offered asset.
+    pub give_amount: SerializableAtLeast32BitUnsigned<Balance>,
+    pub want_asset: AssetId,
+    /// Remaining amount of the requested asset.
+    pub want_amount: SerializableAtLeast32BitUnsigned<Balance>,
+    /// Moment since which the offer can't be filled.
+    pub expires: Option<Moment>,
+    /// Amount of the offered asset at creation. All fills are priced
+    /// by the initial amounts.
+    pub initial_give_amount: SerializableAtLeast32BitUnsigned<Balance>,
+    /// Amount of the requested asset at creation.
+    pub initial_want_amount: SerializableAtLeast32BitUnsigned<Balance>,
+}
+
+impl<T: Config> Pallet<T> {
+    pub fn offer(id: &OfferId) -> Option<SwapOfferOf<T>> {
+        Offers::<T>::get(id)
+    }
+
+    /// Id of the offer derived from the nonce, in the namespace of offers.
+    pub fn offer_id(nonce: u64) -> OfferId {
+        let entropy = (b"deip/swap/offers/", nonce).using_encoded(sp_io::hashing::blake2_256);
+        OfferId::from_slice(&entropy[..20])
+    }
+
+    /// Id of the next offer, the nonce is increased.
+    fn next_offer_id() -> OfferId {
+        let nonce = OfferNonce::<T>::mutate(|nonce| {
+            let current = *nonce;
+            *nonce = nonce.wrapping_add(1);
+            current
+        });
+        Self::offer_id(nonce)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn create_offer_impl(
+        maker: AccountIdOf<T>,
+        give_asset: DeipAssetIdOf<T>,
+        give_amount: DeipAssetBalanceOf<T>,
+        want_asset: DeipAssetIdOf<T>,
+        want_amount: DeipAssetBalanceOf<T>,
+        taker: Option<AccountIdOf<T>>,
+        expires: Option<T::Moment>,
+    ) -> DispatchResult {
+        ensure!(give_asset != want_asset, Error::<T>::OfferSameAssets);
+        ensure!(
+            !give_amount.is_zero() && !want_amount.is_zero(),
+            Error::<T>::OfferAmountMustBePositive
+        );
+        if let Some(expires) = expires {
+            ensure!(
+                expires > pallet_timestamp::Pallet::<T>::get(),
+                Error::<T>::OfferExpirationInPast
+            );
+        }
+
+        // takers pay the requested asset to the reserve and it's passed to the maker
+        // at once, so it isn't locked for its team.
+        // Ids of investments are chosen by their creators, so a derived id may be taken.
+        let mut attempts = 0;
+        let id = loop {
+            let id = Self::next_offer_id();
+            attempts += 1;
+            let shares = [(give_asset, give_amount)];
+            match T::transactionally_reserve_escrow(&maker, id, &shares, want_asset) {
+                Ok(()) => break id,
+                Err(ReserveError::AlreadyReserved) if attempts < OFFER_ID_ATTEMPTS => continue,
+                Err(e) => return Err(match e {
+                    ReserveError::NotEnoughBalance => Error::<T>::BalanceIsNotEnough,
+                    ReserveError::AlreadyReserved => Error::<T>::OfferAlreadyExists,
+                    ReserveError::AssetTransferFailed(_) => Error::<T>::AssetTransferFailed,
+                }
+                .into()),
+            }
+        };
+
+        Offers::<T>::insert(
+            id,
+            SwapOfferOf::<T> {
+                maker: maker.clone(),
+                taker,
+                give_asset,
+                give_amount: SerializableAtLeast32BitUnsigned(give_amount),
+                want_asset,
+                want_amount: SerializableAtLeast32BitUnsigned(want_amount),
+                expires,
+                initial_give_amount: SerializableAtLeast32BitUnsigned(give_amount),
+                initial_want_amount: SerializableAtLeast32BitUnsigned(want_amount),
+            },
+        );
+        OffersByPair::<T>::insert((give_asset, want_asset), id, ());
+
+        Self::deposit_event(Event::<T>::OfferCreated(id, maker));
+
+        Ok(())
+    }
+
+    pub(crate) fn fill_offer_impl(
+        taker: AccountIdOf<T>,
+        id: OfferId,
+        amount: DeipAssetBalanceOf<T>,
+    ) -> DispatchResult {
+        let mut offer = Offers::<T>::get(id).ok_or(Error::<T>::OfferNotFound)?;
+        if let Some(ref allowed) = offer.taker {
+            ensure!(*allowed == taker, Error::<T>::NotOfferTaker);
+        }
+        ensure!(!Self::is_expired(&offer), Error::<T>::OfferExpired);
+        ensure!(!amount.is_zero(), Error::<T>::OfferAmountMustBePositive);
+        ensure!(amount <= offer.want_amount.0, Error::<T>::FillAmountTooBig);
+
+        // rounded down against the taker by the initial price, so the rounding of previous
+        // fills doesn't change the price and the dust is returned to the maker on close
+        let received: DeipAssetBalanceOf<T> = multiply_by_rational(
+            amount.saturated_into(),
+            offer.initial_give_amount.0.saturated_into(),
+            offer.initial_want_amount.0.saturated_into(),
+        )
+        .map_err(|_| Error::<T>::FillAmountTooBig)?
+        .saturated_into();
+        let received = received.min(offer.give_amount.0);
+        ensure!(!received.is_zero(), Error::<T>::FillAmountTooSmall);
+
+        T::transfer_to_reserved(&taker, id, amount)
+            .map_err(|_| Error::<T>::AssetTransferFailed)?;
+        T::transfer_from_reserved(id, &offer.maker, offer.want_asset, amount)
+            .map_err(|_| Error::<T>::AssetTransferFailed)?;
+        T::transfer_from_reserved(id, &taker, offer.give_asset, received)
+            .map_err(|_| Error::<T>::AssetTransferFailed)?;
+
+        offer.give_amount.0 = offer.give_amount.0.saturating_sub(received);
+        offer.want_amount.0 = offer.want_amount.0.saturating_sub(amount);
+
+        Self::deposit_event(Event::<T>::OfferFilled(id, taker, received, amount));
+
+        if offer.want_amount.0.is_zero() {
+            // returns the deposit and the dust left after rounding
+            T::transactionally_unreserve(id).map_err(|_| Error::<T>::AssetTransferFailed)?;
+            Self::remove_offer(id, &offer);
+            Self::deposit_event(Event::<T>::OfferClosed(id));
+        } else {
+            Offers::<T>::insert(id, offer);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn cancel_offer_impl(who: AccountIdOf<T>, id: OfferId) -> DispatchResult {
+        let offer = Offers::<T>::get(id).ok_or(Error::<T>::OfferNotFound)?;
+        ensure!(who == offer.maker || Self::is_expired(&offer), Error::<T>::NotOfferMaker);
+
+        T::transactionally_unreserve(id).map_err(|_| Error::<T>::AssetTransferFailed)?;
+        Self::remove_offer(id, &offer);
+
+        Self::deposit_event(Event::<T>::OfferCancelled(id));
+
+        Ok(())
+    }
+
+    fn is_expired(offer: &SwapOfferOf<T>) -> bool {
+        offer.expires.map_or(false, |e| e <= pallet_timestamp::Pallet::<T>::get())
+    }
+
+    fn remove_offer(id: OfferId, offer: &SwapOfferOf<T>) {
+        Offers::<T>::remove(id);
+        OffersByPair::<T>::remove((offer.give_asset, offer.want_asset), id);
+    }
+}
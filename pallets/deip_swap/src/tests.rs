@@ -0,0 +1,116 @@
+use crate::{mock::*, Error, Event as SwapEvent};
+
+fn create_offer(give_amount: Balance, want_amount: Balance) {
+    assert_ok!(DeipSwap::create_offer(
+        Origin::signed(MAKER),
+        gold(),
+        give_amount,
+        usd(),
+        want_amount,
+        None,
+        None
+    ));
+}
+
+#[test]
+fn requested_asset_isnt_locked() {
+    new_test_ext().execute_with(|| {
+        set_balance(MAKER, gold(), 10);
+        create_offer(10, 3);
+
+        assert_eq!(balance(MAKER, gold()), 0);
+        assert!(is_locked(gold()));
+        assert!(!is_locked(usd()));
+    });
+}
+
+#[test]
+fn offer_ids_taken_by_investments_are_skipped() {
+    new_test_ext().execute_with(|| {
+        set_balance(MAKER, gold(), 10);
+        reserve_investment(ALICE, offer_id(1));
+        create_offer(10, 3);
+
+        assert_eq!(last_event(), Event::DeipSwap(SwapEvent::OfferCreated(offer_id(2), MAKER)));
+        assert!(DeipSwap::offer(&offer_id(1)).is_none());
+        assert_eq!(balance(MAKER, gold()), 0);
+    });
+}
+
+#[test]
+fn partial_fills_are_priced_by_initial_terms() {
+    new_test_ext().execute_with(|| {
+        set_balance(MAKER, gold(), 10);
+        set_balance(ALICE, usd(), 2);
+        set_balance(BOB, usd(), 1);
+        create_offer(10, 3);
+
+        assert_ok!(DeipSwap::fill_offer(Origin::signed(ALICE), offer_id(1), 1));
+        assert_ok!(DeipSwap::fill_offer(Origin::signed(ALICE), offer_id(1), 1));
+        assert_eq!(balance(ALICE, gold()), 3 + 3);
+
+        // the last taker doesn't get the dust of previous fills
+        assert_ok!(DeipSwap::fill_offer(Origin::signed(BOB), offer_id(1), 1));
+        assert_eq!(balance(BOB, gold()), 3);
+        assert_eq!(last_event(), Event::DeipSwap(SwapEvent::OfferClosed(offer_id(1))));
+
+        assert_eq!(balance(MAKER, gold()), 1);
+        assert_eq!(balance(MAKER, usd()), 3);
+        assert!(DeipSwap::offer(&offer_id(1)).is_none());
+        assert!(!is_locked(gold()));
+    });
+}
+
+#[test]
+fn fill_too_small_to_receive_anything_fails() {
+    new_test_ext().execute_with(|| {
+        set_balance(MAKER, gold(), 3);
+        set_balance(ALICE, usd(), 10);
+        create_offer(3, 10);
+
+        assert_noop!(
+            DeipSwap::fill_offer(Origin::signed(ALICE), offer_id(1), 3),
+            Error::<Test>::FillAmountTooSmall
+        );
+        assert_noop!(
+            DeipSwap::fill_offer(Origin::signed(ALICE), offer_id(1), 11),
+            Error::<Test>::FillAmountTooBig
+        );
+        assert_ok!(DeipSwap::fill_offer(Origin::signed(ALICE), offer_id(1), 4));
+        assert_eq!(balance(ALICE, gold()), 1);
+    });
+}
+
+#[test]
+fn expired_offer_is_cancelled_by_anyone() {
+    new_test_ext().execute_with(|| {
+        set_balance(MAKER, gold(), 10);
+        set_balance(ALICE, usd(), 3);
+        assert_ok!(DeipSwap::create_offer(
+            Origin::signed(MAKER),
+            gold(),
+            10,
+            usd(),
+            3,
+            Some(ALICE),
+            Some(5)
+        ));
+
+        assert_noop!(
+            DeipSwap::fill_offer(Origin::signed(BOB), offer_id(1), 1),
+            Error::<Test>::NotOfferTaker
+        );
+        assert_noop!(
+            DeipSwap::cancel_offer(Origin::signed(BOB), offer_id(1)),
+            Error::<Test>::NotOfferMaker
+        );
+
+        Timestamp::set_timestamp(5);
+        assert_noop!(
+            DeipSwap::fill_offer(Origin::signed(ALICE), offer_id(1), 1),
+            Error::<Test>::OfferExpired
+        );
+        assert_ok!(DeipSwap::cancel_offer(Origin::signed(BOB), offer_id(1)));
+        assert_eq!(balance(MAKER, gold()), 10);
+    });
+}
@@ -0,0 +1,38 @@
+//! Weights for pallet_deip_swap
+//!
+//! Estimated from storage accesses of the calls and of the reserves of offers
+//! in `DeipAssetSystem`. Run the benchmarks of the pallet to replace them
+//! with measured values.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+pub trait WeightInfo {
+    fn create_offer() -> Weight;
+    fn fill_offer() -> Weight;
+    fn cancel_offer() -> Weight;
+}
+
+/// Weight functions for pallet_deip_swap.
+pub struct Weights<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for Weights<T> {
+    fn create_offer() -> Weight {
+        (95_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(9 as Weight))
+    }
+    fn fill_offer() -> Weight {
+        (140_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(12 as Weight))
+            .saturating_add(T::DbWeight::get().writes(12 as Weight))
+    }
+    fn cancel_offer() -> Weight {
+        (75_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+}
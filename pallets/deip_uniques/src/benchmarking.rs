@@ -0,0 +1,59 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use codec::{Decode, Encode};
+use core::convert::TryInto;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, CheckedAdd, One, TrailingZeroInput};
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+fn create_class<T: Config>(caller: &T::AccountId) -> DeipNftClassIdOf<T> {
+    T::Currency::make_free_balance_be(caller, CurrencyBalanceOf::<T>::max_value() / 2u32.into());
+    let class = DeipNftClassIdOf::<T>::decode(&mut TrailingZeroInput::new(&[][..])).unwrap();
+    Pallet::<T>::deip_create(
+        RawOrigin::Signed(caller.clone()).into(),
+        class,
+        caller.clone().into(),
+    )
+    .unwrap();
+    class
+}
+
+fn mint_items<T: Config>(n: u32, a: u32) -> Vec<MintItemOf<T>> {
+    let mut instance = T::NftInstanceId::default();
+    let mut items = Vec::with_capacity(n as usize);
+    for i in 0..n {
+        let attributes = (0..a)
+            .map(|k| {
+                let key = k.encode().try_into().unwrap();
+                let value = vec![0u8; T::ValueLimit::get() as usize].try_into().unwrap();
+                (key, value)
+            })
+            .collect::<Vec<_>>();
+        items.push(MintItem {
+            instance,
+            owner: account::<T::AccountId>("owner", i, SEED).into(),
+            metadata: Some(vec![0u8; T::StringLimit::get() as usize].try_into().unwrap()),
+            attributes: attributes.try_into().unwrap(),
+        });
+        instance = instance.checked_add(&One::one()).unwrap();
+    }
+    items
+}
+
+benchmarks! {
+    deip_mint_batch {
+        let n in 1 .. T::MaxBatchMint::get();
+        let a in 0 .. T::MaxBatchAttributes::get();
+        let caller: T::AccountId = whitelisted_caller();
+        let class = create_class::<T>(&caller);
+        let items = mint_items::<T>(n, a).try_into().unwrap();
+    }: _(RawOrigin::Signed(caller), class, items)
+    verify {
+        assert_eq!(Pallet::<T>::holder_count(class), n);
+    }
+}
@@ -0,0 +1,170 @@
+use codec::{Decode, Encode};
+use deip_asset_system::DeipFractions;
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    traits::{tokens::nonfungibles::Inspect, Currency, ExistenceRequirement, WithdrawReasons},
+};
+use frame_system::RawOrigin;
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    SaturatedConversion,
+};
+
+use crate::{
+    AccountIdOf, Config, CurrencyBalanceOf, DeipNftClassIdOf, Error, Event, Fraction,
+    FractionAssetIdOf, FractionBalanceOf, FractionByAsset, FractionOf, Pallet,
+};
+
+impl<T: Config> Pallet<T> {
+    /// Account which holds the fractionalized instance and proceeds of its buyout.
+    pub fn fraction_key(class: &DeipNftClassIdOf<T>, instance: &T::InstanceId) -> AccountIdOf<T> {
+        let entropy =
+            (b"deip/fractions/", class, instance).using_encoded(sp_io::hashing::blake2_256);
+        AccountIdOf::<T>::decode(&mut &entropy[..]).unwrap_or_default()
+    }
+
+    pub fn fraction(asset: FractionAssetIdOf<T>) -> Option<FractionOf<T>> {
+        FractionByAsset::<T>::get(asset)
+    }
+
+    pub(crate) fn fractionalize_impl(
+        who: AccountIdOf<T>,
+        class: DeipNftClassIdOf<T>,
+        instance: T::InstanceId,
+        asset: FractionAssetIdOf<T>,
+        shares: FractionBalanceOf<T>,
+        buyout_price: Option<CurrencyBalanceOf<T>>,
+    ) -> DispatchResult {
+        ensure!(!shares.is_zero(), Error::<T>::FractionSharesMustBePositive);
+        if let Some(price) = buyout_price {
+            ensure!(!price.is_zero(), Error::<T>::FractionBuyoutPriceMustBePositive);
+        }
+        T::Fractions::ensure_can_create(&asset)?;
+
+        let origin_class_id = Self::deip_to_origin_class_id(class)?;
+        let owner = <pallet_uniques::Pallet<T> as Inspect<T::AccountId>>::owner(
+            &origin_class_id,
+            &instance,
+        );
+        ensure!(owner.as_ref() == Some(&who), Error::<T>::NotInstanceOwner);
+
+        let vault = Self::fraction_key(&class, &instance);
+
+        // The vault should exist to own the asset, see `deip_transactionally_reserve`.
+        let existential = T::Currency::withdraw(
+            &who,
+            T::Currency::minimum_balance(),
+            WithdrawReasons::RESERVE,
+            ExistenceRequirement::AllowDeath,
+        )
+        .map_err(|_| Error::<T>::FractionBalanceIsNotEnough)?;
+        T::Currency::resolve_creating(&vault, existential);
+
+        Self::deip_transfer(
+            RawOrigin::Signed(who.clone()).into(),
+            class,
+            instance,
+            vault.clone().into(),
+        )
+        .map_err(|e| e.error)?;
+
+        // the metadata of the asset points to the instance
+        let name = (class, instance).encode();
+        T::Fractions::create(&who, &vault, asset, name, shares)?;
+
+        FractionByAsset::<T>::insert(
+            asset,
+            Fraction { class, instance, buyout_price, proceeds: None },
+        );
+
+        Self::deposit_event(Event::<T>::Fractionalized(class, instance, asset, shares));
+
+        Ok(())
+    }
+
+    pub(crate) fn redeem_impl(who: AccountIdOf<T>, asset: FractionAssetIdOf<T>) -> DispatchResult {
+        let fraction = FractionByAsset::<T>::get(asset).ok_or(Error::<T>::FractionNotFound)?;
+        ensure!(fraction.proceeds.is_none(), Error::<T>::FractionBoughtOut);
+
+        let supply = T::Fractions::total_supply(&asset);
+        ensure!(
+            T::Fractions::account_balance(&who, &asset) == supply,
+            Error::<T>::FractionNotAllShares
+        );
+
+        let vault = Self::fraction_key(&fraction.class, &fraction.instance);
+        T::Fractions::burn(&vault, asset, &who, supply)?;
+
+        Self::deip_transfer(
+            RawOrigin::Signed(vault).into(),
+            fraction.class,
+            fraction.instance,
+            who.clone().into(),
+        )
+        .map_err(|e| e.error)?;
+
+        FractionByAsset::<T>::remove(asset);
+
+        Self::deposit_event(Event::<T>::Redeemed(asset, who));
+
+        Ok(())
+    }
+
+    pub(crate) fn buyout_impl(who: AccountIdOf<T>, asset: FractionAssetIdOf<T>) -> DispatchResult {
+        let mut fraction = FractionByAsset::<T>::get(asset).ok_or(Error::<T>::FractionNotFound)?;
+        ensure!(fraction.proceeds.is_none(), Error::<T>::FractionBoughtOut);
+        let price = fraction.buyout_price.ok_or(Error::<T>::FractionBuyoutNotAllowed)?;
+
+        let vault = Self::fraction_key(&fraction.class, &fraction.instance);
+        T::Currency::transfer(&who, &vault, price, ExistenceRequirement::KeepAlive)?;
+
+        Self::deip_transfer(
+            RawOrigin::Signed(vault).into(),
+            fraction.class,
+            fraction.instance,
+            who.clone().into(),
+        )
+        .map_err(|e| e.error)?;
+
+        fraction.proceeds = Some(price);
+        FractionByAsset::<T>::insert(asset, fraction);
+
+        Self::deposit_event(Event::<T>::BoughtOut(asset, who, price));
+
+        Ok(())
+    }
+
+    pub(crate) fn claim_buyout_impl(
+        who: AccountIdOf<T>,
+        asset: FractionAssetIdOf<T>,
+    ) -> DispatchResult {
+        let mut fraction = FractionByAsset::<T>::get(asset).ok_or(Error::<T>::FractionNotFound)?;
+        let proceeds = fraction.proceeds.ok_or(Error::<T>::FractionNotBoughtOut)?;
+
+        let balance = T::Fractions::account_balance(&who, &asset);
+        ensure!(!balance.is_zero(), Error::<T>::NothingToClaim);
+        let supply = T::Fractions::total_supply(&asset);
+
+        // shares are burned on claim, so the rest of proceeds is split
+        // between the rest of shares and the last holder takes the dust
+        let amount = proceeds.saturated_into::<u128>().saturating_mul(balance.saturated_into()) /
+            supply.saturated_into::<u128>();
+        let amount: CurrencyBalanceOf<T> = amount.saturated_into();
+
+        let vault = Self::fraction_key(&fraction.class, &fraction.instance);
+        T::Fractions::burn(&vault, asset, &who, balance)?;
+        T::Currency::transfer(&vault, &who, amount, ExistenceRequirement::KeepAlive)?;
+
+        if balance == supply {
+            FractionByAsset::<T>::remove(asset);
+        } else {
+            fraction.proceeds = Some(proceeds.saturating_sub(amount));
+            FractionByAsset::<T>::insert(asset, fraction);
+        }
+
+        Self::deposit_event(Event::<T>::BuyoutClaimed(asset, who, amount));
+
+        Ok(())
+    }
+}
@@ -4,41 +4,101 @@
 pub use pallet::*;
 pub use pallet_uniques;
 
+mod fractions;
+mod sale;
+
+pub mod benchmarking;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
 #[frame_support::pallet]
 pub mod pallet {
+    use deip_asset_system::{
+        investment_opportunity::InvestmentId, DeipAssetSystem, DeipFractions,
+        OnNftClassDestroyed,
+    };
     use deip_projects_info::DeipProjectsInfo;
     #[cfg(feature = "std")]
     use frame_support::traits::GenesisBuild;
 
     use frame_support::{
-        dispatch::{DispatchResult, DispatchResultWithPostInfo, UnfilteredDispatchable, Weight},
+        codec::{Decode, Encode, HasCompact},
+        dispatch::{
+            DispatchError, DispatchResult, DispatchResultWithPostInfo, UnfilteredDispatchable,
+            Weight,
+        },
         ensure,
-        pallet_prelude::{OptionQuery, StorageMap, StorageValue, ValueQuery},
-        sp_runtime::traits::{CheckedAdd, One, StaticLookup},
-        traits::{Get, Hooks},
-        Blake2_128Concat, BoundedVec, Identity, Parameter,
+        pallet_prelude::{
+            IsType, OptionQuery, StorageDoubleMap, StorageMap, StorageValue, ValueQuery,
+        },
+        sp_runtime::{
+            traits::{CheckedAdd, Member, One, StaticLookup},
+            Permill,
+        },
+        traits::{tokens::nonfungibles::Inspect, Currency, Get, Hooks},
+        transactional, Blake2_128Concat, BoundedVec, Identity, Parameter, RuntimeDebug,
     };
-    use frame_system::pallet_prelude::{BlockNumberFor, OriginFor};
+    use frame_system::{
+        ensure_signed,
+        pallet_prelude::{BlockNumberFor, OriginFor},
+        RawOrigin,
+    };
+    use scale_info::TypeInfo;
     use pallet_uniques::{
         Call as UniquesCall, DestroyWitness, Pallet as UniquesPallet, WeightInfo,
     };
     use sp_std::vec::Vec;
 
     // Helper types.
-    type DeipNftClassIdOf<T> = <T as Config>::DeipNftClassId;
-    type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+    pub(crate) type DeipNftClassIdOf<T> = <T as Config>::DeipNftClassId;
+    pub(crate) type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
     type DeipProjectIdOf<T> =
         <<T as Config>::ProjectsInfo as DeipProjectsInfo<AccountIdOf<T>>>::ProjectId;
+    pub(crate) type FractionAssetIdOf<T> =
+        <<T as Config>::Fractions as DeipFractions<AccountIdOf<T>>>::AssetId;
+    pub(crate) type FractionBalanceOf<T> =
+        <<T as Config>::Fractions as DeipFractions<AccountIdOf<T>>>::Balance;
+    pub(crate) type CurrencyBalanceOf<T> =
+        <<T as pallet_uniques::Config>::Currency as Currency<AccountIdOf<T>>>::Balance;
+    pub(crate) type DeipAssetIdOf<T> =
+        <T as DeipAssetSystem<AccountIdOf<T>, <T as Config>::SourceId, InvestmentId>>::AssetId;
+    pub(crate) type DeipAssetBalanceOf<T> =
+        <T as DeipAssetSystem<AccountIdOf<T>, <T as Config>::SourceId, InvestmentId>>::Balance;
+    pub type RoyaltyOf<T> = Royalty<AccountIdOf<T>>;
+    pub type SaleOf<T> = Sale<AccountIdOf<T>, DeipAssetIdOf<T>, DeipAssetBalanceOf<T>>;
+    pub type MintItemOf<T> = MintItem<
+        <T as pallet_uniques::Config>::InstanceId,
+        <T as Config>::DeipAccountId,
+        BoundedVec<u8, <T as pallet_uniques::Config>::StringLimit>,
+        BoundedVec<
+            (
+                BoundedVec<u8, <T as pallet_uniques::Config>::KeyLimit>,
+                BoundedVec<u8, <T as pallet_uniques::Config>::ValueLimit>,
+            ),
+            <T as Config>::MaxBatchAttributes,
+        >,
+    >;
+    pub type FractionOf<T> = Fraction<
+        DeipNftClassIdOf<T>,
+        <T as pallet_uniques::Config>::InstanceId,
+        CurrencyBalanceOf<T>,
+    >;
 
     #[pallet::config]
     pub trait Config:
-        frame_system::Config + pallet_uniques::Config<ClassId = Self::NftClassId>
+        frame_system::Config
+        + pallet_uniques::Config<ClassId = Self::NftClassId, InstanceId = Self::NftInstanceId>
+        + DeipAssetSystem<Self::AccountId, Self::SourceId, InvestmentId>
     {
         /// Deip class id.
         type DeipNftClassId: Parameter + Copy;
 
         /// Deip account id.
-        type DeipAccountId: Into<Self::AccountId> + Parameter + Clone;
+        type DeipAccountId: Into<Self::AccountId> + From<Self::AccountId> + Parameter + Clone;
 
         /// Deip project id.
         type ProjectId: Parameter;
@@ -46,21 +106,55 @@ pub mod pallet {
         /// Type of `pallet_uniques::Config::ClassId`.
         type NftClassId: Parameter + CheckedAdd + Default + One + Copy + PartialOrd;
 
+        /// Type of `pallet_uniques::Config::InstanceId`.
+        type NftInstanceId: Parameter
+            + CheckedAdd
+            + Default
+            + One
+            + Copy
+            + HasCompact
+            + PartialOrd;
+
         /// Additional project info.
         type ProjectsInfo: DeipProjectsInfo<Self::AccountId>;
 
         /// Max class id available for asset creation via origin `pallet_uniques::Call`.
         type MaxOriginClassId: Get<Self::ClassId>;
+
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+        /// Fungible shares of fractionalized instances.
+        type Fractions: DeipFractions<Self::AccountId>;
+
+        /// Source id of [`DeipAssetSystem`] the price of sales is paid with.
+        type SourceId: Member + Parameter;
+
+        /// Max number of instances minted by [`Pallet::deip_mint_batch`].
+        #[pallet::constant]
+        type MaxBatchMint: Get<u32>;
+
+        /// Max number of attributes of an instance minted by [`Pallet::deip_mint_batch`].
+        #[pallet::constant]
+        type MaxBatchAttributes: Get<u32>;
+
+        type DeipWeightInfo: crate::weights::WeightInfo;
+
+        /// Unlinks destroyed classes from entities of other pallets.
+        type OnClassDestroyed: OnNftClassDestroyed<Self::DeipNftClassId>;
     }
 
     use frame_support::traits::{GetStorageVersion, StorageVersion};
 
     pub const V0: StorageVersion = StorageVersion::new(0);
     pub const V1: StorageVersion = StorageVersion::new(1);
+    pub const V2: StorageVersion = StorageVersion::new(2);
+
+    /// Number of instances of `pallet_uniques` processed by the backfill of holders in a block.
+    pub const MAX_BACKFILL_INSTANCES_PER_BLOCK: u32 = 100;
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
-    #[pallet::storage_version(V1)]
+    #[pallet::storage_version(V2)]
     pub struct Pallet<T>(_);
 
     fn count_items(pallet_name: &[u8], storage_name: &[u8]) -> usize {
@@ -72,20 +166,26 @@ pub mod pallet {
     #[doc(hidden)]
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+            Self::backfill_holders(MAX_BACKFILL_INSTANCES_PER_BLOCK)
+        }
+
         fn on_runtime_upgrade() -> Weight {
             use core::convert::TryInto;
-            use frame_support::storage::migration::move_storage_from_pallet;
+            use frame_support::storage::migration::{
+                move_storage_from_pallet, remove_storage_prefix,
+            };
 
-            if Pallet::<T>::on_chain_storage_version() == V0 &&
-                Pallet::<T>::current_storage_version() == V1
-            {
+            let on_chain_version = Pallet::<T>::on_chain_storage_version();
+            let mut weight: Weight = 0;
+
+            if on_chain_version == V0 {
                 let mut reads: usize = 0;
                 for x in &[
                     "NftClassIdByDeipNftClassId",
                     "DeipNftClassIdByNftClassId",
                     "NextNftClassId",
                     "ProjectIdByDeipNftClassId",
-                    "NftBalanceMap",
                 ] {
                     reads += count_items(b"Uniques", x.as_bytes());
                     move_storage_from_pallet(
@@ -107,7 +207,8 @@ pub mod pallet {
                     })
                     .for_each(|(k, v)| DeipNftClassIdByNftClassIdV1::<T>::insert(k, v));
                 reads += ProjectIdByDeipNftClassId::<T>::drain().count();
-                reads += NftBalanceMap::<T>::drain().count();
+                reads += count_items(b"Uniques", b"NftBalanceMap");
+                remove_storage_prefix(b"Uniques", b"NftBalanceMap", &[]);
 
                 for x in &[
                     "Class",
@@ -126,9 +227,16 @@ pub mod pallet {
                 }
 
                 let reads: Weight = reads.try_into().unwrap_or(Weight::MAX);
-                return T::DbWeight::get().reads_writes(reads, reads)
+                weight = weight.saturating_add(T::DbWeight::get().reads_writes(reads, reads));
+            }
+
+            if on_chain_version < V2 && !HoldersBackfillCursor::<T>::exists() {
+                // the version is set when the backfill is finished in `on_initialize`
+                HoldersBackfillCursor::<T>::put(Self::uniques_instances_prefix().to_vec());
+                weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
             }
-            0
+
+            weight
         }
     }
 
@@ -197,28 +305,201 @@ pub mod pallet {
     #[pallet::storage]
     pub(super) type NextNftClassId<T> = StorageValue<_, <T as Config>::NftClassId, ValueQuery>;
 
+    /// Storage for next instance id minted by [`Pallet::deip_mint_next`].
+    #[pallet::storage]
+    pub(super) type NextNftInstanceId<T: Config> =
+        StorageMap<_, Blake2_128Concat, DeipNftClassIdOf<T>, T::NftInstanceId, ValueQuery>;
+
     /// Storage with projects ids.
     /// Deprecated
     #[pallet::storage]
     pub(super) type ProjectIdByDeipNftClassId<T> =
         StorageMap<_, Identity, DeipNftClassIdOf<T>, DeipProjectIdOf<T>, OptionQuery>;
 
-    /// Storage with assets classes ant accounts which hold corresponding asset.
-    /// Deprecated
+    /// Project the class is issued for.
     #[pallet::storage]
-    pub(super) type NftBalanceMap<T: Config> =
-        StorageMap<_, Identity, DeipNftClassIdOf<T>, Vec<AccountIdOf<T>>, OptionQuery>;
+    pub(super) type ProjectIdByDeipNftClassIdV1<T: Config> =
+        StorageMap<_, Blake2_128Concat, DeipNftClassIdOf<T>, DeipProjectIdOf<T>, OptionQuery>;
+
+    /// Classes issued for the project.
+    #[pallet::storage]
+    pub(super) type DeipNftClassIdByProjectId<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DeipProjectIdOf<T>,
+        Blake2_128Concat,
+        DeipNftClassIdOf<T>,
+        (),
+        OptionQuery,
+    >;
+
+    /// Storage with accounts which hold instances of the class
+    /// and the number of instances they hold.
+    #[pallet::storage]
+    pub(super) type HoldersByClass<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DeipNftClassIdOf<T>,
+        Blake2_128Concat,
+        AccountIdOf<T>,
+        u32,
+        OptionQuery,
+    >;
+
+    /// Storage with the number of holders of the class.
+    #[pallet::storage]
+    pub(super) type HolderCountByClass<T: Config> =
+        StorageMap<_, Blake2_128Concat, DeipNftClassIdOf<T>, u32, ValueQuery>;
+
+    /// Storage key of the last instance of `pallet_uniques` added to [`HoldersByClass`]
+    /// while the index is backfilled after the upgrade to `V2`.
+    #[pallet::storage]
+    pub(super) type HoldersBackfillCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+    /// Fractionalized instance locked in the vault account,
+    /// see [`Pallet::fraction_key`].
+    #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+    pub struct Fraction<DeipNftClassId, InstanceId, Balance> {
+        pub class: DeipNftClassId,
+        pub instance: InstanceId,
+        /// Price to buy the instance from share holders.
+        pub buyout_price: Option<Balance>,
+        /// Proceeds of the buyout not claimed by share holders yet.
+        pub proceeds: Option<Balance>,
+    }
+
+    /// Storage with fractionalized instances by assets of their shares.
+    #[pallet::storage]
+    pub(super) type FractionByAsset<T: Config> =
+        StorageMap<_, Blake2_128Concat, FractionAssetIdOf<T>, FractionOf<T>, OptionQuery>;
+
+    /// Instance minted by [`Pallet::deip_mint_batch`].
+    #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+    pub struct MintItem<InstanceId, DeipAccountId, Data, Attributes> {
+        pub instance: InstanceId,
+        pub owner: DeipAccountId,
+        /// Hash of the instance metadata.
+        pub metadata: Option<Data>,
+        /// Pairs of key and value.
+        pub attributes: Attributes,
+    }
+
+    /// Royalty paid to `recipient` from every sale of instances of the class
+    /// via [`Pallet::deip_buy`].
+    #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+    pub struct Royalty<AccountId> {
+        pub recipient: AccountId,
+        pub rate: Permill,
+    }
+
+    /// Storage with royalty settings of classes.
+    #[pallet::storage]
+    pub(super) type RoyaltyByClass<T: Config> =
+        StorageMap<_, Blake2_128Concat, DeipNftClassIdOf<T>, RoyaltyOf<T>, OptionQuery>;
+
+    /// Instance listed for sale and locked in the escrow account,
+    /// see [`Pallet::sale_key`].
+    #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+    pub struct Sale<AccountId, AssetId, Balance> {
+        pub seller: AccountId,
+        /// Asset the price is paid in.
+        pub asset: AssetId,
+        pub price: Balance,
+        /// Royalty of the class at the moment of listing.
+        pub royalty: Option<Royalty<AccountId>>,
+    }
+
+    /// Storage with instances listed for sale.
+    #[pallet::storage]
+    pub(super) type SaleByInstance<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DeipNftClassIdOf<T>,
+        Blake2_128Concat,
+        T::InstanceId,
+        SaleOf<T>,
+        OptionQuery,
+    >;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Event emitted when an instance has been fractionalized:
+        /// class, instance, asset of shares, number of shares.
+        Fractionalized(
+            DeipNftClassIdOf<T>,
+            T::InstanceId,
+            FractionAssetIdOf<T>,
+            FractionBalanceOf<T>,
+        ),
+        /// Event emitted when an instance has been redeemed by the holder of all shares:
+        /// asset of shares, holder.
+        Redeemed(FractionAssetIdOf<T>, AccountIdOf<T>),
+        /// Event emitted when an instance has been bought out: asset of shares, buyer, price.
+        BoughtOut(FractionAssetIdOf<T>, AccountIdOf<T>, CurrencyBalanceOf<T>),
+        /// Event emitted when a share holder has claimed proceeds of the buyout:
+        /// asset of shares, holder, amount.
+        BuyoutClaimed(FractionAssetIdOf<T>, AccountIdOf<T>, CurrencyBalanceOf<T>),
+        /// Event emitted when instances of the class have been minted by a batch:
+        /// class, number of instances.
+        BatchMinted(DeipNftClassIdOf<T>, u32),
+        /// Event emitted when the class has been linked to the project or unlinked from it.
+        ClassProjectSet(DeipNftClassIdOf<T>, Option<DeipProjectIdOf<T>>),
+        /// Event emitted when royalty of the class has been set or removed.
+        RoyaltySet(DeipNftClassIdOf<T>, Option<RoyaltyOf<T>>),
+        /// Event emitted when an instance has been listed for sale:
+        /// class, instance, seller, asset, price.
+        SaleListed(
+            DeipNftClassIdOf<T>,
+            T::InstanceId,
+            AccountIdOf<T>,
+            DeipAssetIdOf<T>,
+            DeipAssetBalanceOf<T>,
+        ),
+        /// Event emitted when a sale has been cancelled and the instance
+        /// has been returned to the seller.
+        SaleCancelled(DeipNftClassIdOf<T>, T::InstanceId),
+        /// Event emitted when an instance has been sold: class, instance, buyer, price, royalty.
+        Sold(
+            DeipNftClassIdOf<T>,
+            T::InstanceId,
+            AccountIdOf<T>,
+            DeipAssetBalanceOf<T>,
+            DeipAssetBalanceOf<T>,
+        ),
+    }
 
     #[pallet::error]
     pub enum Error<T> {
         DeipNftClassIdExists,
         DeipNftClassIdDoesNotExist,
         NftClassIdOverflow,
+        NftInstanceIdOverflow,
+        /// Next instance id of the class is taken, the counter is moved forward.
+        NftInstanceIdOccupied,
         ProjectDoesNotExist,
         ProjectDoesNotBelongToTeam,
         ProjectSecurityTokenCannotBeDestroyed,
         ProjectSecurityTokenCannotBeBurned,
         ProjectSecurityTokenCannotBeFrozen,
+        NotInstanceOwner,
+        FractionSharesMustBePositive,
+        FractionBuyoutPriceMustBePositive,
+        /// Not enough balance to create the vault of the instance.
+        FractionBalanceIsNotEnough,
+        FractionNotFound,
+        /// Shares of the instance can be exchanged only for proceeds of the buyout.
+        FractionBoughtOut,
+        FractionNotBoughtOut,
+        FractionBuyoutNotAllowed,
+        /// All shares are required to redeem the instance.
+        FractionNotAllShares,
+        NothingToClaim,
+        NotClassOwner,
+        SalePriceMustBePositive,
+        SaleNotFound,
+        /// Only the seller may cancel the sale.
+        NotSeller,
     }
 
     #[pallet::call]
@@ -237,6 +518,7 @@ pub mod pallet {
             pallet_uniques::Call::<T>::destroy{
                 class: <_>::default(), witness: *witness
             }.get_dispatch_info().weight + T::DbWeight::get().reads(2)
+                + T::OnClassDestroyed::max_weight()
         )]
         pub fn deip_destroy(
             origin: OriginFor<T>,
@@ -252,6 +534,15 @@ pub mod pallet {
             if res.is_ok() {
                 DeipNftClassIdByNftClassIdV1::<T>::mutate_exists(origin_class_id, |v| *v = None);
                 NftClassIdByDeipNftClassIdV1::<T>::mutate_exists(class, |v| *v = None);
+                NextNftInstanceId::<T>::remove(class);
+                HoldersByClass::<T>::remove_prefix(class, None);
+                HolderCountByClass::<T>::remove(class);
+                if let Some(project) = ProjectIdByDeipNftClassIdV1::<T>::take(class) {
+                    DeipNftClassIdByProjectId::<T>::remove(project, class);
+                }
+                RoyaltyByClass::<T>::remove(class);
+                SaleByInstance::<T>::remove_prefix(class, None);
+                T::OnClassDestroyed::on_nft_class_destroyed(&class);
             }
 
             res
@@ -264,8 +555,10 @@ pub mod pallet {
             instance: T::InstanceId,
             owner: T::DeipAccountId,
         ) -> DispatchResultWithPostInfo {
+            let owner = owner.into();
+
             // Convert target to source.
-            let owner_source = <T::Lookup as StaticLookup>::unlookup(owner.into());
+            let owner_source = <T::Lookup as StaticLookup>::unlookup(owner.clone());
 
             let origin_class_id = Self::deip_to_origin_class_id(class)?;
 
@@ -275,7 +568,59 @@ pub mod pallet {
                 instance,
                 owner: owner_source,
             };
-            call.dispatch_bypass_filter(origin)
+            let post_dispatch_info = call.dispatch_bypass_filter(origin)?;
+
+            if Self::is_instance_indexed(&origin_class_id, &instance) {
+                Self::add_holder_instance(class, &owner);
+            }
+            Self::note_minted_instance(class, instance);
+
+            Ok(post_dispatch_info)
+        }
+
+        /// Mints `items` of the class with their metadata and attributes atomically.
+        ///
+        /// The origin for this call must be _Signed_ by the issuer of the class.
+        /// Metadata and attributes require the issuer to be the owner of the class.
+        #[pallet::weight({
+            let a = items.iter().map(|item| item.attributes.len() as u32).max().unwrap_or(0);
+            T::DeipWeightInfo::deip_mint_batch(items.len() as u32, a)
+        })]
+        #[transactional]
+        pub fn deip_mint_batch(
+            origin: OriginFor<T>,
+            class: DeipNftClassIdOf<T>,
+            items: BoundedVec<MintItemOf<T>, T::MaxBatchMint>,
+        ) -> DispatchResultWithPostInfo {
+            let origin_class_id = Self::deip_to_origin_class_id(class)?;
+            let count = items.len() as u32;
+
+            for MintItem { instance, owner, metadata, attributes } in items {
+                Self::deip_mint(origin.clone(), class, instance, owner)?;
+
+                if let Some(data) = metadata {
+                    UniquesPallet::<T>::set_metadata(
+                        origin.clone(),
+                        origin_class_id,
+                        instance,
+                        data,
+                        false,
+                    )?;
+                }
+                for (key, value) in attributes {
+                    UniquesPallet::<T>::set_attribute(
+                        origin.clone(),
+                        origin_class_id,
+                        Some(instance),
+                        key,
+                        value,
+                    )?;
+                }
+            }
+
+            Self::deposit_event(Event::<T>::BatchMinted(class, count));
+
+            Ok(().into())
         }
 
         #[pallet::weight(T::WeightInfo::burn())]
@@ -291,13 +636,25 @@ pub mod pallet {
             let check_owner_source =
                 check_owner.map(|owner| <T::Lookup as StaticLookup>::unlookup(owner.into()));
 
+            let owner = <UniquesPallet<T> as Inspect<T::AccountId>>::owner(
+                &origin_class_id,
+                &instance,
+            );
+            let is_indexed = Self::is_instance_indexed(&origin_class_id, &instance);
+
             // Dispatch destroy call to origin pallet.
             let call = pallet_uniques::Call::<T>::burn {
                 class: origin_class_id,
                 instance,
                 check_owner: check_owner_source,
             };
-            call.dispatch_bypass_filter(origin)
+            let post_dispatch_info = call.dispatch_bypass_filter(origin)?;
+
+            if let Some(owner) = owner.filter(|_| is_indexed) {
+                Self::remove_holder_instance(class, &owner);
+            }
+
+            Ok(post_dispatch_info)
         }
 
         #[pallet::weight(T::WeightInfo::transfer())]
@@ -307,18 +664,34 @@ pub mod pallet {
             instance: T::InstanceId,
             dest: T::DeipAccountId,
         ) -> DispatchResultWithPostInfo {
+            let dest = dest.into();
+
             // Convert target to source.
-            let dest_source = <T::Lookup as StaticLookup>::unlookup(dest.into());
+            let dest_source = <T::Lookup as StaticLookup>::unlookup(dest.clone());
 
             let origin_class_id = Self::deip_to_origin_class_id(class)?;
 
+            let owner = <UniquesPallet<T> as Inspect<T::AccountId>>::owner(
+                &origin_class_id,
+                &instance,
+            );
+
             // Dispatch call to origin pallet.
             let call = pallet_uniques::Call::<T>::transfer {
                 class: origin_class_id,
                 instance,
                 dest: dest_source,
             };
-            call.dispatch_bypass_filter(origin)
+            let post_dispatch_info = call.dispatch_bypass_filter(origin)?;
+
+            if Self::is_instance_indexed(&origin_class_id, &instance) {
+                if let Some(owner) = owner {
+                    Self::remove_holder_instance(class, &owner);
+                }
+                Self::add_holder_instance(class, &dest);
+            }
+
+            Ok(post_dispatch_info)
         }
 
         #[pallet::weight(T::WeightInfo::redeposit(instances.len() as u32))]
@@ -538,11 +911,376 @@ pub mod pallet {
             let class = Self::deip_to_origin_class_id(class)?;
             UniquesPallet::<T>::clear_class_metadata(origin, class)
         }
+
+        /// Links the class to the project or unlinks it if `project` is `None`.
+        ///
+        /// The origin for this call must be _Signed_ by the owner of the class.
+        /// The project must belong to the owner of the class.
+        #[pallet::weight(T::DbWeight::get().reads_writes(4, 3))]
+        pub fn deip_set_class_project(
+            origin: OriginFor<T>,
+            class: DeipNftClassIdOf<T>,
+            project: Option<DeipProjectIdOf<T>>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let owner = Self::class_owner(class).ok_or(Error::<T>::DeipNftClassIdDoesNotExist)?;
+            ensure!(who == owner, Error::<T>::NotClassOwner);
+
+            if let Some(ref project) = project {
+                let team = T::ProjectsInfo::try_get_project_team(project)
+                    .ok_or(Error::<T>::ProjectDoesNotExist)?;
+                ensure!(team == owner, Error::<T>::ProjectDoesNotBelongToTeam);
+            }
+
+            if let Some(previous) = ProjectIdByDeipNftClassIdV1::<T>::take(class) {
+                DeipNftClassIdByProjectId::<T>::remove(previous, class);
+            }
+            if let Some(ref project) = project {
+                ProjectIdByDeipNftClassIdV1::<T>::insert(class, project);
+                DeipNftClassIdByProjectId::<T>::insert(project, class, ());
+            }
+
+            Self::deposit_event(Event::<T>::ClassProjectSet(class, project));
+            Ok(().into())
+        }
+
+        /// Sets royalty paid from sales of instances of the class
+        /// or removes it if `royalty` is `None`. Transfers are royalty-free.
+        ///
+        /// The origin for this call must be _Signed_ by the owner of the class.
+        #[pallet::weight(T::DbWeight::get().reads_writes(3, 1))]
+        pub fn deip_set_royalty(
+            origin: OriginFor<T>,
+            class: DeipNftClassIdOf<T>,
+            royalty: Option<RoyaltyOf<T>>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::set_royalty_impl(who, class, royalty)?;
+            Ok(().into())
+        }
+
+        /// Locks the instance in the escrow account and lists it for `price` of `asset`.
+        ///
+        /// The origin for this call must be _Signed_ by the owner of the instance.
+        #[pallet::weight(
+            T::WeightInfo::transfer().saturating_add(T::DbWeight::get().reads_writes(4, 2))
+        )]
+        #[transactional]
+        pub fn deip_sell(
+            origin: OriginFor<T>,
+            class: DeipNftClassIdOf<T>,
+            instance: T::InstanceId,
+            asset: DeipAssetIdOf<T>,
+            price: DeipAssetBalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::sell_impl(who, class, instance, asset, price)?;
+            Ok(().into())
+        }
+
+        /// Returns the listed instance to the seller.
+        ///
+        /// The origin for this call must be _Signed_ by the seller.
+        #[pallet::weight(
+            T::WeightInfo::transfer().saturating_add(T::DbWeight::get().reads_writes(2, 2))
+        )]
+        #[transactional]
+        pub fn deip_cancel_sale(
+            origin: OriginFor<T>,
+            class: DeipNftClassIdOf<T>,
+            instance: T::InstanceId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::cancel_sale_impl(who, class, instance)?;
+            Ok(().into())
+        }
+
+        /// Buys the listed instance. The royalty is deducted from the price
+        /// in favor of its recipient and the rest is paid to the seller.
+        ///
+        /// The origin for this call must be _Signed_.
+        #[pallet::weight(
+            T::WeightInfo::transfer().saturating_add(T::DbWeight::get().reads_writes(6, 6))
+        )]
+        #[transactional]
+        pub fn deip_buy(
+            origin: OriginFor<T>,
+            class: DeipNftClassIdOf<T>,
+            instance: T::InstanceId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::buy_impl(who, class, instance)?;
+            Ok(().into())
+        }
+
+        /// Locks the instance in the vault account and mints `shares` of a new asset
+        /// to the owner of the instance. Metadata of the asset points to the instance.
+        ///
+        /// The origin for this call must be _Signed_ by the owner of the instance.
+        ///
+        /// If `buyout_price` is set anyone can buy the instance with [`buyout`](Self::buyout).
+        #[pallet::weight(
+            T::WeightInfo::transfer().saturating_add(T::DbWeight::get().reads_writes(12, 14))
+        )]
+        #[transactional]
+        pub fn fractionalize(
+            origin: OriginFor<T>,
+            class: DeipNftClassIdOf<T>,
+            instance: T::InstanceId,
+            asset: FractionAssetIdOf<T>,
+            shares: FractionBalanceOf<T>,
+            buyout_price: Option<CurrencyBalanceOf<T>>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::fractionalize_impl(who, class, instance, asset, shares, buyout_price)?;
+            Ok(().into())
+        }
+
+        /// Burns all shares of the instance and releases it to the caller.
+        ///
+        /// The origin for this call must be _Signed_ by the holder of all shares.
+        #[pallet::weight(
+            T::WeightInfo::transfer().saturating_add(T::DbWeight::get().reads_writes(6, 6))
+        )]
+        #[transactional]
+        pub fn redeem(
+            origin: OriginFor<T>,
+            asset: FractionAssetIdOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::redeem_impl(who, asset)?;
+            Ok(().into())
+        }
+
+        /// Buys the instance at the buyout price. Share holders get the price pro rata
+        /// to their shares with [`claim_buyout`](Self::claim_buyout).
+        ///
+        /// The origin for this call must be _Signed_.
+        #[pallet::weight(
+            T::WeightInfo::transfer().saturating_add(T::DbWeight::get().reads_writes(4, 4))
+        )]
+        #[transactional]
+        pub fn buyout(
+            origin: OriginFor<T>,
+            asset: FractionAssetIdOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::buyout_impl(who, asset)?;
+            Ok(().into())
+        }
+
+        /// Burns shares of the caller in exchange for the part of buyout proceeds.
+        ///
+        /// The origin for this call must be _Signed_.
+        #[pallet::weight(T::DbWeight::get().reads_writes(6, 6))]
+        #[transactional]
+        pub fn claim_buyout(
+            origin: OriginFor<T>,
+            asset: FractionAssetIdOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::claim_buyout_impl(who, asset)?;
+            Ok(().into())
+        }
     }
 
     impl<T: Config> Pallet<T> {
+        /// Owner of the class with Deip class id.
+        pub fn class_owner(class: DeipNftClassIdOf<T>) -> Option<T::AccountId> {
+            let origin_class_id = Self::deip_to_origin_class_id(class).ok()?;
+            <UniquesPallet<T> as Inspect<T::AccountId>>::class_owner(&origin_class_id)
+        }
+
+        pub fn owner(class: DeipNftClassIdOf<T>, instance: T::InstanceId) -> Option<T::AccountId> {
+            let origin_class_id = Self::deip_to_origin_class_id(class).ok()?;
+            <UniquesPallet<T> as Inspect<T::AccountId>>::owner(&origin_class_id, &instance)
+        }
+
+        /// Account which holds the instance for its own benefit: the owner, or the seller
+        /// while the instance is listed for sale. None while the instance is fractionalized
+        /// since it's held by share holders jointly.
+        pub fn holder(class: DeipNftClassIdOf<T>, instance: T::InstanceId) -> Option<T::AccountId> {
+            let owner = Self::owner(class, instance)?;
+            if owner == Self::sale_key(&class, &instance) {
+                return Self::sale(class, instance).map(|sale| sale.seller)
+            }
+            if owner == Self::fraction_key(&class, &instance) {
+                return None
+            }
+            Some(owner)
+        }
+
+        /// Account allowed to mint instances of the class with Deip class id.
+        pub fn class_issuer(class: DeipNftClassIdOf<T>) -> Option<T::AccountId> {
+            use frame_support::{
+                storage::{storage_prefix, unhashed},
+                traits::PalletInfoAccess,
+                StorageHasher,
+            };
+
+            let origin_class_id = Self::deip_to_origin_class_id(class).ok()?;
+            let prefix = storage_prefix(UniquesPallet::<T>::name().as_bytes(), b"Class");
+            let hashed = Blake2_128Concat::hash(&origin_class_id.encode());
+            let key = [&prefix[..], &hashed[..]].concat();
+            // `pallet_uniques::ClassDetails` starts with the owner and the issuer
+            // and its fields are not accessible outside the pallet
+            unhashed::get::<(T::AccountId, T::AccountId)>(&key).map(|(_, issuer)| issuer)
+        }
+
+        /// Mint an instance of the class via `deip_mint` on behalf of `issuer`.
+        /// Instance id is taken from `NextNftInstanceId` which is kept above
+        /// every instance minted via `deip_mint`.
+        pub fn deip_mint_next(
+            issuer: T::AccountId,
+            class: DeipNftClassIdOf<T>,
+            owner: T::AccountId,
+        ) -> Result<T::InstanceId, DispatchError> {
+            let origin_class_id = Self::deip_to_origin_class_id(class)?;
+
+            let instance = NextNftInstanceId::<T>::get(class);
+            if <UniquesPallet<T> as Inspect<T::AccountId>>::owner(&origin_class_id, &instance)
+                .is_some()
+            {
+                // minted before the counter was maintained, the next call takes the next id
+                Self::note_minted_instance(class, instance);
+                return Err(Error::<T>::NftInstanceIdOccupied.into())
+            }
+
+            Self::deip_mint(RawOrigin::Signed(issuer).into(), class, instance, owner.into())
+                .map_err(|e| e.error)?;
+
+            Ok(instance)
+        }
+
+        /// Moves `NextNftInstanceId` of the class past `instance`.
+        fn note_minted_instance(class: DeipNftClassIdOf<T>, instance: T::NftInstanceId) {
+            NextNftInstanceId::<T>::mutate(class, |next| {
+                if instance >= *next {
+                    // the counter stays at the last id on overflow, minting it fails
+                    *next = instance.checked_add(&One::one()).unwrap_or(instance);
+                }
+            });
+        }
+
+        /// Checks if the class of `pallet_uniques` is created via `deip_create`.
+        pub fn is_deip_class(class: &T::NftClassId) -> bool {
+            DeipNftClassIdByNftClassIdV1::<T>::contains_key(class)
+        }
+
+        /// Project the class is issued for.
+        pub fn class_project(class: DeipNftClassIdOf<T>) -> Option<DeipProjectIdOf<T>> {
+            ProjectIdByDeipNftClassIdV1::<T>::get(class)
+        }
+
+        /// Checks if the account holds any instance of the class.
+        pub fn is_holder(class: DeipNftClassIdOf<T>, account: &T::AccountId) -> bool {
+            HoldersByClass::<T>::contains_key(class, account)
+        }
+
+        /// Number of accounts which hold instances of the class.
+        pub fn holder_count(class: DeipNftClassIdOf<T>) -> u32 {
+            HolderCountByClass::<T>::get(class)
+        }
+
+        fn add_holder_instance(class: DeipNftClassIdOf<T>, holder: &T::AccountId) {
+            HoldersByClass::<T>::mutate(class, holder, |instances| {
+                if instances.is_none() {
+                    HolderCountByClass::<T>::mutate(class, |count| {
+                        *count = count.saturating_add(1)
+                    });
+                }
+                *instances = Some(instances.unwrap_or_default().saturating_add(1));
+            });
+        }
+
+        fn remove_holder_instance(class: DeipNftClassIdOf<T>, holder: &T::AccountId) {
+            HoldersByClass::<T>::mutate_exists(class, holder, |instances| {
+                let left = instances.unwrap_or_default().saturating_sub(1);
+                if left == 0 {
+                    if instances.is_some() {
+                        HolderCountByClass::<T>::mutate(class, |count| {
+                            *count = count.saturating_sub(1)
+                        });
+                    }
+                    *instances = None;
+                } else {
+                    *instances = Some(left);
+                }
+            });
+        }
+
+        /// Adds up to `limit` instances of `pallet_uniques` to the index of holders
+        /// in the order of their storage keys. Sets the storage version `V2` when finished.
+        fn backfill_holders(limit: u32) -> Weight {
+            use frame_support::storage::unhashed;
+
+            let mut cursor = match HoldersBackfillCursor::<T>::get() {
+                None => return T::DbWeight::get().reads(1),
+                Some(c) => c,
+            };
+
+            // instances are stored in `pallet_uniques::Asset` keyed by (class id, instance id),
+            // owner is the first field of `InstanceDetails`
+            let prefix = Self::uniques_instances_prefix();
+            let mut reads: Weight = 1;
+            let mut writes: Weight = 1;
+
+            for _ in 0..limit {
+                reads += 2;
+                let key = match sp_io::storage::next_key(&cursor) {
+                    Some(key) if key.starts_with(&prefix) => key,
+                    _ => {
+                        HoldersBackfillCursor::<T>::kill();
+                        V2.put::<Pallet<T>>();
+                        return T::DbWeight::get().reads_writes(reads, writes.saturating_add(1))
+                    },
+                };
+
+                // skip the hash of Blake2_128Concat
+                let origin_class_id =
+                    key.get(48..).and_then(|mut k| T::NftClassId::decode(&mut k).ok());
+                let owner = unhashed::get_raw(&key)
+                    .and_then(|value| T::AccountId::decode(&mut &value[..]).ok());
+                let class = origin_class_id.and_then(DeipNftClassIdByNftClassIdV1::<T>::get);
+                if let (Some(class), Some(owner)) = (class, owner) {
+                    reads += 2;
+                    writes += 2;
+                    Self::add_holder_instance(class, &owner);
+                }
+
+                cursor = key;
+            }
+
+            HoldersBackfillCursor::<T>::put(cursor);
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+
+        /// Checks if the instance is counted in the index of holders.
+        /// While the index is backfilled only instances up to the cursor are counted.
+        fn is_instance_indexed(class: &T::NftClassId, instance: &T::InstanceId) -> bool {
+            use frame_support::StorageHasher;
+
+            match HoldersBackfillCursor::<T>::get() {
+                None => true,
+                Some(cursor) => {
+                    let mut key = Self::uniques_instances_prefix().to_vec();
+                    key.extend(Blake2_128Concat::hash(&class.encode()));
+                    key.extend(Blake2_128Concat::hash(&instance.encode()));
+                    key <= cursor
+                },
+            }
+        }
+
+        fn uniques_instances_prefix() -> [u8; 32] {
+            use frame_support::{storage::storage_prefix, traits::PalletInfoAccess};
+
+            storage_prefix(UniquesPallet::<T>::name().as_bytes(), b"Asset")
+        }
+
         /// Convert DeipNftClassId to origin class id.
-        fn deip_to_origin_class_id(class: DeipNftClassIdOf<T>) -> Result<T::NftClassId, Error<T>> {
+        pub(crate) fn deip_to_origin_class_id(
+            class: DeipNftClassIdOf<T>,
+        ) -> Result<T::NftClassId, Error<T>> {
             NftClassIdByDeipNftClassIdV1::<T>::get(class)
                 .ok_or(Error::<T>::DeipNftClassIdDoesNotExist)
         }
@@ -0,0 +1,364 @@
+use deip_asset_system::{
+    investment_opportunity::InvestmentId, AssetIdInitT, DeipAssetSystem, DeipFractions,
+    ReserveError, UnreserveError,
+};
+use deip_projects_info::DeipProjectsInfo;
+use frame_support::{parameter_types, traits::Everything};
+use sp_core::{H160, H256};
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    DispatchError, DispatchResult,
+};
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap};
+
+use crate as pallet_deip_uniques;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub type AccountId = u64;
+pub type Balance = u64;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+        Uniques: pallet_uniques::{Pallet, Call, Storage, Event<T>},
+        DeipUniques: pallet_deip_uniques::{Pallet, Call, Storage, Event<T>},
+    }
+);
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type Origin = Origin;
+    type Call = Call;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = Event;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: Balance = 1;
+    pub const MaxLocks: u32 = 50;
+    pub const MaxReserves: u32 = 50;
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = MaxLocks;
+    type MaxReserves = MaxReserves;
+    type ReserveIdentifier = [u8; 8];
+    type Balance = Balance;
+    type DustRemoval = ();
+    type Event = Event;
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const ClassDeposit: Balance = 0;
+    pub const InstanceDeposit: Balance = 0;
+    pub const MetadataDepositBase: Balance = 0;
+    pub const AttributeDepositBase: Balance = 0;
+    pub const DepositPerByte: Balance = 0;
+    pub const StringLimit: u32 = 50;
+    pub const KeyLimit: u32 = 32;
+    pub const ValueLimit: u32 = 64;
+}
+
+impl pallet_uniques::Config for Test {
+    type Event = Event;
+    type ClassId = u32;
+    type InstanceId = u32;
+    type Currency = Balances;
+    type ForceOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type ClassDeposit = ClassDeposit;
+    type InstanceDeposit = InstanceDeposit;
+    type MetadataDepositBase = MetadataDepositBase;
+    type AttributeDepositBase = AttributeDepositBase;
+    type DepositPerByte = DepositPerByte;
+    type StringLimit = StringLimit;
+    type KeyLimit = KeyLimit;
+    type ValueLimit = ValueLimit;
+    type WeightInfo = ();
+}
+
+pub const PROJECT_TEAM: AccountId = 1;
+
+pub fn project() -> H160 {
+    H160::from_low_u64_be(1)
+}
+
+impl DeipProjectsInfo<AccountId> for Test {
+    type ProjectId = H160;
+    type InvestmentId = H160;
+
+    fn try_get_project_team(id: &Self::ProjectId) -> Option<AccountId> {
+        if *id == project() {
+            Some(PROJECT_TEAM)
+        } else {
+            None
+        }
+    }
+
+    fn project_id(source: &[u8]) -> Self::ProjectId {
+        H160::from_slice(&sp_io::hashing::blake2_256(source)[..20])
+    }
+}
+
+/// Minimum balance of every asset of the fake asset system.
+pub const MIN_BALANCE: Balance = 10;
+
+thread_local! {
+    static BALANCES: RefCell<BTreeMap<(AccountId, H160), Balance>> =
+        RefCell::new(BTreeMap::new());
+    static SHARES: RefCell<BTreeMap<(AccountId, H160), Balance>> =
+        RefCell::new(BTreeMap::new());
+}
+
+pub fn balance(account: AccountId, asset: H160) -> Balance {
+    BALANCES.with(|b| b.borrow().get(&(account, asset)).copied().unwrap_or_default())
+}
+
+pub fn set_balance(account: AccountId, asset: H160, amount: Balance) {
+    BALANCES.with(|b| b.borrow_mut().insert((account, asset), amount));
+}
+
+pub fn shares(account: AccountId, asset: H160) -> Balance {
+    SHARES.with(|s| s.borrow().get(&(account, asset)).copied().unwrap_or_default())
+}
+
+fn set_shares(account: AccountId, asset: H160, amount: Balance) {
+    SHARES.with(|s| s.borrow_mut().insert((account, asset), amount));
+}
+
+impl AssetIdInitT<H160> for Test {
+    fn asset_id(raw: &[u8]) -> H160 {
+        H160::from_slice(raw)
+    }
+}
+
+/// Plain balances of assets the price of sales is paid with.
+impl DeipAssetSystem<AccountId, H160, InvestmentId> for Test {
+    type Balance = Balance;
+    type AssetId = H160;
+
+    fn account_balance(account: &AccountId, asset: &Self::AssetId) -> Self::Balance {
+        balance(*account, *asset)
+    }
+
+    fn total_supply(asset: &Self::AssetId) -> Self::Balance {
+        BALANCES.with(|b| {
+            b.borrow().iter().filter(|((_, a), _)| a == asset).map(|(_, amount)| amount).sum()
+        })
+    }
+
+    fn minimum_balance(_asset: &Self::AssetId) -> Self::Balance {
+        MIN_BALANCE
+    }
+
+    fn transactionally_transfer(
+        from: &AccountId,
+        asset: Self::AssetId,
+        transfers: &[(Self::Balance, AccountId)],
+    ) -> DispatchResult {
+        let total: Balance = transfers.iter().map(|(amount, _)| amount).sum();
+        let free = balance(*from, asset);
+        if free < total {
+            return Err(DispatchError::Other("BalanceLow"))
+        }
+        if transfers
+            .iter()
+            .any(|(amount, to)| balance(*to, asset) == 0 && *amount < MIN_BALANCE)
+        {
+            return Err(DispatchError::Other("BelowMinimum"))
+        }
+        set_balance(*from, asset, free - total);
+        for (amount, to) in transfers {
+            set_balance(*to, asset, balance(*to, asset) + amount);
+        }
+        Ok(())
+    }
+
+    fn transactionally_reserve(
+        _account: &AccountId,
+        _id: InvestmentId,
+        _shares: &[(Self::AssetId, Self::Balance)],
+        _asset: Self::AssetId,
+    ) -> Result<(), ReserveError<Self::AssetId>> {
+        unimplemented!("sales don't reserve assets")
+    }
+
+    fn transactionally_reserve_escrow(
+        _account: &AccountId,
+        _id: InvestmentId,
+        _shares: &[(Self::AssetId, Self::Balance)],
+        _asset: Self::AssetId,
+    ) -> Result<(), ReserveError<Self::AssetId>> {
+        unimplemented!("sales don't reserve assets")
+    }
+
+    fn transactionally_unreserve(_id: InvestmentId) -> Result<(), UnreserveError<Self::AssetId>> {
+        unimplemented!("sales don't reserve assets")
+    }
+
+    fn transfer_from_reserved(
+        _id: InvestmentId,
+        _who: &AccountId,
+        _asset: Self::AssetId,
+        _amount: Self::Balance,
+    ) -> Result<(), UnreserveError<Self::AssetId>> {
+        unimplemented!("sales don't reserve assets")
+    }
+
+    fn transfer_to_reserved(
+        _who: &AccountId,
+        _id: InvestmentId,
+        _amount: Self::Balance,
+    ) -> Result<(), UnreserveError<Self::AssetId>> {
+        unimplemented!("sales don't reserve assets")
+    }
+}
+
+/// Shares are kept apart from assets of [`DeipAssetSystem`].
+impl DeipFractions<AccountId> for Test {
+    type Balance = Balance;
+    type AssetId = H160;
+
+    fn ensure_can_create(asset: &Self::AssetId) -> DispatchResult {
+        if Self::total_supply(asset) > 0 {
+            return Err(DispatchError::Other("AssetExists"))
+        }
+        Ok(())
+    }
+
+    fn create(
+        owner: &AccountId,
+        _vault: &AccountId,
+        asset: Self::AssetId,
+        _name: Vec<u8>,
+        amount: Self::Balance,
+    ) -> DispatchResult {
+        Self::ensure_can_create(&asset)?;
+        set_shares(*owner, asset, amount);
+        Ok(())
+    }
+
+    fn account_balance(account: &AccountId, asset: &Self::AssetId) -> Self::Balance {
+        shares(*account, *asset)
+    }
+
+    fn total_supply(asset: &Self::AssetId) -> Self::Balance {
+        SHARES.with(|s| {
+            s.borrow().iter().filter(|((_, a), _)| a == asset).map(|(_, amount)| amount).sum()
+        })
+    }
+
+    fn burn(
+        _vault: &AccountId,
+        asset: Self::AssetId,
+        who: &AccountId,
+        amount: Self::Balance,
+    ) -> DispatchResult {
+        let held = shares(*who, asset);
+        if held < amount {
+            return Err(DispatchError::Other("BalanceLow"))
+        }
+        set_shares(*who, asset, held - amount);
+        Ok(())
+    }
+}
+
+parameter_types! {
+    pub const MaxOriginClassId: u32 = 1000;
+    pub const MaxBatchMint: u32 = 10;
+    pub const MaxBatchAttributes: u32 = 4;
+}
+
+impl pallet_deip_uniques::Config for Test {
+    type DeipNftClassId = H160;
+    type DeipAccountId = AccountId;
+    type ProjectId = H160;
+    type NftClassId = u32;
+    type NftInstanceId = u32;
+    type ProjectsInfo = Self;
+    type MaxOriginClassId = MaxOriginClassId;
+    type Event = Event;
+    type Fractions = Self;
+    type SourceId = H160;
+    type MaxBatchMint = MaxBatchMint;
+    type MaxBatchAttributes = MaxBatchAttributes;
+    type DeipWeightInfo = ();
+    type OnClassDestroyed = ();
+}
+
+pub const ALICE: AccountId = 2;
+pub const BOB: AccountId = 3;
+pub const CHARLIE: AccountId = 4;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    use frame_support::traits::GenesisBuild;
+
+    let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(PROJECT_TEAM, 1000), (ALICE, 1000), (BOB, 1000), (CHARLIE, 1000)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    pallet_deip_uniques::GenesisConfig::<Test>::default().assimilate_storage(&mut t).unwrap();
+
+    BALANCES.with(|b| b.borrow_mut().clear());
+    SHARES.with(|s| s.borrow_mut().clear());
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+pub fn class(n: u64) -> H160 {
+    H160::from_low_u64_be(100 + n)
+}
+
+pub fn usd() -> H160 {
+    H160::from_low_u64_be(2)
+}
+
+/// Creates the class owned by `owner` and mints `instances` of it to `owner`.
+pub fn create_class(owner: AccountId, id: H160, instances: &[u32]) {
+    assert_ok!(DeipUniques::deip_create(Origin::signed(owner), id, owner));
+    for instance in instances {
+        assert_ok!(DeipUniques::deip_mint(Origin::signed(owner), id, *instance, owner));
+    }
+}
+
+pub fn last_event() -> Event {
+    System::events().pop().expect("Event expected").event
+}
+
+pub use frame_support::{assert_noop, assert_ok};
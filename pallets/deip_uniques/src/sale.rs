@@ -0,0 +1,149 @@
+use codec::{Decode, Encode};
+use deip_asset_system::DeipAssetSystem;
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    sp_runtime::{
+        traits::{Saturating, Zero},
+        PerThing,
+    },
+    traits::tokens::nonfungibles::Inspect,
+};
+use frame_system::RawOrigin;
+use sp_std::vec;
+
+use crate::{
+    AccountIdOf, Config, DeipAssetBalanceOf, DeipAssetIdOf, DeipNftClassIdOf, Error, Event,
+    Pallet, RoyaltyByClass, RoyaltyOf, Sale, SaleByInstance, SaleOf,
+};
+
+impl<T: Config> Pallet<T> {
+    /// Account which holds the instance listed for sale.
+    pub fn sale_key(class: &DeipNftClassIdOf<T>, instance: &T::InstanceId) -> AccountIdOf<T> {
+        let entropy = (b"deip/sales/", class, instance).using_encoded(sp_io::hashing::blake2_256);
+        AccountIdOf::<T>::decode(&mut &entropy[..]).unwrap_or_default()
+    }
+
+    pub fn royalty(class: DeipNftClassIdOf<T>) -> Option<RoyaltyOf<T>> {
+        RoyaltyByClass::<T>::get(class)
+    }
+
+    pub fn sale(class: DeipNftClassIdOf<T>, instance: T::InstanceId) -> Option<SaleOf<T>> {
+        SaleByInstance::<T>::get(class, instance)
+    }
+
+    pub(crate) fn set_royalty_impl(
+        who: AccountIdOf<T>,
+        class: DeipNftClassIdOf<T>,
+        royalty: Option<RoyaltyOf<T>>,
+    ) -> DispatchResult {
+        let owner = Self::class_owner(class).ok_or(Error::<T>::DeipNftClassIdDoesNotExist)?;
+        ensure!(who == owner, Error::<T>::NotClassOwner);
+
+        match royalty {
+            Some(ref royalty) => RoyaltyByClass::<T>::insert(class, royalty),
+            None => RoyaltyByClass::<T>::remove(class),
+        }
+
+        Self::deposit_event(Event::<T>::RoyaltySet(class, royalty));
+
+        Ok(())
+    }
+
+    pub(crate) fn sell_impl(
+        who: AccountIdOf<T>,
+        class: DeipNftClassIdOf<T>,
+        instance: T::InstanceId,
+        asset: DeipAssetIdOf<T>,
+        price: DeipAssetBalanceOf<T>,
+    ) -> DispatchResult {
+        ensure!(!price.is_zero(), Error::<T>::SalePriceMustBePositive);
+
+        let origin_class_id = Self::deip_to_origin_class_id(class)?;
+        let owner = <pallet_uniques::Pallet<T> as Inspect<T::AccountId>>::owner(
+            &origin_class_id,
+            &instance,
+        );
+        ensure!(owner.as_ref() == Some(&who), Error::<T>::NotInstanceOwner);
+
+        Self::deip_transfer(
+            RawOrigin::Signed(who.clone()).into(),
+            class,
+            instance,
+            Self::sale_key(&class, &instance).into(),
+        )
+        .map_err(|e| e.error)?;
+
+        SaleByInstance::<T>::insert(
+            class,
+            instance,
+            Sale { seller: who.clone(), asset, price, royalty: RoyaltyByClass::<T>::get(class) },
+        );
+
+        Self::deposit_event(Event::<T>::SaleListed(class, instance, who, asset, price));
+
+        Ok(())
+    }
+
+    pub(crate) fn cancel_sale_impl(
+        who: AccountIdOf<T>,
+        class: DeipNftClassIdOf<T>,
+        instance: T::InstanceId,
+    ) -> DispatchResult {
+        let sale = SaleByInstance::<T>::take(class, instance).ok_or(Error::<T>::SaleNotFound)?;
+        ensure!(who == sale.seller, Error::<T>::NotSeller);
+
+        Self::deip_transfer(
+            RawOrigin::Signed(Self::sale_key(&class, &instance)).into(),
+            class,
+            instance,
+            who.into(),
+        )
+        .map_err(|e| e.error)?;
+
+        Self::deposit_event(Event::<T>::SaleCancelled(class, instance));
+
+        Ok(())
+    }
+
+    pub(crate) fn buy_impl(
+        who: AccountIdOf<T>,
+        class: DeipNftClassIdOf<T>,
+        instance: T::InstanceId,
+    ) -> DispatchResult {
+        let sale = SaleByInstance::<T>::take(class, instance).ok_or(Error::<T>::SaleNotFound)?;
+
+        // rounded down in favor of the seller
+        let mut royalty = sale
+            .royalty
+            .as_ref()
+            .map_or_else(Zero::zero, |royalty| royalty.rate.mul_floor(sale.price));
+
+        // a royalty which can't create the account of its recipient stays with the seller
+        let recipient = sale.royalty.map(|royalty| royalty.recipient).filter(|recipient| {
+            royalty >= T::minimum_balance(&sale.asset) ||
+                !T::account_balance(recipient, &sale.asset).is_zero()
+        });
+        if recipient.is_none() {
+            royalty = Zero::zero();
+        }
+
+        let mut transfers = vec![(sale.price.saturating_sub(royalty), sale.seller.clone())];
+        if let Some(recipient) = recipient.filter(|_| !royalty.is_zero()) {
+            transfers.push((royalty, recipient));
+        }
+        T::transactionally_transfer(&who, sale.asset, &transfers)?;
+
+        Self::deip_transfer(
+            RawOrigin::Signed(Self::sale_key(&class, &instance)).into(),
+            class,
+            instance,
+            who.clone().into(),
+        )
+        .map_err(|e| e.error)?;
+
+        Self::deposit_event(Event::<T>::Sold(class, instance, who, sale.price, royalty));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{mock::*, Error, MintItem, MintItemOf, Royalty};
+use core::convert::TryInto;
+use frame_support::sp_runtime::Permill;
+use pallet_uniques::DestroyWitness;
+use sp_core::H160;
+use sp_runtime::DispatchError;
+
+fn mint_item(instance: u32, owner: AccountId) -> MintItemOf<Test> {
+    let attribute = (b"tier".to_vec().try_into().unwrap(), b"gold".to_vec().try_into().unwrap());
+    MintItem {
+        instance,
+        owner,
+        metadata: Some(b"hash".to_vec().try_into().unwrap()),
+        attributes: vec![attribute].try_into().unwrap(),
+    }
+}
+
+fn royalty(recipient: AccountId, percent: u32) -> Option<Royalty<AccountId>> {
+    Some(Royalty { recipient, rate: Permill::from_percent(percent) })
+}
+
+#[test]
+fn failed_payment_returns_its_error() {
+    new_test_ext().execute_with(|| {
+        create_class(PROJECT_TEAM, class(1), &[1]);
+        assert_ok!(DeipUniques::deip_sell(Origin::signed(PROJECT_TEAM), class(1), 1, usd(), 100));
+
+        set_balance(ALICE, usd(), 99);
+        assert_noop!(
+            DeipUniques::deip_buy(Origin::signed(ALICE), class(1), 1),
+            DispatchError::Other("BalanceLow")
+        );
+    });
+}
+
+#[test]
+fn royalty_below_minimum_balance_stays_with_seller() {
+    new_test_ext().execute_with(|| {
+        create_class(PROJECT_TEAM, class(1), &[1, 2]);
+        assert_ok!(DeipUniques::deip_set_royalty(
+            Origin::signed(PROJECT_TEAM),
+            class(1),
+            royalty(CHARLIE, 5)
+        ));
+        assert_ok!(DeipUniques::deip_transfer(Origin::signed(PROJECT_TEAM), class(1), 1, BOB));
+        assert_ok!(DeipUniques::deip_sell(Origin::signed(BOB), class(1), 1, usd(), 100));
+
+        // 5 is below the minimum balance and would fail the payment to a new account
+        set_balance(ALICE, usd(), 100);
+        assert_ok!(DeipUniques::deip_buy(Origin::signed(ALICE), class(1), 1));
+        assert_eq!(balance(BOB, usd()), 100);
+        assert_eq!(balance(CHARLIE, usd()), 0);
+        assert_eq!(DeipUniques::owner(class(1), 1), Some(ALICE));
+        assert_eq!(
+            last_event(),
+            Event::DeipUniques(crate::Event::Sold(class(1), 1, ALICE, 100, 0))
+        );
+
+        // the recipient holds the asset now, so any royalty is paid
+        set_balance(CHARLIE, usd(), 1);
+        assert_ok!(DeipUniques::deip_sell(Origin::signed(ALICE), class(1), 1, usd(), 100));
+        set_balance(BOB, usd(), 100);
+        assert_ok!(DeipUniques::deip_buy(Origin::signed(BOB), class(1), 1));
+        assert_eq!(balance(ALICE, usd()), 95);
+        assert_eq!(balance(CHARLIE, usd()), 6);
+    });
+}
+
+#[test]
+fn listings_are_removed_with_class() {
+    new_test_ext().execute_with(|| {
+        create_class(PROJECT_TEAM, class(1), &[1]);
+        assert_ok!(DeipUniques::deip_sell(Origin::signed(PROJECT_TEAM), class(1), 1, usd(), 100));
+
+        let witness = DestroyWitness { instances: 1, instance_metadatas: 0, attributes: 0 };
+        assert_ok!(DeipUniques::deip_destroy(Origin::signed(PROJECT_TEAM), class(1), witness));
+        assert!(DeipUniques::sale(class(1), 1).is_none());
+
+        // a new class with the same id doesn't inherit the listing
+        create_class(PROJECT_TEAM, class(1), &[1]);
+        set_balance(ALICE, usd(), 100);
+        assert_noop!(
+            DeipUniques::deip_buy(Origin::signed(ALICE), class(1), 1),
+            Error::<Test>::SaleNotFound
+        );
+        assert_eq!(DeipUniques::owner(class(1), 1), Some(PROJECT_TEAM));
+    });
+}
+
+#[test]
+fn batch_is_minted_with_metadata_and_attributes() {
+    new_test_ext().execute_with(|| {
+        create_class(PROJECT_TEAM, class(1), &[]);
+        let items = vec![mint_item(1, ALICE), mint_item(2, BOB)].try_into().unwrap();
+        assert_ok!(DeipUniques::deip_mint_batch(Origin::signed(PROJECT_TEAM), class(1), items));
+
+        assert_eq!(DeipUniques::owner(class(1), 1), Some(ALICE));
+        assert_eq!(DeipUniques::owner(class(1), 2), Some(BOB));
+        assert_eq!(DeipUniques::holder_count(class(1)), 2);
+        assert_eq!(last_event(), Event::DeipUniques(crate::Event::BatchMinted(class(1), 2)));
+    });
+}
+
+#[test]
+fn batch_with_failed_item_mints_nothing() {
+    new_test_ext().execute_with(|| {
+        create_class(PROJECT_TEAM, class(1), &[2]);
+        let items = vec![mint_item(1, ALICE), mint_item(2, BOB)].try_into().unwrap();
+        assert!(DeipUniques::deip_mint_batch(Origin::signed(PROJECT_TEAM), class(1), items)
+            .is_err());
+
+        assert_eq!(DeipUniques::owner(class(1), 1), None);
+        assert_eq!(DeipUniques::owner(class(1), 2), Some(PROJECT_TEAM));
+    });
+}
+
+#[test]
+fn holder_is_seller_while_listed_and_none_while_fractionalized() {
+    new_test_ext().execute_with(|| {
+        create_class(PROJECT_TEAM, class(1), &[1, 2]);
+        assert_ok!(DeipUniques::deip_sell(Origin::signed(PROJECT_TEAM), class(1), 1, usd(), 100));
+        assert_eq!(DeipUniques::owner(class(1), 1), Some(DeipUniques::sale_key(&class(1), &1)));
+        assert_eq!(DeipUniques::holder(class(1), 1), Some(PROJECT_TEAM));
+
+        let asset = H160::from_low_u64_be(3);
+        assert_ok!(DeipUniques::fractionalize(
+            Origin::signed(PROJECT_TEAM),
+            class(1),
+            2,
+            asset,
+            100,
+            None
+        ));
+        assert_eq!(DeipUniques::holder(class(1), 2), None);
+    });
+}
@@ -0,0 +1,37 @@
+//! Weights for pallet_deip_uniques
+//!
+//! Weights of batch calls are composed per item from the benchmarked weights
+//! of `pallet_uniques` calls they dispatch, plus bookkeeping of this pallet.
+//! Run the `deip_mint_batch` benchmark to replace them with measured values.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use pallet_uniques::WeightInfo as UniquesWeightInfo;
+use sp_std::marker::PhantomData;
+
+pub trait WeightInfo {
+    /// `n` items with at most `a` attributes each.
+    fn deip_mint_batch(n: u32, a: u32) -> Weight;
+}
+
+/// Weight functions for pallet_deip_uniques.
+pub struct Weights<T>(PhantomData<T>);
+
+impl<T: pallet_uniques::Config> WeightInfo for Weights<T> {
+    fn deip_mint_batch(n: u32, a: u32) -> Weight {
+        // mint, metadata and `a` attributes of every item, holders index
+        let item = T::WeightInfo::mint()
+            .saturating_add(T::WeightInfo::set_metadata())
+            .saturating_add(T::WeightInfo::set_attribute().saturating_mul(a as Weight))
+            .saturating_add(T::DbWeight::get().reads_writes(3, 2));
+        (item.saturating_mul(n as Weight)).saturating_add(T::DbWeight::get().reads(1 as Weight))
+    }
+}
+
+impl WeightInfo for () {
+    fn deip_mint_batch(n: u32, a: u32) -> Weight {
+        (n as Weight).saturating_mul((a as Weight).saturating_add(1))
+    }
+}
@@ -2,14 +2,25 @@ use jsonrpc_core::{
     futures::{future, FutureExt, TryFutureExt},
     futures_executor::block_on,
     futures_util::{stream::FuturesOrdered, TryStreamExt},
+    Result as RpcResult,
 };
 use jsonrpc_derive::rpc;
 
-use std::{iter::FromIterator, vec::Vec};
+use std::{iter::FromIterator, sync::Arc, vec::Vec};
 
 use codec::{Codec, Decode, Encode, Input};
 
-use sp_runtime::traits::{AtLeast32BitUnsigned, Block as BlockT};
+use sp_runtime::{
+    generic::BlockId,
+    traits::{AtLeast32BitUnsigned, Block as BlockT},
+};
+
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+
+pub use pallet_deip_assets::api::DeipAssetsRuntimeApi;
+
+use deip_serializable_u128::SerializableAtLeast32BitUnsigned;
 
 use sp_core::storage::StorageKey;
 
@@ -18,7 +29,7 @@ use frame_support::{Blake2_128Concat, Identity, ReversibleStorageHasher, Storage
 use common_rpc::{
     chain_key_hash_double_map, chain_key_hash_map, get_list_by_keys, get_value, prefix,
     to_rpc_error, BoxFutureResult, Error, HashOf, HashedKey, HashedKeyRef, HashedKeyTrait,
-    ListResult,
+    ListResult, StorageMap,
 };
 
 mod types;
@@ -27,10 +38,20 @@ use types::*;
 /// Names of pallets in construct_runtime!.
 const PARITYTECH_PALLET_ASSETS: &[u8] = b"ParityTechAssets";
 const DEIP_PALLET_ASSETS: &[u8] = b"Assets";
+/// Name of `pallet_deip_assets` in construct_runtime! since the storage version V1.
+const DEIP_PALLET_ASSETS_V1: &[u8] = b"DeipAssets";
 
 #[rpc]
-pub trait DeipAssetsRpc<BlockHash, AssetId, Balance, AccountId, DepositBalance, Extra, DeipAssetId>
-where
+pub trait DeipAssetsRpc<
+    BlockHash,
+    AssetId,
+    Balance,
+    AccountId,
+    DepositBalance,
+    Extra,
+    DeipAssetId,
+    ProjectId,
+> where
     AssetId: Encode + Decode,
     DeipAssetId: Encode + Decode,
     Balance: Decode + AtLeast32BitUnsigned + Clone,
@@ -38,12 +59,15 @@ where
     DepositBalance: Decode + AtLeast32BitUnsigned + Clone,
     Extra: Decode,
 {
+    /// Get details of the asset with the latest version of its security metadata.
     #[rpc(name = "assets_getAsset")]
     fn get_asset(
         &self,
         at: Option<BlockHash>,
         id: DeipAssetId,
-    ) -> BoxFutureResult<Option<AssetDetails<Balance, AccountId, DepositBalance>>>;
+    ) -> BoxFutureResult<
+        Option<AssetInfo<Balance, AccountId, DepositBalance, ProjectId, BlockHash>>,
+    >;
 
     #[rpc(name = "assets_getAssetList")]
     fn get_asset_list(
@@ -79,37 +103,84 @@ where
         count: u32,
         start_id: Option<AccountId>,
     ) -> BoxFutureResult<Vec<AssetBalanceWithOwner<Balance, AccountId, Extra>>>;
+
+    /// Get accounts with non-zero balance of the asset.
+    /// Pass the last account of the previous page as `start_id` to get the next one.
+    #[rpc(name = "assets_getAssetHolderList")]
+    fn get_asset_holder_list(
+        &self,
+        at: Option<BlockHash>,
+        asset: DeipAssetId,
+        count: u32,
+        start_id: Option<AccountId>,
+    ) -> BoxFutureResult<Vec<AccountId>>;
+
+    /// Get amount of the asset which can be minted at the moment.
+    /// Returns `None` if minting of the asset isn't limited.
+    #[rpc(name = "assets_getRemainingSupply")]
+    fn get_remaining_supply(
+        &self,
+        at: Option<BlockHash>,
+        asset: DeipAssetId,
+    ) -> RpcResult<Option<SerializableAtLeast32BitUnsigned<Balance>>>;
 }
 
-pub struct DeipAssetsRpcObj<State, B> {
+pub struct DeipAssetsRpcObj<C, State, B> {
+    client: Arc<C>,
     state: State,
     _marker: std::marker::PhantomData<B>,
 }
 
-impl<State, B> DeipAssetsRpcObj<State, B> {
-    pub fn new(state: State) -> Self {
-        Self { state, _marker: Default::default() }
+impl<C, State, B> DeipAssetsRpcObj<C, State, B> {
+    pub fn new(client: Arc<C>, state: State) -> Self {
+        Self { client, state, _marker: Default::default() }
     }
 }
 
-impl<State, Block, AssetId, Balance, AccountId, DepositBalance, Extra, DeipAssetId>
-    DeipAssetsRpc<HashOf<Block>, AssetId, Balance, AccountId, DepositBalance, Extra, DeipAssetId>
-    for DeipAssetsRpcObj<State, Block>
+impl<
+        C,
+        State,
+        Block,
+        AssetId,
+        Balance,
+        AccountId,
+        DepositBalance,
+        Extra,
+        DeipAssetId,
+        ProjectId,
+    >
+    DeipAssetsRpc<
+        HashOf<Block>,
+        AssetId,
+        Balance,
+        AccountId,
+        DepositBalance,
+        Extra,
+        DeipAssetId,
+        ProjectId,
+    > for DeipAssetsRpcObj<C, State, Block>
 where
     AssetId: 'static + Codec + Send,
     DeipAssetId: 'static + Send + Codec + Clone,
-    Balance: 'static + Decode + AtLeast32BitUnsigned + Clone + Send,
+    Balance: 'static + Codec + AtLeast32BitUnsigned + Clone + Send,
     AccountId: 'static + Codec + Send,
     DepositBalance: 'static + Send + Encode + Decode + AtLeast32BitUnsigned + Clone,
     Extra: 'static + Send + Decode,
+    ProjectId: 'static + Send + Decode,
     State: sc_rpc_api::state::StateApi<HashOf<Block>>,
     Block: BlockT,
+    C: Send + Sync + 'static,
+    C: ProvideRuntimeApi<Block>,
+    C: HeaderBackend<Block>,
+    C::Api: DeipAssetsRuntimeApi<Block, DeipAssetId, AccountId, Balance>,
 {
     fn get_asset(
         &self,
         at: Option<HashOf<Block>>,
         id: DeipAssetId,
-    ) -> BoxFutureResult<Option<AssetDetails<Balance, AccountId, DepositBalance>>> {
+    ) -> BoxFutureResult<
+        Option<AssetInfo<Balance, AccountId, DepositBalance, ProjectId, HashOf<Block>>>,
+    > {
         let key_encoded = id.encode();
         let key_encoded_size = key_encoded.len();
 
@@ -132,7 +203,7 @@ where
         let index_key = HashedKey::<Identity>::unsafe_from_encoded(&key_encoded);
 
         let prefix_key = chain_key_hash_map(&index_prefix, &index_key);
-        get_list_by_keys::<
+        let details = get_list_by_keys::<
             types::AssetKeyValue<AssetId, Balance, AccountId, DepositBalance>,
             Identity,
             _,
@@ -141,8 +212,23 @@ where
             _,
             _,
         >(&self.state, at, prefix_key, 1, None, map)
-        .map_ok(|mut v| v.pop().map(|item| item.value))
-        .boxed()
+        .map_ok(|mut v| v.pop().map(|item| item.value));
+
+        let security_metadata = StorageMap::<Blake2_128Concat>::get_value::<
+            types::SecurityMetadataValue<ProjectId, HashOf<Block>, AccountId>,
+            _,
+            _,
+            _,
+        >(&self.state, at, DEIP_PALLET_ASSETS_V1, b"SecurityMetadataByAsset", &id);
+
+        future::try_join(details, security_metadata)
+            .map_ok(|(details, security_metadata)| {
+                details.map(|details| AssetInfo {
+                    details,
+                    security_metadata: security_metadata.map(|m| m.0),
+                })
+            })
+            .boxed()
     }
 
     fn get_asset_list(
@@ -540,4 +626,62 @@ where
             })
             .boxed()
     }
+
+    fn get_asset_holder_list(
+        &self,
+        at: Option<HashOf<Block>>,
+        asset: DeipAssetId,
+        count: u32,
+        start_id: Option<AccountId>,
+    ) -> BoxFutureResult<Vec<AccountId>> {
+        let asset_hashed = HashedKey::<Blake2_128Concat>::new(&asset);
+        let asset_encoded_size = asset.encode().len();
+
+        let prefix = prefix(DEIP_PALLET_ASSETS_V1, b"HoldersByAsset");
+        let start_key = start_id.map(|account| {
+            chain_key_hash_double_map(
+                &prefix,
+                &asset_hashed,
+                &HashedKey::<Blake2_128Concat>::new(&account),
+            )
+        });
+        let prefix_key = chain_key_hash_map(&prefix, &asset_hashed);
+
+        self.state
+            .storage_keys_paged(Some(prefix_key), count, start_key, at)
+            .map_err(|e| to_rpc_error(Error::ScRpcApiError, Some(format!("{:?}", e))))
+            .and_then(move |keys| {
+                let holders = keys.into_iter().try_fold(Vec::new(), |mut result, key| {
+                    let no_prefix = Blake2_128Concat::reverse(&key.0[32..]);
+                    let no_prefix = Blake2_128Concat::reverse(&no_prefix[asset_encoded_size..]);
+                    match AccountId::decode(&mut &*no_prefix) {
+                        Err(_) => Err(to_rpc_error(
+                            Error::AccountIdDecodeFailed,
+                            Some(format!("{:?}", &key.0)),
+                        )),
+                        Ok(account) => {
+                            result.push(account);
+                            Ok(result)
+                        },
+                    }
+                });
+                future::ready(holders)
+            })
+            .boxed()
+    }
+
+    fn get_remaining_supply(
+        &self,
+        at: Option<HashOf<Block>>,
+        asset: DeipAssetId,
+    ) -> RpcResult<Option<SerializableAtLeast32BitUnsigned<Balance>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.remaining_supply(&at, asset)
+            .map(|supply| supply.map(SerializableAtLeast32BitUnsigned))
+            .map_err(|e| {
+                to_rpc_error(Error::AssetsApiRemainingSupplyFailed, Some(format!("{:?}", e)))
+            })
+    }
 }
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use sp_runtime::traits::AtLeast32BitUnsigned;
 
 use deip_serializable_u128::SerializableAtLeast32BitUnsigned;
+use pallet_deip_assets::VersionedSecurityMetadata;
 
 pub struct AssetIdError;
 impl common_rpc::GetError for AssetIdError {
@@ -46,6 +47,33 @@ where
 	}
 }
 
+/// Asset details with the latest version of its security metadata.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetInfo<Balance, AccountId, DepositBalance, ProjectId, Hash>
+where
+	AccountId: Decode,
+	DepositBalance: Clone + Decode + AtLeast32BitUnsigned,
+	Balance: Clone + Decode + AtLeast32BitUnsigned,
+{
+	#[serde(flatten)]
+	pub details: AssetDetails<Balance, AccountId, DepositBalance>,
+	pub security_metadata: Option<VersionedSecurityMetadata<ProjectId, Hash, AccountId>>,
+}
+
+#[derive(Decode)]
+pub struct SecurityMetadataValue<ProjectId, Hash, AccountId>(
+	pub VersionedSecurityMetadata<ProjectId, Hash, AccountId>,
+);
+
+impl<ProjectId, Hash, AccountId> common_rpc::GetError
+	for SecurityMetadataValue<ProjectId, Hash, AccountId>
+{
+	fn get_error() -> common_rpc::Error {
+		common_rpc::Error::SecurityMetadataDecodeFailed
+	}
+}
+
 pub struct AssetKeyValue<AssetId, Balance, AccountId, DepositBalance> {
 	pub id: AssetId,
 	_m: std::marker::PhantomData<(Balance, AccountId, DepositBalance)>,
@@ -18,7 +18,7 @@ use pallet_deip_dao::{
 
 use frame_support::Blake2_128Concat;
 
-use common_rpc::{BoxFutureResult, HashOf, ListResult, StorageMap};
+use common_rpc::{get_list_by_index, BoxFutureResult, HashOf, ListResult, StorageMap};
 
 mod types;
 
@@ -41,6 +41,15 @@ pub trait DeipDaoRpcApi<BlockHash, AccountId> {
         count: u32,
         start_id: Option<DaoId>,
     ) -> BoxFutureResult<Vec<ListResult<DaoId, Dao<AccountId, DaoId>>>>;
+
+    #[rpc(name = "deipDao_getListByMember")]
+    fn list_by_member(
+        &self,
+        at: Option<BlockHash>,
+        member: AccountId,
+        count: u32,
+        start_id: Option<DaoId>,
+    ) -> BoxFutureResult<Vec<ListResult<DaoId, Dao<AccountId, DaoId>>>>;
 }
 
 pub struct DeipDaoRpcApiObj<C, State, Block> {
@@ -112,4 +121,23 @@ where
             start_id.map(types::DaoKeyValue::new),
         )
     }
+
+    fn list_by_member(
+        &self,
+        at: Option<HashOf<Block>>,
+        member: AccountId,
+        count: u32,
+        start_id: Option<DaoId>,
+    ) -> BoxFutureResult<Vec<ListResult<DaoId, Dao<AccountId, DaoId>>>> {
+        get_list_by_index::<Blake2_128Concat, Blake2_128Concat, _, _, _, _>(
+            &self.state,
+            at,
+            b"DeipDao",
+            b"DaoIdByMember",
+            b"DaoRepository",
+            count,
+            &member,
+            start_id.map(types::DaoKeyValue::new),
+        )
+    }
 }
@@ -1,8 +1,12 @@
 use codec::Codec;
-use jsonrpc_core::Result;
+use jsonrpc_core::{futures::FutureExt, Result};
 use jsonrpc_derive::rpc;
 pub use pallet_deip::api::DeipApi as DeipStorageRuntimeApi;
-use pallet_deip::{investment_opportunity::*, *};
+use pallet_deip::{
+    contract::{Agreement, LicenseStatus},
+    investment_opportunity::*,
+    *,
+};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::{
@@ -19,6 +23,12 @@ use frame_support::{Blake2_128Concat, Identity, Twox64Concat};
 
 mod types;
 
+pub type AgreementOf<AccountId, Hash, Moment, AssetId, AssetBalance> =
+    Agreement<AccountId, Hash, Moment, Asset<AssetId, AssetBalance>>;
+pub type AgreementListOf<AccountId, Hash, Moment, AssetId, AssetBalance> = Vec<
+    ListResult<ContractAgreementId, AgreementOf<AccountId, Hash, Moment, AssetId, AssetBalance>>,
+>;
+
 #[rpc]
 pub trait DeipStorageApi<BlockHash, AccountId, Moment, AssetId, AssetBalance, Hash, TransactionCtx>
 where
@@ -146,7 +156,7 @@ where
         &self,
         at: Option<BlockHash>,
         id: ContractAgreementId,
-    ) -> Result<Option<contract::Agreement<AccountId, Hash, Moment>>>;
+    ) -> Result<Option<AgreementOf<AccountId, Hash, Moment, AssetId, AssetBalance>>>;
 
     #[rpc(name = "deip_getContractAgreementList")]
     fn get_contract_agreement_list(
@@ -154,9 +164,7 @@ where
         at: Option<BlockHash>,
         count: u32,
         start_id: Option<ContractAgreementId>,
-    ) -> BoxFutureResult<
-        Vec<ListResult<ContractAgreementId, contract::Agreement<AccountId, Hash, Moment>>>,
-    >;
+    ) -> BoxFutureResult<AgreementListOf<AccountId, Hash, Moment, AssetId, AssetBalance>>;
 
     #[rpc(name = "deip_getContractAgreementListByType")]
     fn get_contract_agreement_list_by_type(
@@ -165,9 +173,7 @@ where
         key: ContractAgreementIndexTerms,
         count: u32,
         start_id: Option<ContractAgreementId>,
-    ) -> BoxFutureResult<
-        Vec<ListResult<ContractAgreementId, contract::Agreement<AccountId, Hash, Moment>>>,
-    >;
+    ) -> BoxFutureResult<AgreementListOf<AccountId, Hash, Moment, AssetId, AssetBalance>>;
 
     #[rpc(name = "deip_getReviewUpvoteListByReview")]
     fn get_review_upvote_list_by_review(
@@ -477,7 +483,7 @@ where
         &self,
         at: Option<HashOf<Block>>,
         id: ContractAgreementId,
-    ) -> Result<Option<contract::Agreement<AccountId, Hash, Moment>>> {
+    ) -> Result<Option<AgreementOf<AccountId, Hash, Moment, AssetId, AssetBalance>>> {
         let api = self.client.runtime_api();
         let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
 
@@ -491,17 +497,17 @@ where
         at: Option<HashOf<Block>>,
         count: u32,
         start_id: Option<ContractAgreementId>,
-    ) -> BoxFutureResult<
-        Vec<ListResult<ContractAgreementId, contract::Agreement<AccountId, Hash, Moment>>>,
-    > {
-        StorageMap::<Blake2_128Concat>::get_list(
+    ) -> BoxFutureResult<AgreementListOf<AccountId, Hash, Moment, AssetId, AssetBalance>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        let list = StorageMap::<Blake2_128Concat>::get_list(
             &self.state,
-            at,
+            Some(at),
             b"Deip",
             b"ContractAgreementMap",
             count,
             start_id.map(types::AgreementKeyValue::new),
-        )
+        );
+        resolve_licensees::<_, Block, _, _, _, _, _, TransactionCtx>(self.client.clone(), at, list)
     }
 
     fn get_contract_agreement_list_by_type(
@@ -510,19 +516,19 @@ where
         key: ContractAgreementIndexTerms,
         count: u32,
         start_id: Option<ContractAgreementId>,
-    ) -> BoxFutureResult<
-        Vec<ListResult<ContractAgreementId, contract::Agreement<AccountId, Hash, Moment>>>,
-    > {
-        get_list_by_index::<Twox64Concat, Blake2_128Concat, _, _, _, _>(
+    ) -> BoxFutureResult<AgreementListOf<AccountId, Hash, Moment, AssetId, AssetBalance>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        let list = get_list_by_index::<Twox64Concat, Blake2_128Concat, _, _, _, _>(
             &self.state,
-            at,
+            Some(at),
             b"Deip",
             b"ContractAgreementIdByType",
             b"ContractAgreementMap",
             count,
             &key,
             start_id.map(types::AgreementKeyValue::new),
-        )
+        );
+        resolve_licensees::<_, Block, _, _, _, _, _, TransactionCtx>(self.client.clone(), at, list)
     }
 
     fn get_review_upvote_list_by_review(
@@ -567,3 +573,52 @@ where
         )
     }
 }
+
+/// Replaces licensees of signed licenses read from the storage with holders of their NFTs,
+/// see [`pallet_deip::Module::get_contract_agreement`].
+fn resolve_licensees<C, Block, AccountId, Moment, AssetId, AssetBalance, Hash, TransactionCtx>(
+    client: Arc<C>,
+    at: HashOf<Block>,
+    list: BoxFutureResult<AgreementListOf<AccountId, Hash, Moment, AssetId, AssetBalance>>,
+) -> BoxFutureResult<AgreementListOf<AccountId, Hash, Moment, AssetId, AssetBalance>>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static,
+    C: ProvideRuntimeApi<Block>,
+    C::Api: DeipStorageRuntimeApi<
+        Block,
+        AccountId,
+        Moment,
+        AssetId,
+        AssetBalance,
+        Hash,
+        TransactionCtx,
+    >,
+    AccountId: 'static + Codec + Send,
+    Moment: 'static + Codec + Send,
+    AssetId: 'static + Codec + Send,
+    AssetBalance: 'static + Codec + Send + Clone + AtLeast32BitUnsigned,
+    Hash: 'static + Codec + Send,
+    TransactionCtx: 'static + Codec + Send,
+{
+    list.map(move |list| {
+        let api = client.runtime_api();
+        let at = BlockId::hash(at);
+        list?
+            .into_iter()
+            .map(|item| {
+                if !matches!(item.value, Agreement::License(LicenseStatus::Signed(_))) {
+                    return Ok(item)
+                }
+                let value = api
+                    .get_contract_agreement(&at, &item.key.key)
+                    .map_err(|e| {
+                        to_rpc_error(Error::AgreementApiGetFailed, Some(format!("{:?}", e)))
+                    })?
+                    .unwrap_or(item.value);
+                Ok(ListResult { key: item.key, value })
+            })
+            .collect()
+    })
+    .boxed()
+}
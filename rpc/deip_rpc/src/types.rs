@@ -144,26 +144,31 @@ impl GetError for AgreementError {
     }
 }
 
-pub struct AgreementKeyValue<AccountId, Hash, Moment> {
+pub struct AgreementKeyValue<AccountId, Hash, Moment, AssetId, AssetBalance> {
     pub id: super::ContractAgreementId,
-    _m: std::marker::PhantomData<(AccountId, Hash, Moment)>,
+    _m: std::marker::PhantomData<(AccountId, Hash, Moment, AssetId, AssetBalance)>,
 }
 
-impl<AccountId, Hash, Moment> AgreementKeyValue<AccountId, Hash, Moment> {
+impl<AccountId, Hash, Moment, AssetId, AssetBalance>
+    AgreementKeyValue<AccountId, Hash, Moment, AssetId, AssetBalance>
+{
     pub fn new(id: super::ContractAgreementId) -> Self {
         Self { id, _m: Default::default() }
     }
 }
 
-impl<AccountId, Hash, Moment> KeyValueInfo for AgreementKeyValue<AccountId, Hash, Moment>
+impl<AccountId, Hash, Moment, AssetId, AssetBalance> KeyValueInfo
+    for AgreementKeyValue<AccountId, Hash, Moment, AssetId, AssetBalance>
 where
     AccountId: 'static + Decode + Send,
     Hash: 'static + Decode + Send,
     Moment: 'static + Decode + Send,
+    AssetId: 'static + Decode + Send,
+    AssetBalance: 'static + Decode + Send,
 {
     type Key = super::ContractAgreementId;
     type KeyError = AgreementIdError;
-    type Value = super::contract::Agreement<AccountId, Hash, Moment>;
+    type Value = super::AgreementOf<AccountId, Hash, Moment, AssetId, AssetBalance>;
     type ValueError = AgreementError;
 
     fn key(&self) -> &Self::Key {
@@ -0,0 +1,112 @@
+use jsonrpc_derive::rpc;
+
+use std::vec::Vec;
+
+use codec::{Decode, Encode};
+
+use sp_runtime::traits::{AtLeast32BitUnsigned, Block as BlockT};
+
+use frame_support::Blake2_128Concat;
+
+use common_rpc::{get_list_by_index, BoxFutureResult, HashOf, ListResult, StorageMap};
+
+pub use pallet_deip_swap::{OfferId, SwapOffer};
+
+mod types;
+use types::OfferKeyValue;
+
+/// Name of `pallet_deip_swap` in construct_runtime!.
+const DEIP_PALLET_SWAP: &[u8] = b"DeipSwap";
+
+pub type OfferList<AccountId, AssetId, Balance, Moment> =
+    Vec<ListResult<OfferId, SwapOffer<AccountId, AssetId, Balance, Moment>>>;
+
+#[rpc]
+pub trait DeipSwapRpc<BlockHash, AccountId, AssetId, Balance, Moment>
+where
+    AccountId: Decode,
+    AssetId: Encode + Decode,
+    Balance: Decode + AtLeast32BitUnsigned + Clone,
+    Moment: Decode,
+{
+    /// Get open offers.
+    /// Pass the last offer of the previous page as `start_id` to get the next one.
+    #[rpc(name = "swap_getOfferList")]
+    fn get_offer_list(
+        &self,
+        at: Option<BlockHash>,
+        count: u32,
+        start_id: Option<OfferId>,
+    ) -> BoxFutureResult<OfferList<AccountId, AssetId, Balance, Moment>>;
+
+    /// Get open offers of `give_asset` for `want_asset`.
+    /// Pass the last offer of the previous page as `start_id` to get the next one.
+    #[rpc(name = "swap_getOfferListByPair")]
+    fn get_offer_list_by_pair(
+        &self,
+        at: Option<BlockHash>,
+        give_asset: AssetId,
+        want_asset: AssetId,
+        count: u32,
+        start_id: Option<OfferId>,
+    ) -> BoxFutureResult<OfferList<AccountId, AssetId, Balance, Moment>>;
+}
+
+pub struct DeipSwapRpcObj<State, B> {
+    state: State,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<State, B> DeipSwapRpcObj<State, B> {
+    pub fn new(state: State) -> Self {
+        Self { state, _marker: Default::default() }
+    }
+}
+
+impl<State, Block, AccountId, AssetId, Balance, Moment>
+    DeipSwapRpc<HashOf<Block>, AccountId, AssetId, Balance, Moment>
+    for DeipSwapRpcObj<State, Block>
+where
+    AccountId: 'static + Decode + Send,
+    AssetId: 'static + Encode + Decode + Send,
+    Balance: 'static + Decode + AtLeast32BitUnsigned + Clone + Send,
+    Moment: 'static + Decode + Send,
+    State: sc_rpc_api::state::StateApi<HashOf<Block>>,
+    Block: BlockT,
+{
+    fn get_offer_list(
+        &self,
+        at: Option<HashOf<Block>>,
+        count: u32,
+        start_id: Option<OfferId>,
+    ) -> BoxFutureResult<OfferList<AccountId, AssetId, Balance, Moment>> {
+        StorageMap::<Blake2_128Concat>::get_list(
+            &self.state,
+            at,
+            DEIP_PALLET_SWAP,
+            b"Offers",
+            count,
+            start_id.map(OfferKeyValue::new),
+        )
+    }
+
+    fn get_offer_list_by_pair(
+        &self,
+        at: Option<HashOf<Block>>,
+        give_asset: AssetId,
+        want_asset: AssetId,
+        count: u32,
+        start_id: Option<OfferId>,
+    ) -> BoxFutureResult<OfferList<AccountId, AssetId, Balance, Moment>> {
+        get_list_by_index::<Blake2_128Concat, Blake2_128Concat, _, _, _, _>(
+            &self.state,
+            at,
+            DEIP_PALLET_SWAP,
+            b"OffersByPair",
+            b"Offers",
+            count,
+            &(give_asset, want_asset),
+            start_id.map(OfferKeyValue::new),
+        )
+    }
+}
@@ -0,0 +1,47 @@
+use codec::Decode;
+use sp_runtime::traits::AtLeast32BitUnsigned;
+
+use pallet_deip_swap::{OfferId, SwapOffer};
+
+pub struct OfferIdError;
+impl common_rpc::GetError for OfferIdError {
+    fn get_error() -> common_rpc::Error {
+        common_rpc::Error::OfferIdDecodeFailed
+    }
+}
+
+pub struct OfferError;
+impl common_rpc::GetError for OfferError {
+    fn get_error() -> common_rpc::Error {
+        common_rpc::Error::OfferDecodeFailed
+    }
+}
+
+pub struct OfferKeyValue<AccountId, AssetId, Balance, Moment> {
+    pub id: OfferId,
+    _m: std::marker::PhantomData<(AccountId, AssetId, Balance, Moment)>,
+}
+
+impl<AccountId, AssetId, Balance, Moment> OfferKeyValue<AccountId, AssetId, Balance, Moment> {
+    pub fn new(id: OfferId) -> Self {
+        Self { id, _m: Default::default() }
+    }
+}
+
+impl<AccountId, AssetId, Balance, Moment> common_rpc::KeyValueInfo
+    for OfferKeyValue<AccountId, AssetId, Balance, Moment>
+where
+    AccountId: 'static + Decode + Send,
+    AssetId: 'static + Decode + Send,
+    Balance: 'static + Decode + Send + Clone + AtLeast32BitUnsigned,
+    Moment: 'static + Decode + Send,
+{
+    type Key = OfferId;
+    type KeyError = OfferIdError;
+    type Value = SwapOffer<AccountId, AssetId, Balance, Moment>;
+    type ValueError = OfferError;
+
+    fn key(&self) -> &Self::Key {
+        &self.id
+    }
+}
@@ -4,16 +4,19 @@ use jsonrpc_core::{
     futures::{future, FutureExt, TryFutureExt},
     futures_executor::block_on,
     futures_util::{stream::FuturesOrdered, TryStreamExt},
+    Result as RpcResult,
 };
 use jsonrpc_derive::rpc;
 
-use std::{iter::FromIterator, vec::Vec};
+use std::{iter::FromIterator, sync::Arc, vec::Vec};
 
 use codec::{Codec, Decode, Encode, Input};
 
 use sp_runtime::traits::{AtLeast32BitUnsigned, Block as BlockT};
 
-use sp_core::storage::StorageKey;
+use sp_blockchain::HeaderBackend;
+
+use sp_core::storage::{StorageData, StorageKey};
 
 use frame_support::{Blake2_128Concat, Identity, ReversibleStorageHasher, StorageHasher};
 
@@ -26,9 +29,9 @@ use common_rpc::{
 mod types;
 use types::*;
 
-/// Names of pallets in construct_runtime!.
-const PARITYTECH_PALLET_UNIQUES: &[u8] = b"ParityTechUniques";
-const DEIP_PALLET_UNIQUES: &[u8] = b"Uniques";
+/// Names of pallets in construct_runtime! since the storage version V1 of `pallet_deip_uniques`.
+const PARITYTECH_PALLET_UNIQUES_V1: &[u8] = b"Uniques";
+const DEIP_PALLET_UNIQUES_V1: &[u8] = b"DeipUniques";
 
 #[rpc]
 pub trait DeipUniquesRpc<
@@ -39,6 +42,7 @@ pub trait DeipUniquesRpc<
     DepositBalance,
     Extra,
     DeipClassId,
+    ProjectId,
 > where
     ClassId: Encode + Decode,
     InstanceId: Encode + Decode,
@@ -55,54 +59,502 @@ pub trait DeipUniquesRpc<
         id: DeipClassId,
     ) -> BoxFutureResult<Option<ClassDetails<AccountId, DepositBalance>>>;
 
-    // #[rpc(name = "uniques_getClassList")]
-    // fn get_class_list(
-    //     &self,
-    //     at: Option<BlockHash>,
-    //     count: u32,
-    //     start_id: Option<(DeipClassId, ClassId)>,
-    // ) -> BoxFutureResult<
-    //     Vec<ListResult<(DeipClassId, ClassId), ClassDetails<AccountId, DepositBalance>>>,
-    // >;
-
-    // #[rpc(name = "uniques_getClassInstanceList")]
-    // fn get_class_instance_list(
-    //     &self,
-    //     at: Option<BlockHash>,
-    //     count: u32,
-    //     start_id: Option<(DeipClassId, AccountId)>,
-    // ) -> BoxFutureResult<Vec<ClassInstanceWithIds<DeipClassId, InstanceId, AccountId, Extra>>>;
-
-    // #[rpc(name = "uniques_getClassInstanceByOwner")]
-    // fn get_class_instance_by_owner(
-    //     &self,
-    //     at: Option<BlockHash>,
-    //     owner: AccountId,
-    //     class: DeipClassId,
-    // ) -> BoxFutureResult<Option<ClassInstance<InstanceId, Extra>>>;
-
-    // #[rpc(name = "uniques_getClassInstanceListByClass")]
-    // fn get_class_instance_list_by_class(
-    //     &self,
-    //     at: Option<BlockHash>,
-    //     class: DeipClassId,
-    //     count: u32,
-    //     start_id: Option<AccountId>,
-    // ) -> BoxFutureResult<Vec<ClassInstanceWithOwner<InstanceId, AccountId, Extra>>>;
+    /// Get classes created via `deip_create` with their metadata and attributes.
+    /// Pass the last class of the previous page as `start_id` to get the next one.
+    #[rpc(name = "uniques_getClassList")]
+    fn get_class_list(
+        &self,
+        at: Option<BlockHash>,
+        count: u32,
+        start_id: Option<DeipClassId>,
+    ) -> BoxFutureResult<Vec<ClassInfo<DeipClassId, AccountId, DepositBalance>>>;
+
+    /// Get classes linked to the project with `deip_set_class_project`.
+    /// Pass the last class of the previous page as `start_id` to get the next one.
+    #[rpc(name = "uniques_getClassListByProject")]
+    fn get_class_list_by_project(
+        &self,
+        at: Option<BlockHash>,
+        project: ProjectId,
+        count: u32,
+        start_id: Option<DeipClassId>,
+    ) -> BoxFutureResult<Vec<ClassInfo<DeipClassId, AccountId, DepositBalance>>>;
+
+    /// Get instances of all classes created via `deip_create`.
+    /// Pass the last class and instance of the previous page as `start_id` to get the next one.
+    #[rpc(name = "uniques_getClassInstanceList")]
+    fn get_class_instance_list(
+        &self,
+        at: Option<BlockHash>,
+        count: u32,
+        start_id: Option<(DeipClassId, InstanceId)>,
+    ) -> BoxFutureResult<
+        Vec<ClassInstanceInfo<DeipClassId, InstanceId, AccountId, DepositBalance>>,
+    >;
+
+    /// Get instances of the class owned by the account.
+    /// Pass the last instance of the previous page as `start_id` to get the next one.
+    #[rpc(name = "uniques_getClassInstanceByOwner")]
+    fn get_class_instance_by_owner(
+        &self,
+        at: Option<BlockHash>,
+        owner: AccountId,
+        class: DeipClassId,
+        count: u32,
+        start_id: Option<InstanceId>,
+    ) -> BoxFutureResult<
+        Vec<ClassInstanceInfo<DeipClassId, InstanceId, AccountId, DepositBalance>>,
+    >;
+
+    /// Get instances of the class.
+    /// Pass the last instance of the previous page as `start_id` to get the next one.
+    #[rpc(name = "uniques_getClassInstanceListByClass")]
+    fn get_class_instance_list_by_class(
+        &self,
+        at: Option<BlockHash>,
+        class: DeipClassId,
+        count: u32,
+        start_id: Option<InstanceId>,
+    ) -> BoxFutureResult<
+        Vec<ClassInstanceInfo<DeipClassId, InstanceId, AccountId, DepositBalance>>,
+    >;
+
+    /// Get accounts which hold instances of the class.
+    /// Pass the last account of the previous page as `start_id` to get the next one.
+    #[rpc(name = "uniques_getClassHolderList")]
+    fn get_class_holder_list(
+        &self,
+        at: Option<BlockHash>,
+        class: DeipClassId,
+        count: u32,
+        start_id: Option<AccountId>,
+    ) -> BoxFutureResult<Vec<ClassHolder<AccountId>>>;
 }
 
-pub struct DeipUniquesRpcObj<State, B> {
+/// Max number of attributes returned with a class or an instance.
+const MAX_ATTRIBUTES: u32 = 100;
+
+pub struct DeipUniquesRpcObj<C, State, B> {
+    client: Arc<C>,
     state: State,
     _marker: std::marker::PhantomData<B>,
 }
 
-impl<State, B> DeipUniquesRpcObj<State, B> {
-    pub fn new(state: State) -> Self {
-        Self { state, _marker: Default::default() }
+impl<C, State, B> DeipUniquesRpcObj<C, State, B> {
+    pub fn new(client: Arc<C>, state: State) -> Self {
+        Self { client, state, _marker: Default::default() }
+    }
+}
+
+type InstanceItem<DeipClassId, InstanceId, AccountId, DepositBalance> =
+    (DeipClassId, InstanceId, Vec<u8>, InstanceDetails<AccountId, DepositBalance>);
+
+impl<C, State, Block> DeipUniquesRpcObj<C, State, Block>
+where
+    State: sc_rpc_api::state::StateApi<HashOf<Block>>,
+    Block: BlockT,
+    C: HeaderBackend<Block>,
+{
+    /// Pins the block of the request, so all its reads are made at the same state
+    /// even if a new block is imported meanwhile.
+    fn pin(&self, at: Option<HashOf<Block>>) -> Option<HashOf<Block>> {
+        Some(at.unwrap_or_else(|| self.client.info().best_hash))
+    }
+
+    fn storage(
+        &self,
+        at: Option<HashOf<Block>>,
+        key: StorageKey,
+    ) -> BoxFutureResult<Option<StorageData>> {
+        self.state
+            .storage(key, at)
+            .map_err(|e| to_rpc_error(Error::ScRpcApiError, Some(format!("{:?}", e))))
+            .boxed()
+    }
+
+    /// Encoded id of the class in `pallet_uniques`.
+    fn origin_class_id<DeipClassId: Encode>(
+        &self,
+        at: Option<HashOf<Block>>,
+        class: &DeipClassId,
+    ) -> RpcResult<Option<Vec<u8>>> {
+        let key = chain_key_hash_map(
+            &prefix(DEIP_PALLET_UNIQUES_V1, b"NftClassIdByDeipNftClassIdV1"),
+            &HashedKey::<Blake2_128Concat>::new(class),
+        );
+        Ok(block_on(self.storage(at, key))?.map(|data| data.0))
+    }
+
+    /// DEIP id of the class with encoded id `origin_class` in `pallet_uniques`.
+    fn deip_class_id<DeipClassId: Decode>(
+        &self,
+        at: Option<HashOf<Block>>,
+        origin_class: &[u8],
+    ) -> RpcResult<Option<DeipClassId>> {
+        let key = chain_key_hash_map(
+            &prefix(DEIP_PALLET_UNIQUES_V1, b"DeipNftClassIdByNftClassIdV1"),
+            &HashedKey::<Blake2_128Concat>::unsafe_from_encoded(origin_class),
+        );
+        match block_on(self.storage(at, key))? {
+            None => Ok(None),
+            Some(data) => DeipClassId::decode(&mut &data.0[..]).map(Some).map_err(|e| {
+                to_rpc_error(Error::DeipClassIdDecodeFailed, Some(format!("{:?}: {}", data, e)))
+            }),
+        }
+    }
+
+    /// Attributes of the class if `instance` is `None` or attributes of the instance,
+    /// at most `MAX_ATTRIBUTES` of them.
+    fn attributes<InstanceId: Encode>(
+        &self,
+        at: Option<HashOf<Block>>,
+        origin_class: &[u8],
+        instance: Option<&InstanceId>,
+    ) -> BoxFutureResult<Vec<Attribute>> {
+        // attributes are stored in `pallet_uniques::Attribute`
+        // keyed by (class id, optional instance id, attribute key)
+        let mut prefix = prefix(PARITYTECH_PALLET_UNIQUES_V1, b"Attribute");
+        prefix.extend(Blake2_128Concat::hash(origin_class));
+        prefix.extend(instance.using_encoded(Blake2_128Concat::hash));
+        let prefix_len = prefix.len();
+
+        let keys = match block_on(self.state.storage_keys_paged(
+            Some(StorageKey(prefix)),
+            MAX_ATTRIBUTES,
+            None,
+            at,
+        )) {
+            Ok(k) => k,
+            Err(e) =>
+                return future::err(to_rpc_error(Error::ScRpcApiError, Some(format!("{:?}", e))))
+                    .boxed(),
+        };
+
+        let values: FuturesOrdered<_> = keys
+            .into_iter()
+            .map(|k| self.storage(at, k.clone()).map_ok(|v| (k, v)))
+            .collect();
+        let result = Vec::with_capacity(values.len());
+        values
+            .try_fold(result, move |mut result, (key, value)| {
+                let data = match value {
+                    None => return future::ok(result),
+                    Some(d) => d,
+                };
+                let attribute_key =
+                    Vec::<u8>::decode(&mut Blake2_128Concat::reverse(&key.0[prefix_len..]));
+                // the value is followed by the deposit
+                let value = Vec::<u8>::decode(&mut &data.0[..]);
+                match (attribute_key, value) {
+                    (Ok(key), Ok(value)) => {
+                        result.push(Attribute { key, value });
+                        future::ok(result)
+                    },
+                    _ => future::err(to_rpc_error(
+                        Error::NftAttributeDecodeFailed,
+                        Some(format!("{:?}: {:?}", &key.0, data)),
+                    )),
+                }
+            })
+            .boxed()
+    }
+
+    /// Details, metadata and attributes of classes, pairs of DEIP class id
+    /// and class id in `pallet_uniques`. Classes which don't exist are skipped.
+    fn class_infos<DeipClassId, ClassId, AccountId, DepositBalance, InstanceId>(
+        &self,
+        at: Option<HashOf<Block>>,
+        classes: Vec<ListResult<DeipClassId, ClassId>>,
+    ) -> BoxFutureResult<Vec<ClassInfo<DeipClassId, AccountId, DepositBalance>>>
+    where
+        DeipClassId: 'static + Send,
+        ClassId: Encode,
+        AccountId: 'static + Decode + Send,
+        DepositBalance: 'static + Send + Decode + AtLeast32BitUnsigned + Clone,
+        InstanceId: Encode,
+    {
+        let infos: FuturesOrdered<_> = classes
+            .into_iter()
+            .map(|item| {
+                let class = item.key.key;
+                let origin_class = item.value.encode();
+                let class_hashed =
+                    HashedKey::<Blake2_128Concat>::unsafe_from_encoded(&origin_class);
+
+                let key = chain_key_hash_map(
+                    &prefix(PARITYTECH_PALLET_UNIQUES_V1, b"Class"),
+                    &class_hashed,
+                );
+                let details = get_value::<ClassDetails<AccountId, DepositBalance>, _, _>(
+                    &self.state,
+                    key,
+                    at,
+                );
+                let key = chain_key_hash_map(
+                    &prefix(PARITYTECH_PALLET_UNIQUES_V1, b"ClassMetadataOf"),
+                    &class_hashed,
+                );
+                let metadata = get_value::<Metadata<DepositBalance>, _, _>(&self.state, key, at);
+                let attributes = self.attributes::<InstanceId>(at, &origin_class, None);
+
+                future::try_join3(details, metadata, attributes).map_ok(
+                    move |(details, metadata, attributes)| {
+                        details.map(|details| ClassInfo { class, details, metadata, attributes })
+                    },
+                )
+            })
+            .collect();
+
+        infos.try_filter_map(future::ok).try_collect::<Vec<_>>().boxed()
+    }
+
+    /// Metadata and attributes of instances.
+    fn instance_infos<DeipClassId, InstanceId, AccountId, DepositBalance>(
+        &self,
+        at: Option<HashOf<Block>>,
+        instances: Vec<InstanceItem<DeipClassId, InstanceId, AccountId, DepositBalance>>,
+    ) -> BoxFutureResult<Vec<ClassInstanceInfo<DeipClassId, InstanceId, AccountId, DepositBalance>>>
+    where
+        DeipClassId: 'static + Send,
+        InstanceId: 'static + Encode + Send,
+        AccountId: 'static + Decode + Send,
+        DepositBalance: 'static + Send + Decode + AtLeast32BitUnsigned + Clone,
+    {
+        let infos: FuturesOrdered<_> = instances
+            .into_iter()
+            .map(|(class, instance, origin_class, details)| {
+                let key = chain_key_hash_double_map(
+                    &prefix(PARITYTECH_PALLET_UNIQUES_V1, b"InstanceMetadataOf"),
+                    &HashedKey::<Blake2_128Concat>::unsafe_from_encoded(&origin_class),
+                    &HashedKey::<Blake2_128Concat>::new(&instance),
+                );
+                let metadata = get_value::<Metadata<DepositBalance>, _, _>(&self.state, key, at);
+                let attributes = self.attributes(at, &origin_class, Some(&instance));
+
+                future::try_join(metadata, attributes).map_ok(move |(metadata, attributes)| {
+                    ClassInstanceInfo { class, instance, details, metadata, attributes }
+                })
+            })
+            .collect();
+
+        infos.try_collect::<Vec<_>>().boxed()
+    }
+
+    /// Classes from the index which keys end with the DEIP class id
+    /// hashed with `Blake2_128Concat`.
+    fn class_list<DeipClassId, ClassId, AccountId, DepositBalance, InstanceId>(
+        &self,
+        at: Option<HashOf<Block>>,
+        index_prefix: Vec<u8>,
+        count: u32,
+        start_id: Option<DeipClassId>,
+    ) -> BoxFutureResult<Vec<ClassInfo<DeipClassId, AccountId, DepositBalance>>>
+    where
+        DeipClassId: 'static + Send + Codec,
+        ClassId: 'static + Send + Codec,
+        AccountId: 'static + Decode + Send,
+        DepositBalance: 'static + Send + Decode + AtLeast32BitUnsigned + Clone,
+        InstanceId: Encode,
+    {
+        let prefix_len = index_prefix.len();
+        let start_key = start_id.map(|class| {
+            chain_key_hash_map(&index_prefix, &HashedKey::<Blake2_128Concat>::new(&class))
+        });
+        let origin_index = prefix(DEIP_PALLET_UNIQUES_V1, b"NftClassIdByDeipNftClassIdV1");
+
+        // the index key is mapped to the key of the class in `NftClassIdByDeipNftClassIdV1`
+        let map = |k: StorageKey| -> BoxFutureResult<(Option<StorageData>, StorageKey)> {
+            let class = Blake2_128Concat::reverse(&k.0[prefix_len..]);
+            let key = chain_key_hash_map(
+                &origin_index,
+                &HashedKey::<Blake2_128Concat>::unsafe_from_encoded(class),
+            );
+            self.storage(at, key.clone()).map_ok(|v| (v, key)).boxed()
+        };
+
+        let classes = get_list_by_keys::<
+            OriginClassKeyValue<DeipClassId, ClassId>,
+            Blake2_128Concat,
+            _,
+            _,
+            _,
+            _,
+            _,
+        >(&self.state, at, StorageKey(index_prefix), count, start_key, map);
+
+        match block_on(classes) {
+            Err(e) => future::err(e).boxed(),
+            Ok(classes) => self.class_infos::<_, _, _, _, InstanceId>(at, classes),
+        }
+    }
+
+    /// Instances of the class from the map which keys are prefixed with `index_prefix`
+    /// and end with the instance id hashed with `Blake2_128Concat`.
+    fn instance_list<DeipClassId, ClassId, InstanceId, AccountId, DepositBalance>(
+        &self,
+        at: Option<HashOf<Block>>,
+        index_prefix: Vec<u8>,
+        class: DeipClassId,
+        origin_class: Vec<u8>,
+        count: u32,
+        start_id: Option<InstanceId>,
+    ) -> BoxFutureResult<Vec<ClassInstanceInfo<DeipClassId, InstanceId, AccountId, DepositBalance>>>
+    where
+        DeipClassId: 'static + Send + Clone,
+        ClassId: 'static + Send + Codec,
+        InstanceId: 'static + Send + Codec,
+        AccountId: 'static + Decode + Send,
+        DepositBalance: 'static + Send + Decode + AtLeast32BitUnsigned + Clone,
+    {
+        let prefix_len = index_prefix.len();
+        let start_key = start_id.map(|instance| {
+            chain_key_hash_map(&index_prefix, &HashedKey::<Blake2_128Concat>::new(&instance))
+        });
+        let class_hashed = HashedKey::<Blake2_128Concat>::unsafe_from_encoded(&origin_class);
+        let asset_prefix = prefix(PARITYTECH_PALLET_UNIQUES_V1, b"Asset");
+
+        // the index key is mapped to the key of the instance in `pallet_uniques::Asset`
+        let map = |k: StorageKey| -> BoxFutureResult<_> {
+            let instance =
+                match InstanceId::decode(&mut Blake2_128Concat::reverse(&k.0[prefix_len..])) {
+                    Err(e) => {
+                        let data = Some(format!("{:?}: {}", &k.0, e));
+                        return future::err(to_rpc_error(Error::InstanceIdDecodeFailed, data))
+                            .boxed()
+                    },
+                    Ok(id) => id,
+                };
+            let key = chain_key_hash_double_map(
+                &asset_prefix,
+                &class_hashed,
+                &HashedKey::<Blake2_128Concat>::new(&instance),
+            );
+            self.storage(at, key.clone()).map_ok(|v| (v, key, instance)).boxed()
+        };
+
+        let instances = get_list_by_keys::<
+            InstanceKeyValue<ClassId, AccountId, DepositBalance>,
+            Blake2_128Concat,
+            _,
+            _,
+            _,
+            _,
+            _,
+        >(&self.state, at, StorageKey(index_prefix), count, start_key, map);
+
+        match block_on(instances) {
+            Err(e) => future::err(e).boxed(),
+            Ok(instances) => {
+                let instances = instances
+                    .into_iter()
+                    .map(|item| {
+                        let (instance, _) = item.key.key;
+                        (class.clone(), instance, origin_class.clone(), item.value)
+                    })
+                    .collect();
+                self.instance_infos(at, instances)
+            },
+        }
+    }
+
+    /// Instances of all classes created via `deip_create`.
+    fn all_instance_list<ClassId, DeipClassId, InstanceId, AccountId, DepositBalance>(
+        &self,
+        at: Option<HashOf<Block>>,
+        count: u32,
+        start_id: Option<(DeipClassId, InstanceId)>,
+    ) -> BoxFutureResult<Vec<ClassInstanceInfo<DeipClassId, InstanceId, AccountId, DepositBalance>>>
+    where
+        ClassId: 'static + Send + Codec,
+        DeipClassId: 'static + Send + Codec + Clone,
+        InstanceId: 'static + Send + Codec,
+        AccountId: 'static + Decode + Send,
+        DepositBalance: 'static + Send + Decode + AtLeast32BitUnsigned + Clone,
+    {
+        let prefix = prefix(PARITYTECH_PALLET_UNIQUES_V1, b"Asset");
+        let start_key = match start_id {
+            None => None,
+            Some((class, instance)) => match self.origin_class_id(at, &class) {
+                Err(e) => return future::err(e).boxed(),
+                Ok(None) => return future::ok(vec![]).boxed(),
+                Ok(Some(origin_class)) => Some(chain_key_hash_double_map(
+                    &prefix,
+                    &HashedKey::<Blake2_128Concat>::unsafe_from_encoded(&origin_class),
+                    &HashedKey::<Blake2_128Concat>::new(&instance),
+                )),
+            },
+        };
+
+        // the class is decoded from the key by `get_list_by_keys`, the instance is decoded here
+        let map = |k: StorageKey| -> BoxFutureResult<_> {
+            let no_prefix = Blake2_128Concat::reverse(&k.0[32..]);
+            let input = &mut &*no_prefix;
+            if let Err(e) = ClassId::skip(input) {
+                let data = Some(format!("{:?}: {}", &k.0, e));
+                return future::err(to_rpc_error(Error::ClassIdDecodeFailed, data)).boxed()
+            }
+            let instance = match InstanceId::decode(&mut Blake2_128Concat::reverse(*input)) {
+                Err(e) => {
+                    let data = Some(format!("{:?}: {}", &k.0, e));
+                    return future::err(to_rpc_error(Error::InstanceIdDecodeFailed, data)).boxed()
+                },
+                Ok(id) => id,
+            };
+            self.storage(at, k.clone()).map_ok(|v| (v, k, instance)).boxed()
+        };
+
+        let instances = get_list_by_keys::<
+            InstanceKeyValue<ClassId, AccountId, DepositBalance>,
+            Blake2_128Concat,
+            _,
+            _,
+            _,
+            _,
+            _,
+        >(&self.state, at, StorageKey(prefix), count, start_key, map);
+        let instances = match block_on(instances) {
+            Err(e) => return future::err(e).boxed(),
+            Ok(i) => i,
+        };
+
+        // instances of a class are stored one after another so its DEIP id is cached
+        let mut last_class: Option<(Vec<u8>, Option<DeipClassId>)> = None;
+        let mut result = Vec::with_capacity(instances.len());
+        for item in instances {
+            let (instance, origin_class) = item.key.key;
+            let origin_class = origin_class.encode();
+            let class = match last_class {
+                Some((ref cached, ref class)) if *cached == origin_class => class.clone(),
+                _ => {
+                    let class = match self.deip_class_id(at, &origin_class) {
+                        Err(e) => return future::err(e).boxed(),
+                        Ok(c) => c,
+                    };
+                    last_class = Some((origin_class.clone(), class.clone()));
+                    class
+                },
+            };
+            // classes created directly in `pallet_uniques` are skipped
+            if let Some(class) = class {
+                result.push((class, instance, origin_class, item.value));
+            }
+        }
+        self.instance_infos(at, result)
     }
 }
 
-impl<State, Block, ClassId, InstanceId, AccountId, DepositBalance, Extra, DeipClassId>
+impl<
+        C,
+        State,
+        Block,
+        ClassId,
+        InstanceId,
+        AccountId,
+        DepositBalance,
+        Extra,
+        DeipClassId,
+        ProjectId,
+    >
     DeipUniquesRpc<
         HashOf<Block>,
         ClassId,
@@ -111,7 +563,8 @@ impl<State, Block, ClassId, InstanceId, AccountId, DepositBalance, Extra, DeipCl
         DepositBalance,
         Extra,
         DeipClassId,
-    > for DeipUniquesRpcObj<State, Block>
+        ProjectId,
+    > for DeipUniquesRpcObj<C, State, Block>
 where
     ClassId: 'static + Codec + Send,
     InstanceId: Codec + Send + 'static,
@@ -119,27 +572,29 @@ where
     AccountId: Codec + Send + 'static,
     DepositBalance: 'static + Send + Encode + Decode + AtLeast32BitUnsigned + Clone,
     Extra: 'static + Send + Decode,
+    ProjectId: Encode,
     State: sc_rpc_api::state::StateApi<HashOf<Block>>,
     Block: BlockT,
+    C: Send + Sync + 'static,
+    C: HeaderBackend<Block>,
 {
     fn get_class(
         &self,
         at: Option<HashOf<Block>>,
         id: DeipClassId,
     ) -> BoxFutureResult<Option<ClassDetails<AccountId, DepositBalance>>> {
+        let at = self.pin(at);
         let key_encoded = id.encode();
 
         let map = |k: StorageKey| {
             let key_hashed = HashedKey::<Blake2_128Concat>::unsafe_from_encoded(&k.0);
-            let key = chain_key_hash_map(&prefix(PARITYTECH_PALLET_UNIQUES, b"Class"), &key_hashed);
-            self.state
-                .storage(key.clone(), at)
-                .map_ok(|v| (v, key))
-                .map_err(|e| to_rpc_error(Error::ScRpcApiError, Some(format!("{:?}", e))))
+            let key =
+                chain_key_hash_map(&prefix(PARITYTECH_PALLET_UNIQUES_V1, b"Class"), &key_hashed);
+            self.storage(at, key.clone()).map_ok(|v| (v, key))
         };
 
-        let index_prefix = prefix(DEIP_PALLET_UNIQUES, b"NftClassIdByDeipNftClassId");
-        let index_key = HashedKey::<Identity>::unsafe_from_encoded(&key_encoded);
+        let index_prefix = prefix(DEIP_PALLET_UNIQUES_V1, b"NftClassIdByDeipNftClassIdV1");
+        let index_key = HashedKey::<Blake2_128Concat>::unsafe_from_encoded(&key_encoded);
 
         let prefix_key = chain_key_hash_map(&index_prefix, &index_key);
         get_value_and_map::<
@@ -155,383 +610,172 @@ where
         .boxed()
     }
 
-    // fn get_class_list(
-    //     &self,
-    //     at: Option<HashOf<Block>>,
-    //     count: u32,
-    //     start_id: Option<(DeipClassId, ClassId)>,
-    // ) -> BoxFutureResult<
-    //     Vec<ListResult<(DeipClassId, ClassId), ClassDetails<AccountId, DepositBalance>>>,
-    // > {
-    //     let index_prefix = prefix(DEIP_PALLET_UNIQUES, b"NftClassIdByDeipNftClassId");
-    //     let start_key = start_id.map(|(index_id, id)| {
-    //         let key_first = HashedKey::<Identity>::new(&index_id);
-    //         let key_second = HashedKey::<Blake2_128Concat>::new(&id);
-    //         chain_key_hash_double_map(&index_prefix, &key_first, &key_second)
-    //     });
-
-    //     let map = |k: StorageKey| -> BoxFutureResult<(
-    //         Option<common_rpc::StorageData>,
-    //         StorageKey,
-    //         DeipClassId,
-    //     )> {
-    //         // below we retrieve key in the other map from the index map key
-    //         let no_prefix = Identity::reverse(&k.0[32..]);
-    //         // decode DeipClassId and save the length of processed bytes
-    //         let input = &mut &*no_prefix;
-    //         let index_key = match DeipClassId::decode(input) {
-    //             Ok(k) => k,
-    //             Err(e) => {
-    //                 let data = format!("{:?}: {}", no_prefix, e);
-    //                 let rpc_error = to_rpc_error(Error::DeipClassIdDecodeFailed, Some(data));
-    //                 return future::err(rpc_error).boxed()
-    //             },
-    //         };
-
-    //         let len = match Input::remaining_len(input).ok().flatten() {
-    //             Some(l) => l,
-    //             None => {
-    //                 let data = format!("{:?}", input);
-    //                 let rpc_err = to_rpc_error(Error::DeipClassIdRemainingLengthFailed, Some(data));
-    //                 return future::err(rpc_err).boxed()
-    //             },
-    //         };
-
-    //         let key_hashed =
-    //             HashedKeyRef::<'_, Blake2_128Concat>::unsafe_from_hashed(&no_prefix[len..]);
-
-    //         let key = chain_key_hash_map(&prefix(PARITYTECH_PALLET_UNIQUES, b"Class"), &key_hashed);
-
-    //         self.state
-    //             .storage(key.clone(), at)
-    //             .map_ok(|v| (v, key, index_key))
-    //             .map_err(|e| to_rpc_error(Error::ScRpcApiError, Some(format!("{:?}", e))))
-    //             .boxed()
-    //     };
-
-    //     get_values_paged_and_map::<
-    //         ClassKeyValue<ClassId, AccountId, DepositBalance>,
-    //         Blake2_128Concat,
-    //         _,
-    //         _,
-    //         _,
-    //         _,
-    //         _,
-    //     >(&self.state, at, StorageKey(index_prefix), count, start_key, map)
-    // }
-
-    // fn get_class_instance_list(
-    //     &self,
-    //     at: Option<HashOf<Block>>,
-    //     count: u32,
-    //     start_id: Option<(DeipClassId, AccountId)>,
-    // ) -> BoxFutureResult<Vec<ClassInstanceWithIds<DeipClassId, InstanceId, AccountId, Extra>>> {
-    //     let storage_prefix = prefix(PARITYTECH_PALLET_UNIQUES, b"Account");
-
-    //     let fut = async {
-    //         let start_key = match start_id {
-    //             None => None,
-    //             Some((class, account)) => {
-    //                 let index_hashed = HashedKey::<Identity>::new(&class);
-    //                 let storage_prefix = prefix(DEIP_PALLET_UNIQUES, b"NftClassIdByDeipNftClassId");
-    //                 let prefix_key = chain_key_hash_map(&storage_prefix, &index_hashed);
-    //                 let mut keys = self
-    //                     .state
-    //                     .storage_keys_paged(Some(prefix_key), 1, None, at)
-    //                     .await
-    //                     .map_err(|e| {
-    //                         let data = format!("{:?}", e);
-    //                         to_rpc_error(Error::ScRpcApiError, Some(data))
-    //                     })?;
-    //                 if keys.is_empty() {
-    //                     return Ok(vec![])
-    //                 }
-
-    //                 let index_key = keys.pop().unwrap();
-    //                 let no_prefix = &index_key.0[32..];
-    //                 let key_hashed = HashedKeyRef::<'_, Blake2_128Concat>::unsafe_from_hashed(
-    //                     &no_prefix[index_hashed.as_ref().len()..],
-    //                 );
-
-    //                 Some(chain_key_hash_double_map(
-    //                     &storage_prefix,
-    //                     &key_hashed,
-    //                     &HashedKey::<Blake2_128Concat>::new(&account),
-    //                 ))
-    //             },
-    //         };
-
-    //         let state = &self.state;
-    //         let keys = state
-    //             .storage_keys_paged(Some(StorageKey(storage_prefix)), count, start_key, at)
-    //             .await
-    //             .map_err(|e| to_rpc_error(Error::ScRpcApiError, Some(format!("{:?}", e))))?;
-    //         if keys.is_empty() {
-    //             return Ok(vec![])
-    //         }
-
-    //         let keys: Vec<_> = FuturesOrdered::from_iter(keys.into_iter().map(|k| async {
-    //             // we have to wait for data so another request to
-    //             // index 1-to-1 map can be made
-    //             let storage_data = state
-    //                 .storage(k.clone(), at)
-    //                 .await
-    //                 .map_err(|e| to_rpc_error(Error::ScRpcApiError, Some(format!("{:?}", e))))?;
-
-    //             let no_prefix = &k.0[32..];
-    //             let len = no_prefix.len();
-    //             let no_prefix_no_hash = &mut Blake2_128Concat::reverse(no_prefix);
-
-    //             ClassId::skip(no_prefix_no_hash).map_err(|e| {
-    //                 to_rpc_error(Error::ClassIdDecodeFailed, Some(format!("{:?}", e)))
-    //             })?;
-    //             let remaining_len =
-    //                 Input::remaining_len(no_prefix_no_hash).ok().flatten().ok_or_else(|| {
-    //                     let data = Some(format!("{:?}", no_prefix_no_hash));
-    //                     to_rpc_error(Error::ClassIdRemainingLengthFailed, data)
-    //                 })?;
-
-    //             let key_hashed = HashedKeyRef::<'_, Blake2_128Concat>::unsafe_from_hashed(
-    //                 &no_prefix[..len - remaining_len],
-    //             );
-    //             let storage_prefix = prefix(DEIP_PALLET_UNIQUES, b"DeipClassIdByClassId");
-    //             let prefix_key = chain_key_hash_map(&storage_prefix, &key_hashed);
-    //             state
-    //                 .storage_keys_paged(Some(prefix_key), 1, None, at)
-    //                 .await
-    //                 .map_err(|e| to_rpc_error(Error::ScRpcApiError, Some(format!("{:?}", e))))
-    //                 .map(|mut index_keys| (index_keys.pop(), k, storage_data))
-    //         }))
-    //         .try_collect()
-    //         .await?;
-
-    //         let result = Vec::with_capacity(keys.len());
-    //         keys.into_iter().try_fold(result, |mut result, kv| {
-    //             let (index_key, key, value) = kv;
-    //             let data = match value {
-    //                 None => return Ok(result),
-    //                 Some(d) => d,
-    //             };
-
-    //             let index_key = if let Some(key) = index_key {
-    //                 key
-    //             } else {
-    //                 return Err(to_rpc_error(Error::DeipClassIdInverseIndexFailed, None))
-    //             };
-
-    //             let no_prefix = &index_key.0[32..];
-    //             let len = no_prefix.len();
-    //             let no_prefix_no_hash = &mut Blake2_128Concat::reverse(no_prefix);
-
-    //             if let Err(e) = ClassId::skip(no_prefix_no_hash) {
-    //                 return Err(to_rpc_error(
-    //                     Error::ClassIdDecodeFailed,
-    //                     Some(format!("{:?}: {}", &index_key.0, e)),
-    //                 ))
-    //             }
-    //             let remaining_len = match Input::remaining_len(no_prefix_no_hash).ok().flatten() {
-    //                 Some(l) => l,
-    //                 None =>
-    //                     return Err(to_rpc_error(
-    //                         Error::ClassIdRemainingLengthFailed,
-    //                         Some(format!("{:?}", no_prefix_no_hash)),
-    //                     )),
-    //             };
-
-    //             let no_prefix = Identity::reverse(&no_prefix[len - remaining_len..]);
-    //             let class = match DeipClassId::decode(&mut &*no_prefix) {
-    //                 Err(e) =>
-    //                     return Err(to_rpc_error(
-    //                         Error::DeipClassIdDecodeFailed,
-    //                         Some(format!("{:?}: {}", &key.0, e)),
-    //                     )),
-    //                 Ok(id) => id,
-    //             };
-
-    //             let no_prefix = &key.0[32..];
-    //             let len = no_prefix.len();
-    //             let no_prefix_no_hash = &mut Blake2_128Concat::reverse(no_prefix);
-
-    //             match ClassId::skip(no_prefix_no_hash) {
-    //                 Ok(_) => (),
-    //                 Err(_) =>
-    //                     return Err(to_rpc_error(
-    //                         Error::ClassIdDecodeFailed,
-    //                         Some(format!("{:?}", &key.0)),
-    //                     )),
-    //             };
-    //             let remaining_len = match Input::remaining_len(no_prefix_no_hash).ok().flatten() {
-    //                 Some(l) => l,
-    //                 None =>
-    //                     return Err(to_rpc_error(
-    //                         Error::ClassIdRemainingLengthFailed,
-    //                         Some(format!("{:?}", no_prefix_no_hash)),
-    //                     )),
-    //             };
-
-    //             let no_prefix = Blake2_128Concat::reverse(&no_prefix[len - remaining_len..]);
-    //             let account = match AccountId::decode(&mut &*no_prefix) {
-    //                 Err(_) =>
-    //                     return Err(to_rpc_error(
-    //                         Error::AccountIdDecodeFailed,
-    //                         Some(format!("{:?}", &key.0)),
-    //                     )),
-    //                 Ok(id) => id,
-    //             };
-
-    //             match ClassInstance::<InstanceId, Extra>::decode(&mut &data.0[..]) {
-    //                 Err(_) => Err(to_rpc_error(
-    //                     Error::ClassInstanceDecodeFailed,
-    //                     Some(format!("{:?}", data)),
-    //                 )),
-    //                 Ok(balance) => {
-    //                     result.push(ClassInstanceWithIds { class, account, balance });
-    //                     Ok(result)
-    //                 },
-    //             }
-    //         })
-    //     };
-    //     future::ready(block_on(fut)).boxed() //@TODO remove block_on
-    // }
-
-    // fn get_class_instance_by_owner(
-    //     &self,
-    //     at: Option<HashOf<Block>>,
-    //     owner: AccountId,
-    //     class: DeipClassId,
-    // ) -> BoxFutureResult<Option<ClassInstance<InstanceId, Extra>>> {
-    //     let index_hashed = HashedKey::<Identity>::new(&class);
-    //     let storage_prefix = prefix(DEIP_PALLET_UNIQUES, b"ClassIdByDeipClassId");
-    //     let prefix_key = chain_key_hash_map(&storage_prefix, &index_hashed);
-    //     let mut keys = match block_on(self.state.storage_keys_paged(Some(prefix_key), 1, None, at))
-    //     {
-    //         Ok(k) => k,
-    //         Err(e) =>
-    //             return future::err(to_rpc_error(Error::ScRpcApiError, Some(format!("{:?}", e))))
-    //                 .boxed(),
-    //     };
-    //     if keys.is_empty() {
-    //         return future::ok(None).boxed()
-    //     }
-
-    //     let key = keys.pop().unwrap();
-
-    //     let no_prefix = &key.0[32..];
-    //     let key_hashed = HashedKeyRef::<'_, Blake2_128Concat>::unsafe_from_hashed(
-    //         &no_prefix[index_hashed.as_ref().len()..],
-    //     );
-
-    //     let storage_prefix = prefix(PARITYTECH_PALLET_UNIQUES, b"Account");
-    //     let key_second = HashedKey::<Blake2_128Concat>::new(&owner);
-    //     let key = chain_key_hash_double_map(&storage_prefix, &key_hashed, &key_second);
-    //     get_value(&self.state, key, at)
-    // }
-
-    // fn get_class_instance_list_by_class(
-    //     &self,
-    //     at: Option<HashOf<Block>>,
-    //     class: DeipClassId,
-    //     count: u32,
-    //     start_id: Option<AccountId>,
-    // ) -> BoxFutureResult<Vec<ClassInstanceWithOwner<InstanceId, AccountId, Extra>>> {
-    //     // work with index
-    //     let index_hashed = HashedKey::<Identity>::new(&class);
-    //     let prefix_key = chain_key_hash_map(
-    //         &prefix(DEIP_PALLET_UNIQUES, b"ClassIdByDeipClassId"),
-    //         &index_hashed,
-    //     );
-    //     let mut keys = match block_on(self.state.storage_keys_paged(Some(prefix_key), 1, None, at))
-    //     {
-    //         Ok(k) => k,
-    //         Err(e) =>
-    //             return future::err(to_rpc_error(Error::ScRpcApiError, Some(format!("{:?}", e))))
-    //                 .boxed(),
-    //     };
-    //     if keys.is_empty() {
-    //         return future::ok(vec![]).boxed()
-    //     }
-
-    //     let key = keys.pop().unwrap();
-
-    //     let no_prefix = &key.0[32..];
-    //     let len = index_hashed.as_ref().len();
-    //     let class_encoded = Blake2_128Concat::reverse(&no_prefix[len..]);
-    //     let class_encoded_size = class_encoded.len();
-    //     let class_hashed =
-    //         HashedKeyRef::<'_, Blake2_128Concat>::unsafe_from_hashed(&no_prefix[len..]);
-
-    //     let prefix = prefix(PARITYTECH_PALLET_UNIQUES, b"Account");
-
-    //     let start_key = start_id.map(|account_id| {
-    //         StorageKey(
-    //             prefix
-    //                 .iter()
-    //                 .chain(class_hashed.as_ref())
-    //                 .chain(&account_id.using_encoded(Blake2_128Concat::hash))
-    //                 .copied()
-    //                 .collect(),
-    //         )
-    //     });
-
-    //     let prefix = prefix.iter().chain(class_hashed.as_ref()).copied().collect();
-
-    //     let state = &self.state;
-    //     let keys = match block_on(state.storage_keys_paged(
-    //         Some(StorageKey(prefix)),
-    //         count,
-    //         start_key,
-    //         at,
-    //     )) {
-    //         Ok(k) => k,
-    //         Err(e) =>
-    //             return future::err(to_rpc_error(Error::ScRpcApiError, Some(format!("{:?}", e))))
-    //                 .boxed(),
-    //     };
-    //     if keys.is_empty() {
-    //         return future::ok(vec![]).boxed()
-    //     }
-
-    //     let key_futures: FuturesOrdered<_> = keys
-    //         .into_iter()
-    //         .map(|k| {
-    //             state
-    //                 .storage(k.clone(), at)
-    //                 .map_ok(|v| (k, v))
-    //                 .map_err(|e| to_rpc_error(Error::ScRpcApiError, Some(format!("{:?}", e))))
-    //         })
-    //         .collect();
-
-    //     let result = Vec::with_capacity(key_futures.len());
-    //     key_futures
-    //         .try_fold(result, move |mut result, kv| {
-    //             let (key, value) = kv;
-    //             let data = match value {
-    //                 None => return future::ok(result),
-    //                 Some(d) => d,
-    //             };
-
-    //             let no_prefix = Blake2_128Concat::reverse(&key.0[32..]);
-    //             let no_prefix = Blake2_128Concat::reverse(&no_prefix[class_encoded_size..]);
-    //             let account = match AccountId::decode(&mut &*no_prefix) {
-    //                 Err(_) =>
-    //                     return future::err(to_rpc_error(
-    //                         Error::AccountIdDecodeFailed,
-    //                         Some(format!("{:?}", &key.0)),
-    //                     )),
-    //                 Ok(id) => id,
-    //             };
-
-    //             match ClassInstance::<InstanceId, Extra>::decode(&mut &data.0[..]) {
-    //                 Err(_) => future::err(to_rpc_error(
-    //                     Error::ClassInstanceDecodeFailed,
-    //                     Some(format!("{:?}", data)),
-    //                 )),
-    //                 Ok(balance) => {
-    //                     result.push(ClassInstanceWithOwner { account, balance });
-    //                     future::ok(result)
-    //                 },
-    //             }
-    //         })
-    //         .boxed()
-    // }
+    fn get_class_list(
+        &self,
+        at: Option<HashOf<Block>>,
+        count: u32,
+        start_id: Option<DeipClassId>,
+    ) -> BoxFutureResult<Vec<ClassInfo<DeipClassId, AccountId, DepositBalance>>> {
+        let at = self.pin(at);
+        let prefix = prefix(DEIP_PALLET_UNIQUES_V1, b"NftClassIdByDeipNftClassIdV1");
+        self.class_list::<_, ClassId, _, _, InstanceId>(at, prefix, count, start_id)
+    }
+
+    fn get_class_list_by_project(
+        &self,
+        at: Option<HashOf<Block>>,
+        project: ProjectId,
+        count: u32,
+        start_id: Option<DeipClassId>,
+    ) -> BoxFutureResult<Vec<ClassInfo<DeipClassId, AccountId, DepositBalance>>> {
+        let at = self.pin(at);
+        let prefix = chain_key_hash_map(
+            &prefix(DEIP_PALLET_UNIQUES_V1, b"DeipNftClassIdByProjectId"),
+            &HashedKey::<Blake2_128Concat>::new(&project),
+        );
+        self.class_list::<_, ClassId, _, _, InstanceId>(at, prefix.0, count, start_id)
+    }
+
+    fn get_class_instance_list(
+        &self,
+        at: Option<HashOf<Block>>,
+        count: u32,
+        start_id: Option<(DeipClassId, InstanceId)>,
+    ) -> BoxFutureResult<Vec<ClassInstanceInfo<DeipClassId, InstanceId, AccountId, DepositBalance>>>
+    {
+        let at = self.pin(at);
+        self.all_instance_list::<ClassId, _, _, _, _>(at, count, start_id)
+    }
+
+    fn get_class_instance_by_owner(
+        &self,
+        at: Option<HashOf<Block>>,
+        owner: AccountId,
+        class: DeipClassId,
+        count: u32,
+        start_id: Option<InstanceId>,
+    ) -> BoxFutureResult<Vec<ClassInstanceInfo<DeipClassId, InstanceId, AccountId, DepositBalance>>>
+    {
+        let at = self.pin(at);
+        let origin_class = match self.origin_class_id(at, &class) {
+            Err(e) => return future::err(e).boxed(),
+            Ok(None) => return future::ok(vec![]).boxed(),
+            Ok(Some(id)) => id,
+        };
+        // instances of the account are stored in `pallet_uniques::Account`
+        // keyed by (owner, class id, instance id)
+        let prefix = chain_key_hash_double_map(
+            &prefix(PARITYTECH_PALLET_UNIQUES_V1, b"Account"),
+            &HashedKey::<Blake2_128Concat>::new(&owner),
+            &HashedKey::<Blake2_128Concat>::unsafe_from_encoded(&origin_class),
+        );
+        self.instance_list::<_, ClassId, _, _, _>(
+            at,
+            prefix.0,
+            class,
+            origin_class,
+            count,
+            start_id,
+        )
+    }
+
+    fn get_class_instance_list_by_class(
+        &self,
+        at: Option<HashOf<Block>>,
+        class: DeipClassId,
+        count: u32,
+        start_id: Option<InstanceId>,
+    ) -> BoxFutureResult<Vec<ClassInstanceInfo<DeipClassId, InstanceId, AccountId, DepositBalance>>>
+    {
+        let at = self.pin(at);
+        let origin_class = match self.origin_class_id(at, &class) {
+            Err(e) => return future::err(e).boxed(),
+            Ok(None) => return future::ok(vec![]).boxed(),
+            Ok(Some(id)) => id,
+        };
+        let prefix = chain_key_hash_map(
+            &prefix(PARITYTECH_PALLET_UNIQUES_V1, b"Asset"),
+            &HashedKey::<Blake2_128Concat>::unsafe_from_encoded(&origin_class),
+        );
+        self.instance_list::<_, ClassId, _, _, _>(
+            at,
+            prefix.0,
+            class,
+            origin_class,
+            count,
+            start_id,
+        )
+    }
+
+    fn get_class_holder_list(
+        &self,
+        at: Option<HashOf<Block>>,
+        class: DeipClassId,
+        count: u32,
+        start_id: Option<AccountId>,
+    ) -> BoxFutureResult<Vec<ClassHolder<AccountId>>> {
+        let at = self.pin(at);
+        let class_hashed = HashedKey::<Blake2_128Concat>::new(&class);
+        let class_encoded_size = class.encode().len();
+
+        let prefix = prefix(DEIP_PALLET_UNIQUES_V1, b"HoldersByClass");
+        let start_key = start_id.map(|account| {
+            chain_key_hash_double_map(
+                &prefix,
+                &class_hashed,
+                &HashedKey::<Blake2_128Concat>::new(&account),
+            )
+        });
+        let prefix_key = chain_key_hash_map(&prefix, &class_hashed);
+
+        let state = &self.state;
+        let keys = match block_on(state.storage_keys_paged(Some(prefix_key), count, start_key, at))
+        {
+            Ok(k) => k,
+            Err(e) =>
+                return future::err(to_rpc_error(Error::ScRpcApiError, Some(format!("{:?}", e))))
+                    .boxed(),
+        };
+        if keys.is_empty() {
+            return future::ok(vec![]).boxed()
+        }
+
+        let key_futures: FuturesOrdered<_> = keys
+            .into_iter()
+            .map(|k| self.storage(at, k.clone()).map_ok(|v| (k, v)))
+            .collect();
+
+        let result = Vec::with_capacity(key_futures.len());
+        key_futures
+            .try_fold(result, move |mut result, kv| {
+                let (key, value) = kv;
+                let data = match value {
+                    None => return future::ok(result),
+                    Some(d) => d,
+                };
+
+                let no_prefix = Blake2_128Concat::reverse(&key.0[32..]);
+                let no_prefix = Blake2_128Concat::reverse(&no_prefix[class_encoded_size..]);
+                let account = match AccountId::decode(&mut &*no_prefix) {
+                    Err(_) =>
+                        return future::err(to_rpc_error(
+                            Error::AccountIdDecodeFailed,
+                            Some(format!("{:?}", &key.0)),
+                        )),
+                    Ok(id) => id,
+                };
+
+                match u32::decode(&mut &data.0[..]) {
+                    Err(_) => future::err(to_rpc_error(
+                        Error::ClassInstanceDecodeFailed,
+                        Some(format!("{:?}", data)),
+                    )),
+                    Ok(instances) => {
+                        result.push(ClassHolder { account, instances });
+                        future::ok(result)
+                    },
+                }
+            })
+            .boxed()
+    }
 }
@@ -12,6 +12,35 @@ impl common_rpc::GetError for ClassIdError {
     }
 }
 
+pub struct DeipClassIdError;
+
+impl common_rpc::GetError for DeipClassIdError {
+    fn get_error() -> common_rpc::Error {
+        common_rpc::Error::DeipClassIdDecodeFailed
+    }
+}
+
+/// Entry of `NftClassIdByDeipNftClassIdV1`: DEIP class id to the class id in `pallet_uniques`.
+pub struct OriginClassKeyValue<DeipClassId, ClassId> {
+    pub id: DeipClassId,
+    _m: std::marker::PhantomData<ClassId>,
+}
+
+impl<DeipClassId, ClassId> common_rpc::KeyValueInfo for OriginClassKeyValue<DeipClassId, ClassId>
+where
+    DeipClassId: 'static + Encode + Decode + Send,
+    ClassId: 'static + Decode + Send,
+{
+    type Key = DeipClassId;
+    type KeyError = DeipClassIdError;
+    type Value = ClassId;
+    type ValueError = ClassIdError;
+
+    fn key(&self) -> &Self::Key {
+        &self.id
+    }
+}
+
 // copied from pallet_uniques since struct members are not public
 #[derive(Decode, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -74,35 +103,123 @@ where
     }
 }
 
-#[derive(Decode)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
-pub struct ClassInstance<InstanceId: Decode, Extra: Decode> {
-    instance: InstanceId,
-    is_frozen: bool,
-    sufficient: bool,
-    extra: Extra,
+// copied from pallet_uniques since struct members are not public
+#[derive(Decode, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceDetails<AccountId, DepositBalance>
+where
+    DepositBalance: AtLeast32BitUnsigned + Clone,
+{
+    /// The owner of this asset.
+    pub(super) owner: AccountId,
+    /// The approved transferrer of this asset, if one is set.
+    pub(super) approved: Option<AccountId>,
+    /// Whether the asset can be transferred or not.
+    pub(super) is_frozen: bool,
+    /// The amount held in the pallet's default account for this asset. Free-hold assets will have
+    /// this as zero.
+    pub(super) deposit: SerializableAtLeast32BitUnsigned<DepositBalance>,
 }
 
-impl<InstanceId: Decode, Extra: Decode> common_rpc::GetError for ClassInstance<InstanceId, Extra> {
+impl<AccountId, DepositBalance> common_rpc::GetError for InstanceDetails<AccountId, DepositBalance>
+where
+    AccountId: Decode,
+    DepositBalance: Clone + Decode + AtLeast32BitUnsigned,
+{
     fn get_error() -> common_rpc::Error {
         common_rpc::Error::ClassInstanceDecodeFailed
     }
 }
 
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
-pub struct ClassInstanceWithIds<ClassId, InstanceId: Decode, AccountId, Extra: Decode> {
-    pub class: ClassId,
-    pub account: AccountId,
+/// Entry of `pallet_uniques::Asset`, its key is decoded up to the class id.
+pub struct InstanceKeyValue<ClassId, AccountId, DepositBalance> {
+    pub id: ClassId,
+    _m: std::marker::PhantomData<(AccountId, DepositBalance)>,
+}
+
+impl<ClassId, AccountId, DepositBalance> common_rpc::KeyValueInfo
+    for InstanceKeyValue<ClassId, AccountId, DepositBalance>
+where
+    ClassId: 'static + Encode + Decode + Send,
+    AccountId: 'static + Decode + Send,
+    DepositBalance: 'static + Send + Clone + Decode + AtLeast32BitUnsigned,
+{
+    type Key = ClassId;
+    type KeyError = ClassIdError;
+    type Value = InstanceDetails<AccountId, DepositBalance>;
+    type ValueError = Self::Value;
+
+    fn key(&self) -> &Self::Key {
+        &self.id
+    }
+}
+
+// copied from pallet_uniques since struct members are not public,
+// `ClassMetadata` and `InstanceMetadata` have the same layout
+#[derive(Decode, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Metadata<DepositBalance>
+where
+    DepositBalance: AtLeast32BitUnsigned + Clone,
+{
+    /// The balance deposited for this metadata.
+    pub(super) deposit: SerializableAtLeast32BitUnsigned<DepositBalance>,
+    /// General information concerning this asset. Limited in length by `StringLimit`.
+    #[serde(with = "sp_core::bytes")]
+    pub(super) data: Vec<u8>,
+    /// Whether the metadata may be changed by a non Force origin.
+    pub(super) is_frozen: bool,
+}
+
+impl<DepositBalance> common_rpc::GetError for Metadata<DepositBalance>
+where
+    DepositBalance: Clone + Decode + AtLeast32BitUnsigned,
+{
+    fn get_error() -> common_rpc::Error {
+        common_rpc::Error::NftMetadataDecodeFailed
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Attribute {
+    #[serde(with = "sp_core::bytes")]
+    pub key: Vec<u8>,
+    #[serde(with = "sp_core::bytes")]
+    pub value: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassInfo<DeipClassId, AccountId, DepositBalance>
+where
+    DepositBalance: AtLeast32BitUnsigned + Clone,
+{
+    pub class: DeipClassId,
+    #[serde(flatten)]
+    pub details: ClassDetails<AccountId, DepositBalance>,
+    pub metadata: Option<Metadata<DepositBalance>>,
+    /// Attributes of the class itself.
+    pub attributes: Vec<Attribute>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassInstanceInfo<DeipClassId, InstanceId, AccountId, DepositBalance>
+where
+    DepositBalance: AtLeast32BitUnsigned + Clone,
+{
+    pub class: DeipClassId,
+    pub instance: InstanceId,
     #[serde(flatten)]
-    pub balance: ClassInstance<InstanceId, Extra>,
+    pub details: InstanceDetails<AccountId, DepositBalance>,
+    pub metadata: Option<Metadata<DepositBalance>>,
+    pub attributes: Vec<Attribute>,
 }
 
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
-pub struct ClassInstanceWithOwner<InstanceId: Decode, AccountId, Extra: Decode> {
+pub struct ClassHolder<AccountId> {
     pub account: AccountId,
-    #[serde(flatten)]
-    pub balance: ClassInstance<InstanceId, Extra>,
+    /// Number of instances of the class held by the account.
+    pub instances: u32,
 }
@@ -0,0 +1,47 @@
+use frame_support::{pallet_prelude::*, traits::InstanceFilter};
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Call, DeipAssetId};
+
+/// Permission of a DAO role to dispatch calls on behalf of the DAO.
+/// Amounts the role transfers are limited by budgets of the role per period.
+#[derive(Debug, Clone, Eq, PartialEq, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum DaoPermission {
+    /// Transfer of `asset`
+    Transfer { asset: DeipAssetId },
+    /// Transfer of the native currency
+    TransferNative,
+    /// Creation of project contents
+    CreateProjectContent,
+}
+
+impl InstanceFilter<Call> for DaoPermission {
+    fn filter(&self, c: &Call) -> bool {
+        use pallet_balances::Call as BalancesCall;
+        use pallet_deip::Call as DeipCall;
+        use pallet_deip_assets::Call as DeipAssetsCall;
+        match (self, c) {
+            (
+                DaoPermission::Transfer { asset },
+                Call::DeipAssets(DeipAssetsCall::deip_transfer { id, .. }),
+            ) => id == asset,
+            (
+                DaoPermission::TransferNative,
+                Call::Balances(BalancesCall::transfer { .. }) |
+                Call::Balances(BalancesCall::transfer_keep_alive { .. }),
+            ) => true,
+            (
+                DaoPermission::CreateProjectContent,
+                Call::Deip(DeipCall::create_project_content { .. }),
+            ) => true,
+            _ => false,
+        }
+    }
+
+    fn is_superset(&self, o: &Self) -> bool {
+        self == o
+    }
+}
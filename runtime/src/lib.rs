@@ -54,6 +54,7 @@ use sp_std::prelude::*;
 #[cfg(feature = "std")]
 use sp_version::NativeVersion;
 
+pub mod dao_permission;
 pub mod deip_account;
 
 /// An index to a block.
@@ -253,8 +254,55 @@ parameter_types! {
 use frame_support::traits::Contains;
 pub struct CallFilter;
 impl Contains<Call> for CallFilter {
-    fn contains(_t: &Call) -> bool {
-        true
+    fn contains(t: &Call) -> bool {
+        // Balances of DEIP assets are tracked by `DeipAssets` (checkpoints, holders),
+        // so they should be changed via its `deip_*` calls only.
+        use pallet_assets::Call::*;
+        use pallet_deip_assets::Call as DeipAssetsCall;
+        // The same for holders of DEIP NFT classes tracked by `DeipUniques`.
+        use pallet_deip_uniques::Call as DeipUniquesCall;
+        use pallet_uniques::Call as UniquesCall;
+        match t {
+            Call::Assets(mint { id, .. }) |
+            Call::Assets(burn { id, .. }) |
+            Call::Assets(transfer { id, .. }) |
+            Call::Assets(transfer_keep_alive { id, .. }) |
+            Call::Assets(force_transfer { id, .. }) |
+            Call::Assets(transfer_approved { id, .. }) => !DeipAssets::is_deip_asset(id),
+            // `DeipAssets` calls are exposed for dividends and the features built on
+            // checkpoints and holders, and as the `deip_*` replacements of the `Assets`
+            // calls filtered above. Its wrappers of `Assets` calls which don't change
+            // balances aren't exposed, the `Assets` calls are dispatched directly.
+            Call::DeipAssets(DeipAssetsCall::deip_freeze { .. }) |
+            Call::DeipAssets(DeipAssetsCall::deip_thaw { .. }) |
+            Call::DeipAssets(DeipAssetsCall::deip_freeze_asset { .. }) |
+            Call::DeipAssets(DeipAssetsCall::deip_thaw_asset { .. }) |
+            Call::DeipAssets(DeipAssetsCall::deip_transfer_ownership { .. }) |
+            Call::DeipAssets(DeipAssetsCall::deip_set_team { .. }) |
+            Call::DeipAssets(DeipAssetsCall::deip_set_metadata { .. }) => false,
+            Call::Uniques(UniquesCall::mint { class, .. }) |
+            Call::Uniques(UniquesCall::burn { class, .. }) |
+            Call::Uniques(UniquesCall::transfer { class, .. }) |
+            Call::Uniques(UniquesCall::destroy { class, .. }) => !DeipUniques::is_deip_class(class),
+            // Wrappers of `Uniques` calls which don't change holders aren't exposed,
+            // the `Uniques` calls are dispatched directly for DEIP classes.
+            Call::DeipUniques(DeipUniquesCall::deip_redeposit { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_freeze { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_thaw { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_freeze_class { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_thaw_class { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_transfer_ownership { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_set_team { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_approve_transfer { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_cancel_approval { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_set_attribute { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_clear_attribute { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_set_metadata { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_clear_metadata { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_set_class_metadata { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_clear_class_metadata { .. }) => false,
+            _ => true,
+        }
     }
 }
 
@@ -584,6 +632,13 @@ impl deip_projects_info::DeipProjectsInfo<AccountId> for Runtime {
 
 parameter_types! {
     pub const WipePeriod: BlockNumber = DAYS;
+    pub const MaxDividendClaimsPerBlock: u32 = 100;
+    pub const CheckpointRetention: u32 = 100;
+    /// Proposals open at once in different blocks of DAOs governed by the asset.
+    pub const MaxPinnedCheckpoints: u32 = 50;
+    pub const MaxWipeAccountsPerBlock: u32 = 100;
+    /// Id of the native currency in `DeipAssetSystem`.
+    pub const NativeAssetId: DeipAssetId = H160::zero();
 }
 
 pub struct AssetIdInit;
@@ -599,7 +654,14 @@ impl pallet_deip_assets::Config for Runtime {
     type AssetsAssetId = AssetId;
     type AssetId = DeipAssetId;
     type AssetIdInit = AssetIdInit;
+    type NativeAssetId = NativeAssetId;
     type WipePeriod = WipePeriod;
+    type MaxWipeAccountsPerBlock = MaxWipeAccountsPerBlock;
+    type Event = Event;
+    type MaxDividendClaimsPerBlock = MaxDividendClaimsPerBlock;
+    type CheckpointRetention = CheckpointRetention;
+    type MaxPinnedCheckpoints = MaxPinnedCheckpoints;
+    type TransferPolicy = pallet_deip_assets::OnChainTransferPolicy<Self, Timestamp>;
 }
 
 impl pallet_deip_balances::Config for Runtime {}
@@ -626,6 +688,12 @@ parameter_types! {
 
     /// Greater class ids will be reserved for `deip_*` calls.
     pub const MaxOriginClassId: NftClassId = NftClassId::MAX / 2;
+
+    /// The maximum number of instances minted by a batch.
+    pub const MaxBatchMint: u32 = 1000;
+
+    /// The maximum number of attributes of an instance minted by a batch.
+    pub const MaxBatchAttributes: u32 = 10;
 }
 
 impl pallet_uniques::Config for Runtime {
@@ -650,8 +718,16 @@ impl pallet_deip_uniques::Config for Runtime {
     type DeipAccountId = deip_account::DeipAccountId<<Self as frame_system::Config>::AccountId>;
     type ProjectId = pallet_deip::ProjectId;
     type NftClassId = <Self as pallet_uniques::Config>::ClassId;
+    type NftInstanceId = <Self as pallet_uniques::Config>::InstanceId;
     type ProjectsInfo = Self;
     type MaxOriginClassId = MaxOriginClassId;
+    type Event = Event;
+    type Fractions = Self;
+    type SourceId = ProjectId;
+    type MaxBatchMint = MaxBatchMint;
+    type MaxBatchAttributes = MaxBatchAttributes;
+    type DeipWeightInfo = pallet_deip_uniques::weights::Weights<Self>;
+    type OnClassDestroyed = Deip;
 }
 
 impl pallet_beefy::Config for Runtime {
@@ -745,6 +821,14 @@ pub type TransactionCtxId = pallet_deip_portal::TransactionCtxId<TransactionCtx>
 parameter_types! {
     pub const MaxNdaParties: u16 = 50;
     pub const MaxInvestmentShares: u16 = 10;
+    pub const MaxContentsPerNftClass: u32 = 100;
+    pub const MaxContentAccessKeyLength: u32 = 1024;
+    // The request with the longest keys and its indexes: keys size is 3 * 20 + 20;
+    // value is 3 keys of 1 KiB + 128 bytes.
+    pub const ContentAccessRequestDeposit: Balance =
+        currency::deposit(3, 80 + 3 * 1024 + 128);
+    pub const MaxContributionTiers: u16 = 10;
+    pub const MaxPerkMintsPerBlock: u32 = 50;
 }
 
 impl pallet_deip::Config for Runtime {
@@ -755,6 +839,10 @@ impl pallet_deip::Config for Runtime {
     type DeipWeightInfo = pallet_deip::Weights<Self>;
     type MaxNdaParties = MaxNdaParties;
     type MaxInvestmentShares = MaxInvestmentShares;
+    type NftSystem = Self;
+    type MaxContentsPerNftClass = MaxContentsPerNftClass;
+    type MaxContentAccessKeyLength = MaxContentAccessKeyLength;
+    type ContentAccessRequestDeposit = ContentAccessRequestDeposit;
 }
 
 impl pallet_deip_investment_opportunity::Config for Runtime {
@@ -764,6 +852,29 @@ impl pallet_deip_investment_opportunity::Config for Runtime {
     type DeipAccountId = deip_account::DeipAccountId<Self::AccountId>;
     type MaxInvestmentShares = MaxInvestmentShares;
     type SourceId = ProjectId;
+    type ProjectsInfo = Self;
+    type NftSystem = Self;
+    type MaxContributionTiers = MaxContributionTiers;
+    type MaxPerkMintsPerBlock = MaxPerkMintsPerBlock;
+}
+
+impl pallet_deip_swap::Config for Runtime {
+    type Event = Event;
+    type DeipAccountId = deip_account::DeipAccountId<Self::AccountId>;
+    type SourceId = ProjectId;
+    type DeipSwapWeightInfo = pallet_deip_swap::weights::Weights<Self>;
+}
+
+parameter_types! {
+    pub const AmmSwapFee: Permill = Permill::from_parts(3_000);
+    pub const AmmMinimumLiquidity: AssetBalance = 1_000;
+}
+
+impl pallet_deip_amm::Config for Runtime {
+    type Event = Event;
+    type SwapFee = AmmSwapFee;
+    type MinimumLiquidity = AmmMinimumLiquidity;
+    type DeipAmmWeightInfo = pallet_deip_amm::weights::Weights<Self>;
 }
 
 impl deip_asset_system::AssetIdInitT<DeipAssetId> for Runtime {
@@ -773,25 +884,98 @@ impl deip_asset_system::AssetIdInitT<DeipAssetId> for Runtime {
 }
 
 use deip_asset_system::{ReserveError, UnreserveError};
+use frame_support::{
+    storage::{with_transaction, TransactionOutcome},
+    traits::{Currency, ExistenceRequirement},
+};
+use pallet_deip_assets::TransferPolicy as _;
+
+fn transfer_native(
+    from: &AccountId,
+    to: &AccountId,
+    amount: Balance,
+) -> sp_runtime::DispatchResult {
+    <Balances as Currency<AccountId>>::transfer(from, to, amount, ExistenceRequirement::KeepAlive)
+}
+
+/// Reserves DEIP assets with `DeipAssets` and native ones on the same account.
+fn reserve(
+    account: &AccountId,
+    id: InvestmentId,
+    shares: &[(DeipAssetId, AssetBalance)],
+    asset: DeipAssetId,
+    lock_asset_to_raise: bool,
+) -> Result<(), ReserveError<DeipAssetId>> {
+    let native = NativeAssetId::get();
+    let (native_shares, shares): (Vec<_>, Vec<_>) =
+        shares.iter().copied().partition(|(a, _)| *a == native);
+
+    with_transaction(|| {
+        if let Err(e) = DeipAssets::deip_transactionally_reserve(
+            account,
+            id,
+            &shares,
+            asset,
+            lock_asset_to_raise,
+        ) {
+            return TransactionOutcome::Rollback(Err(e))
+        }
 
+        let reserve = DeipAssets::investment_key(&id);
+        for (_, amount) in native_shares {
+            if transfer_native(account, &reserve, amount).is_err() {
+                return TransactionOutcome::Rollback(Err(ReserveError::NotEnoughBalance))
+            }
+        }
+
+        TransactionOutcome::Commit(Ok(()))
+    })
+}
+
+/// The native currency is available under `NativeAssetId` and is routed to `Balances`.
+/// Reserved native balances are kept on the same account as reserved DEIP assets.
 impl deip_asset_system::DeipAssetSystem<AccountId, ProjectId, InvestmentId> for Runtime {
     type Balance = AssetBalance;
     type AssetId = DeipAssetId;
 
     fn account_balance(account: &AccountId, asset: &Self::AssetId) -> Self::Balance {
+        if *asset == NativeAssetId::get() {
+            return Balances::free_balance(account)
+        }
         DeipAssets::account_balance(account, asset)
     }
 
     fn total_supply(asset: &Self::AssetId) -> Self::Balance {
+        if *asset == NativeAssetId::get() {
+            return Balances::total_issuance()
+        }
         DeipAssets::total_supply(asset)
     }
 
+    fn minimum_balance(asset: &Self::AssetId) -> Self::Balance {
+        if *asset == NativeAssetId::get() {
+            return Balances::minimum_balance()
+        }
+        DeipAssets::minimum_balance(asset)
+    }
+
     fn transactionally_transfer(
         from: &AccountId,
         asset: Self::AssetId,
         transfers: &[(Self::Balance, AccountId)],
-    ) -> Result<(), ()> {
-        DeipAssets::transactionally_transfer(from, asset, transfers)
+    ) -> sp_runtime::DispatchResult {
+        if asset != NativeAssetId::get() {
+            return DeipAssets::transactionally_transfer(from, asset, transfers)
+        }
+
+        with_transaction(|| {
+            for (amount, to) in transfers {
+                if let Err(e) = transfer_native(from, to, *amount) {
+                    return TransactionOutcome::Rollback(Err(e))
+                }
+            }
+            TransactionOutcome::Commit(Ok(()))
+        })
     }
 
     fn transactionally_reserve(
@@ -800,11 +984,40 @@ impl deip_asset_system::DeipAssetSystem<AccountId, ProjectId, InvestmentId> for
         shares: &[(Self::AssetId, Self::Balance)],
         asset: Self::AssetId,
     ) -> Result<(), ReserveError<Self::AssetId>> {
-        DeipAssets::deip_transactionally_reserve(account, id, shares, asset)
+        reserve(account, id, shares, asset, true)
+    }
+
+    fn transactionally_reserve_escrow(
+        account: &AccountId,
+        id: InvestmentId,
+        shares: &[(Self::AssetId, Self::Balance)],
+        asset: Self::AssetId,
+    ) -> Result<(), ReserveError<Self::AssetId>> {
+        reserve(account, id, shares, asset, false)
     }
 
     fn transactionally_unreserve(id: InvestmentId) -> Result<(), UnreserveError<Self::AssetId>> {
-        DeipAssets::transactionally_unreserve(id)
+        let creator = match DeipAssets::investment(&id) {
+            Some(info) => info.creator,
+            None => return Err(UnreserveError::NoSuchInvestment),
+        };
+
+        with_transaction(|| {
+            // the minimum balance is returned by `DeipAssets` with the reserved assets
+            let reserve = DeipAssets::investment_key(&id);
+            let amount =
+                Balances::free_balance(&reserve).saturating_sub(Balances::minimum_balance());
+            if amount > 0 && transfer_native(&reserve, &creator, amount).is_err() {
+                return TransactionOutcome::Rollback(Err(UnreserveError::AssetTransferFailed(
+                    NativeAssetId::get(),
+                )))
+            }
+
+            match DeipAssets::transactionally_unreserve(id) {
+                Ok(()) => TransactionOutcome::Commit(Ok(())),
+                Err(e) => TransactionOutcome::Rollback(Err(e)),
+            }
+        })
     }
 
     fn transfer_from_reserved(
@@ -813,7 +1026,15 @@ impl deip_asset_system::DeipAssetSystem<AccountId, ProjectId, InvestmentId> for
         asset: Self::AssetId,
         amount: Self::Balance,
     ) -> Result<(), UnreserveError<Self::AssetId>> {
-        DeipAssets::transfer_from_reserved(id, who, asset, amount)
+        if asset != NativeAssetId::get() {
+            return DeipAssets::transfer_from_reserved(id, who, asset, amount)
+        }
+
+        if DeipAssets::investment(&id).is_none() {
+            return Err(UnreserveError::NoSuchInvestment)
+        }
+        transfer_native(&DeipAssets::investment_key(&id), who, amount)
+            .map_err(|_| UnreserveError::AssetTransferFailed(asset))
     }
 
     fn transfer_to_reserved(
@@ -821,7 +1042,439 @@ impl deip_asset_system::DeipAssetSystem<AccountId, ProjectId, InvestmentId> for
         id: InvestmentId,
         amount: Self::Balance,
     ) -> Result<(), UnreserveError<Self::AssetId>> {
-        DeipAssets::deip_transfer_to_reserved(who, id, amount)
+        let info = DeipAssets::investment(&id).ok_or(UnreserveError::NoSuchInvestment)?;
+        if info.asset_id != NativeAssetId::get() {
+            return DeipAssets::deip_transfer_to_reserved(who, id, amount)
+        }
+
+        // shares are distributed from the reserve after the investment is finished
+        for share in &info.assets {
+            <Runtime as pallet_deip_assets::Config>::TransferPolicy::ensure_can_receive(
+                share, who, 0,
+            )
+            .map_err(|_| UnreserveError::AssetTransferFailed(*share))?;
+        }
+
+        transfer_native(who, &DeipAssets::investment_key(&id), amount)
+            .map_err(|_| UnreserveError::AssetTransferFailed(info.asset_id))
+    }
+}
+
+impl deip_asset_system::DeipNftSystem<AccountId> for Runtime {
+    type ClassId = DeipNftClassId;
+    type InstanceId = InstanceId;
+
+    fn class_owner(class: &Self::ClassId) -> Option<AccountId> {
+        DeipUniques::class_owner(*class)
+    }
+
+    fn class_issuer(class: &Self::ClassId) -> Option<AccountId> {
+        DeipUniques::class_issuer(*class)
+    }
+
+    fn holder(class: &Self::ClassId, instance: &Self::InstanceId) -> Option<AccountId> {
+        DeipUniques::holder(*class, *instance)
+    }
+
+    fn mint(
+        issuer: &AccountId,
+        class: Self::ClassId,
+        owner: &AccountId,
+    ) -> Result<Self::InstanceId, ()> {
+        DeipUniques::deip_mint_next(issuer.clone(), class, owner.clone()).map_err(|_| ())
+    }
+
+    fn mint_weight() -> Weight {
+        use pallet_uniques::WeightInfo;
+        // class id mapping, the instance counter and the index of holders
+        <Runtime as pallet_uniques::Config>::WeightInfo::mint()
+            .saturating_add(RocksDbWeight::get().reads_writes(5, 3))
+    }
+
+    fn is_holder(class: &Self::ClassId, account: &AccountId) -> bool {
+        DeipUniques::is_holder(*class, account)
+    }
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl deip_asset_system::DeipNftSystemBenchmarking<AccountId> for Runtime {
+    fn benchmark_class(index: u8, owner: &AccountId) -> DeipNftClassId {
+        let mut raw = [0xbe; 20];
+        raw[0] = index;
+        let class = DeipNftClassId::from(raw);
+        Balances::make_free_balance_be(owner, 1_000 * currency::DOLLARS);
+        let origin = frame_system::RawOrigin::Signed(owner.clone()).into();
+        DeipUniques::deip_create(origin, class, owner.clone().into())
+            .expect("benchmark class is created");
+        class
+    }
+}
+
+impl deip_asset_system::DeipFractions<AccountId> for Runtime {
+    type Balance = AssetBalance;
+    type AssetId = DeipAssetId;
+
+    fn ensure_can_create(asset: &Self::AssetId) -> sp_runtime::DispatchResult {
+        DeipAssets::ensure_asset_id_available(asset).map_err(Into::into)
+    }
+
+    fn create(
+        owner: &AccountId,
+        vault: &AccountId,
+        asset: Self::AssetId,
+        name: Vec<u8>,
+        amount: Self::Balance,
+    ) -> sp_runtime::DispatchResult {
+        let signed =
+            |who: &AccountId| -> Origin { frame_system::RawOrigin::Signed(who.clone()).into() };
+
+        // the owner pays deposits and then passes all rights to the vault
+        // so that nobody can mint more shares
+        DeipAssets::deip_create(signed(owner), asset, owner.clone().into(), 1, Some(amount), None)
+            .map_err(|e| e.error)?;
+        DeipAssets::deip_set_metadata(signed(owner), asset, name, b"F-NFT".to_vec(), 0)
+            .map_err(|e| e.error)?;
+        DeipAssets::deip_mint(signed(owner), asset, owner.clone().into(), amount)
+            .map_err(|e| e.error)?;
+        DeipAssets::deip_set_team(
+            signed(owner),
+            asset,
+            vault.clone().into(),
+            vault.clone().into(),
+            vault.clone().into(),
+        )
+        .map_err(|e| e.error)?;
+        DeipAssets::deip_transfer_ownership(signed(owner), asset, vault.clone().into())
+            .map_err(|e| e.error)?;
+
+        Ok(())
+    }
+
+    fn account_balance(account: &AccountId, asset: &Self::AssetId) -> Self::Balance {
+        DeipAssets::account_balance(account, asset)
+    }
+
+    fn total_supply(asset: &Self::AssetId) -> Self::Balance {
+        DeipAssets::total_supply(asset)
+    }
+
+    fn burn(
+        vault: &AccountId,
+        asset: Self::AssetId,
+        who: &AccountId,
+        amount: Self::Balance,
+    ) -> sp_runtime::DispatchResult {
+        let origin = frame_system::RawOrigin::Signed(vault.clone()).into();
+        DeipAssets::deip_burn(origin, asset, who.clone().into(), amount)
+            .map(|_| ())
+            .map_err(|e| e.error)
+    }
+}
+
+impl deip_asset_system::DeipCheckpoints<AccountId> for Runtime {
+    type Balance = AssetBalance;
+    type AssetId = DeipAssetId;
+    type CheckpointId = pallet_deip_assets::CheckpointId;
+
+    fn create_checkpoint(
+        asset: Self::AssetId,
+    ) -> Result<Self::CheckpointId, sp_runtime::DispatchError> {
+        DeipAssets::create_checkpoint(asset).map_err(Into::into)
+    }
+
+    fn pin_checkpoint(
+        asset: Self::AssetId,
+    ) -> Result<Self::CheckpointId, sp_runtime::DispatchError> {
+        DeipAssets::pin_checkpoint(asset).map_err(Into::into)
+    }
+
+    fn unpin_checkpoint(asset: &Self::AssetId, checkpoint: Self::CheckpointId) {
+        DeipAssets::unpin_checkpoint(asset, checkpoint)
+    }
+
+    fn balance_at(
+        asset: &Self::AssetId,
+        account: &AccountId,
+        checkpoint: Self::CheckpointId,
+    ) -> Option<Self::Balance> {
+        DeipAssets::balance_at(asset, account, checkpoint)
+    }
+
+    fn total_supply_at(
+        asset: &Self::AssetId,
+        checkpoint: Self::CheckpointId,
+    ) -> Option<Self::Balance> {
+        DeipAssets::total_supply_at(asset, checkpoint)
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn benchmark_mint(account: &AccountId, amount: Self::Balance) -> Self::AssetId {
+        mint_benchmark_asset(DeipAssetId::repeat_byte(0xbe), account, amount)
+    }
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl deip_asset_system::DeipAssetSystemBenchmarking<AccountId, ProjectId, InvestmentId>
+    for Runtime
+{
+    fn benchmark_mint(index: u8, account: &AccountId, amount: AssetBalance) -> DeipAssetId {
+        let mut raw = [0xbe; 20];
+        raw[0] = index;
+        mint_benchmark_asset(DeipAssetId::from(raw), account, amount)
+    }
+}
+
+/// Mints `amount` of `asset` to `account`, creating the asset on the first call.
+#[cfg(feature = "runtime-benchmarks")]
+fn mint_benchmark_asset(
+    asset: DeipAssetId,
+    account: &AccountId,
+    amount: AssetBalance,
+) -> DeipAssetId {
+    let admin = AccountId::from([0xbe; 32]);
+    let signed = frame_system::RawOrigin::Signed(admin.clone());
+    if DeipAssets::ensure_asset_id_available(&asset).is_ok() {
+        Balances::make_free_balance_be(&admin, 1_000 * currency::DOLLARS);
+        let origin = signed.clone().into();
+        DeipAssets::deip_create(origin, asset, admin.clone().into(), 1, None, None)
+            .expect("benchmark asset is created");
+    }
+    DeipAssets::deip_mint(signed.into(), asset, account.clone().into(), amount)
+        .expect("benchmark asset is minted");
+    asset
+}
+
+/// Calls not listed explicitly may move funds which aren't measured, so DAOs having
+/// budgets dispatch them only with approval of their budget guardians.
+impl pallet_deip_dao::budget::DaoSpending<AccountId, Call> for Runtime {
+    type AssetId = DeipAssetId;
+    type Balance = AssetBalance;
+
+    fn spending(spender: &AccountId, call: &Call) -> Option<Vec<(DeipAssetId, AssetBalance)>> {
+        use pallet_balances::Call as BalancesCall;
+        use pallet_deip::Call as DeipCall;
+        use pallet_deip_amm::Call as AmmCall;
+        use pallet_deip_assets::Call as DeipAssetsCall;
+        use pallet_deip_dao::Call as DeipDaoCall;
+        use pallet_deip_investment_opportunity::Call as InvestmentCall;
+        use pallet_deip_swap::Call as SwapCall;
+        use pallet_deip_uniques::Call as DeipUniquesCall;
+        use pallet_utility::Call as UtilityCall;
+        let native = NativeAssetId::get();
+        let spending = match call {
+            Call::Balances(BalancesCall::transfer { value, .. }) |
+            Call::Balances(BalancesCall::transfer_keep_alive { value, .. }) =>
+                vec![(native, *value)],
+            Call::Balances(BalancesCall::transfer_all { .. }) =>
+                vec![(native, Balances::free_balance(spender))],
+
+            Call::DeipAssets(DeipAssetsCall::deip_transfer { id, amount, .. }) |
+            Call::DeipAssets(DeipAssetsCall::deip_approve_transfer { id, amount, .. }) =>
+                vec![(*id, *amount)],
+            Call::DeipAssets(DeipAssetsCall::deip_burn { id, who, amount }) => {
+                let who: AccountId = who.clone().into();
+                if who == *spender {
+                    vec![(*id, *amount)]
+                } else {
+                    vec![]
+                }
+            },
+            Call::DeipAssets(DeipAssetsCall::distribute { payment_asset, amount, .. }) =>
+                vec![(*payment_asset, *amount)],
+            // spent funds are charged on approval
+            Call::DeipAssets(DeipAssetsCall::deip_transfer_approved { .. }) |
+            Call::DeipAssets(DeipAssetsCall::deip_cancel_approval { .. }) |
+            Call::DeipAssets(DeipAssetsCall::deip_create { .. }) |
+            Call::DeipAssets(DeipAssetsCall::deip_mint { .. }) |
+            Call::DeipAssets(DeipAssetsCall::deip_freeze { .. }) |
+            Call::DeipAssets(DeipAssetsCall::deip_thaw { .. }) |
+            Call::DeipAssets(DeipAssetsCall::deip_freeze_asset { .. }) |
+            Call::DeipAssets(DeipAssetsCall::deip_thaw_asset { .. }) |
+            Call::DeipAssets(DeipAssetsCall::deip_set_team { .. }) |
+            Call::DeipAssets(DeipAssetsCall::deip_set_metadata { .. }) |
+            Call::DeipAssets(DeipAssetsCall::checkpoint { .. }) |
+            Call::DeipAssets(DeipAssetsCall::claim { .. }) |
+            Call::DeipAssets(DeipAssetsCall::reclaim_dividends { .. }) |
+            Call::DeipAssets(DeipAssetsCall::set_transfer_rules { .. }) |
+            Call::DeipAssets(DeipAssetsCall::allowlist_add { .. }) |
+            Call::DeipAssets(DeipAssetsCall::allowlist_remove { .. }) |
+            Call::DeipAssets(DeipAssetsCall::set_security_metadata { .. }) |
+            Call::DeipAssets(DeipAssetsCall::attest_security_metadata { .. }) => vec![],
+
+            Call::DeipSwap(SwapCall::create_offer { give_asset, give_amount, .. }) =>
+                vec![(*give_asset, *give_amount)],
+            Call::DeipSwap(SwapCall::fill_offer { id, amount }) => DeipSwap::offer(id)
+                .map(|offer| vec![(offer.want_asset, *amount)])
+                .unwrap_or_default(),
+            Call::DeipSwap(SwapCall::cancel_offer { .. }) => vec![],
+
+            Call::DeipAmm(AmmCall::add_liquidity {
+                asset_a,
+                asset_b,
+                amount_a_desired,
+                amount_b_desired,
+                ..
+            }) => vec![(*asset_a, *amount_a_desired), (*asset_b, *amount_b_desired)],
+            Call::DeipAmm(AmmCall::swap { asset_in, amount_in, .. }) =>
+                vec![(*asset_in, *amount_in)],
+            Call::DeipAmm(AmmCall::remove_liquidity { .. }) => vec![],
+
+            Call::DeipUniques(DeipUniquesCall::deip_buy { class, instance }) =>
+                DeipUniques::sale(*class, *instance)
+                    .map(|sale| vec![(sale.asset, sale.price)])
+                    .unwrap_or_default(),
+            Call::DeipUniques(DeipUniquesCall::buyout { asset }) => DeipUniques::fraction(*asset)
+                .and_then(|fraction| fraction.buyout_price)
+                .map(|price| vec![(native, price)])
+                .unwrap_or_default(),
+            Call::DeipUniques(DeipUniquesCall::deip_create { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_mint { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_mint_batch { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_freeze { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_thaw { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_freeze_class { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_thaw_class { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_set_team { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_cancel_approval { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_set_attribute { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_clear_attribute { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_set_metadata { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_clear_metadata { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_set_class_metadata { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_clear_class_metadata { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_set_class_project { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_set_royalty { .. }) |
+            Call::DeipUniques(DeipUniquesCall::deip_cancel_sale { .. }) |
+            Call::DeipUniques(DeipUniquesCall::redeem { .. }) |
+            Call::DeipUniques(DeipUniquesCall::claim_buyout { .. }) => vec![],
+
+            Call::Deip(DeipCall::accept_contract_agreement { id, .. }) =>
+                Deip::license_fee(id, spender)
+                    .map(|fee| vec![(*fee.id(), *fee.amount())])
+                    .unwrap_or_default(),
+            // reserved till the request is resolved
+            Call::Deip(DeipCall::create_content_access_request { .. }) =>
+                vec![(native, ContentAccessRequestDeposit::get())],
+            Call::Deip(DeipCall::create_project { .. }) |
+            Call::Deip(DeipCall::update_project { .. }) |
+            Call::Deip(DeipCall::create_project_content { .. }) |
+            Call::Deip(DeipCall::create_review { .. }) |
+            Call::Deip(DeipCall::upvote_review { .. }) |
+            Call::Deip(DeipCall::create_contract_agreement { .. }) |
+            Call::Deip(DeipCall::reject_contract_agreement { .. }) |
+            Call::Deip(DeipCall::set_license_nft_class { .. }) |
+            Call::Deip(DeipCall::set_content_nft_class { .. }) |
+            Call::Deip(DeipCall::fulfill_content_access_request { .. }) |
+            Call::Deip(DeipCall::reject_content_access_request { .. }) => vec![],
+
+            Call::DeipInvestmentOpportunity(InvestmentCall::invest { asset, .. }) =>
+                vec![(*asset.id(), *asset.amount())],
+            Call::DeipInvestmentOpportunity(InvestmentCall::create_investment_opportunity {
+                shares,
+                ..
+            }) => shares.iter().map(|share| (*share.id(), *share.amount())).collect(),
+
+            // sweeps all balances of the DAO
+            Call::DeipDao(DeipDaoCall::dissolve { .. }) => return None,
+            // reserved till the proposal or the request is resolved
+            Call::DeipDao(DeipDaoCall::propose { .. }) => vec![(native, DaoProposalDeposit::get())],
+            Call::DeipDao(DeipDaoCall::invite_member { .. }) |
+            Call::DeipDao(DeipDaoCall::request_to_join { .. }) =>
+                vec![(native, DaoMembershipRequestDeposit::get())],
+            // funds of other DAOs are charged to their own budgets
+            Call::DeipDao(DeipDaoCall::on_behalf { .. }) |
+            Call::DeipDao(DeipDaoCall::on_behalf_over_budget { .. }) |
+            Call::DeipDao(DeipDaoCall::on_behalf_as_role { .. }) |
+            Call::DeipDao(DeipDaoCall::create { .. }) |
+            Call::DeipDao(DeipDaoCall::alter_authority { .. }) |
+            Call::DeipDao(DeipDaoCall::update_dao { .. }) |
+            Call::DeipDao(DeipDaoCall::set_token_governance { .. }) |
+            Call::DeipDao(DeipDaoCall::vote { .. }) |
+            Call::DeipDao(DeipDaoCall::close_proposal { .. }) |
+            Call::DeipDao(DeipDaoCall::define_role { .. }) |
+            Call::DeipDao(DeipDaoCall::remove_role { .. }) |
+            Call::DeipDao(DeipDaoCall::grant_role { .. }) |
+            Call::DeipDao(DeipDaoCall::revoke_role { .. }) |
+            Call::DeipDao(DeipDaoCall::set_budget { .. }) |
+            Call::DeipDao(DeipDaoCall::set_role_budget { .. }) |
+            Call::DeipDao(DeipDaoCall::set_budget_guardian { .. }) |
+            Call::DeipDao(DeipDaoCall::set_budget_by_guardian { .. }) |
+            Call::DeipDao(DeipDaoCall::approve_over_budget { .. }) |
+            Call::DeipDao(DeipDaoCall::accept_invitation { .. }) |
+            Call::DeipDao(DeipDaoCall::reject_invitation { .. }) |
+            Call::DeipDao(DeipDaoCall::accept_join_request { .. }) |
+            Call::DeipDao(DeipDaoCall::reject_join_request { .. }) |
+            Call::DeipDao(DeipDaoCall::prune_membership_requests { .. }) |
+            Call::DeipDao(DeipDaoCall::consent_to_nesting { .. }) => vec![],
+
+            Call::System(_) => vec![],
+
+            Call::Utility(UtilityCall::batch { calls }) |
+            Call::Utility(UtilityCall::batch_all { calls }) => calls
+                .iter()
+                .map(|c| Self::spending(spender, c))
+                .collect::<Option<Vec<_>>>()?
+                .concat(),
+            _ => return None,
+        };
+        Some(spending)
+    }
+}
+
+impl pallet_deip_dao::dissolution::DaoDissolution<AccountId> for Runtime {
+    fn ensure_dissolvable(
+        dao_key: &AccountId,
+    ) -> Result<(), pallet_deip_dao::dissolution::DissolveError> {
+        use pallet_deip_dao::dissolution::DissolveError;
+        // holdings swept on dissolution are known only after the backfill of holders
+        if !DeipAssets::holders_indexed() {
+            return Err(DissolveError::HoldingsNotIndexed)
+        }
+        if Deip::has_active_projects(dao_key) {
+            return Err(DissolveError::ActiveProjects)
+        }
+        if DeipAssets::has_investments(dao_key) {
+            return Err(DissolveError::UnfinishedSales)
+        }
+        if DeipProposal::has_pending_proposals(dao_key) {
+            return Err(DissolveError::PendingProposals)
+        }
+        Ok(())
+    }
+
+    fn holding_count(dao_key: &AccountId) -> u32 {
+        DeipAssets::holding_count(dao_key)
+    }
+
+    fn sweep(dao_key: &AccountId, beneficiary: &AccountId) -> sp_runtime::DispatchResult {
+        let signed = frame_system::RawOrigin::Signed(dao_key.clone());
+        for (asset, amount) in DeipAssets::holdings(dao_key) {
+            let target = beneficiary.clone().into();
+            DeipAssets::deip_transfer(signed.clone().into(), asset, target, amount)
+                .map_err(|e| e.error)?;
+        }
+        <Balances as Currency<AccountId>>::transfer(
+            dao_key,
+            beneficiary,
+            Balances::free_balance(dao_key),
+            ExistenceRequirement::AllowDeath,
+        )
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn setup_holdings(dao_key: &AccountId, count: u32) {
+        let admin = AccountId::from([0xbd; 32]);
+        let signed = frame_system::RawOrigin::Signed(admin.clone());
+        let deposits = Balance::from(count + 1) * AssetDeposit::get();
+        Balances::make_free_balance_be(&admin, deposits * 2);
+        for i in 0..count {
+            let asset = DeipAssetId::from_low_u64_be(0xbd00 + u64::from(i));
+            let origin = signed.clone().into();
+            DeipAssets::deip_create(origin, asset, admin.clone().into(), 1, None, None)
+                .expect("benchmark asset is created");
+            DeipAssets::deip_mint(signed.clone().into(), asset, dao_key.clone().into(), 100)
+                .expect("benchmark asset is minted");
+        }
     }
 }
 
@@ -842,6 +1495,26 @@ impl pallet_deip_proposal::pallet::Config for Runtime {
 
 parameter_types! {
     pub const DaoMaxSignatories: u16 = 50;
+    pub const DaoMaxRolePermissions: u16 = 20;
+    pub const DaoMembershipRequestTtl: BlockNumber = 7 * DAYS;
+    pub const DaoMaxAuthorityDepth: u16 = 4;
+    pub const DaoMaxProposalLength: u32 = 4 * 1024;
+    // The proposal with the longest call: key size is 20; value is 4 KiB + 256 bytes.
+    pub const DaoProposalDeposit: Balance = currency::deposit(1, 20 + 4 * 1024 + 256);
+    // An invitation or a join request: key size is 20 + 32; value is 4 + 1 + 16 bytes.
+    pub const DaoMembershipRequestDeposit: Balance = currency::deposit(1, 52 + 21);
+    pub const DaoMaxMembershipRequests: u32 = 100;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+parameter_types! {
+    pub DaoBenchmarkPermission: (dao_permission::DaoPermission, Call) = (
+        dao_permission::DaoPermission::TransferNative,
+        Call::Balances(pallet_balances::Call::transfer {
+            dest: AccountId::from([0xbe; 32]).into(),
+            value: currency::EXISTENSIAL_DEPOSIT,
+        }),
+    );
 }
 
 impl pallet_deip_dao::Config for Runtime {
@@ -850,6 +1523,20 @@ impl pallet_deip_dao::Config for Runtime {
     type DaoId = pallet_deip_dao::DaoId;
     type DeipDaoWeightInfo = pallet_deip_dao::weights::Weights<Self>;
     type MaxSignatories = DaoMaxSignatories;
+    type Checkpoints = Self;
+    type Permission = dao_permission::DaoPermission;
+    type MaxRolePermissions = DaoMaxRolePermissions;
+    type Spending = Self;
+    type Dissolution = Self;
+    type MembershipRequestTtl = DaoMembershipRequestTtl;
+    type MaxAuthorityDepth = DaoMaxAuthorityDepth;
+    type Currency = Balances;
+    type ProposalDeposit = DaoProposalDeposit;
+    type MembershipRequestDeposit = DaoMembershipRequestDeposit;
+    type MaxMembershipRequests = DaoMaxMembershipRequests;
+    type MaxProposalLength = DaoMaxProposalLength;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkPermission = DaoBenchmarkPermission;
 }
 
 parameter_types! {
@@ -941,14 +1628,16 @@ construct_runtime!(
         Multisig: pallet_multisig::{Pallet, Call, Storage, Event<T>},
         Utility: pallet_utility::{Pallet, Call, Event},
         Deip: pallet_deip::{Pallet, Call, Storage, Event<T>, Config},
-        DeipAssets: pallet_deip_assets::{Pallet, Storage, Config<T>},
-        DeipUniques: pallet_deip_uniques::{Pallet, Storage, Config<T>},
+        DeipAssets: pallet_deip_assets::{Pallet, Call, Storage, Event<T>, Config<T>},
+        DeipUniques: pallet_deip_uniques::{Pallet, Call, Storage, Event<T>, Config<T>},
         DeipProposal: pallet_deip_proposal::{Pallet, Call, Storage, Event<T>, Config, ValidateUnsigned},
         DeipDao: pallet_deip_dao::{Pallet, Call, Storage, Event<T>, Config},
         DeipPortal: pallet_deip_portal::{Pallet, Call, Storage, Config, ValidateUnsigned},
         DeipVesting: pallet_deip_vesting::{Pallet, Call, Storage, Event<T>, Config<T>},
         DeipEcosystemFund: pallet_deip_ecosystem_fund::{Pallet, Config<T>, Storage},
         DeipInvestmentOpportunity: pallet_deip_investment_opportunity,
+        DeipSwap: pallet_deip_swap::{Pallet, Call, Storage, Event<T>},
+        DeipAmm: pallet_deip_amm::{Pallet, Call, Storage, Event<T>},
     }
 );
 
@@ -1230,6 +1919,9 @@ impl_runtime_apis! {
             list_benchmark!(list, extra, pallet_deip_portal, DeipPortal);
             // list_benchmark!(list, extra, pallet_deip, Deip);
             list_benchmark!(list, extra, pallet_deip_investment_opportunity, DeipInvestmentOpportunity);
+            list_benchmark!(list, extra, pallet_deip_uniques, DeipUniques);
+            list_benchmark!(list, extra, pallet_deip_swap, DeipSwap);
+            list_benchmark!(list, extra, pallet_deip_amm, DeipAmm);
 
             let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -1271,6 +1963,9 @@ impl_runtime_apis! {
             add_benchmark!(params, batches, pallet_deip_portal, DeipPortal);
             // add_benchmark!(params, batches, pallet_deip, Deip);
             add_benchmark!(params, batches, pallet_deip_investment_opportunity, DeipInvestmentOpportunity);
+            add_benchmark!(params, batches, pallet_deip_uniques, DeipUniques);
+            add_benchmark!(params, batches, pallet_deip_swap, DeipSwap);
+            add_benchmark!(params, batches, pallet_deip_amm, DeipAmm);
 
             Ok(batches)
         }
@@ -1286,6 +1981,46 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_deip_dao::api::DeipDaoBudgetRuntimeApi<Block, DeipAssetId, AssetBalance> for Runtime {
+        fn remaining_budget(
+            id: pallet_deip_dao::dao::DaoId,
+            asset: DeipAssetId,
+        ) -> Option<AssetBalance> {
+            DeipDao::remaining_budget(&id, &asset)
+        }
+    }
+
+    impl pallet_deip_assets::api::DeipAssetsRuntimeApi<Block, DeipAssetId, AccountId, AssetBalance> for Runtime {
+        fn balance_at(
+            asset: DeipAssetId,
+            account: AccountId,
+            checkpoint: pallet_deip_assets::CheckpointId,
+        ) -> Option<AssetBalance> {
+            DeipAssets::balance_at(&asset, &account, checkpoint)
+        }
+
+        fn remaining_supply(asset: DeipAssetId) -> Option<AssetBalance> {
+            DeipAssets::remaining_supply(&asset)
+        }
+    }
+
+    impl pallet_deip_amm::api::DeipAmmRuntimeApi<Block, DeipAssetId, AssetBalance> for Runtime {
+        fn quote(
+            asset_in: DeipAssetId,
+            asset_out: DeipAssetId,
+            amount_in: AssetBalance,
+        ) -> Option<AssetBalance> {
+            DeipAmm::quote(&asset_in, &asset_out, amount_in)
+        }
+
+        fn reserves(
+            asset_a: DeipAssetId,
+            asset_b: DeipAssetId,
+        ) -> Option<(AssetBalance, AssetBalance)> {
+            DeipAmm::reserves(&asset_a, &asset_b)
+        }
+    }
+
     impl pallet_deip::api::DeipApi
     <
         Block,
@@ -1320,5 +2055,13 @@ impl_runtime_apis! {
         fn get_contract_agreement(id: &pallet_deip::ContractAgreementId) -> Option<pallet_deip::ContractAgreementOf<crate::Runtime>> {
             Deip::get_contract_agreement(id)
         }
+
+        fn may_access_content(account: &AccountId, id: &pallet_deip::ProjectContentId) -> bool {
+            Deip::may_access_content(account, id)
+        }
+
+        fn get_content_access_request(id: &pallet_deip::ContentAccessRequestId) -> Option<pallet_deip::ContentAccessRequestOf<crate::Runtime>> {
+            Deip::get_content_access_request(id)
+        }
     }
 }